@@ -61,6 +61,10 @@ impl Rpc {
                 let result = rpc.get_current_network_call(None, GetCurrentNetworkRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetMiningRules => {
+                let result = rpc.get_mining_rules_call(None, GetMiningRulesRequest {}).await?;
+                self.println(&ctx, result);
+            }
             // RpcApiOps::SubmitBlock => {
             //     let result = rpc.submit_block_call(SubmitBlockRequest {  }).await?;
             //     self.println(&ctx, result);
@@ -206,6 +210,16 @@ impl Rpc {
                 let result = rpc.unban_call(None, UnbanRequest { ip }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::SetRetentionPeriod => {
+                let retention_period_days = argv.first().map(|x| x.parse::<f64>()).transpose()?;
+                let result = rpc.set_retention_period_call(None, SetRetentionPeriodRequest { retention_period_days }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetRecentReorgs => {
+                let limit = argv.first().map(|x| x.parse::<u32>()).transpose()?;
+                let result = rpc.get_recent_reorgs_call(None, GetRecentReorgsRequest { limit }).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetInfo => {
                 let result = rpc.get_info_call(None, GetInfoRequest {}).await?;
                 self.println(&ctx, result);
@@ -284,6 +298,24 @@ impl Rpc {
 
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransactionConfirmations => {
+                if argv.len() != 2 {
+                    return Err(Error::custom("Please specify a transaction id and an accepting block hash"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let accepting_block_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let request = GetTransactionConfirmationsRequest::new(transaction_id, accepting_block_hash);
+                let result = rpc.get_transaction_confirmations_call(None, request).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::VerifyTransactionReceipt => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the hex encoded receipt"));
+                }
+                let receipt = Vec::<u8>::from_hex(argv.remove(0).as_str())?;
+                let result = rpc.verify_transaction_receipt_call(None, VerifyTransactionReceiptRequest::new(receipt)).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
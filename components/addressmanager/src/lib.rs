@@ -16,9 +16,10 @@ use itertools::{
 use kaspa_consensus_core::config::Config;
 use kaspa_core::{debug, info, task::tick::TickService, time::unix_now, warn};
 use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
-use kaspa_utils::networking::IpAddress;
+use kaspa_utils::networking::{AddressRecord, IpAddress};
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
+use stores::address_store::Entry;
 use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
 use thiserror::Error;
 
@@ -114,6 +115,11 @@ impl AddressManager {
 
     fn local_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
         match self.config.externalip {
+            // An external onion address was passed, meaning an onion service forwards to our listen address
+            Some(local_net_address) if local_net_address.is_onion() => {
+                info!("External address is an onion service {}", local_net_address);
+                return Left(iter::once(local_net_address));
+            }
             // An external IP was passed, we will try to bind that if it's valid
             Some(local_net_address) if local_net_address.ip.is_publicly_routable() => {
                 info!("External address is publicly routable {}", local_net_address);
@@ -210,7 +216,7 @@ impl AddressManager {
             let port =
                 gateway.add_any_port(igd::PortMappingProtocol::TCP, local_addr, UPNP_DEADLINE_SEC as u32, UPNP_REGISTRATION_NAME)?;
             info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-            return Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })));
+            return Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })));
         }
 
         match gateway.add_port(
@@ -223,7 +229,7 @@ impl AddressManager {
             Ok(_) => {
                 info!("[UPnP] Added port mapping to default external port: {ip}:{desired_external_port}");
                 Ok(Some((
-                    NetAddress { ip, port: desired_external_port },
+                    NetAddress::new(ip, desired_external_port),
                     ExtendHelper { gateway, local_addr, external_port: desired_external_port },
                 )))
             }
@@ -235,7 +241,7 @@ impl AddressManager {
                     UPNP_REGISTRATION_NAME,
                 )?;
                 info!("[UPnP] Added port mapping to random external port: {ip}:{port}");
-                Ok(Some((NetAddress { ip, port }, ExtendHelper { gateway, local_addr, external_port: port })))
+                Ok(Some((NetAddress::new(ip, port), ExtendHelper { gateway, local_addr, external_port: port })))
             }
             Err(err) => Err(err.into()),
         }
@@ -252,17 +258,59 @@ impl AddressManager {
     }
 
     pub fn add_address(&mut self, address: NetAddress) {
-        if address.ip.is_loopback() || address.ip.is_unspecified() {
-            debug!("[Address manager] skipping local address {}", address.ip);
+        if !Self::is_storable(address) || self.address_store.has(address) {
             return;
         }
 
-        if self.address_store.has(address) {
+        // We mark `connection_failed_count` as 0 only after first success
+        self.address_store.set(address, 1);
+    }
+
+    /// Adds an address gossiped by another peer along with the services it advertised and the time it was last seen.
+    /// If the address is already known, its services and last seen time are updated only if the known ones were
+    /// gossiped as well and are less recent
+    pub fn add_address_record(&mut self, record: AddressRecord) {
+        self.merge_address_record(record, false);
+    }
+
+    /// Adds an address along with the services the peer itself advertised to us. These always override the known
+    /// services and last seen time, and are never overridden by gossip
+    pub fn add_first_hand_address_record(&mut self, record: AddressRecord) {
+        self.merge_address_record(record, true);
+    }
+
+    fn merge_address_record(&mut self, record: AddressRecord, is_first_hand: bool) {
+        let address = record.address;
+        if !Self::is_storable(address) {
             return;
         }
 
-        // We mark `connection_failed_count` as 0 only after first success
-        self.address_store.set(address, 1);
+        let entry = if self.address_store.has(address) {
+            let entry = self.address_store.get(address);
+            if !is_first_hand && (entry.is_first_hand || entry.last_seen > record.last_seen) {
+                return;
+            }
+            entry
+        } else {
+            // We mark `connection_failed_count` as 0 only after first success
+            Entry::new(address, 1)
+        };
+        self.address_store.set_entry(Entry { services: record.services, last_seen: record.last_seen, is_first_hand, ..entry });
+    }
+
+    fn is_storable(address: NetAddress) -> bool {
+        if address.ip.is_loopback() || address.ip.is_unspecified() {
+            debug!("[Address manager] skipping local address {}", address.ip);
+            return false;
+        }
+
+        // An OnionCat IP is only meaningful along with the onion address it was mapped from
+        if address.ip.is_onioncat() && !address.is_onion() {
+            debug!("[Address manager] skipping OnionCat address {} with no onion address", address.ip);
+            return false;
+        }
+
+        true
     }
 
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
@@ -283,15 +331,31 @@ impl AddressManager {
             return;
         }
 
-        self.address_store.set(address, 0);
+        let entry = self.address_store.get(address);
+        self.address_store.set_entry(Entry { connection_failed_count: 0, last_seen: unix_now(), ..entry });
     }
 
     pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
         self.address_store.iterate_addresses()
     }
 
-    pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
-        self.address_store.iterate_prioritized_random_addresses(exceptions)
+    pub fn iterate_address_records(&self) -> impl Iterator<Item = AddressRecord> + '_ {
+        self.address_store.iterate_address_records()
+    }
+
+    /// The services known to be offered by the peer at `address`, or zero if the address is unknown
+    pub fn known_services(&self, address: NetAddress) -> u64 {
+        self.address_store.services(address)
+    }
+
+    /// Iterates the known addresses in random order, prioritized by connection failures and network prefix.
+    /// Only addresses of peers known to offer all of the `required_services` flags are included
+    pub fn iterate_prioritized_random_addresses(
+        &self,
+        exceptions: HashSet<NetAddress>,
+        required_services: u64,
+    ) -> impl ExactSizeIterator<Item = NetAddress> {
+        self.address_store.iterate_prioritized_random_addresses(exceptions, required_services)
     }
 
     pub fn ban(&mut self, ip: IpAddress) {
@@ -331,14 +395,16 @@ mod address_store_with_cache {
     // Since we need operations such as iterating all addresses, count, etc, we keep an easy to use copy of the database addresses.
     // We don't expect it to be expensive since we limit the number of saved addresses.
     use std::{
+        cmp::Reverse,
         collections::{HashMap, HashSet},
         net::IpAddr,
         sync::Arc,
     };
 
     use itertools::Itertools;
+    use kaspa_core::info;
     use kaspa_database::prelude::{CachePolicy, DB};
-    use kaspa_utils::networking::PrefixBucket;
+    use kaspa_utils::networking::{AddressRecord, PrefixBucket};
     use rand::{
         distributions::{WeightedError, WeightedIndex},
         prelude::Distribution,
@@ -361,6 +427,10 @@ mod address_store_with_cache {
         fn new(db: Arc<DB>) -> Self {
             // We manage the cache ourselves on this level, so we disable the inner builtin cache
             let db_store = DbAddressesStore::new(db, CachePolicy::Empty);
+            let upgraded = db_store.upgrade_legacy_entries().unwrap();
+            if upgraded > 0 {
+                info!("[Address manager] upgraded {} stored addresses to include peer services", upgraded);
+            }
             let mut addresses = HashMap::new();
             for (key, entry) in db_store.iterator().map(|res| res.unwrap()) {
                 addresses.insert(key, entry);
@@ -375,19 +445,28 @@ mod address_store_with_cache {
 
         pub fn set(&mut self, address: NetAddress, connection_failed_count: u64) {
             let entry = match self.addresses.get(&address.into()) {
-                Some(entry) => Entry { connection_failed_count, address: entry.address },
-                None => Entry { connection_failed_count, address },
+                Some(entry) => Entry { connection_failed_count, ..*entry },
+                None => Entry::new(address, connection_failed_count),
             };
-            self.db_store.set(address.into(), entry).unwrap();
-            self.addresses.insert(address.into(), entry);
+            self.set_entry(entry);
+        }
+
+        pub fn set_entry(&mut self, entry: Entry) {
+            self.db_store.set(entry.address.into(), entry).unwrap();
+            self.addresses.insert(entry.address.into(), entry);
             self.keep_limit();
         }
 
         fn keep_limit(&mut self) {
             while self.addresses.len() > MAX_ADDRESSES {
-                let to_remove =
-                    self.addresses.iter().max_by(|a, b| (a.1).connection_failed_count.cmp(&(b.1).connection_failed_count)).unwrap();
-                self.remove_by_key(*to_remove.0);
+                // Evict the most failing address, preferring the least recently seen one among equals
+                let to_remove = self
+                    .addresses
+                    .iter()
+                    .max_by_key(|(_, entry)| (entry.connection_failed_count, Reverse(entry.last_seen)))
+                    .map(|(key, _)| *key)
+                    .unwrap();
+                self.remove_by_key(to_remove);
             }
         }
 
@@ -395,6 +474,10 @@ mod address_store_with_cache {
             *self.addresses.get(&address.into()).unwrap()
         }
 
+        pub fn services(&self, address: NetAddress) -> u64 {
+            self.addresses.get(&address.into()).map_or(0, |entry| entry.services)
+        }
+
         pub fn remove(&mut self, address: NetAddress) {
             self.remove_by_key(address.into())
         }
@@ -408,6 +491,10 @@ mod address_store_with_cache {
             self.addresses.values().map(|entry| entry.address)
         }
 
+        pub fn iterate_address_records(&self) -> impl Iterator<Item = AddressRecord> + '_ {
+            self.addresses.values().map(|entry| entry.record())
+        }
+
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
@@ -416,6 +503,9 @@ mod address_store_with_cache {
         /// This ensures a distributed selection across the global network, while respecting
         /// weight reductions due to ip connection failures.
         ///
+        /// Addresses of peers not known to offer all of the `required_services` flags are skipped,
+        /// so that passing zero iterates all addresses.
+        ///
         /// The exact weight formula for any given ip, is as follows:
         ///```ignore
         ///         ip_weight = (64 ^ (x - y)) / n
@@ -428,13 +518,14 @@ mod address_store_with_cache {
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
+            required_services: u64,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
             let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
             let (mut weights, filtered_addresses): (Vec<f64>, Vec<NetAddress>) = self
                 .addresses
                 .iter()
-                .filter(|(addr_key, _)| !exceptions.contains(addr_key))
+                .filter(|(addr_key, entry)| !exceptions.contains(addr_key) && entry.record().has_services(required_services))
                 .map(|(_, e)| {
                     let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
                     *count += 1;
@@ -535,6 +626,80 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_onion_address_persistence() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let ip_address = NetAddress::from_str("1.2.3.4:16111").unwrap();
+            let onion_address = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111").unwrap();
+            {
+                let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default()));
+                let mut am_guard = am.lock();
+                am_guard.add_address(ip_address);
+                am_guard.add_address(onion_address);
+            }
+
+            // Both kinds of addresses are restored when the store is reloaded
+            let (am, _) = AddressManager::new(config, db.1.clone(), Arc::new(TickService::default()));
+            let mut am_guard = am.lock();
+            assert_eq!(am_guard.get_all_addresses().into_iter().collect::<HashSet<_>>(), HashSet::from([ip_address, onion_address]));
+
+            // Onion addresses are removed after repeated connection failures, same as IP addresses
+            for _ in 0..=MAX_CONNECTION_FAILED_COUNT {
+                am_guard.mark_connection_failure(onion_address);
+            }
+            assert_eq!(am_guard.get_all_addresses(), vec![ip_address]);
+        }
+
+        #[test]
+        fn test_address_services() {
+            const ARCHIVAL: u64 = 1 << 4;
+            const UTXOINDEX: u64 = 1 << 5;
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default()));
+            let mut am_guard = am.lock();
+
+            let unknown = NetAddress::from_str("1.1.1.1:16111").unwrap();
+            let archival = NetAddress::from_str("2.2.2.2:16111").unwrap();
+            let full = NetAddress::from_str("3.3.3.3:16111").unwrap();
+            am_guard.add_address(unknown);
+            am_guard.add_address_record(AddressRecord::new(archival, ARCHIVAL, 1000));
+            am_guard.add_address_record(AddressRecord::new(full, ARCHIVAL | UTXOINDEX, 1000));
+
+            let with_services = |am: &AddressManager, required| {
+                am.iterate_prioritized_random_addresses(HashSet::new(), required).collect::<HashSet<_>>()
+            };
+            assert_eq!(with_services(&*am_guard, 0), HashSet::from([unknown, archival, full]));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL), HashSet::from([archival, full]));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL | UTXOINDEX), HashSet::from([full]));
+            assert_eq!(am_guard.known_services(full), ARCHIVAL | UTXOINDEX);
+            assert_eq!(am_guard.known_services(unknown), 0);
+            assert_eq!(am_guard.known_services(NetAddress::from_str("4.4.4.4:16111").unwrap()), 0);
+
+            // Outdated records do not override more recent knowledge, while newer ones do
+            am_guard.add_address_record(AddressRecord::new(archival, 0, 500));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL), HashSet::from([archival, full]));
+            am_guard.add_address_record(AddressRecord::new(full, 0, 2000));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL), HashSet::from([archival]));
+
+            // Services advertised by the peer itself override gossip, and are not overridden by newer gossip
+            am_guard.add_first_hand_address_record(AddressRecord::new(full, ARCHIVAL | UTXOINDEX, 1500));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL | UTXOINDEX), HashSet::from([full]));
+            am_guard.add_address_record(AddressRecord::new(full, 0, 3000));
+            assert_eq!(with_services(&*am_guard, ARCHIVAL | UTXOINDEX), HashSet::from([full]));
+
+            // A successful connection refreshes the last seen time
+            am_guard.mark_connection_success(archival);
+            let record = am_guard.iterate_address_records().find(|record| record.address == archival).unwrap();
+            assert!(record.last_seen > 1000 && record.services == ARCHIVAL);
+            drop(am_guard);
+
+            // Services and last seen times are persisted
+            let (am, _) = AddressManager::new(config, db.1.clone(), Arc::new(TickService::default()));
+            assert!(am.lock().iterate_address_records().any(|r| r == record));
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             kaspa_core::log::try_init_logger("info");
@@ -599,7 +764,7 @@ mod address_store_with_cache {
                 // The weight sampled expected uniform distribution
                 let prioritized_address_distribution = am
                     .lock()
-                    .iterate_prioritized_random_addresses(HashSet::new())
+                    .iterate_prioritized_random_addresses(HashSet::new(), 0)
                     .take(num_of_buckets)
                     .map(|addr| addr.prefix_bucket().as_u64() as f64)
                     .collect_vec();
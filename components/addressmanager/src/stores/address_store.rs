@@ -1,10 +1,14 @@
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::{
     prelude::DB,
+    prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter},
     prelude::{CachePolicy, StoreError, StoreResult},
-    prelude::{CachedDbAccess, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{
+    mem_size::MemSizeEstimator,
+    networking::{AddressRecord, IpAddress, OnionAddress},
+};
 use serde::{Deserialize, Serialize};
 use std::net::Ipv6Addr;
 use std::{error::Error, fmt::Display, sync::Arc};
@@ -12,13 +16,87 @@ use std::{error::Error, fmt::Display, sync::Arc};
 use super::AddressKey;
 use crate::NetAddress;
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy)]
 pub struct Entry {
     pub connection_failed_count: u64,
     pub address: NetAddress,
+    /// Service flags last advertised by the peer, zero if unknown
+    pub services: u64,
+    /// Unix time in milliseconds the peer was last seen, zero if unknown
+    pub last_seen: u64,
+    /// Whether the services were advertised by the peer itself rather than gossiped by others
+    pub is_first_hand: bool,
+}
+
+impl Entry {
+    pub fn new(address: NetAddress, connection_failed_count: u64) -> Self {
+        Self { connection_failed_count, address, services: 0, last_seen: 0, is_first_hand: false }
+    }
+
+    pub fn record(&self) -> AddressRecord {
+        AddressRecord::new(self.address, self.services, self.last_seen)
+    }
+}
+
+/// The persisted form of an address entry
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct DbEntry {
+    connection_failed_count: u64,
+    ip: IpAddress,
+    port: u16,
+    onion: Option<OnionAddress>,
+    services: u64,
+    last_seen: u64,
+    is_first_hand: bool,
+}
+
+impl MemSizeEstimator for DbEntry {}
+
+impl From<DbEntry> for Entry {
+    fn from(entry: DbEntry) -> Self {
+        let address = match entry.onion {
+            Some(onion) => NetAddress::new_onion(onion, entry.port),
+            None => NetAddress::new(entry.ip, entry.port),
+        };
+        Self {
+            connection_failed_count: entry.connection_failed_count,
+            address,
+            services: entry.services,
+            last_seen: entry.last_seen,
+            is_first_hand: entry.is_first_hand,
+        }
+    }
+}
+
+impl From<Entry> for DbEntry {
+    fn from(entry: Entry) -> Self {
+        Self {
+            connection_failed_count: entry.connection_failed_count,
+            ip: entry.address.ip,
+            port: entry.address.port,
+            onion: entry.address.onion,
+            services: entry.services,
+            last_seen: entry.last_seen,
+            is_first_hand: entry.is_first_hand,
+        }
+    }
+}
+
+/// The legacy persisted form of an IP address entry, read only for upgrading existing address databases
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct LegacyDbIpEntry {
+    connection_failed_count: u64,
+    ip: IpAddress,
+    port: u16,
 }
 
-impl MemSizeEstimator for Entry {}
+impl MemSizeEstimator for LegacyDbIpEntry {}
+
+impl From<LegacyDbIpEntry> for Entry {
+    fn from(entry: LegacyDbIpEntry) -> Self {
+        Self::new(NetAddress::new(entry.ip, entry.port), entry.connection_failed_count)
+    }
+}
 
 pub trait AddressesStoreReader {
     #[allow(dead_code)]
@@ -72,24 +150,57 @@ impl From<DbAddressKey> for AddressKey {
     }
 }
 
+/// Persists address entries along with the services and last seen time of each peer. Onion addresses
+/// are keyed by their OnionCat mapping, so they share the same key space as IP addresses
 #[derive(Clone)]
 pub struct DbAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<DbAddressKey, Entry>,
+    access: CachedDbAccess<DbAddressKey, DbEntry>,
+    legacy_access: CachedDbAccess<DbAddressKey, LegacyDbIpEntry>,
 }
 
 impl DbAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), cache_policy, DatabaseStorePrefixes::AddressRecords.into()),
+            legacy_access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into()),
+        }
+    }
+
+    /// Moves entries persisted in the legacy address layout into the address records layout, with unknown
+    /// services and last seen time. Returns the number of upgraded entries
+    pub fn upgrade_legacy_entries(&self) -> StoreResult<usize> {
+        let legacy_entries = self.legacy_access.iterator().map(|res| res.map(|(key, entry)| (key, Entry::from(entry))));
+        let legacy_entries = Self::parse_keys(legacy_entries)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+        if legacy_entries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut batch = WriteBatch::default();
+        for (key, entry) in legacy_entries.iter().copied() {
+            self.access.write(BatchDbWriter::new(&mut batch), key.into(), entry.into())?;
+        }
+        self.legacy_access.delete_all(BatchDbWriter::new(&mut batch))?;
+        self.db.write(batch)?;
+        Ok(legacy_entries.len())
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> + '_ {
-        self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_failed_count)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
+        Self::parse_keys(self.access.iterator().map(|res| res.map(|(key, entry)| (key, Entry::from(entry)))))
+    }
+
+    fn parse_keys(
+        iter: impl Iterator<Item = Result<(Box<[u8]>, Entry), Box<dyn Error>>>,
+    ) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> {
+        iter.map(|iter_result| match iter_result {
+            Ok((key_bytes, entry)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
                 Ok(address_key_slice) => {
                     let addr_key = DbAddressKey(address_key_slice);
                     let address: AddressKey = addr_key.into();
-                    Ok((address, connection_failed_count))
+                    Ok((address, entry))
                 }
                 Err(e) => Err(e.into()),
             },
@@ -100,13 +211,13 @@ impl DbAddressesStore {
 
 impl AddressesStoreReader for DbAddressesStore {
     fn get(&self, key: AddressKey) -> Result<Entry, StoreError> {
-        self.access.read(key.into())
+        self.access.read(key.into()).map(Entry::from)
     }
 }
 
 impl AddressesStore for DbAddressesStore {
     fn set(&mut self, key: AddressKey, entry: Entry) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), key.into(), entry)
+        self.access.write(DirectDbWriter::new(&self.db), key.into(), entry.into())
     }
 
     fn remove(&mut self, key: AddressKey) -> StoreResult<()> {
@@ -115,6 +226,44 @@ impl AddressesStore for DbAddressesStore {
 
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()> {
         let entry = self.get(key)?;
-        self.set(key, Entry { connection_failed_count, address: entry.address })
+        self.set(key, Entry { connection_failed_count, ..entry })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_legacy_entries_upgrade() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = DbAddressesStore::new(db.clone(), CachePolicy::Empty);
+        let ip_address = NetAddress::from_str("1.2.3.4:16111").unwrap();
+        let other_address = NetAddress::from_str("5.6.7.8:16111").unwrap();
+
+        // Write entries the way nodes did before services were tracked
+        let legacy_ip_entry = LegacyDbIpEntry { connection_failed_count: 2, ip: ip_address.ip, port: ip_address.port };
+        store.legacy_access.write(DirectDbWriter::new(&db), AddressKey::from(ip_address).into(), legacy_ip_entry).unwrap();
+        let legacy_other_entry = LegacyDbIpEntry { connection_failed_count: 0, ip: other_address.ip, port: other_address.port };
+        store.legacy_access.write(DirectDbWriter::new(&db), AddressKey::from(other_address).into(), legacy_other_entry).unwrap();
+        assert_eq!(store.iterator().count(), 0);
+
+        assert_eq!(store.upgrade_legacy_entries().unwrap(), 2);
+        let ip_entry = store.get(ip_address.into()).unwrap();
+        assert_eq!((ip_entry.address, ip_entry.connection_failed_count), (ip_address, 2));
+        assert_eq!(ip_entry.record(), AddressRecord::from(ip_address));
+        let other_entry = store.get(other_address.into()).unwrap();
+        assert_eq!((other_entry.address, other_entry.connection_failed_count), (other_address, 0));
+
+        // The legacy entries are gone, so upgrading again is a no-op
+        assert_eq!(store.legacy_access.iterator().count(), 0);
+        assert_eq!(store.upgrade_legacy_entries().unwrap(), 0);
+
+        // Upgraded entries keep services and last seen times once set
+        store.set(ip_address.into(), Entry { services: 0b11, last_seen: 1000, ..ip_entry }).unwrap();
+        assert_eq!(store.get(ip_address.into()).unwrap().record(), AddressRecord::new(ip_address, 0b11, 1000));
+        assert_eq!(store.iterator().count(), 2);
     }
 }
@@ -0,0 +1,188 @@
+//!
+//! Selection of inbound peers to evict once the inbound limit is exceeded.
+//!
+//! Evicting a random peer lets an attacker who holds many inbound slots crowd out honest peers. Instead, peers
+//! with properties an attacker cannot cheaply imitate are protected first: peers from distinct network groups,
+//! peers with the lowest ping, peers which recently relayed new blocks or transactions, and the longest connected
+//! peers. The evicted peer is the youngest connection within the network group holding the most remaining peers.
+//!
+
+use kaspa_p2p_lib::{Peer, PeerKey};
+use kaspa_utils::networking::{IpAddress, PrefixBucket};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    hash::BuildHasher,
+    net::SocketAddr,
+};
+
+/// The number of peers protected by belonging to distinct network groups
+const PROTECTED_BY_NETWORK_GROUP: usize = 4;
+
+/// The number of peers protected by having the lowest ping
+const PROTECTED_BY_PING: usize = 8;
+
+/// The number of peers protected by most recently relaying new transactions
+const PROTECTED_BY_TX_RELAY: usize = 4;
+
+/// The number of peers protected by most recently relaying new blocks
+const PROTECTED_BY_BLOCK_RELAY: usize = 4;
+
+#[derive(Clone, Debug)]
+pub(crate) struct EvictionCandidate {
+    pub key: PeerKey,
+    pub address: SocketAddr,
+    pub network_group: PrefixBucket,
+    pub last_ping_duration: u64,
+    pub time_connected: u64,
+    pub time_since_block_relay: Option<u64>,
+    pub time_since_tx_relay: Option<u64>,
+}
+
+impl From<&Peer> for EvictionCandidate {
+    fn from(peer: &Peer) -> Self {
+        Self {
+            key: peer.key(),
+            address: peer.net_address(),
+            network_group: IpAddress::from(peer.net_address().ip()).prefix_bucket(),
+            last_ping_duration: peer.last_ping_duration(),
+            time_connected: peer.time_connected(),
+            time_since_block_relay: peer.time_since_block_relay(),
+            time_since_tx_relay: peer.time_since_tx_relay(),
+        }
+    }
+}
+
+/// Selects the inbound peer to evict out of `candidates`. The network groups are ordered by a hash keyed with
+/// `group_hasher`, so that remote peers cannot predict which groups are protected.
+///
+/// If all candidates are protected, the youngest connection is evicted, which amounts to refusing the newest peer.
+/// Returns `None` only if there are no candidates.
+pub(crate) fn select_peer_to_evict(candidates: &[EvictionCandidate], group_hasher: &impl BuildHasher) -> Option<EvictionCandidate> {
+    let mut unprotected = candidates.to_vec();
+
+    // Protect a single peer from each of a few network groups
+    unprotected.sort_by_cached_key(|candidate| group_hasher.hash_one(candidate.network_group));
+    let mut protected_groups = HashSet::with_capacity(PROTECTED_BY_NETWORK_GROUP);
+    unprotected.retain(|candidate| {
+        !(protected_groups.len() < PROTECTED_BY_NETWORK_GROUP && protected_groups.insert(candidate.network_group))
+    });
+
+    // A zero duration means the peer was not pinged yet, which must not count as the lowest ping
+    protect(&mut unprotected, PROTECTED_BY_PING, |candidate| match candidate.last_ping_duration {
+        0 => u64::MAX,
+        duration => duration,
+    });
+    protect_relayers(&mut unprotected, PROTECTED_BY_TX_RELAY, |candidate| candidate.time_since_tx_relay);
+    protect_relayers(&mut unprotected, PROTECTED_BY_BLOCK_RELAY, |candidate| candidate.time_since_block_relay);
+    let protected_by_age = unprotected.len() / 2;
+    protect(&mut unprotected, protected_by_age, |candidate| Reverse(candidate.time_connected));
+
+    let mut groups: HashMap<PrefixBucket, Vec<EvictionCandidate>> = HashMap::new();
+    for candidate in unprotected {
+        groups.entry(candidate.network_group).or_default().push(candidate);
+    }
+    // The largest group, where ties are broken in favor of the group holding the youngest connection
+    let evicted_group =
+        groups.into_values().max_by_key(|group| (group.len(), Reverse(group.iter().map(|candidate| candidate.time_connected).min())));
+
+    evicted_group.unwrap_or_else(|| candidates.to_vec()).into_iter().min_by_key(|candidate| candidate.time_connected)
+}
+
+/// Removes up to `count` candidates with the lowest `sort_key` values
+fn protect<K: Ord>(candidates: &mut Vec<EvictionCandidate>, count: usize, sort_key: impl FnMut(&EvictionCandidate) -> K) {
+    candidates.sort_by_cached_key(sort_key);
+    candidates.drain(..count.min(candidates.len()));
+}
+
+/// Removes up to `count` of the candidates which relayed most recently, skipping those which never relayed
+fn protect_relayers(
+    candidates: &mut Vec<EvictionCandidate>,
+    count: usize,
+    time_since_relay: impl Fn(&EvictionCandidate) -> Option<u64>,
+) {
+    let relayers = candidates.iter().filter(|candidate| time_since_relay(candidate).is_some()).count();
+    protect(candidates, count.min(relayers), |candidate| time_since_relay(candidate).unwrap_or(u64::MAX));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_utils::networking::PeerId;
+    use std::{collections::hash_map::RandomState, str::FromStr};
+
+    fn candidate(id: u8, ip: &str, time_connected: u64) -> EvictionCandidate {
+        let address = SocketAddr::from_str(&format!("{ip}:16111")).unwrap();
+        EvictionCandidate {
+            key: PeerKey::new(PeerId::from_slice(&[id; 16]).unwrap(), address.ip().into()),
+            address,
+            network_group: IpAddress::from(address.ip()).prefix_bucket(),
+            last_ping_duration: 1000,
+            time_connected,
+            time_since_block_relay: None,
+            time_since_tx_relay: None,
+        }
+    }
+
+    #[test]
+    fn test_eviction_targets_largest_network_group() {
+        let hasher = RandomState::new();
+        // Twenty long connected honest peers from distinct groups, and twenty newer sybil peers sharing a single group
+        let mut candidates =
+            (0..20u8).map(|i| candidate(i, &format!("{}.1.1.1", i + 1), 10_000 + i as u64)).collect::<Vec<EvictionCandidate>>();
+        candidates.extend((20..40u8).map(|i| candidate(i, &format!("100.100.1.{i}"), 1000 + i as u64)));
+
+        // Half of the sybil peers are evicted before any of the honest peers
+        for _ in 0..10 {
+            let evicted = select_peer_to_evict(&candidates, &hasher).unwrap();
+            assert!(evicted.address.ip().to_string().starts_with("100.100."));
+            candidates.retain(|candidate| candidate.key != evicted.key);
+        }
+    }
+
+    #[test]
+    fn test_eviction_protections() {
+        let hasher = RandomState::new();
+        // All peers share a network group, hence only the youngest unprotected peer is evicted
+        let mut candidates = (0..40u8).map(|i| candidate(i, &format!("1.1.1.{i}"), 100_000 - i as u64)).collect::<Vec<_>>();
+        let youngest = candidates.last().unwrap().key;
+
+        // The youngest peer is protected by the lowest ping
+        candidates.last_mut().unwrap().last_ping_duration = 10;
+        let evicted = select_peer_to_evict(&candidates, &hasher).unwrap();
+        assert_eq!(evicted.key, candidate(38, "1.1.1.38", 0).key);
+
+        // Or by relaying blocks or transactions recently
+        candidates.last_mut().unwrap().last_ping_duration = 1000;
+        candidates.last_mut().unwrap().time_since_block_relay = Some(5000);
+        assert_ne!(select_peer_to_evict(&candidates, &hasher).unwrap().key, youngest);
+        candidates.last_mut().unwrap().time_since_block_relay = None;
+        candidates.last_mut().unwrap().time_since_tx_relay = Some(5000);
+        assert_ne!(select_peer_to_evict(&candidates, &hasher).unwrap().key, youngest);
+
+        // Without any protection it is evicted
+        candidates.last_mut().unwrap().time_since_tx_relay = None;
+        assert_eq!(select_peer_to_evict(&candidates, &hasher).unwrap().key, youngest);
+
+        // When all candidates are protected the youngest is evicted anyway
+        let few = candidates[32..].to_vec();
+        assert_eq!(select_peer_to_evict(&few, &hasher).unwrap().key, youngest);
+        assert!(select_peer_to_evict(&[], &hasher).is_none());
+    }
+
+    #[test]
+    fn test_eviction_of_unpinged_peers() {
+        let hasher = RandomState::new();
+        // All peers share a network group, and the youngest peers were not pinged yet
+        let mut candidates = (0..40u8).map(|i| candidate(i, &format!("1.1.1.{i}"), 100_000 - i as u64)).collect::<Vec<_>>();
+        candidates[36..].iter_mut().for_each(|candidate| candidate.last_ping_duration = 0);
+        let youngest = candidates.last().unwrap().key;
+
+        // Unpinged peers are not protected by the ping, so the youngest of them is evicted
+        assert_eq!(select_peer_to_evict(&candidates, &hasher).unwrap().key, youngest);
+
+        // Once pinged, the lowest ping protects it
+        candidates.last_mut().unwrap().last_ping_duration = 10;
+        assert_eq!(select_peer_to_evict(&candidates, &hasher).unwrap().key, candidate(38, "1.1.1.38", 0).key);
+    }
+}
@@ -1,12 +1,18 @@
+mod eviction;
+
 use std::{
     cmp::min,
-    collections::{HashMap, HashSet},
+    collections::{hash_map::RandomState, HashMap, HashSet},
     net::{IpAddr, SocketAddr, ToSocketAddrs},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::{Duration, SystemTime},
 };
 
 use duration_string::DurationString;
+use eviction::{select_peer_to_evict, EvictionCandidate};
 use futures_util::future::{join_all, try_join_all};
 use itertools::Itertools;
 use kaspa_addressmanager::{AddressManager, NetAddress};
@@ -30,10 +36,15 @@ pub struct ConnectionManager {
     inbound_limit: usize,
     dns_seeders: &'static [&'static str],
     default_port: u16,
+    /// Service flags which at least one outbound peer is deliberately chosen to offer, if any are known
+    preferred_services: u64,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
-    connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
+    connection_requests: TokioMutex<HashMap<NetAddress, ConnectionRequest>>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
+    /// Keys the network group ordering used for inbound eviction, so that it cannot be predicted by remote peers
+    eviction_hasher: RandomState,
+    inbound_evictions: AtomicU64,
 }
 
 #[derive(Clone, Debug)]
@@ -56,6 +67,7 @@ impl ConnectionManager {
         inbound_limit: usize,
         dns_seeders: &'static [&'static str],
         default_port: u16,
+        preferred_services: u64,
         address_manager: Arc<ParkingLotMutex<AddressManager>>,
     ) -> Arc<Self> {
         let (tx, rx) = unbounded_channel::<()>();
@@ -69,6 +81,9 @@ impl ConnectionManager {
             shutdown_signal: SingleTrigger::new(),
             dns_seeders,
             default_port,
+            preferred_services,
+            eviction_hasher: RandomState::new(),
+            inbound_evictions: AtomicU64::new(0),
         });
         manager.clone().start_event_loop(rx);
         manager.force_next_iteration.send(()).unwrap();
@@ -103,7 +118,7 @@ impl ConnectionManager {
         self.handle_inbound_connections(&peer_by_address).await;
     }
 
-    pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
+    pub async fn add_connection_request(&self, address: NetAddress, is_permanent: bool) {
         // If the request already exists, it resets the attempts count and overrides the `is_permanent` setting.
         self.connection_requests.lock().await.insert(address, ConnectionRequest::new(is_permanent));
         self.force_next_iteration.send(()).unwrap(); // We force the next iteration of the connection loop.
@@ -113,13 +128,18 @@ impl ConnectionManager {
         self.shutdown_signal.trigger.trigger()
     }
 
+    /// The number of inbound peers evicted since the node started
+    pub fn inbound_evictions(&self) -> u64 {
+        self.inbound_evictions.load(Ordering::Relaxed)
+    }
+
     async fn handle_connection_requests(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
         let mut requests = self.connection_requests.lock().await;
         let mut new_requests = HashMap::with_capacity(requests.len());
         for (address, request) in requests.iter() {
             let address = *address;
             let request = request.clone();
-            let is_connected = peer_by_address.contains_key(&address);
+            let is_connected = peer_by_address.contains_key(&SocketAddr::from(address));
             if is_connected && !request.is_permanent {
                 // The peer is connected and the request is not permanent - no need to keep the request
                 continue;
@@ -127,7 +147,8 @@ impl ConnectionManager {
 
             if !is_connected && request.next_attempt <= SystemTime::now() {
                 debug!("Connecting to peer request {}", address);
-                match self.p2p_adaptor.connect_peer(address.to_string()).await {
+                let services = self.address_manager.lock().known_services(address);
+                match self.p2p_adaptor.connect_peer(address.to_string(), services).await {
                     Err(err) => {
                         debug!("Failed connecting to peer request: {}, {}", address, err);
                         if request.is_permanent {
@@ -166,8 +187,28 @@ impl ConnectionManager {
             return;
         }
 
+        let has_preferred_services = self.preferred_services == 0
+            || peer_by_address
+                .values()
+                .any(|peer| peer.is_outbound() && peer.properties().services & self.preferred_services == self.preferred_services);
+
         let mut missing_connections = self.outbound_target - active_outbound.len();
-        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound);
+        let (mut preferred_addr, mut addr_iter) = {
+            let address_manager = self.address_manager.lock();
+            let mut exceptions = active_outbound;
+            if !self.p2p_adaptor.can_reach_onion() {
+                // Onion addresses are kept for gossip, but connecting to them requires a proxy
+                exceptions.extend(address_manager.iterate_addresses().filter(|addr| addr.is_onion()));
+            }
+            // If no outbound peer offers the preferred services, the first connection attempt goes to a peer known to offer them
+            let preferred_addr = if has_preferred_services {
+                None
+            } else {
+                address_manager.iterate_prioritized_random_addresses(exceptions.clone(), self.preferred_services).next()
+            };
+            exceptions.extend(preferred_addr);
+            (preferred_addr, address_manager.iterate_prioritized_random_addresses(exceptions, 0))
+        };
 
         let mut progressing = true;
         let mut connecting = true;
@@ -178,14 +219,14 @@ impl ConnectionManager {
             let mut addrs_to_connect = Vec::with_capacity(missing_connections);
             let mut jobs = Vec::with_capacity(missing_connections);
             for _ in 0..missing_connections {
-                let Some(net_addr) = addr_iter.next() else {
+                let Some(net_addr) = preferred_addr.take().or_else(|| addr_iter.next()) else {
                     connecting = false;
                     break;
                 };
-                let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
-                debug!("Connecting to {}", &socket_addr);
+                debug!("Connecting to {}", net_addr);
                 addrs_to_connect.push(net_addr);
-                jobs.push(self.p2p_adaptor.connect_peer(socket_addr.clone()));
+                let services = self.address_manager.lock().known_services(net_addr);
+                jobs.push(self.p2p_adaptor.connect_peer(net_addr.to_string(), services));
             }
 
             if progressing && !jobs.is_empty() {
@@ -239,16 +280,24 @@ impl ConnectionManager {
     }
 
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
-        let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
-        let active_inbound_len = active_inbound.len();
-        if self.inbound_limit >= active_inbound_len {
+        let mut candidates = peer_by_address.values().filter(|peer| !peer.is_outbound()).map(EvictionCandidate::from).collect_vec();
+        if self.inbound_limit >= candidates.len() {
             return;
         }
 
-        let mut futures = Vec::with_capacity(active_inbound_len - self.inbound_limit);
-        for peer in active_inbound.choose_multiple(&mut thread_rng(), active_inbound_len - self.inbound_limit) {
-            debug!("Disconnecting from {} because we're above the inbound limit", peer.net_address());
-            futures.push(self.p2p_adaptor.terminate(peer.key()));
+        let mut futures = Vec::with_capacity(candidates.len() - self.inbound_limit);
+        while candidates.len() > self.inbound_limit {
+            let evicted = select_peer_to_evict(&candidates, &self.eviction_hasher).expect("there are more candidates than the limit");
+            let group_size = candidates.iter().filter(|candidate| candidate.network_group == evicted.network_group).count();
+            info!(
+                "Evicting inbound peer {} connected for {} since we're above the inbound limit ({} inbound peer(s) in its network group)",
+                evicted.address,
+                DurationString::from(Duration::from_millis(evicted.time_connected)),
+                group_size
+            );
+            candidates.retain(|candidate| candidate.key != evicted.key);
+            self.inbound_evictions.fetch_add(1, Ordering::Relaxed);
+            futures.push(self.p2p_adaptor.terminate(evicted.key));
         }
         join_all(futures).await;
     }
@@ -330,11 +379,11 @@ impl ConnectionManager {
 
     /// Returns whether the given address is a permanent request.
     pub async fn is_permanent(&self, address: &SocketAddr) -> bool {
-        self.connection_requests.lock().await.contains_key(address)
+        self.connection_requests.lock().await.keys().any(|request_address| SocketAddr::from(*request_address) == *address)
     }
 
     /// Returns whether the given IP has some permanent request.
     pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
-        self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
+        self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip.0 == ip)
     }
 }
@@ -35,10 +35,14 @@ pub trait ConsensusFactory: Sync + Send {
     /// Create a new empty staging consensus
     fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl);
 
+    /// Load the staging consensus left by a previously interrupted IBD, if such exists
+    fn existing_staging_consensus(&self) -> Option<(ConsensusInstance, DynConsensusCtl)>;
+
     /// Close the factory and cleanup any shared resources used by it
     fn close(&self);
 
-    /// If the node is not configured as archival -- delete inactive consensus entries and their databases  
+    /// If the node is not configured as archival -- delete inactive consensus entries and their databases.
+    /// The staging entry is retained so that an interrupted IBD can resume
     fn delete_inactive_consensus_entries(&self);
 
     /// Delete the staging consensus entry and its database (this is done even if the node is archival
@@ -58,6 +62,10 @@ impl ConsensusFactory for MockFactory {
         unimplemented!()
     }
 
+    fn existing_staging_consensus(&self) -> Option<(ConsensusInstance, DynConsensusCtl)> {
+        unimplemented!()
+    }
+
     fn close(&self) {
         unimplemented!()
     }
@@ -143,6 +151,12 @@ impl ConsensusManager {
         StagingConsensus::new(self.clone(), ConsensusInner::new(consensus, ctl))
     }
 
+    /// Loads the staging consensus left by a previously interrupted IBD, if such exists
+    pub fn existing_staging_consensus(self: &Arc<Self>) -> Option<StagingConsensus> {
+        let (consensus, ctl) = self.factory.existing_staging_consensus()?;
+        Some(StagingConsensus::new(self.clone(), ConsensusInner::new(consensus, ctl)))
+    }
+
     pub fn register_consensus_reset_handler(&self, handler: Arc<dyn ConsensusResetHandler>) {
         self.inner.write().consensus_reset_handlers.push(handler);
     }
@@ -227,6 +241,15 @@ impl StagingConsensus {
         // Delete the canceled staging consensus
         self.manager.delete_staging_entry();
     }
+
+    /// Stops the staging consensus while retaining its entry and database, so that the IBD
+    /// syncing into it can later resume via [`ConsensusManager::existing_staging_consensus`]
+    pub fn suspend(self) {
+        self.staging.ctl.stop();
+        for handle in self.handles {
+            handle.join().unwrap();
+        }
+    }
 }
 
 impl Deref for StagingConsensus {
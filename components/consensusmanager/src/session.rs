@@ -4,14 +4,17 @@
 
 use kaspa_consensus_core::{
     acceptance_data::AcceptanceData,
-    api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus},
+    api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus, StorageStats},
     block::Block,
     blockstatus::BlockStatus,
+    confirmations::TxConfirmations,
     daa_score_timestamp::DaaScoreTimestamp,
     errors::consensus::ConsensusResult,
     header::Header,
     mass::{ContextualMasses, NonContextualMasses},
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    pruning::{IbdCheckpoint, PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    receipts::{Pochm, TxReceipt},
+    reorg::ChainReorg,
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, SignableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     utxo::utxo_inquirer::UtxoInquirerError,
@@ -231,6 +234,14 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(|c| c.get_stats()).await
     }
 
+    pub async fn async_get_storage_stats(&self) -> StorageStats {
+        self.clone().spawn_blocking(|c| c.get_storage_stats()).await
+    }
+
+    pub async fn async_get_recent_reorgs(&self, limit: usize) -> Vec<ChainReorg> {
+        self.clone().spawn_blocking(move |c| c.get_recent_reorgs(limit)).await
+    }
+
     pub async fn async_get_virtual_merge_depth_root(&self) -> Option<Hash> {
         self.clone().spawn_blocking(|c| c.get_virtual_merge_depth_root()).await
     }
@@ -263,6 +274,10 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(|c| c.get_retention_period_root()).await
     }
 
+    pub async fn async_set_retention_period_days(&self, retention_period_days: Option<f64>) -> ConsensusResult<()> {
+        self.clone().spawn_blocking(move |c| c.set_retention_period_days(retention_period_days)).await
+    }
+
     pub async fn async_estimate_block_count(&self) -> BlockCount {
         self.clone().spawn_blocking(|c| c.estimate_block_count()).await
     }
@@ -275,6 +290,14 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_virtual_chain_from_block(low, chain_path_added_limit)).await
     }
 
+    pub async fn async_get_headers_selected_chain_from_block(
+        &self,
+        low: Hash,
+        chain_path_added_limit: Option<usize>,
+    ) -> ConsensusResult<ChainPath> {
+        self.clone().spawn_blocking(move |c| c.get_headers_selected_chain_from_block(low, chain_path_added_limit)).await
+    }
+
     pub async fn async_get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -424,6 +447,14 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(|c| c.pruning_point()).await
     }
 
+    pub async fn async_get_ibd_checkpoint(&self) -> Option<IbdCheckpoint> {
+        self.clone().spawn_blocking(|c| c.get_ibd_checkpoint()).await
+    }
+
+    pub async fn async_set_ibd_checkpoint(&self, checkpoint: IbdCheckpoint) {
+        self.clone().spawn_blocking(move |c| c.set_ibd_checkpoint(checkpoint)).await
+    }
+
     pub async fn async_get_daa_window(&self, hash: Hash) -> ConsensusResult<Vec<Hash>> {
         self.clone().spawn_blocking(move |c| c.get_daa_window(hash)).await
     }
@@ -455,6 +486,26 @@ impl ConsensusSessionOwned {
     pub async fn async_finality_point(&self) -> Hash {
         self.clone().spawn_blocking(move |c| c.finality_point()).await
     }
+
+    pub async fn async_generate_tx_receipt(&self, tx_id: Hash, accepting_block: Hash) -> ConsensusResult<TxReceipt> {
+        self.clone().spawn_blocking(move |c| c.generate_tx_receipt(tx_id, Some(accepting_block), None)).await
+    }
+
+    pub async fn async_generate_pochm(&self, block: Hash) -> ConsensusResult<Pochm> {
+        self.clone().spawn_blocking(move |c| c.generate_pochm(block)).await
+    }
+
+    pub async fn async_get_posterity_headers(&self, low: Hash, limit: usize) -> ConsensusResult<Vec<Arc<Header>>> {
+        self.clone().spawn_blocking(move |c| c.get_posterity_headers(low, limit)).await
+    }
+
+    pub async fn async_verify_tx_receipt(&self, receipt: TxReceipt) -> bool {
+        self.clone().spawn_blocking(move |c| c.verify_tx_receipt(&receipt)).await
+    }
+
+    pub async fn async_get_tx_confirmations(&self, tx_id: Hash, accepting_block: Hash) -> ConsensusResult<TxConfirmations> {
+        self.clone().spawn_blocking(move |c| c.get_tx_confirmations(tx_id, accepting_block)).await
+    }
 }
 
 pub type ConsensusProxy = ConsensusSessionOwned;
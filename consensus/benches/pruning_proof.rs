@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+use kaspa_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::SIMNET_PARAMS};
+use kaspa_consensus_core::{
+    api::ConsensusApi,
+    constants::BLOCK_VERSION,
+    header::Header,
+    pruning::{PruningPointProof, PruningProofMetadata},
+    BlockLevel,
+};
+use kaspa_hashes::Hash;
+use kaspa_pow::calc_block_level_check_pow;
+use kaspa_utils::iter::parallelism_in_power_steps;
+
+/// Builds a proof over a chain of `chain_length` headers mined on top of genesis. Each proof level holds all
+/// chain headers of at least that level, which makes for a valid (albeit not minimal) proof
+fn build_chain_proof(genesis: Arc<Header>, max_block_level: BlockLevel, chain_length: u64) -> PruningPointProof {
+    let mut tips_by_level = vec![genesis.hash; max_block_level as usize + 1];
+    let mut chain = Vec::with_capacity(chain_length as usize);
+    for i in 1..=chain_length {
+        // Levels pointing only at genesis are omitted, as is the case with real headers
+        let parents_by_level = tips_by_level.iter().take_while(|&&tip| tip != genesis.hash).map(|&tip| vec![tip]).collect_vec();
+        let parents_by_level = if parents_by_level.is_empty() { vec![vec![genesis.hash]] } else { parents_by_level };
+        let mut nonce = 0;
+        let (header, level) = loop {
+            let header = Header::new_finalized(
+                BLOCK_VERSION,
+                parents_by_level.clone(),
+                Hash::default(),
+                Hash::default(),
+                Hash::default(),
+                genesis.timestamp + i,
+                genesis.bits,
+                nonce,
+                i,
+                i.into(),
+                i,
+                genesis.hash,
+            );
+            let (level, pow_passes) = calc_block_level_check_pow(&header, max_block_level);
+            if pow_passes {
+                break (header, level);
+            }
+            nonce += 1;
+        };
+        tips_by_level.iter_mut().take(level as usize + 1).for_each(|tip| *tip = header.hash);
+        chain.push((Arc::new(header), level));
+    }
+
+    (0..=max_block_level)
+        .map(|level| {
+            std::iter::once(genesis.clone())
+                .chain(chain.iter().filter(|(_, header_level)| *header_level >= level).map(|(header, _)| header.clone()))
+                .collect_vec()
+        })
+        .collect_vec()
+}
+
+pub fn pruning_proof_validation_benchmark(c: &mut Criterion) {
+    let mut params = SIMNET_PARAMS;
+    params.pruning_proof_m = 100;
+    let config = Config::new(params);
+    let tc = TestConsensus::new(&config);
+
+    let genesis: Arc<Header> = Arc::new((&config.params.genesis).into());
+    let proof = build_chain_proof(genesis, config.params.max_block_level, 1000);
+    let proof_metadata = PruningProofMetadata::new(proof[0].last().unwrap().blue_work);
+
+    let mut group = c.benchmark_group("pruning proof validation");
+    group.sample_size(10);
+    for threads in parallelism_in_power_steps() {
+        group.bench_function(format!("par {threads}"), |b| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            b.iter(|| pool.install(|| black_box(tc.validate_pruning_proof(&proof, &proof_metadata).unwrap())))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, pruning_proof_validation_benchmark);
+criterion_main!(benches);
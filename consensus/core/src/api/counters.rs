@@ -1,5 +1,7 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::reorg::{reorg_depth_bucket, REORG_DEPTH_BUCKETS};
+
 #[derive(Default)]
 pub struct ProcessingCounters {
     pub blocks_submitted: AtomicU64,
@@ -11,9 +13,17 @@ pub struct ProcessingCounters {
     pub chain_block_counts: AtomicU64,
     pub chain_disqualified_counts: AtomicU64,
     pub mass_counts: AtomicU64,
+    /// Selected chain reorg counts, bucketed by the number of removed chain blocks (see [`crate::reorg::REORG_DEPTH_BUCKET_BOUNDS`])
+    pub reorg_depth_counts: [AtomicU64; REORG_DEPTH_BUCKETS],
+    pub max_reorg_depth: AtomicU64,
 }
 
 impl ProcessingCounters {
+    pub fn record_reorg(&self, removed_depth: u64) {
+        self.reorg_depth_counts[reorg_depth_bucket(removed_depth)].fetch_add(1, Ordering::Relaxed);
+        self.max_reorg_depth.fetch_max(removed_depth, Ordering::Relaxed);
+    }
+
     pub fn snapshot(&self) -> ProcessingCountersSnapshot {
         ProcessingCountersSnapshot {
             blocks_submitted: self.blocks_submitted.load(Ordering::Relaxed),
@@ -25,6 +35,8 @@ impl ProcessingCounters {
             chain_block_counts: self.chain_block_counts.load(Ordering::Relaxed),
             chain_disqualified_counts: self.chain_disqualified_counts.load(Ordering::Relaxed),
             mass_counts: self.mass_counts.load(Ordering::Relaxed),
+            reorg_depth_counts: std::array::from_fn(|i| self.reorg_depth_counts[i].load(Ordering::Relaxed)),
+            max_reorg_depth: self.max_reorg_depth.load(Ordering::Relaxed),
         }
     }
 }
@@ -40,6 +52,8 @@ pub struct ProcessingCountersSnapshot {
     pub chain_block_counts: u64,
     pub chain_disqualified_counts: u64,
     pub mass_counts: u64,
+    pub reorg_depth_counts: [u64; REORG_DEPTH_BUCKETS],
+    pub max_reorg_depth: u64,
 }
 
 impl core::ops::Sub for &ProcessingCountersSnapshot {
@@ -56,6 +70,9 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
             chain_block_counts: self.chain_block_counts.saturating_sub(rhs.chain_block_counts),
             chain_disqualified_counts: self.chain_disqualified_counts.saturating_sub(rhs.chain_disqualified_counts),
             mass_counts: self.mass_counts.saturating_sub(rhs.mass_counts),
+            reorg_depth_counts: std::array::from_fn(|i| self.reorg_depth_counts[i].saturating_sub(rhs.reorg_depth_counts[i])),
+            // The max depth is not a counter, hence the latest value is kept
+            max_reorg_depth: self.max_reorg_depth,
         }
     }
 }
@@ -4,6 +4,7 @@ use crate::{
     block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId},
     blockstatus::BlockStatus,
     coinbase::MinerData,
+    confirmations::TxConfirmations,
     daa_score_timestamp::DaaScoreTimestamp,
     errors::{
         block::{BlockProcessResult, RuleError},
@@ -14,8 +15,9 @@ use crate::{
     },
     header::Header,
     mass::{ContextualMasses, NonContextualMasses},
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
+    pruning::{IbdCheckpoint, PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     receipts::{Pochm, ProofOfPublication, TxReceipt},
+    reorg::ChainReorg,
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, SignableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     utxo::utxo_inquirer::UtxoInquirerError,
@@ -26,7 +28,7 @@ use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use std::sync::Arc;
 
-pub use self::stats::{BlockCount, ConsensusStats};
+pub use self::stats::{BlockCount, ConsensusStats, PruningProofStats, RetentionStats, StorageStats};
 
 pub mod args;
 pub mod counters;
@@ -104,6 +106,17 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns size estimates of the consensus stores. Queries the DB, hence unlike [`Self::get_stats`] it is not designed to be
+    /// called frequently.
+    fn get_storage_stats(&self) -> StorageStats {
+        unimplemented!()
+    }
+
+    /// Returns up to `limit` of the most recent reorgs of the virtual selected chain, ordered from the most recent one
+    fn get_recent_reorgs(&self, limit: usize) -> Vec<ChainReorg> {
+        unimplemented!()
+    }
+
     fn get_virtual_daa_score(&self) -> u64 {
         unimplemented!()
     }
@@ -156,6 +169,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Sets the retention period at runtime (`None` retains data down to the pruning point only). A shortened period is
+    /// applied gradually by the pruning processor, while already pruned data is not restored when the period is extended
+    fn set_retention_period_days(&self, retention_period_days: Option<f64>) -> ConsensusResult<()> {
+        unimplemented!()
+    }
+
     /// Gets the virtual chain paths from `low` to the `sink` hash, or until `chain_path_added_limit` is reached
     ///
     /// Note:   
@@ -165,6 +184,13 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Gets the headers selected chain paths from `low` to the headers selected tip, or until `chain_path_added_limit`
+    /// is reached. Unlike [`Self::get_virtual_chain_from_block`] this does not depend on block bodies being processed,
+    /// hence is also available on header-only nodes
+    fn get_headers_selected_chain_from_block(&self, low: Hash, chain_path_added_limit: Option<usize>) -> ConsensusResult<ChainPath> {
+        unimplemented!()
+    }
+
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
         unimplemented!()
     }
@@ -228,6 +254,15 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the persisted progress of the IBD syncing into this (staging) consensus, if any
+    fn get_ibd_checkpoint(&self) -> Option<IbdCheckpoint> {
+        unimplemented!()
+    }
+
+    fn set_ibd_checkpoint(&self, checkpoint: IbdCheckpoint) {
+        unimplemented!()
+    }
+
     fn is_chain_ancestor_of(&self, low: Hash, high: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
@@ -386,6 +421,11 @@ pub trait ConsensusApi: Send + Sync {
     fn generate_pochm(&self, block: Hash) -> ConsensusResult<Pochm> {
         unimplemented!()
     }
+    /// Returns up to `limit` consecutive posterity headers following `low`, ordered from low to high.
+    /// The list ends early once the next posterity block does not exist yet.
+    fn get_posterity_headers(&self, low: Hash, limit: usize) -> ConsensusResult<Vec<Arc<Header>>> {
+        unimplemented!()
+    }
     fn verify_tx_receipt(&self, receipt: &TxReceipt) -> bool {
         unimplemented!()
     }
@@ -398,6 +438,11 @@ pub trait ConsensusApi: Send + Sync {
     fn is_posterity_reached(&self, cutoff_bscore: u64) -> bool {
         unimplemented!()
     }
+    /// Returns the settlement status of transaction `tx_id` as accepted by `accepting_block`, combining the acceptance
+    /// data of the block, its selected chain membership and its position relative to the finality point and posterity
+    fn get_tx_confirmations(&self, tx_id: Hash, accepting_block: Hash) -> ConsensusResult<TxConfirmations> {
+        unimplemented!()
+    }
 }
 
 pub type DynConsensus = Arc<dyn ConsensusApi>;
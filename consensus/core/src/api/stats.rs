@@ -43,6 +43,34 @@ pub struct VirtualStateStats {
     pub past_median_time: u64,
 }
 
+#[derive(Clone, Default)]
+pub struct RetentionStats {
+    /// The retention period currently applied (`None` retains data down to the pruning point only)
+    pub retention_period_days: Option<f64>,
+    /// The DAA score distance between the retention period root and the root satisfying the retention period,
+    /// approximating the number of blocks still to be pruned (or moved to cold storage by archival nodes)
+    pub pending_blocks: u64,
+    /// The number of blocks pruned (or moved to cold storage) since the node started
+    pub pruned_blocks: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct PruningProofStats {
+    /// The duration in milliseconds of the latest pruning point proof build (zero if no proof was built since the node started)
+    pub build_duration_ms: u64,
+    /// The duration in milliseconds of the latest pruning point proof validation (zero if no proof was validated since the node started)
+    pub validation_duration_ms: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct StorageStats {
+    /// The estimated total size of the consensus databases in bytes
+    pub total_size_bytes: u64,
+    /// The estimated size in bytes of the data held by each store, keyed by the store prefix and name. Stores holding no
+    /// data are omitted
+    pub store_sizes: Vec<(u8, String, u64)>,
+}
+
 pub struct ConsensusStats {
     /// Block and header counts
     pub block_counts: BlockCount,
@@ -52,4 +80,10 @@ pub struct ConsensusStats {
 
     /// Virtual-related stats
     pub virtual_stats: VirtualStateStats,
+
+    /// Retention period and pruning progress stats
+    pub retention_stats: RetentionStats,
+
+    /// Pruning point proof build and validation stats
+    pub pruning_proof_stats: PruningProofStats,
 }
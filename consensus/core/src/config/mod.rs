@@ -3,6 +3,7 @@ pub mod constants;
 pub mod genesis;
 pub mod params;
 
+use kaspa_hashes::Hash;
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
 
 #[cfg(feature = "devnet-prealloc")]
@@ -17,6 +18,21 @@ use {
     params::Params,
 };
 
+/// If set, the retention period days must be at least this value
+/// (otherwise it is meaningless since pruning periods are typically at least 2 days long)
+pub const MINIMUM_RETENTION_PERIOD_DAYS: f64 = 2.0;
+
+/// By default, reorgs removing at least this number of blocks from the selected chain are notified
+pub const DEFAULT_REORG_NOTIFICATION_DEPTH: u64 = 2;
+
+/// A pruning point trusted by the node operator along with its expected UTXO commitment. A node configured with a
+/// trusted pruning point syncs from it without validating the headers proof received from the syncer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrustedPruningPoint {
+    pub hash: Hash,
+    pub utxo_commitment: Hash,
+}
+
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
 /// a `Params` instance. For anything more complex it is recommended to use `ConfigBuilder`. NOTE: this struct can be
 /// implicitly de-refed into `Params`
@@ -35,6 +51,10 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Indicates whether this node is a header-only light node which does not download
+    /// block bodies nor maintain the UTXO set
+    pub is_light: bool,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -66,11 +86,20 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Relay transactions to supporting peers by periodic set reconciliation rather than by flooding
+    pub enable_tx_reconciliation: bool,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
 
-    /// The number of days to keep data for
+    /// The number of days to keep data for. This is the initial value, which can be changed at runtime
     pub retention_period_days: Option<f64>,
+
+    /// A pruning point to sync from without validating its headers proof
+    pub trusted_pruning_point: Option<TrustedPruningPoint>,
+
+    /// Reorgs removing at least this number of blocks from the selected chain are notified via `ChainReorg` notifications
+    pub reorg_notification_depth: u64,
 }
 
 impl Config {
@@ -84,6 +113,7 @@ impl Config {
             perf,
             process_genesis: true,
             is_archival: false,
+            is_light: false,
             enable_sanity_checks: false,
             utxoindex: false,
             unsafe_rpc: false,
@@ -97,8 +127,11 @@ impl Config {
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            enable_tx_reconciliation: false,
             ram_scale: 1.0,
             retention_period_days: None,
+            trusted_pruning_point: None,
+            reorg_notification_depth: DEFAULT_REORG_NOTIFICATION_DEPTH,
         }
     }
 
@@ -0,0 +1,25 @@
+/// The settlement status of a transaction, as viewed from a block claimed to accept it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxConfirmations {
+    /// Whether the block is on the virtual selected chain and accepts the transaction. All the remaining
+    /// status fields are `false` (or zero) if not
+    pub is_accepted: bool,
+    /// The DAA score of the accepting block
+    pub accepting_block_daa_score: u64,
+    /// The blue score of the accepting block
+    pub accepting_block_blue_score: u64,
+    /// The number of confirmations, measured as the DAA score difference between virtual and the accepting block
+    pub confirmations: u64,
+    /// Whether the accepting block is in the past of the finality point, hence cannot be reorged
+    pub is_final: bool,
+    /// Whether the posterity block following the accepting block was reached with a safety margin
+    pub is_posterity_reached: bool,
+    /// Whether a receipt proving the acceptance of the transaction can already be generated
+    pub is_receipt_available: bool,
+}
+
+impl TxConfirmations {
+    pub fn not_accepted(accepting_block_daa_score: u64, accepting_block_blue_score: u64) -> Self {
+        Self { accepting_block_daa_score, accepting_block_blue_score, ..Default::default() }
+    }
+}
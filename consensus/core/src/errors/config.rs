@@ -14,9 +14,57 @@ pub enum ConfigError {
     #[error("Configuration: --ram-scale cannot be set above 10.0")]
     RamScaleTooHigh,
 
+    #[error("Configuration: --memory-ceiling-gb cannot be set below {0}")]
+    MemoryCeilingTooLow(f64),
+
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --stratum-listen requires --stratum-pay-address")]
+    MissingStratumPayAddress,
+
+    #[error("Configuration: --stratum-pay-address {0} is not a valid address for the selected network")]
+    InvalidStratumPayAddress(String),
+
+    #[error("Configuration: --stratum-difficulty must be greater than 0")]
+    StratumDifficultyZero,
+
+    #[error("Configuration: --sync-rate-threshold must be within (0, 1]")]
+    SyncRateThresholdOutOfRange,
+
+    #[error("Configuration: --sync-rate-window cannot be set below {0} seconds")]
+    SyncRateWindowTooShort(u64),
+
+    #[error("Configuration: --sync-rate-min-window must be between {0} and --sync-rate-window seconds")]
+    SyncRateMinWindowOutOfRange(u64),
+
+    #[error("Configuration: --rbf-min-feerate-increment must be a non-negative number")]
+    RbfMinFeerateIncrementOutOfRange,
+
+    #[error("Configuration: --rbf-max-evictions must be greater than 0")]
+    RbfMaxEvictionsZero,
+
+    #[error("Configuration: --payload-fee-multiplier must be a non-negative number")]
+    PayloadFeeMultiplierOutOfRange,
+
+    #[error("Configuration: --payload-allow-subnetwork and --payload-deny-subnetwork cannot be used together")]
+    PayloadSubnetworkAllowAndDeny,
+
+    #[error("Configuration: --light cannot be used together with {0}")]
+    LightModeConflict(&'static str),
+
+    #[error("Configuration: --trusted-pruning-point and --trusted-utxo-commitment must be specified together")]
+    TrustedPruningPointMismatch,
+
+    #[error("Configuration: --cold-storage-dir requires --archival")]
+    ColdStorageRequiresArchival,
+
+    #[error("Configuration: --{0} must be an IP address")]
+    ProxyNotAnIpAddress(&'static str),
+
+    #[error("Configuration: --{0}user and --{0}pass must be specified together")]
+    ProxyCredentialsMismatch(&'static str),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -15,6 +15,9 @@ pub enum ConsensusError {
     #[error("some data is missing for block {0}")]
     MissingData(Hash),
 
+    #[error("the acceptance data of block {0} was pruned, so transaction acceptance by it cannot be verified")]
+    AcceptanceDataPruned(Hash),
+
     #[error("tx with id:{0} was not found in local storage")]
     MissingTx(Hash),
 
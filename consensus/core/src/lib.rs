@@ -20,6 +20,7 @@ pub mod blockhash;
 pub mod blockstatus;
 pub mod coinbase;
 pub mod config;
+pub mod confirmations;
 pub mod constants;
 pub mod daa_score_timestamp;
 pub mod errors;
@@ -32,6 +33,7 @@ pub mod muhash;
 pub mod network;
 pub mod pruning;
 pub mod receipts;
+pub mod reorg;
 pub mod sign;
 pub mod subnets;
 pub mod trusted;
@@ -1,7 +1,7 @@
 use crate::{hashing, tx::Transaction};
 use kaspa_hashes::Hash;
-pub use kaspa_merkle::MerkleWitness;
 use kaspa_merkle::{calc_merkle_root, create_merkle_witness_from_unsorted, MerkleTreeError};
+pub use kaspa_merkle::{LeafRoute, MerkleWitness, WitnessSegment};
 pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>, include_mass_field: bool) -> Hash {
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
 }
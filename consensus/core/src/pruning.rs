@@ -1,9 +1,12 @@
 use crate::{
     header::Header,
     trusted::{TrustedGhostdagData, TrustedHeader},
+    tx::TransactionOutpoint,
     BlueWorkType,
 };
 use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 pub type PruningPointProof = Vec<Vec<Arc<Header>>>;
@@ -32,3 +35,24 @@ impl PruningProofMetadata {
         Self { relay_block_blue_work }
     }
 }
+
+/// The progress of an IBD with headers proof into a staging consensus. The checkpoint is persisted within the
+/// staging consensus so that an interrupted IBD can resume from the last stage reached rather than start over.
+/// Synced headers and block bodies need no checkpoint since they are recovered from the consensus state itself
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum IbdCheckpoint {
+    /// The pruning proof was validated and applied along with the pruning point trusted data
+    ProofApplied { pruning_point: Hash },
+
+    /// The pruning point UTXO set was partially imported. `last_outpoint` is the last imported outpoint
+    /// and `multiset` accumulates all UTXOs imported up to and including it
+    UtxosetChunk { pruning_point: Hash, last_outpoint: TransactionOutpoint, multiset: MuHash },
+}
+
+impl IbdCheckpoint {
+    pub fn pruning_point(&self) -> Hash {
+        match self {
+            IbdCheckpoint::ProofApplied { pruning_point } | IbdCheckpoint::UtxosetChunk { pruning_point, .. } => *pruning_point,
+        }
+    }
+}
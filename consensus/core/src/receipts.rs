@@ -41,12 +41,17 @@ impl LegacyPochm {
                     return false;
                 }
             }
-            next_chain_blk = *self.bfs_map.get(&next_chain_blk).unwrap().parents_by_level[0]
+            // A header without parents cannot lead down to the chain purporter
+            let Some((&selected_parent, _)) = self.bfs_map.get(&next_chain_blk).unwrap().parents_by_level[0]
                 .iter()
                 .map(|blk| (blk, self.bfs_map.get(blk).unwrap().blue_score))
-                .reduce(|(blk, bscore), (max_blk, max_bscore)| if bscore > max_bscore { (blk, bscore) } else { (max_blk, max_bscore) })
-                .unwrap()
-                .0;
+                .reduce(
+                    |(blk, bscore), (max_blk, max_bscore)| if bscore > max_bscore { (blk, bscore) } else { (max_blk, max_bscore) },
+                )
+            else {
+                return false;
+            };
+            next_chain_blk = selected_parent;
         }
     }
 }
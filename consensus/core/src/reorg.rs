@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ChainPath, Hash};
+
+/// Upper bounds (inclusive) of the reorg depth histogram buckets. Reorgs deeper than the last bound fall into an
+/// additional last bucket
+pub const REORG_DEPTH_BUCKET_BOUNDS: [u64; 3] = [1, 3, 7];
+
+/// The number of reorg depth histogram buckets
+pub const REORG_DEPTH_BUCKETS: usize = REORG_DEPTH_BUCKET_BOUNDS.len() + 1;
+
+/// Returns the index of the histogram bucket the reorg depth falls into
+pub fn reorg_depth_bucket(depth: u64) -> usize {
+    REORG_DEPTH_BUCKET_BOUNDS.iter().position(|&bound| depth <= bound).unwrap_or(REORG_DEPTH_BUCKET_BOUNDS.len())
+}
+
+/// A reorganization of the virtual selected chain, i.e., a sink change which removed blocks from the selected chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorg {
+    /// The number of blocks removed from the selected chain
+    pub removed_depth: u64,
+    /// The number of blocks added to the selected chain
+    pub added_depth: u64,
+    /// The sink prior to the reorg, i.e., the tip of the removed chain
+    pub removed_sink: Hash,
+    /// The sink following the reorg, i.e., the tip of the added chain
+    pub added_sink: Hash,
+    /// The DAA score of the sink following the reorg
+    pub daa_score: u64,
+    /// The time in milliseconds at which the reorg was applied by the node
+    pub timestamp: u64,
+}
+
+impl ChainReorg {
+    /// Returns the reorg described by the chain path leading to `new_sink`, or `None` if the path did not remove any chain block.
+    /// Note that the path might remove blocks without adding any, in case the new sink is a chain ancestor of the previous one
+    pub fn from_chain_path(chain_path: &ChainPath, new_sink: Hash, daa_score: u64, timestamp: u64) -> Option<Self> {
+        let removed_sink = *chain_path.removed.first()?;
+        Some(Self {
+            removed_depth: chain_path.removed.len() as u64,
+            added_depth: chain_path.added.len() as u64,
+            removed_sink,
+            added_sink: new_sink,
+            daa_score,
+            timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorg_depth_bucket() {
+        assert_eq!(reorg_depth_bucket(1), 0);
+        assert_eq!(reorg_depth_bucket(2), 1);
+        assert_eq!(reorg_depth_bucket(3), 1);
+        assert_eq!(reorg_depth_bucket(4), 2);
+        assert_eq!(reorg_depth_bucket(7), 2);
+        assert_eq!(reorg_depth_bucket(8), 3);
+        assert_eq!(reorg_depth_bucket(1000), REORG_DEPTH_BUCKETS - 1);
+    }
+}
@@ -1,5 +1,7 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData, block::Block, reorg::ChainReorg, tx::TransactionId, utxo::utxo_diff::UtxoDiff,
+};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -7,11 +9,11 @@ use kaspa_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{OverallSubscription, TransactionReplacedSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
-use std::sync::Arc;
+use std::{iter::once, sync::Arc};
 
 full_featured! {
 #[derive(Clone, Debug, Display)]
@@ -42,6 +44,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionReplaced notification: transaction id {}, {} evicted transactions", "_0.transaction_id", "_0.evicted_transaction_ids.len()")]
+    TransactionReplaced(TransactionReplacedNotification),
+
+    #[display(fmt = "ChainReorg notification: {} removed chain blocks, {} added chain blocks", "_0.reorg.removed_depth", "_0.reorg.added_depth")]
+    ChainReorg(ChainReorgNotification),
 }
 }
 
@@ -87,6 +95,22 @@ impl NotificationTrait for Notification {
         Some(self.clone())
     }
 
+    fn apply_transaction_replaced_subscription(
+        &self,
+        subscription: &TransactionReplacedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::TransactionReplaced(notification) = self else { return None };
+                subscription
+                    .matches(once(&notification.transaction_id).chain(notification.evicted_transaction_ids.iter()))
+                    .then(|| self.clone())
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -181,3 +205,34 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+#[derive(Debug, Clone)]
+pub struct TransactionReplacedNotification {
+    /// Id of the transaction inserted into the mempool
+    pub transaction_id: TransactionId,
+    /// Ids of the mempool transactions double spent by the inserted transaction
+    pub replaced_transaction_ids: Arc<Vec<TransactionId>>,
+    /// Ids of all the transactions evicted from the mempool, ie. the replaced transactions followed by their descendants
+    pub evicted_transaction_ids: Arc<Vec<TransactionId>>,
+}
+
+impl TransactionReplacedNotification {
+    pub fn new(
+        transaction_id: TransactionId,
+        replaced_transaction_ids: Arc<Vec<TransactionId>>,
+        evicted_transaction_ids: Arc<Vec<TransactionId>>,
+    ) -> Self {
+        Self { transaction_id, replaced_transaction_ids, evicted_transaction_ids }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainReorgNotification {
+    pub reorg: ChainReorg,
+}
+
+impl ChainReorgNotification {
+    pub fn new(reorg: ChainReorg) -> Self {
+        Self { reorg }
+    }
+}
@@ -108,6 +108,7 @@ impl CachePolicyBuilder {
                         max_size: noise(self.bytes_budget, 512), // 0.5KB noise magnitude
                         min_items: noise(self.min_items, 1),
                         mem_mode: MemMode::Bytes,
+                        unit_bytes: 1,
                     }
                 }
                 MemMode::Units => {
@@ -124,6 +125,7 @@ impl CachePolicyBuilder {
                         max_size: noise(max_items, 1),
                         min_items: noise(self.min_items, 1),
                         mem_mode: MemMode::Units,
+                        unit_bytes: self.unit_bytes.expect("unit_bytes are expected in tracked units mode"),
                     }
                 }
                 MemMode::Undefined => panic!("tracked mode requires a defined mem mode"),
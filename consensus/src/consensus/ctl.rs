@@ -11,7 +11,7 @@ use std::{
 pub struct Ctl {
     management_store: Arc<RwLock<MultiConsensusManagementStore>>,
     consensus_db_ref: Weak<DB>,
-    consensus_db_path: PathBuf,
+    consensus_db_path: Option<PathBuf>,
     consensus: Arc<Consensus>,
 }
 
@@ -21,7 +21,7 @@ impl Ctl {
         consensus_db: Arc<DB>,
         consensus: Arc<Consensus>,
     ) -> Self {
-        let consensus_db_path = consensus_db.path().to_owned();
+        let consensus_db_path = consensus_db.path().map(ToOwned::to_owned);
         let consensus_db_ref = Arc::downgrade(&consensus_db);
         Self { management_store, consensus_db_ref, consensus_db_path, consensus }
     }
@@ -9,15 +9,16 @@ use kaspa_consensusmanager::{ConsensusFactory, ConsensusInstance, DynConsensusCt
 use kaspa_core::{debug, time::unix_now, warn};
 use kaspa_database::{
     prelude::{
-        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError, StoreResult, StoreResultExtensions, DB,
+        BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError, StoreResult, StoreResultExtensions,
+        TrackedCacheRegistry, DB,
     },
     registry::DatabaseStorePrefixes,
 };
 
+use kaspa_database::prelude::WriteBatch;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
 
@@ -59,7 +60,10 @@ pub struct MultiConsensusMetadata {
     version: u32,
 }
 
-const LATEST_DB_VERSION: u32 = 4;
+const LATEST_DB_VERSION: u32 = 5;
+
+/// The metadata property key memorizing whether this node was recently a light node
+const LIGHT_NODE_PROP_KEY: &[u8] = b"is-light-node";
 impl Default for MultiConsensusMetadata {
     fn default() -> Self {
         Self {
@@ -109,6 +113,15 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    /// The directory name of the staging consensus, if one exists. None otherwise
+    pub fn staging_consensus_dir_name(&self) -> StoreResult<Option<String>> {
+        let metadata = self.metadata.read()?;
+        match metadata.staging_consensus_key {
+            Some(key) => Ok(Some(self.entries.read(key.into()).unwrap().directory_name)),
+            None => Ok(None),
+        }
+    }
+
     /// The entry type signifies whether the returned entry is an existing/new consensus
     pub fn active_consensus_entry(&mut self) -> StoreResult<ConsensusEntryType> {
         let mut metadata = self.metadata.read()?;
@@ -187,11 +200,13 @@ impl MultiConsensusManagementStore {
         })
     }
 
+    /// Iterates all entries which are neither the current consensus nor the staging consensus
     fn iterate_inactive_entries(&self) -> impl Iterator<Item = Result<ConsensusEntry, Box<dyn Error>>> + '_ {
-        let current_consensus_key = self.metadata.read().unwrap().current_consensus_key;
+        let metadata = self.metadata.read().unwrap();
+        let (current_consensus_key, staging_consensus_key) = (metadata.current_consensus_key, metadata.staging_consensus_key);
         self.iterator().filter(move |entry_result| {
             if let Ok(entry) = entry_result {
-                return Some(entry.key) != current_consensus_key;
+                return Some(entry.key) != current_consensus_key && Some(entry.key) != staging_consensus_key;
             }
 
             true
@@ -219,6 +234,23 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    pub fn is_light_node(&self) -> StoreResult<bool> {
+        match self.metadata.read() {
+            Ok(data) => Ok(data.props.get(LIGHT_NODE_PROP_KEY).is_some_and(|value| value.first() == Some(&1))),
+            Err(StoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn set_is_light_node(&mut self, is_light_node: bool) {
+        self.metadata
+            .update(DirectDbWriter::new(&self.db), |mut data| {
+                data.props.insert(LIGHT_NODE_PROP_KEY.to_vec(), vec![is_light_node as u8]);
+                data
+            })
+            .unwrap();
+    }
+
     /// Returns the current version of this database
     pub fn version(&self) -> StoreResult<u32> {
         match self.metadata.read() {
@@ -249,12 +281,14 @@ pub struct Factory {
     management_store: Arc<RwLock<MultiConsensusManagementStore>>,
     config: Config,
     db_root_dir: PathBuf,
+    cold_db_root_dir: Option<PathBuf>,
     db_parallelism: usize,
     notification_root: Arc<ConsensusNotificationRoot>,
     counters: Arc<ProcessingCounters>,
     tx_script_cache_counters: Arc<TxScriptCacheCounters>,
     fd_budget: i32,
     mining_rules: Arc<MiningRules>,
+    cache_registry: Option<TrackedCacheRegistry>,
 }
 
 impl Factory {
@@ -262,12 +296,14 @@ impl Factory {
         management_db: Arc<DB>,
         config: &Config,
         db_root_dir: PathBuf,
+        cold_db_root_dir: Option<PathBuf>,
         db_parallelism: usize,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
         tx_script_cache_counters: Arc<TxScriptCacheCounters>,
         fd_budget: i32,
         mining_rules: Arc<MiningRules>,
+        cache_registry: Option<TrackedCacheRegistry>,
     ) -> Self {
         assert!(fd_budget > 0, "fd_budget has to be positive");
         let mut config = config.clone();
@@ -276,20 +312,70 @@ impl Factory {
         config.process_genesis = false;
         let management_store = Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db)));
         management_store.write().set_is_archival_node(config.is_archival);
+        management_store.write().set_is_light_node(config.is_light);
         let factory = Self {
             management_store,
             config,
             db_root_dir,
+            cold_db_root_dir,
             db_parallelism,
             notification_root,
             counters,
             tx_script_cache_counters,
             fd_budget,
             mining_rules,
+            cache_registry,
         };
         factory.delete_inactive_consensus_entries();
         factory
     }
+
+    /// Opens the DB of the consensus entry along with its cold storage DB, if configured
+    fn open_consensus_dbs(&self, directory_name: &str) -> (Arc<DB>, Option<Arc<DB>>) {
+        // Active and staging consensuses should have equal budgets. The cold store is rarely read, hence it is given a small share
+        let files_limit = self.fd_budget / 2;
+        let cold_files_limit = if self.cold_db_root_dir.is_some() { files_limit / 4 } else { 0 };
+        let db = kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(self.db_root_dir.join(directory_name))
+            .with_parallelism(self.db_parallelism)
+            .with_column_families(true)
+            .with_files_limit(files_limit - cold_files_limit)
+            .with_cache_registry(self.cache_registry.clone())
+            .build()
+            .unwrap();
+        let cold_db = self.cold_db_root_dir.as_ref().map(|cold_db_root_dir| {
+            kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(cold_db_root_dir.join(directory_name))
+                .with_files_limit(cold_files_limit)
+                .with_compression(true)
+                .with_column_families(true)
+                .with_cache_registry(self.cache_registry.clone())
+                .build()
+                .unwrap()
+        });
+        (db, cold_db)
+    }
+
+    fn open_staging_consensus(&self, entry: ConsensusEntry) -> (ConsensusInstance, DynConsensusCtl) {
+        assert!(!self.notification_root.is_closed());
+
+        let (db, cold_db) = self.open_consensus_dbs(&entry.directory_name);
+
+        let session_lock = SessionLock::new();
+        let consensus = Arc::new(Consensus::new(
+            db.clone(),
+            cold_db,
+            Arc::new(self.config.to_builder().skip_adding_genesis().build()),
+            session_lock.clone(),
+            self.notification_root.clone(),
+            self.counters.clone(),
+            self.tx_script_cache_counters.clone(),
+            entry.creation_timestamp,
+            self.mining_rules.clone(),
+        ));
+
+        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+    }
 }
 
 impl ConsensusFactory for Factory {
@@ -311,17 +397,12 @@ impl ConsensusFactory for Factory {
             }
         };
 
-        let dir = self.db_root_dir.join(entry.directory_name.clone());
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let (db, cold_db) = self.open_consensus_dbs(&entry.directory_name);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
+            cold_db,
             Arc::new(config),
             session_lock.clone(),
             self.notification_root.clone(),
@@ -343,30 +424,16 @@ impl ConsensusFactory for Factory {
     }
 
     fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
-        assert!(!self.notification_root.is_closed());
-
         let entry = self.management_store.write().new_staging_consensus_entry().unwrap();
-        let dir = self.db_root_dir.join(entry.directory_name);
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
-
-        let session_lock = SessionLock::new();
-        let consensus = Arc::new(Consensus::new(
-            db.clone(),
-            Arc::new(self.config.to_builder().skip_adding_genesis().build()),
-            session_lock.clone(),
-            self.notification_root.clone(),
-            self.counters.clone(),
-            self.tx_script_cache_counters.clone(),
-            entry.creation_timestamp,
-            self.mining_rules.clone(),
-        ));
+        self.open_staging_consensus(entry)
+    }
 
-        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+    fn existing_staging_consensus(&self) -> Option<(ConsensusInstance, DynConsensusCtl)> {
+        let entry = self.management_store.write().staging_consensus_entry()?;
+        if !self.db_root_dir.join(&entry.directory_name).exists() {
+            return None;
+        }
+        Some(self.open_staging_consensus(entry))
     }
 
     fn close(&self) {
@@ -375,9 +442,8 @@ impl ConsensusFactory for Factory {
     }
 
     fn delete_inactive_consensus_entries(&self) {
-        // Staging entry is deleted also by archival nodes since it represents non-final data
-        self.delete_staging_entry();
-
+        // The staging entry is retained (also by non-archival nodes) since it holds the progress of an interrupted IBD.
+        // It is deleted once the IBD syncing into it is canceled
         if self.config.is_archival {
             return;
         }
@@ -411,6 +477,13 @@ impl ConsensusFactory for Factory {
         let mut write_guard = self.management_store.write();
         if let Some(entry) = write_guard.staging_consensus_entry() {
             let dir = self.db_root_dir.join(entry.directory_name.clone());
+            if let Some(cold_dir) =
+                self.cold_db_root_dir.as_ref().map(|root| root.join(&entry.directory_name)).filter(|dir| dir.exists())
+            {
+                if let Err(e) = fs::remove_dir_all(cold_dir) {
+                    warn!("Error deleting the cold storage of staging consensus entry {}: {}", entry.key, e);
+                }
+            }
             match fs::remove_dir_all(dir) {
                 Ok(_) => {
                     write_guard.delete_entry(entry).unwrap();
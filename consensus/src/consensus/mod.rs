@@ -9,7 +9,7 @@ pub mod test_consensus;
 mod utxo_set_override;
 
 use crate::{
-    config::Config,
+    config::{Config, MINIMUM_RETENTION_PERIOD_DAYS},
     errors::{BlockProcessResult, RuleError},
     model::{
         services::reachability::ReachabilityService,
@@ -19,11 +19,13 @@ use crate::{
             ghostdag::{GhostdagData, GhostdagStoreReader},
             headers::{CompactHeaderData, HeaderStoreReader},
             headers_selected_tip::HeadersSelectedTipStoreReader,
+            ibd_checkpoint::{IbdCheckpointStore, IbdCheckpointStoreReader},
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::PruningStoreReader,
             pruning_samples::{PruningSamplesStore, PruningSamplesStoreReader},
             reachability::ReachabilityStoreReader,
             relations::RelationsStoreReader,
+            reorgs::ChainReorgsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
@@ -35,6 +37,7 @@ use crate::{
         deps_manager::{BlockProcessingMessage, BlockResultSender, BlockTask, VirtualStateProcessingMessage},
         header_processor::HeaderProcessor,
         pruning_processor::processor::{PruningProcessingMessage, PruningProcessor},
+        receipts_errors::ReceiptsErrors,
         virtual_processor::{errors::PruningImportResult, VirtualStateProcessor},
         ProcessingCounters,
     },
@@ -48,12 +51,13 @@ use kaspa_consensus_core::{
     api::{
         args::{TransactionValidationArgs, TransactionValidationBatchArgs},
         stats::BlockCount,
-        BlockValidationFutures, ConsensusApi, ConsensusStats,
+        BlockValidationFutures, ConsensusApi, ConsensusStats, StorageStats,
     },
     block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId},
     blockhash::BlockHashExtensions,
     blockstatus::BlockStatus,
     coinbase::MinerData,
+    confirmations::TxConfirmations,
     daa_score_timestamp::DaaScoreTimestamp,
     errors::{
         coinbase::CoinbaseResult,
@@ -68,8 +72,9 @@ use kaspa_consensus_core::{
     mining_rules::MiningRules,
     muhash::MuHashExtensions,
     network::NetworkType,
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
+    pruning::{IbdCheckpoint, PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     receipts::{Pochm, ProofOfPublication, TxReceipt},
+    reorg::ChainReorg,
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, SignableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     utxo::utxo_inquirer::UtxoInquirerError,
@@ -83,7 +88,10 @@ use crossbeam_channel::{
 use itertools::{EitherOrBoth, Itertools};
 use kaspa_consensusmanager::{SessionLock, SessionReadGuard};
 
-use kaspa_database::prelude::{StoreResultEmptyTuple, StoreResultExtensions};
+use kaspa_database::{
+    prelude::{StoreResultEmptyTuple, StoreResultExtensions, WriteBatch},
+    registry::DatabaseStorePrefixes,
+};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_txscript::caches::TxScriptCacheCounters;
@@ -158,6 +166,7 @@ impl Deref for Consensus {
 impl Consensus {
     pub fn new(
         db: Arc<DB>,
+        cold_db: Option<Arc<DB>>,
         config: Arc<Config>,
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
@@ -174,7 +183,7 @@ impl Consensus {
         // Storage layer
         //
 
-        let storage = ConsensusStorage::new(db.clone(), config.clone());
+        let storage = ConsensusStorage::new(db.clone(), cold_db, config.clone());
 
         //
         // Services and managers
@@ -235,8 +244,11 @@ impl Consensus {
         let header_processor = Arc::new(HeaderProcessor::new(
             receiver,
             body_sender,
+            pruning_sender.clone(),
+            pruning_receiver.clone(),
             block_processors_pool.clone(),
             params,
+            config.is_light,
             db.clone(),
             &storage,
             &services,
@@ -269,6 +281,7 @@ impl Consensus {
             pruning_lock.clone(),
             notification_root.clone(),
             counters.clone(),
+            config.reorg_notification_depth,
             mining_rules,
         ));
 
@@ -322,6 +335,9 @@ impl Consensus {
         // Upgrade to initialize the new retention root field correctly
         self.retention_root_database_upgrade();
 
+        // Upgrade to initialize the cold storage root at the retention root (nothing was moved to cold storage yet)
+        self.cold_storage_root_database_upgrade();
+
         // TODO (post HF): remove this upgrade
         // Database upgrade to include pruning samples
         self.pruning_samples_database_upgrade();
@@ -330,7 +346,7 @@ impl Consensus {
     fn retention_root_database_upgrade(&self) {
         let mut pruning_point_store = self.pruning_point_store.write();
         if pruning_point_store.retention_period_root().unwrap_option().is_none() {
-            let mut batch = rocksdb::WriteBatch::default();
+            let mut batch = WriteBatch::default();
             if self.config.is_archival {
                 // The retention checkpoint is what was previously known as history root
                 let retention_checkpoint = pruning_point_store.retention_checkpoint().unwrap();
@@ -344,6 +360,17 @@ impl Consensus {
         }
     }
 
+    fn cold_storage_root_database_upgrade(&self) {
+        let mut pruning_point_store = self.pruning_point_store.write();
+        if pruning_point_store.cold_storage_root().unwrap_option().is_none() {
+            let retention_period_root = pruning_point_store.retention_period_root().unwrap();
+            let mut batch = WriteBatch::default();
+            pruning_point_store.set_cold_storage_checkpoint(&mut batch, retention_period_root).unwrap();
+            pruning_point_store.set_cold_storage_root(&mut batch, retention_period_root).unwrap();
+            self.db.write(batch).unwrap();
+        }
+    }
+
     fn pruning_samples_database_upgrade(&self) {
         //
         // For the first time this version runs, make sure we populate pruning samples
@@ -469,6 +496,19 @@ impl Consensus {
         }
     }
 
+    /// Validates that `low` exists and has the retention root on its chain, as required from the low end of chain paths
+    fn validate_chain_path_low(&self, low: Hash) -> Result<(), ConsensusError> {
+        // Verify that the block exists
+        self.validate_block_exists(low)?;
+
+        // Verify that retention root is on chain(block)
+        self.services
+            .reachability_service
+            .is_chain_ancestor_of(self.get_retention_period_root(), low)
+            .then_some(())
+            .ok_or(ConsensusError::General("the queried hash does not have retention root on its chain"))
+    }
+
     fn estimate_network_hashes_per_second_impl(&self, ghostdag_data: &GhostdagData, window_size: usize) -> ConsensusResult<u64> {
         let window = match self.services.window_manager.block_window(ghostdag_data, WindowType::VaryingWindow(window_size)) {
             Ok(w) => w,
@@ -720,9 +760,25 @@ impl ConsensusApi for Consensus {
             // turns out to be not fast enough then we should maintain an atomic integer holding this value
             num_tips: self.get_tips_len() as u64,
             virtual_stats: self.lkg_virtual_state.load().as_ref().into(),
+            retention_stats: self.pruning_processor.retention_stats(),
+            pruning_proof_stats: self.services.pruning_proof_manager.proof_stats(),
         }
     }
 
+    fn get_storage_stats(&self) -> StorageStats {
+        // Archival data moved to the cold store is accounted under the same stores
+        let dbs = std::iter::once(&self.db).chain(self.storage.cold_db.as_ref());
+        let store_sizes = DatabaseStorePrefixes::iter()
+            .map(|prefix| (prefix as u8, format!("{prefix:?}"), dbs.clone().map(|db| db.estimated_prefix_size(prefix)).sum::<u64>()))
+            .filter(|(_, _, size)| *size > 0)
+            .collect();
+        StorageStats { total_size_bytes: dbs.map(|db| db.estimated_size()).sum(), store_sizes }
+    }
+
+    fn get_recent_reorgs(&self, limit: usize) -> Vec<ChainReorg> {
+        self.storage.chain_reorgs_store.read().get_recent(limit).unwrap()
+    }
+
     fn get_virtual_daa_score(&self) -> u64 {
         self.lkg_virtual_state.load().daa_score
     }
@@ -852,6 +908,25 @@ impl ConsensusApi for Consensus {
         BlockCount { header_count, block_count }
     }
 
+    fn set_retention_period_days(&self, retention_period_days: Option<f64>) -> ConsensusResult<()> {
+        if self.config.is_archival && self.cold_db.is_none() {
+            return Err(ConsensusError::General(
+                "archival nodes without cold storage keep all data regardless of the retention period",
+            ));
+        }
+        if let Some(retention_period_days) =
+            retention_period_days.filter(|days| days.is_nan() || *days < MINIMUM_RETENTION_PERIOD_DAYS)
+        {
+            return Err(ConsensusError::GeneralOwned(format!(
+                "retention period ({}) must be at least {} days",
+                retention_period_days, MINIMUM_RETENTION_PERIOD_DAYS
+            )));
+        }
+        kaspa_core::info!("Setting the retention period to {:?} days", retention_period_days);
+        self.pruning_processor.set_retention_period_days(retention_period_days);
+        Ok(())
+    }
+
     fn get_virtual_chain_from_block(&self, low: Hash, chain_path_added_limit: Option<usize>) -> ConsensusResult<ChainPath> {
         // Calculate chain changes between the given `low` and the current sink hash (up to `limit` amount of block hashes).
         // Note:
@@ -860,20 +935,16 @@ impl ConsensusApi for Consensus {
         // won't later need to remove it from the result.
         // 2) supplying `None` as `chain_path_added_limit` will result in the full chain path, with optimized performance.
         let _guard = self.pruning_lock.blocking_read();
-
-        // Verify that the block exists
-        self.validate_block_exists(low)?;
-
-        // Verify that retention root is on chain(block)
-        self.services
-            .reachability_service
-            .is_chain_ancestor_of(self.get_retention_period_root(), low)
-            .then_some(())
-            .ok_or(ConsensusError::General("the queried hash does not have retention root on its chain"))?;
-
+        self.validate_chain_path_low(low)?;
         Ok(self.services.dag_traversal_manager.calculate_chain_path(low, self.get_sink(), chain_path_added_limit))
     }
 
+    fn get_headers_selected_chain_from_block(&self, low: Hash, chain_path_added_limit: Option<usize>) -> ConsensusResult<ChainPath> {
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_chain_path_low(low)?;
+        Ok(self.services.dag_traversal_manager.calculate_chain_path(low, self.get_headers_selected_tip(), chain_path_added_limit))
+    }
+
     /// Returns a Vec of header samples since genesis
     /// ordered by ascending daa_score, first entry is genesis
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
@@ -1042,6 +1113,14 @@ impl ConsensusApi for Consensus {
         self.virtual_processor.import_pruning_point_utxo_set(new_pruning_point, imported_utxo_multiset)
     }
 
+    fn get_ibd_checkpoint(&self) -> Option<IbdCheckpoint> {
+        self.storage.ibd_checkpoint_store.read().get().unwrap_option()
+    }
+
+    fn set_ibd_checkpoint(&self, checkpoint: IbdCheckpoint) {
+        self.storage.ibd_checkpoint_store.write().set(&checkpoint).unwrap()
+    }
+
     fn validate_pruning_points(&self, syncer_virtual_selected_parent: Hash) -> ConsensusResult<()> {
         let hst = self.storage.headers_selected_tip_store.read().get().unwrap().hash;
         let pp_info = self.pruning_point_store.read().get().unwrap();
@@ -1322,7 +1401,11 @@ impl ConsensusApi for Consensus {
     ) -> ConsensusResult<TxReceipt> {
         if let Some(accepting_block) = accepting_block {
             //if a block hash is supplied, generate receipt directly
-            let accepting_block_header = self.headers_store.get_header(accepting_block).unwrap();
+            let accepting_block_header = self
+                .headers_store
+                .get_header(accepting_block)
+                .unwrap_option()
+                .ok_or(ConsensusError::HeaderNotFound(accepting_block))?;
             return self
                 .services
                 .tx_receipts_manager
@@ -1402,9 +1485,33 @@ impl ConsensusApi for Consensus {
     fn generate_pochm(&self, chain_purporter: Hash) -> ConsensusResult<Pochm> {
         self.services
             .tx_receipts_manager
-            .create_pochm_proof(self.headers_store.get_header(chain_purporter).unwrap())
+            .create_pochm_proof(
+                self.headers_store
+                    .get_header(chain_purporter)
+                    .unwrap_option()
+                    .ok_or(ConsensusError::HeaderNotFound(chain_purporter))?,
+            )
             .map_err(|_| ConsensusError::General("required data to create a proof of chain membership appears missing"))
     }
+    fn get_posterity_headers(&self, low: Hash, limit: usize) -> ConsensusResult<Vec<Arc<Header>>> {
+        // `low` is supplied by peers, so store errors are reported rather than unwrapped
+        if !self.headers_store.has(low).map_err(|_| ConsensusError::MissingData(low))? {
+            return Err(ConsensusError::HeaderNotFound(low));
+        }
+        let mut headers = Vec::with_capacity(limit);
+        let mut current = low;
+        while headers.len() < limit {
+            match self.services.tx_receipts_manager.get_post_posterity_block(current) {
+                Ok(posterity) => {
+                    headers.push(self.headers_store.get_header(posterity).map_err(|_| ConsensusError::MissingData(posterity))?);
+                    current = posterity;
+                }
+                Err(ReceiptsErrors::PosterityDoesNotExistYet(_)) => break,
+                Err(_) => return Err(ConsensusError::General("required data to find posterity blocks appears missing")),
+            }
+        }
+        Ok(headers)
+    }
     //Note: wallets are expected to verify on their own that the tx_id corresponds to the tx they have stored
     fn verify_tx_receipt(&self, receipt: &TxReceipt) -> bool {
         self.services.tx_receipts_manager.verify_tx_receipt(receipt)
@@ -1432,4 +1539,45 @@ impl ConsensusApi for Consensus {
         let security_margin = std::cmp::min(100 * self.config.bps().get(tip_daa), self.config.finality_depth().get(tip_daa)) / 2;
         tip_bscore >= cutoff_bscore + security_margin
     }
+    fn get_tx_confirmations(&self, tx_id: Hash, accepting_block: Hash) -> ConsensusResult<TxConfirmations> {
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_block_exists(accepting_block)?;
+        let header = self.headers_store.get_header(accepting_block).unwrap();
+
+        // Only a selected chain block accepts transactions, hence a block which was reorged out of the chain
+        // no longer accepts the transaction, even if its acceptance data is still kept
+        if !self.services.reachability_service.is_chain_ancestor_of(accepting_block, self.get_sink()) {
+            return Ok(TxConfirmations::not_accepted(header.daa_score, header.blue_score));
+        }
+        match self.acceptance_data_store.get(accepting_block).unwrap_option() {
+            Some(acceptance_data) => {
+                if !acceptance_data
+                    .iter()
+                    .any(|mergeset_data| mergeset_data.accepted_transactions.iter().any(|tx| tx.transaction_id == tx_id))
+                {
+                    return Ok(TxConfirmations::not_accepted(header.daa_score, header.blue_score));
+                }
+            }
+            // The acceptance data of chain blocks below the pruning point is pruned, so acceptance can no longer be verified
+            None if self.services.reachability_service.is_chain_ancestor_of(accepting_block, self.pruning_point()) => {
+                return Err(ConsensusError::AcceptanceDataPruned(accepting_block))
+            }
+            None => return Err(ConsensusError::MissingData(accepting_block)),
+        }
+
+        // The posterity following a chain block is the first chain block reaching the next multiple of the posterity depth
+        let posterity_depth = self.config.finality_depth().get(header.daa_score);
+        let cutoff_bscore = header.blue_score - header.blue_score % posterity_depth + posterity_depth;
+        let is_posterity_reached = self.is_posterity_reached(cutoff_bscore);
+        Ok(TxConfirmations {
+            is_accepted: true,
+            accepting_block_daa_score: header.daa_score,
+            accepting_block_blue_score: header.blue_score,
+            confirmations: self.get_virtual_daa_score().saturating_sub(header.daa_score),
+            is_final: self.services.reachability_service.is_chain_ancestor_of(accepting_block, self.finality_point()),
+            is_posterity_reached,
+            is_receipt_available: is_posterity_reached
+                && self.services.tx_receipts_manager.get_post_posterity_block(accepting_block).is_ok(),
+        })
+    }
 }
@@ -198,6 +198,7 @@ impl ConsensusServices {
             params.pruning_proof_m,
             params.anticone_finalization_depth(),
             params.ghostdag_k(),
+            config.is_light,
             is_consensus_exiting,
         ));
 
@@ -9,13 +9,16 @@ use crate::{
         ghostdag::{CompactGhostdagData, DbGhostdagStore},
         headers::{CompactHeaderData, DbHeadersStore},
         headers_selected_tip::DbHeadersSelectedTipStore,
+        ibd_checkpoint::DbIbdCheckpointStore,
         past_pruning_points::DbPastPruningPointsStore,
         pchmr_store::DbPchmrStore,
         pruning::DbPruningStore,
+        pruning_proof::DbPruningProofStore,
         pruning_samples::DbPruningSamplesStore,
         pruning_utxoset::PruningUtxosetStores,
         reachability::{DbReachabilityStore, ReachabilityData},
         relations::DbRelationsStore,
+        reorgs::DbChainReorgsStore,
         selected_chain::DbSelectedChainStore,
         statuses::DbStatusesStore,
         tips::DbTipsStore,
@@ -35,10 +38,16 @@ use kaspa_hashes::Hash;
 use parking_lot::RwLock;
 use std::{ops::DerefMut, sync::Arc};
 
+/// The number of most recent selected chain reorgs kept in the DB
+const CHAIN_REORGS_CAPACITY: u64 = 10_000;
+
 pub struct ConsensusStorage {
     // DB
     db: Arc<DB>,
 
+    /// A secondary, compressed DB holding archival block data below the cold storage root (archival nodes only)
+    pub cold_db: Option<Arc<DB>>,
+
     // Locked stores
     pub statuses_store: Arc<RwLock<DbStatusesStore>>,
     pub relations_stores: Arc<RwLock<Vec<DbRelationsStore>>>,
@@ -50,6 +59,9 @@ pub struct ConsensusStorage {
     pub pruning_utxoset_stores: Arc<RwLock<PruningUtxosetStores>>,
     pub virtual_stores: Arc<RwLock<VirtualStores>>,
     pub selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
+    pub ibd_checkpoint_store: Arc<RwLock<DbIbdCheckpointStore>>,
+    pub pruning_proof_store: Arc<RwLock<DbPruningProofStore>>,
+    pub chain_reorgs_store: Arc<RwLock<DbChainReorgsStore>>,
 
     // temporary
     pub hash_to_pchmr_store: Arc<DbPchmrStore>,
@@ -79,7 +91,7 @@ pub struct ConsensusStorage {
 }
 
 impl ConsensusStorage {
-    pub fn new(db: Arc<DB>, config: Arc<Config>) -> Arc<Self> {
+    pub fn new(db: Arc<DB>, cold_db: Option<Arc<DB>>, config: Arc<Config>) -> Arc<Self> {
         let scale_factor = config.ram_scale;
         let scaled = |s| (s as f64 * scale_factor) as usize;
 
@@ -211,18 +223,30 @@ impl ConsensusStorage {
         let headers_store = Arc::new(DbHeadersStore::new(db.clone(), headers_builder.build(), headers_compact_builder.build()));
         let depth_store = Arc::new(DbDepthStore::new(db.clone(), header_data_builder.build()));
         let selected_chain_store = Arc::new(RwLock::new(DbSelectedChainStore::new(db.clone(), header_data_builder.build())));
+        let chain_reorgs_store = Arc::new(RwLock::new(DbChainReorgsStore::new(db.clone(), CHAIN_REORGS_CAPACITY)));
 
         // Pruning
         let pruning_point_store = Arc::new(RwLock::new(DbPruningStore::new(db.clone())));
         let past_pruning_points_store = Arc::new(DbPastPruningPointsStore::new(db.clone(), past_pruning_points_builder.build()));
         let pruning_utxoset_stores = Arc::new(RwLock::new(PruningUtxosetStores::new(db.clone(), utxo_set_builder.build())));
         let pruning_samples_store = Arc::new(DbPruningSamplesStore::new(db.clone(), header_data_builder.build()));
+        let ibd_checkpoint_store = Arc::new(RwLock::new(DbIbdCheckpointStore::new(db.clone())));
+        let pruning_proof_store = Arc::new(RwLock::new(DbPruningProofStore::new(db.clone())));
 
         // Txs
-        let block_transactions_store = Arc::new(DbBlockTransactionsStore::new(db.clone(), transactions_builder.build()));
-        let utxo_diffs_store = Arc::new(DbUtxoDiffsStore::new(db.clone(), utxo_diffs_builder.build()));
+        let mut block_transactions_store = DbBlockTransactionsStore::new(db.clone(), transactions_builder.build());
+        let mut utxo_diffs_store = DbUtxoDiffsStore::new(db.clone(), utxo_diffs_builder.build());
         let utxo_multisets_store = Arc::new(DbUtxoMultisetsStore::new(db.clone(), block_data_builder.build()));
-        let acceptance_data_store = Arc::new(DbAcceptanceDataStore::new(db.clone(), acceptance_data_builder.build()));
+        let mut acceptance_data_store = DbAcceptanceDataStore::new(db.clone(), acceptance_data_builder.build());
+        // Archival data moved to the cold store remains readable through the same stores
+        if let Some(cold_db) = cold_db.clone() {
+            block_transactions_store = block_transactions_store.with_cold_store(cold_db.clone());
+            utxo_diffs_store = utxo_diffs_store.with_cold_store(cold_db.clone());
+            acceptance_data_store = acceptance_data_store.with_cold_store(cold_db);
+        }
+        let block_transactions_store = Arc::new(block_transactions_store);
+        let utxo_diffs_store = Arc::new(utxo_diffs_store);
+        let acceptance_data_store = Arc::new(acceptance_data_store);
         //temp
         let hash_to_pchmr_store = Arc::new(DbPchmrStore::new(db.clone(), header_data_builder.build()));
         //TODO: think about the correc cache policy above
@@ -232,8 +256,10 @@ impl ConsensusStorage {
         let body_tips_store = Arc::new(RwLock::new(DbTipsStore::new(db.clone())));
 
         // Block windows
-        let block_window_cache_for_difficulty = Arc::new(BlockWindowCacheStore::new(difficulty_window_builder.build()));
-        let block_window_cache_for_past_median_time = Arc::new(BlockWindowCacheStore::new(median_window_builder.build()));
+        let block_window_cache_for_difficulty =
+            Arc::new(BlockWindowCacheStore::new(difficulty_window_builder.build(), db.cache_registry()));
+        let block_window_cache_for_past_median_time =
+            Arc::new(BlockWindowCacheStore::new(median_window_builder.build(), db.cache_registry()));
 
         // Virtual stores
         let lkg_virtual_state = LkgVirtualState::default();
@@ -246,6 +272,7 @@ impl ConsensusStorage {
 
         Arc::new(Self {
             db,
+            cold_db,
             statuses_store,
             relations_stores,
             reachability_relations_store,
@@ -259,6 +286,9 @@ impl ConsensusStorage {
             pruning_utxoset_stores,
             virtual_stores,
             selected_chain_store,
+            ibd_checkpoint_store,
+            pruning_proof_store,
+            chain_reorgs_store,
             acceptance_data_store,
             past_pruning_points_store,
             daa_excluded_store,
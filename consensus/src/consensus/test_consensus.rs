@@ -33,8 +33,7 @@ use crate::{
     pipeline::{body_processor::BlockBodyProcessor, virtual_processor::VirtualStateProcessor, ProcessingCounters},
     test_helpers::header_from_precomputed_hash,
 };
-use kaspa_database::create_temp_db;
-use kaspa_database::prelude::ConnBuilder;
+use kaspa_database::create_memory_db;
 use std::future::Future;
 use std::{sync::Arc, thread::JoinHandle};
 
@@ -53,6 +52,7 @@ impl TestConsensus {
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -66,14 +66,15 @@ impl TestConsensus {
         Self { params: config.params.clone(), consensus, block_builder, db_lifetime: Default::default() }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and the provided `notification_sender`
+    /// Creates a test consensus instance based on `config` with an in-memory DB and the provided `notification_sender`
     pub fn with_notifier(config: &Config, notification_sender: Sender<Notification>, context: SubscriptionContext) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_memory_db!();
         let notification_root = Arc::new(ConsensusNotificationRoot::with_context(notification_sender, context));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -87,15 +88,16 @@ impl TestConsensus {
         Self { consensus, block_builder, params: config.params.clone(), db_lifetime }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and no notifier
+    /// Creates a test consensus instance based on `config` with an in-memory DB and no notifier
     pub fn new(config: &Config) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_memory_db!();
         let (dummy_notification_sender, _) = async_channel::unbounded();
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -293,6 +295,10 @@ impl ConsensusFactory for TestConsensusFactory {
         unimplemented!()
     }
 
+    fn existing_staging_consensus(&self) -> Option<(ConsensusInstance, DynConsensusCtl)> {
+        unimplemented!()
+    }
+
     fn close(&self) {
         self.tc.notification_root().close();
     }
@@ -4,12 +4,12 @@ use kaspa_consensus_core::acceptance_data::MergesetBlockAcceptanceData;
 use kaspa_consensus_core::BlockHasher;
 use kaspa_database::prelude::CachePolicy;
 use kaspa_database::prelude::StoreError;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
 use kaspa_utils::mem_size::MemSizeEstimator;
-use rocksdb::WriteBatch;
 use serde::Deserialize;
 use serde::Serialize;
 use std::sync::Arc;
@@ -37,19 +37,32 @@ impl MemSizeEstimator for AcceptanceDataEntry {
 }
 
 /// A DB + cache implementation of `DbAcceptanceDataStore` trait, with concurrency support.
+/// Reads fall through to the cold store (if any) for acceptance data which was moved there.
 #[derive(Clone)]
 pub struct DbAcceptanceDataStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, AcceptanceDataEntry, BlockHasher>,
+    cold_access: Option<CachedDbAccess<Hash, AcceptanceDataEntry, BlockHasher>>,
 }
 
 impl DbAcceptanceDataStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AcceptanceData.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AcceptanceData.into()),
+            cold_access: None,
+        }
+    }
+
+    pub fn with_cold_store(self, cold_db: Arc<DB>) -> Self {
+        Self {
+            cold_access: Some(CachedDbAccess::new(cold_db, CachePolicy::Empty, DatabaseStorePrefixes::AcceptanceData.into())),
+            ..self
+        }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self { cold_access: self.cold_access.clone(), ..Self::new(Arc::clone(&self.db), cache_policy) }
     }
 
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, acceptance_data: Arc<AcceptanceData>) -> Result<(), StoreError> {
@@ -63,11 +76,29 @@ impl DbAcceptanceDataStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Moves the acceptance data (if held by the main DB) to the cold store. See `DbBlockTransactionsStore::move_to_cold_batch`
+    pub fn move_to_cold_batch(&self, batch: &mut WriteBatch, cold_batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        let Some(cold_access) = &self.cold_access else {
+            return Ok(());
+        };
+        match self.access.read(hash) {
+            Ok(entry) => {
+                cold_access.write(BatchDbWriter::new(cold_batch), hash, entry)?;
+                self.access.delete(BatchDbWriter::new(batch), hash)
+            }
+            Err(StoreError::KeyNotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 impl AcceptanceDataStoreReader for DbAcceptanceDataStore {
     fn get(&self, hash: Hash) -> Result<Arc<AcceptanceData>, StoreError> {
-        Ok(self.access.read(hash)?.0)
+        match (self.access.read(hash), &self.cold_access) {
+            (Err(StoreError::KeyNotFound(_)), Some(cold_access)) => Ok(cold_access.read(hash)?.0),
+            (result, _) => Ok(result?.0),
+        }
     }
 }
 
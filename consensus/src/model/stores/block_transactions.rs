@@ -2,12 +2,12 @@ use kaspa_consensus_core::tx::{TransactionInput, TransactionOutput};
 use kaspa_consensus_core::{tx::Transaction, BlockHasher};
 use kaspa_database::prelude::CachePolicy;
 use kaspa_database::prelude::StoreError;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
 use kaspa_utils::mem_size::MemSizeEstimator;
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -42,23 +42,39 @@ impl MemSizeEstimator for BlockBody {
 }
 
 /// A DB + cache implementation of `BlockTransactionsStore` trait, with concurrency support.
+/// Reads fall through to the cold store (if any) for bodies which were moved there.
 #[derive(Clone)]
 pub struct DbBlockTransactionsStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, BlockBody, BlockHasher>,
+    cold_access: Option<CachedDbAccess<Hash, BlockBody, BlockHasher>>,
 }
 
 impl DbBlockTransactionsStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BlockTransactions.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BlockTransactions.into()),
+            cold_access: None,
+        }
+    }
+
+    pub fn with_cold_store(self, cold_db: Arc<DB>) -> Self {
+        Self {
+            cold_access: Some(CachedDbAccess::new(cold_db, CachePolicy::Empty, DatabaseStorePrefixes::BlockTransactions.into())),
+            ..self
+        }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self { cold_access: self.cold_access.clone(), ..Self::new(Arc::clone(&self.db), cache_policy) }
     }
 
     pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
-        self.access.has(hash)
+        match &self.cold_access {
+            Some(cold_access) => Ok(self.access.has(hash)? || cold_access.has(hash)?),
+            None => self.access.has(hash),
+        }
     }
 
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, transactions: Arc<Vec<Transaction>>) -> Result<(), StoreError> {
@@ -72,11 +88,30 @@ impl DbBlockTransactionsStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Moves the block body (if held by the main DB) to the cold store. The write to the cold store is staged in
+    /// `cold_batch` which must be committed before `batch`, so that the body remains readable throughout
+    pub fn move_to_cold_batch(&self, batch: &mut WriteBatch, cold_batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        let Some(cold_access) = &self.cold_access else {
+            return Ok(());
+        };
+        match self.access.read(hash) {
+            Ok(body) => {
+                cold_access.write(BatchDbWriter::new(cold_batch), hash, body)?;
+                self.access.delete(BatchDbWriter::new(batch), hash)
+            }
+            Err(StoreError::KeyNotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 impl BlockTransactionsStoreReader for DbBlockTransactionsStore {
     fn get(&self, hash: Hash) -> Result<Arc<Vec<Transaction>>, StoreError> {
-        Ok(self.access.read(hash)?.0)
+        match (self.access.read(hash), &self.cold_access) {
+            (Err(StoreError::KeyNotFound(_)), Some(cold_access)) => Ok(cold_access.read(hash)?.0),
+            (result, _) => Ok(result?.0),
+        }
     }
 }
 
@@ -93,3 +128,35 @@ impl BlockTransactionsStore for DbBlockTransactionsStore {
         self.access.delete(DirectDbWriter::new(&self.db), hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_move_to_cold_store() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_cold_lifetime, cold_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10).with_compression(true));
+        let store = DbBlockTransactionsStore::new(db.clone(), CachePolicy::Count(10)).with_cold_store(cold_db.clone());
+        let transactions = Arc::new(vec![]);
+        store.insert(1.into(), transactions.clone()).unwrap();
+
+        let (mut batch, mut cold_batch) = (WriteBatch::default(), WriteBatch::default());
+        store.move_to_cold_batch(&mut batch, &mut cold_batch, 1.into()).unwrap();
+        cold_db.write(cold_batch).unwrap();
+        db.write(batch).unwrap();
+
+        // The body is read through from the cold store, also by a store with a fresh cache
+        assert!(!DbBlockTransactionsStore::new(db.clone(), CachePolicy::Empty).has(1.into()).unwrap());
+        assert!(store.has(1.into()).unwrap());
+        assert_eq!(store.get(1.into()).unwrap(), transactions);
+        assert_eq!(store.clone_with_new_cache(CachePolicy::Empty).get(1.into()).unwrap(), transactions);
+
+        // Moving again is a no-op since the main DB no longer holds the body
+        let (mut batch, mut cold_batch) = (WriteBatch::default(), WriteBatch::default());
+        store.move_to_cold_batch(&mut batch, &mut cold_batch, 1.into()).unwrap();
+        assert!(cold_batch.is_empty());
+        assert!(matches!(store.get(2.into()), Err(StoreError::KeyNotFound(_))));
+    }
+}
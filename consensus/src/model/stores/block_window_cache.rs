@@ -1,6 +1,6 @@
 use crate::processes::ghostdag::ordering::SortableBlock;
 use kaspa_consensus_core::BlockHasher;
-use kaspa_database::prelude::{Cache, CachePolicy};
+use kaspa_database::prelude::{Cache, CachePolicy, TrackedCacheRegistry};
 use kaspa_hashes::Hash;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use std::{
@@ -61,8 +61,8 @@ pub struct BlockWindowCacheStore {
 }
 
 impl BlockWindowCacheStore {
-    pub fn new(policy: CachePolicy) -> Self {
-        Self { inner: Cache::new(policy) }
+    pub fn new(policy: CachePolicy, registry: Option<&TrackedCacheRegistry>) -> Self {
+        Self { inner: Cache::with_registry(policy, registry) }
     }
 
     pub fn contains_key(&self, key: &Hash) -> bool {
@@ -1,9 +1,9 @@
 use crate::processes::ghostdag::ordering::SortableBlock;
 use kaspa_database::prelude::StoreResult;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
-use rocksdb::WriteBatch;
 use std::sync::Arc;
 
 /// Reader API for `SelectedTipStore`.
@@ -0,0 +1,40 @@
+use kaspa_consensus_core::pruning::IbdCheckpoint;
+use kaspa_database::prelude::StoreResult;
+use kaspa_database::prelude::DB;
+use kaspa_database::prelude::{CachedDbItem, DirectDbWriter};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use std::sync::Arc;
+
+/// Reader API for `IbdCheckpointStore`.
+pub trait IbdCheckpointStoreReader {
+    fn get(&self) -> StoreResult<IbdCheckpoint>;
+}
+
+pub trait IbdCheckpointStore: IbdCheckpointStoreReader {
+    fn set(&mut self, checkpoint: &IbdCheckpoint) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `IbdCheckpointStore` trait
+#[derive(Clone)]
+pub struct DbIbdCheckpointStore {
+    db: Arc<DB>,
+    access: CachedDbItem<IbdCheckpoint>,
+}
+
+impl DbIbdCheckpointStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::IbdCheckpoint.into()) }
+    }
+}
+
+impl IbdCheckpointStoreReader for DbIbdCheckpointStore {
+    fn get(&self) -> StoreResult<IbdCheckpoint> {
+        self.access.read()
+    }
+}
+
+impl IbdCheckpointStore for DbIbdCheckpointStore {
+    fn set(&mut self, checkpoint: &IbdCheckpoint) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), checkpoint)
+    }
+}
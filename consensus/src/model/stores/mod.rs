@@ -7,13 +7,16 @@ pub mod depth;
 pub mod ghostdag;
 pub mod headers;
 pub mod headers_selected_tip;
+pub mod ibd_checkpoint;
 pub mod past_pruning_points;
 pub mod pchmr_store;
 pub mod pruning;
+pub mod pruning_proof;
 pub mod pruning_samples;
 pub mod pruning_utxoset;
 pub mod reachability;
 pub mod relations;
+pub mod reorgs;
 pub mod selected_chain;
 pub mod statuses;
 pub mod tips;
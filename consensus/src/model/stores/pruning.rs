@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
 use kaspa_database::prelude::StoreResult;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Serialize, Deserialize)]
@@ -49,6 +49,14 @@ pub trait PruningStoreReader {
     // After pruning, this is updated to point to the retention period root.
     // This checkpoint is used to determine if pruning has successfully completed.
     fn retention_checkpoint(&self) -> StoreResult<Hash>;
+
+    /// For archival nodes with a cold store, block bodies, acceptance data and UTXO diffs of blocks in the strict past
+    /// of this point are moved out of the main DB and into the cold store. Advanced at every pruning point movement
+    /// similarly to the retention period root of non-archival nodes.
+    fn cold_storage_root(&self) -> StoreResult<Hash>;
+
+    // Similar to the retention checkpoint: the cold storage root up to which data was fully moved to the cold store
+    fn cold_storage_checkpoint(&self) -> StoreResult<Hash>;
 }
 
 pub trait PruningStore: PruningStoreReader {
@@ -62,6 +70,8 @@ pub struct DbPruningStore {
     access: CachedDbItem<PruningPointInfo>,
     retention_checkpoint_access: CachedDbItem<Hash>,
     retention_period_root_access: CachedDbItem<Hash>,
+    cold_storage_root_access: CachedDbItem<Hash>,
+    cold_storage_checkpoint_access: CachedDbItem<Hash>,
 }
 
 impl DbPruningStore {
@@ -70,7 +80,9 @@ impl DbPruningStore {
             db: Arc::clone(&db),
             access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::PruningPoint.into()),
             retention_checkpoint_access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::RetentionCheckpoint.into()),
-            retention_period_root_access: CachedDbItem::new(db, DatabaseStorePrefixes::RetentionPeriodRoot.into()),
+            retention_period_root_access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::RetentionPeriodRoot.into()),
+            cold_storage_root_access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::ColdStorageRoot.into()),
+            cold_storage_checkpoint_access: CachedDbItem::new(db, DatabaseStorePrefixes::ColdStorageCheckpoint.into()),
         }
     }
 
@@ -89,6 +101,14 @@ impl DbPruningStore {
     pub fn set_retention_period_root(&mut self, batch: &mut WriteBatch, retention_period_root: Hash) -> StoreResult<()> {
         self.retention_period_root_access.write(BatchDbWriter::new(batch), &retention_period_root)
     }
+
+    pub fn set_cold_storage_root(&mut self, batch: &mut WriteBatch, cold_storage_root: Hash) -> StoreResult<()> {
+        self.cold_storage_root_access.write(BatchDbWriter::new(batch), &cold_storage_root)
+    }
+
+    pub fn set_cold_storage_checkpoint(&mut self, batch: &mut WriteBatch, cold_storage_checkpoint: Hash) -> StoreResult<()> {
+        self.cold_storage_checkpoint_access.write(BatchDbWriter::new(batch), &cold_storage_checkpoint)
+    }
 }
 
 impl PruningStoreReader for DbPruningStore {
@@ -115,6 +135,14 @@ impl PruningStoreReader for DbPruningStore {
     fn retention_period_root(&self) -> StoreResult<Hash> {
         self.retention_period_root_access.read()
     }
+
+    fn cold_storage_root(&self) -> StoreResult<Hash> {
+        self.cold_storage_root_access.read()
+    }
+
+    fn cold_storage_checkpoint(&self) -> StoreResult<Hash> {
+        self.cold_storage_checkpoint_access.read()
+    }
 }
 
 impl PruningStore for DbPruningStore {
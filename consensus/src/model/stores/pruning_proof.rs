@@ -0,0 +1,50 @@
+use kaspa_consensus_core::pruning::PruningPointProof;
+use kaspa_database::prelude::StoreResult;
+use kaspa_database::prelude::DB;
+use kaspa_database::prelude::{CachedDbItem, DirectDbWriter};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_hashes::Hash;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A pruning point proof along with the pruning point it was built for
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoredPruningPointProof {
+    pub pruning_point: Hash,
+    pub proof: Arc<PruningPointProof>,
+}
+
+/// Reader API for `PruningProofStore`.
+pub trait PruningProofStoreReader {
+    fn get(&self) -> StoreResult<StoredPruningPointProof>;
+}
+
+pub trait PruningProofStore: PruningProofStoreReader {
+    fn set(&mut self, pruning_point: Hash, proof: Arc<PruningPointProof>) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `PruningProofStore` trait. Only the proof of the latest
+/// pruning point is kept, so that it can be served right after a restart without being rebuilt
+#[derive(Clone)]
+pub struct DbPruningProofStore {
+    db: Arc<DB>,
+    access: CachedDbItem<StoredPruningPointProof>,
+}
+
+impl DbPruningProofStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::PruningPointProof.into()) }
+    }
+}
+
+impl PruningProofStoreReader for DbPruningProofStore {
+    fn get(&self) -> StoreResult<StoredPruningPointProof> {
+        self.access.read()
+    }
+}
+
+impl PruningProofStore for DbPruningProofStore {
+    fn set(&mut self, pruning_point: Hash, proof: Arc<PruningPointProof>) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), &StoredPruningPointProof { pruning_point, proof })
+    }
+}
@@ -3,11 +3,11 @@ use std::sync::Arc;
 use kaspa_consensus_core::BlockHasher;
 use kaspa_database::prelude::CachePolicy;
 use kaspa_database::prelude::StoreError;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 
 pub trait PruningSamplesStoreReader {
     fn pruning_sample_from_pov(&self, hash: Hash) -> Result<Hash, StoreError>;
@@ -12,9 +12,9 @@ use kaspa_database::{
 use kaspa_hashes::Hash;
 
 use itertools::Itertools;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
-use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::hash_map::Entry::{Occupied, Vacant},
@@ -198,8 +198,14 @@ impl DbReachabilityStore {
         Self {
             db: db.clone(),
             access,
-            children_access: DbReachabilitySet::new(DbSetAccess::new(db.clone(), children_prefix), Cache::new(sets_cache_policy)),
-            fcs_access: DbReachabilitySet::new(DbSetAccess::new(db.clone(), fcs_prefix), Cache::new(sets_cache_policy)),
+            children_access: DbReachabilitySet::new(
+                DbSetAccess::new(db.clone(), children_prefix),
+                Cache::with_registry(sets_cache_policy, db.cache_registry()),
+            ),
+            fcs_access: DbReachabilitySet::new(
+                DbSetAccess::new(db.clone(), fcs_prefix),
+                Cache::with_registry(sets_cache_policy, db.cache_registry()),
+            ),
             reindex_root: CachedDbItem::new(db, reindex_root_prefix),
             prefix_end,
         }
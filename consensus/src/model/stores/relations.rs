@@ -1,6 +1,7 @@
 use itertools::Itertools;
 use kaspa_consensus_core::BlockHashSet;
 use kaspa_consensus_core::{blockhash::BlockHashes, BlockHashMap, BlockHasher, BlockLevel};
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::{BatchDbWriter, CachePolicy, DbWriter};
 use kaspa_database::prelude::{CachedDbAccess, DbKey, DirectDbWriter};
 use kaspa_database::prelude::{DirectWriter, MemoryWriter};
@@ -8,7 +9,6 @@ use kaspa_database::prelude::{ReadLock, StoreError};
 use kaspa_database::prelude::{StoreResult, DB};
 use kaspa_database::registry::{DatabaseStorePrefixes, SEPARATOR};
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
 use std::iter::once;
@@ -423,8 +423,8 @@ mod tests {
         test_relations_store(DbRelationsStore::new(
             db,
             0,
-            CachePolicy::Tracked { max_size: 2, min_items: 0, mem_mode: MemMode::Units },
-            CachePolicy::Tracked { max_size: 2, min_items: 0, mem_mode: MemMode::Units },
+            CachePolicy::Tracked { max_size: 2, min_items: 0, mem_mode: MemMode::Units, unit_bytes: 1 },
+            CachePolicy::Tracked { max_size: 2, min_items: 0, mem_mode: MemMode::Units, unit_bytes: 1 },
         ));
         drop(lt)
     }
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::reorg::ChainReorg;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, WriteBatch, DB};
+use kaspa_database::prelude::{StoreError, StoreResult};
+use kaspa_database::registry::DatabaseStorePrefixes;
+
+use super::U64Key;
+
+pub trait ChainReorgsStoreReader {
+    /// Returns up to `limit` of the most recent reorgs, ordered from the most recent one
+    fn get_recent(&self, limit: usize) -> StoreResult<Vec<ChainReorg>>;
+}
+
+pub trait ChainReorgsStore: ChainReorgsStoreReader {
+    /// Appends the reorg, dropping the oldest reorg if the store is full
+    fn insert(&mut self, reorg: ChainReorg) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `ChainReorgsStore` trait, holding the last `capacity` reorgs of the virtual selected
+/// chain. Reorgs are indexed by their overall order, so that the store acts as a persistent ring buffer
+#[derive(Clone)]
+pub struct DbChainReorgsStore {
+    db: Arc<DB>,
+    capacity: u64,
+    access: CachedDbAccess<U64Key, ChainReorg>,
+    /// The overall number of reorgs ever recorded, which is also the index of the next reorg
+    count: CachedDbItem<u64>,
+}
+
+impl DbChainReorgsStore {
+    pub fn new(db: Arc<DB>, capacity: u64) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            capacity,
+            access: CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::ChainReorgs.into()),
+            count: CachedDbItem::new(db, DatabaseStorePrefixes::ChainReorgsCount.into()),
+        }
+    }
+
+    fn count(&self) -> StoreResult<u64> {
+        match self.count.read() {
+            Err(StoreError::KeyNotFound(_)) => Ok(0),
+            result => result,
+        }
+    }
+}
+
+impl ChainReorgsStoreReader for DbChainReorgsStore {
+    fn get_recent(&self, limit: usize) -> StoreResult<Vec<ChainReorg>> {
+        let count = self.count()?;
+        let first = count.saturating_sub(self.capacity.min(limit as u64));
+        (first..count).rev().map(|index| self.access.read(index.into())).collect()
+    }
+}
+
+impl ChainReorgsStore for DbChainReorgsStore {
+    fn insert(&mut self, reorg: ChainReorg) -> StoreResult<()> {
+        let index = self.count()?;
+        let mut batch = WriteBatch::default();
+        self.access.write(BatchDbWriter::new(&mut batch), index.into(), reorg)?;
+        if index >= self.capacity {
+            self.access.delete(BatchDbWriter::new(&mut batch), (index - self.capacity).into())?;
+        }
+        self.count.write(BatchDbWriter::new(&mut batch), &(index + 1))?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn reorg(removed_depth: u64) -> ChainReorg {
+        ChainReorg {
+            removed_depth,
+            added_depth: removed_depth + 1,
+            removed_sink: removed_depth.into(),
+            added_sink: (removed_depth + 1).into(),
+            daa_score: removed_depth * 10,
+            timestamp: removed_depth * 1000,
+        }
+    }
+
+    #[test]
+    fn test_chain_reorgs_store_capacity() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = DbChainReorgsStore::new(db.clone(), 3);
+        assert!(store.get_recent(10).unwrap().is_empty());
+
+        (1..=5).for_each(|depth| store.insert(reorg(depth)).unwrap());
+
+        // Only the last `capacity` reorgs are kept, most recent first
+        assert_eq!(store.get_recent(10).unwrap(), vec![reorg(5), reorg(4), reorg(3)]);
+        assert_eq!(store.get_recent(2).unwrap(), vec![reorg(5), reorg(4)]);
+        assert!(matches!(store.access.read(0u64.into()), Err(StoreError::KeyNotFound(_))));
+
+        // The ring buffer position survives a store reload
+        let mut store = DbChainReorgsStore::new(db, 3);
+        store.insert(reorg(6)).unwrap();
+        assert_eq!(store.get_recent(usize::MAX).unwrap(), vec![reorg(6), reorg(5), reorg(4)]);
+    }
+}
@@ -1,8 +1,8 @@
 use kaspa_consensus_core::blockstatus::BlockStatus;
 use kaspa_consensus_core::ChainPath;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::registry::DatabaseStorePrefixes;
 use parking_lot::RwLockWriteGuard;
-use rocksdb::WriteBatch;
 
 use std::sync::Arc;
 
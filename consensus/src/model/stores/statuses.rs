@@ -1,7 +1,7 @@
 use kaspa_consensus_core::{blockstatus::BlockStatus, BlockHasher};
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::registry::DatabaseStorePrefixes;
 use parking_lot::{RwLock, RwLockWriteGuard};
-use rocksdb::WriteBatch;
 use std::sync::Arc;
 
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
@@ -3,11 +3,11 @@ use std::sync::Arc;
 use kaspa_consensus_core::{utxo::utxo_diff::UtxoDiff, BlockHasher};
 use kaspa_database::prelude::CachePolicy;
 use kaspa_database::prelude::StoreError;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
-use rocksdb::WriteBatch;
 
 /// Store for holding the UTXO difference (delta) of a block relative to its selected parent.
 /// Note that this data is lazy-computed only for blocks which are candidates to being chain
@@ -24,19 +24,29 @@ pub trait UtxoDiffsStore: UtxoDiffsStoreReader {
 }
 
 /// A DB + cache implementation of `UtxoDifferencesStore` trait, with concurrency support.
+/// Reads fall through to the cold store (if any) for diffs which were moved there.
 #[derive(Clone)]
 pub struct DbUtxoDiffsStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, Arc<UtxoDiff>, BlockHasher>,
+    cold_access: Option<CachedDbAccess<Hash, Arc<UtxoDiff>, BlockHasher>>,
 }
 
 impl DbUtxoDiffsStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::UtxoDiffs.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::UtxoDiffs.into()),
+            cold_access: None,
+        }
+    }
+
+    pub fn with_cold_store(self, cold_db: Arc<DB>) -> Self {
+        Self { cold_access: Some(CachedDbAccess::new(cold_db, CachePolicy::Empty, DatabaseStorePrefixes::UtxoDiffs.into())), ..self }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self { cold_access: self.cold_access.clone(), ..Self::new(Arc::clone(&self.db), cache_policy) }
     }
 
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, utxo_diff: Arc<UtxoDiff>) -> Result<(), StoreError> {
@@ -50,11 +60,29 @@ impl DbUtxoDiffsStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Moves the UTXO diff (if held by the main DB) to the cold store. See `DbBlockTransactionsStore::move_to_cold_batch`
+    pub fn move_to_cold_batch(&self, batch: &mut WriteBatch, cold_batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        let Some(cold_access) = &self.cold_access else {
+            return Ok(());
+        };
+        match self.access.read(hash) {
+            Ok(utxo_diff) => {
+                cold_access.write(BatchDbWriter::new(cold_batch), hash, utxo_diff)?;
+                self.access.delete(BatchDbWriter::new(batch), hash)
+            }
+            Err(StoreError::KeyNotFound(_)) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
 }
 
 impl UtxoDiffsStoreReader for DbUtxoDiffsStore {
     fn get(&self, hash: Hash) -> Result<Arc<UtxoDiff>, StoreError> {
-        self.access.read(hash)
+        match (self.access.read(hash), &self.cold_access) {
+            (Err(StoreError::KeyNotFound(_)), Some(cold_access)) => cold_access.read(hash),
+            (result, _) => result,
+        }
     }
 }
 
@@ -39,11 +39,11 @@ use kaspa_consensus_notify::{
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::SessionLock;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_hashes::Hash;
 use kaspa_notify::notifier::Notify;
 use parking_lot::RwLock;
 use rayon::ThreadPool;
-use rocksdb::WriteBatch;
 use std::sync::{atomic::Ordering, Arc};
 
 pub struct BlockBodyProcessor {
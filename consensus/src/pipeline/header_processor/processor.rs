@@ -17,6 +17,7 @@ use crate::{
             headers::{DbHeadersStore, HeaderStoreReader},
             headers_selected_tip::{DbHeadersSelectedTipStore, HeadersSelectedTipStoreReader},
             pruning::{DbPruningStore, PruningPointInfo, PruningStoreReader},
+            pruning_samples::DbPruningSamplesStore,
             reachability::{DbReachabilityStore, StagingReachabilityStore},
             relations::{DbRelationsStore, RelationsStoreReader},
             statuses::{DbStatusesStore, StatusesStore, StatusesStoreBatchExtensions, StatusesStoreReader},
@@ -24,7 +25,10 @@ use crate::{
         },
     },
     params::Params,
-    pipeline::deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+    pipeline::{
+        deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+        pruning_processor::processor::PruningProcessingMessage,
+    },
     processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations::RelationsStoreExtensions},
 };
 use crossbeam_channel::{Receiver, Sender};
@@ -40,12 +44,12 @@ use kaspa_consensus_core::{
     BlockHashSet, BlockLevel,
 };
 use kaspa_consensusmanager::SessionLock;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::{StoreResultEmptyTuple, StoreResultExtensions};
 use kaspa_hashes::Hash;
 use kaspa_utils::vec::VecExtensions;
 use parking_lot::RwLock;
 use rayon::ThreadPool;
-use rocksdb::WriteBatch;
 use std::sync::{atomic::Ordering, Arc};
 
 use super::super::ProcessingCounters;
@@ -116,6 +120,8 @@ pub struct HeaderProcessor {
     // Channels
     receiver: Receiver<BlockProcessingMessage>,
     body_sender: Sender<BlockProcessingMessage>,
+    pruning_sender: Sender<PruningProcessingMessage>,
+    pruning_receiver: Receiver<PruningProcessingMessage>,
 
     // Thread pool
     pub(super) thread_pool: Arc<ThreadPool>,
@@ -128,6 +134,7 @@ pub struct HeaderProcessor {
     pub(super) skip_proof_of_work: bool,
     pub(super) max_block_level: BlockLevel,
     pub(super) crescendo_activation: ForkActivation,
+    is_light: bool,
 
     // DB
     db: Arc<DB>,
@@ -144,6 +151,7 @@ pub struct HeaderProcessor {
     pub(super) daa_excluded_store: Arc<DbDaaStore>,
     pub(super) headers_store: Arc<DbHeadersStore>,
     pub(super) headers_selected_tip_store: Arc<RwLock<DbHeadersSelectedTipStore>>,
+    pub(super) pruning_samples_store: Arc<DbPruningSamplesStore>,
     pub(super) depth_store: Arc<DbDepthStore>,
 
     // Managers and services
@@ -169,8 +177,11 @@ impl HeaderProcessor {
     pub fn new(
         receiver: Receiver<BlockProcessingMessage>,
         body_sender: Sender<BlockProcessingMessage>,
+        pruning_sender: Sender<PruningProcessingMessage>,
+        pruning_receiver: Receiver<PruningProcessingMessage>,
         thread_pool: Arc<ThreadPool>,
         params: &Params,
+        is_light: bool,
         db: Arc<DB>,
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
@@ -180,6 +191,8 @@ impl HeaderProcessor {
         Self {
             receiver,
             body_sender,
+            pruning_sender,
+            pruning_receiver,
             thread_pool,
             genesis: params.genesis.clone(),
             db,
@@ -194,6 +207,7 @@ impl HeaderProcessor {
             headers_store: storage.headers_store.clone(),
             depth_store: storage.depth_store.clone(),
             headers_selected_tip_store: storage.headers_selected_tip_store.clone(),
+            pruning_samples_store: storage.pruning_samples_store.clone(),
             block_window_cache_for_difficulty: storage.block_window_cache_for_difficulty.clone(),
             block_window_cache_for_past_median_time: storage.block_window_cache_for_past_median_time.clone(),
 
@@ -215,6 +229,7 @@ impl HeaderProcessor {
             skip_proof_of_work: params.skip_proof_of_work,
             max_block_level: params.max_block_level,
             crescendo_activation: params.crescendo_activation,
+            is_light,
         }
     }
 
@@ -406,9 +421,17 @@ impl HeaderProcessor {
         // Note we need to keep the lock write guards until the batch is written.
         let mut hst_write = self.headers_selected_tip_store.write();
         let prev_hst = hst_write.get().unwrap();
-        if SortableBlock::new(ctx.hash, header.blue_work) > prev_hst
-            && reachability::is_chain_ancestor_of(&staging, pp, ctx.hash).unwrap()
-        {
+        let is_selected_tip_candidate = SortableBlock::new(ctx.hash, header.blue_work) > prev_hst;
+        let is_chain_qualified =
+            (self.is_light || is_selected_tip_candidate) && reachability::is_chain_ancestor_of(&staging, pp, ctx.hash).unwrap();
+        if self.is_light && is_chain_qualified {
+            // Light nodes never validate UTXO state, hence the pruning samples of chain qualified headers are populated here
+            let pruning_sample_from_pov =
+                self.pruning_point_manager.expected_header_pruning_point_v2(ghostdag_data.to_compact()).pruning_sample;
+            self.pruning_samples_store.insert_batch(&mut batch, ctx.hash, pruning_sample_from_pov).unwrap_or_exists();
+        }
+        let is_new_selected_tip = is_selected_tip_candidate && is_chain_qualified;
+        if is_new_selected_tip {
             // Hint reachability about the new tip.
             reachability::hint_virtual_selected_parent(&mut staging, ctx.hash).unwrap();
             hst_write.set_batch(&mut batch, SortableBlock::new(ctx.hash, header.blue_work)).unwrap();
@@ -444,6 +467,13 @@ impl HeaderProcessor {
         drop(statuses_write);
         drop(reachability_relations_write);
         drop(relations_write);
+
+        // Light nodes never resolve virtual, hence the pruning point is advanced by the headers selected tip instead of the sink.
+        // The selected tip write guard is still held, so messages are sent in tip order and the drained channel cannot block
+        if self.is_light && is_new_selected_tip {
+            let _consume = self.pruning_receiver.try_iter().count();
+            self.pruning_sender.send(PruningProcessingMessage::Process { sink_ghostdag_data: ghostdag_data.to_compact() }).unwrap();
+        }
         drop(hst_write);
     }
 
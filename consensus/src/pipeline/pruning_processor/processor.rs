@@ -10,11 +10,12 @@ use crate::{
         stores::{
             ghostdag::{CompactGhostdagData, GhostdagStoreReader},
             headers::HeaderStoreReader,
+            headers_selected_tip::HeadersSelectedTipStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::{PruningStore, PruningStoreReader},
             pruning_samples::PruningSamplesStoreReader,
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
-            relations::StagingRelationsStore,
+            relations::{RelationsStoreReader, StagingRelationsStore},
             selected_chain::{SelectedChainStore, SelectedChainStoreReader},
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
@@ -26,6 +27,7 @@ use crate::{
 use crossbeam_channel::Receiver as CrossbeamReceiver;
 use itertools::Itertools;
 use kaspa_consensus_core::{
+    api::RetentionStats,
     blockhash::ORIGIN,
     blockstatus::BlockStatus::StatusHeaderOnly,
     config::Config,
@@ -36,22 +38,57 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensusmanager::SessionLock;
 use kaspa_core::{debug, info, trace, warn};
-use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, DB};
+use kaspa_database::{
+    prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, WriteBatch, DB},
+    registry::DatabaseStorePrefixes,
+};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_utils::iter::IterExtensions;
-use parking_lot::RwLockUpgradableReadGuard;
-use rocksdb::WriteBatch;
+use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use std::{
     collections::{hash_map::Entry::Vacant, VecDeque},
     ops::Deref,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::{Duration, Instant},
 };
 
+/// The number of blocks whose data is moved to the cold store per batch
+const COLD_STORAGE_BATCH_SIZE: usize = 256;
+
+/// The minimal interval between retention period root adjustments made in between pruning point movements
+const RETENTION_ADJUSTMENT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The deletion budget of a single retention period root adjustment, in units of finality depth (in DAA score). Hence a
+/// shortened retention period is applied over several adjustments rather than by a single large prune pass
+const RETENTION_ADJUSTMENT_BUDGET: u64 = 2;
+
+/// The stores whose data is deleted by pruning (or moved out by cold storage). These are compacted after pruning rounds
+/// so that the disk space of the deleted entries is reclaimed promptly rather than whenever RocksDB compacts them on its own
+const PRUNED_STORE_PREFIXES: &[DatabaseStorePrefixes] = &[
+    DatabaseStorePrefixes::AcceptanceData,
+    DatabaseStorePrefixes::BlockTransactions,
+    DatabaseStorePrefixes::NonDaaMergeset,
+    DatabaseStorePrefixes::BlockDepth,
+    DatabaseStorePrefixes::Ghostdag,
+    DatabaseStorePrefixes::GhostdagCompact,
+    DatabaseStorePrefixes::Headers,
+    DatabaseStorePrefixes::HeadersCompact,
+    DatabaseStorePrefixes::Reachability,
+    DatabaseStorePrefixes::ReachabilityRelations,
+    DatabaseStorePrefixes::RelationsParents,
+    DatabaseStorePrefixes::RelationsChildren,
+    DatabaseStorePrefixes::Statuses,
+    DatabaseStorePrefixes::UtxoDiffs,
+    DatabaseStorePrefixes::UtxoMultisets,
+    DatabaseStorePrefixes::PruningSamples,
+    DatabaseStorePrefixes::ReachabilityTreeChildren,
+    DatabaseStorePrefixes::ReachabilityFutureCoveringSet,
+];
+
 pub enum PruningProcessingMessage {
     Exit,
     Process { sink_ghostdag_data: CompactGhostdagData },
@@ -80,6 +117,16 @@ pub struct PruningProcessor {
     // Config
     config: Arc<Config>,
 
+    // Retention period, initialized from config and adjustable at runtime. Runtime changes are not persisted
+    retention_period_days: RwLock<Option<f64>>,
+
+    // Retention progress
+    retention_pending_blocks: AtomicU64,
+    pruned_blocks: AtomicU64,
+
+    // The number of pruned blocks as of the last compaction of the pruned stores
+    compacted_pruned_blocks: AtomicU64,
+
     // Signals
     is_consensus_exiting: Arc<AtomicBool>,
 }
@@ -111,7 +158,11 @@ impl PruningProcessor {
             pruning_proof_manager: services.pruning_proof_manager.clone(),
             parents_manager: services.parents_manager.clone(),
             pruning_lock,
+            retention_period_days: RwLock::new(config.retention_period_days),
             config,
+            retention_pending_blocks: Default::default(),
+            pruned_blocks: Default::default(),
+            compacted_pruned_blocks: Default::default(),
             is_consensus_exiting,
         }
     }
@@ -125,12 +176,56 @@ impl PruningProcessor {
         // in order to make sure the node is already connected and receiving blocks before we start background recovery operations
         self.recover_pruning_workflows_if_needed();
         self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+        self.compact_pruned_stores_if_needed();
 
+        let mut last_retention_adjustment = Instant::now();
         while let Ok(PruningProcessingMessage::Process { sink_ghostdag_data }) = self.receiver.recv() {
             self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+
+            // Keep applying a shortened retention period also while the pruning point does not move
+            if last_retention_adjustment.elapsed() >= RETENTION_ADJUSTMENT_INTERVAL {
+                self.adjust_retention_period_root();
+                last_retention_adjustment = Instant::now();
+            }
+
+            self.compact_pruned_stores_if_needed();
+        }
+    }
+
+    /// Sets the retention period which applies from the next retention period root adjustment onwards
+    pub fn set_retention_period_days(&self, retention_period_days: Option<f64>) {
+        *self.retention_period_days.write() = retention_period_days;
+    }
+
+    pub fn retention_stats(&self) -> RetentionStats {
+        RetentionStats {
+            retention_period_days: *self.retention_period_days.read(),
+            pending_blocks: self.retention_pending_blocks.load(Ordering::Relaxed),
+            pruned_blocks: self.pruned_blocks.load(Ordering::Relaxed),
         }
     }
 
+    /// Compacts the pruned stores once at least half a finality depth worth of blocks were pruned since the last compaction.
+    /// Compaction runs on this worker thread, hence it delays the next pruning round but not consensus processing
+    fn compact_pruned_stores_if_needed(&self) {
+        let pruned_blocks = self.pruned_blocks.load(Ordering::Relaxed);
+        if pruned_blocks - self.compacted_pruned_blocks.load(Ordering::Relaxed) < self.config.finality_depth().after() / 2 {
+            return;
+        }
+
+        info!("Compacting the pruned stores...");
+        let start = Instant::now();
+        for &prefix in PRUNED_STORE_PREFIXES {
+            if self.is_consensus_exiting.load(Ordering::Relaxed) {
+                info!("Compaction of the pruned stores interrupted: Process is exiting");
+                return;
+            }
+            self.db.compact_prefix(prefix);
+        }
+        self.compacted_pruned_blocks.store(pruned_blocks, Ordering::Relaxed);
+        info!("Compacted the pruned stores in {:.2} seconds", start.elapsed().as_secs_f64());
+    }
+
     fn recover_pruning_workflows_if_needed(&self) {
         let pruning_point_read = self.pruning_point_store.read();
         let pruning_point = pruning_point_read.pruning_point().unwrap();
@@ -144,8 +239,9 @@ impl PruningProcessor {
             pruning_point, retention_checkpoint, pruning_utxoset_position
         );
 
-        // This indicates the node crashed during a former pruning point move and we need to recover
-        if pruning_utxoset_position != pruning_point {
+        // This indicates the node crashed during a former pruning point move and we need to recover.
+        // Light nodes never import the pruning point UTXO set so there is nothing to recover
+        if pruning_utxoset_position != pruning_point && !self.config.is_light {
             info!("Recovering pruning utxo-set from {} to the pruning point {}", pruning_utxoset_position, pruning_point);
             if !self.advance_pruning_utxoset(pruning_utxoset_position, pruning_point) {
                 info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
@@ -165,6 +261,11 @@ impl PruningProcessor {
         if retention_checkpoint != retention_period_root {
             self.prune(pruning_point, retention_period_root);
         }
+
+        // Similarly, complete an interrupted move of archival data to the cold store
+        if self.config.is_archival {
+            self.move_to_cold_storage();
+        }
     }
 
     fn advance_pruning_point_and_candidate_if_possible(&self, sink_ghostdag_data: CompactGhostdagData) {
@@ -197,6 +298,13 @@ impl PruningProcessor {
                 adjusted_retention_period_root
             };
 
+            // For archival nodes with a cold store, the cold storage root advances like the retention root of a non-archival node
+            if self.config.is_archival && self.cold_db.is_some() {
+                let cold_storage_root = pruning_point_write.cold_storage_root().unwrap();
+                let adjusted_cold_storage_root = self.advance_retention_period_root(cold_storage_root, new_pruning_point);
+                pruning_point_write.set_cold_storage_root(&mut batch, adjusted_cold_storage_root).unwrap();
+            }
+
             self.db.write(batch).unwrap();
             drop(pruning_point_write);
 
@@ -205,12 +313,15 @@ impl PruningProcessor {
             // Inform the user
             info!("Periodic pruning point movement: advancing from {} to {}", current_pruning_info.pruning_point, new_pruning_point);
 
-            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs
-            if !self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point) {
-                info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
-                return;
+            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs.
+            // Light nodes hold no UTXO set, hence there is nothing to advance
+            if !self.config.is_light {
+                if !self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point) {
+                    info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
+                    return;
+                }
+                info!("Updated the pruning point UTXO set");
             }
-            info!("Updated the pruning point UTXO set");
 
             // Finally, prune data in the new pruning point past
             self.prune(new_pruning_point, adjusted_retention_period_root);
@@ -220,6 +331,42 @@ impl PruningProcessor {
         }
     }
 
+    /// Advances the retention period root (or the cold storage root of archival nodes) in between pruning point movements,
+    /// in case it lags behind the retention period, and prunes the data below it
+    fn adjust_retention_period_root(&self) {
+        let use_cold_storage = self.config.is_archival;
+        if use_cold_storage && self.cold_db.is_none() {
+            return;
+        }
+
+        let pruning_point_read = self.pruning_point_store.upgradable_read();
+        let pruning_point = pruning_point_read.pruning_point().unwrap();
+        let root = if use_cold_storage { pruning_point_read.cold_storage_root() } else { pruning_point_read.retention_period_root() }
+            .unwrap();
+        let adjusted_root = self.advance_retention_period_root(root, pruning_point);
+        if adjusted_root == root {
+            return;
+        }
+
+        let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
+        let mut batch = WriteBatch::default();
+        if use_cold_storage {
+            pruning_point_write.set_cold_storage_root(&mut batch, adjusted_root).unwrap();
+        } else {
+            pruning_point_write.set_retention_period_root(&mut batch, adjusted_root).unwrap();
+        }
+        self.db.write(batch).unwrap();
+        drop(pruning_point_write);
+
+        info!(
+            "Adjusting the retention period root from {} to {} ({} blocks pending)",
+            root,
+            adjusted_root,
+            self.retention_pending_blocks.load(Ordering::Relaxed)
+        );
+        self.prune(pruning_point, adjusted_root);
+    }
+
     fn advance_pruning_utxoset(&self, utxoset_position: Hash, new_pruning_point: Hash) -> bool {
         let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
         for chain_block in self.reachability_service.forward_chain_iterator(utxoset_position, new_pruning_point, true).skip(1) {
@@ -254,6 +401,11 @@ impl PruningProcessor {
     }
 
     fn prune(&self, new_pruning_point: Hash, retention_period_root: Hash) {
+        if self.config.is_archival && self.cold_db.is_some() {
+            // Rather than pruning, archival data is moved to the cold store
+            self.move_to_cold_storage();
+            return;
+        }
         if self.config.is_archival {
             warn!("The node is configured as an archival node -- avoiding data pruning. Note this might lead to heavy disk usage.");
             return;
@@ -436,6 +588,7 @@ impl PruningProcessor {
             self.block_window_cache_for_past_median_time.remove(&current);
 
             if !keep_blocks.contains(&current) {
+                self.pruned_blocks.fetch_add(1, Ordering::Relaxed);
                 let mut batch = WriteBatch::default();
                 let mut level_relations_write = self.relations_stores.write();
                 let mut reachability_relations_write = self.reachability_relations_store.write();
@@ -550,22 +703,105 @@ impl PruningProcessor {
         }
     }
 
+    /// Moves block bodies, acceptance data and UTXO diffs of blocks in the strict past of the cold storage root from the
+    /// main DB to the cold store. Blocks in the strict past of the cold storage checkpoint were already moved by a previous
+    /// call, hence the traversal stops there. Data is written to the cold store before being deleted from the main DB, so
+    /// it remains readable throughout and an interrupted move is safely repeated on recovery.
+    fn move_to_cold_storage(&self) {
+        let Some(cold_db) = self.cold_db.as_ref() else {
+            return;
+        };
+        let pruning_point_read = self.pruning_point_store.read();
+        let cold_storage_checkpoint = pruning_point_read.cold_storage_checkpoint().unwrap();
+        let cold_storage_root = pruning_point_read.cold_storage_root().unwrap();
+        drop(pruning_point_read);
+
+        if cold_storage_checkpoint == cold_storage_root {
+            return;
+        }
+
+        info!("Cold storage: moving archival data below {} (previously {})", cold_storage_root, cold_storage_checkpoint);
+
+        // The cold batch is synced to disk before the corresponding deletions are applied to the main DB
+        let commit = |batch: &mut WriteBatch, cold_batch: &mut WriteBatch| {
+            cold_db.write_sync(std::mem::take(cold_batch)).unwrap();
+            self.db.write(std::mem::take(batch)).unwrap();
+        };
+
+        let mut queue = VecDeque::from_iter(self.relations_stores.read()[0].get_parents(cold_storage_root).unwrap().iter().copied());
+        let mut visited = BlockHashSet::new();
+        let (mut batch, mut cold_batch) = (WriteBatch::default(), WriteBatch::default());
+        let mut moved = 0;
+        while let Some(current) = queue.pop_front() {
+            if current == ORIGIN || !visited.insert(current) {
+                continue;
+            }
+            if current != cold_storage_checkpoint && self.reachability_service.is_dag_ancestor_of(current, cold_storage_checkpoint) {
+                continue;
+            }
+            if let Some(parents) = self.relations_stores.read()[0].get_parents(current).unwrap_option() {
+                queue.extend(parents.iter().copied());
+            }
+
+            self.block_transactions_store.move_to_cold_batch(&mut batch, &mut cold_batch, current).unwrap();
+            self.acceptance_data_store.move_to_cold_batch(&mut batch, &mut cold_batch, current).unwrap();
+            self.utxo_diffs_store.move_to_cold_batch(&mut batch, &mut cold_batch, current).unwrap();
+            self.pruned_blocks.fetch_add(1, Ordering::Relaxed);
+            moved += 1;
+
+            if moved % COLD_STORAGE_BATCH_SIZE == 0 {
+                commit(&mut batch, &mut cold_batch);
+                if self.is_consensus_exiting.load(Ordering::Relaxed) {
+                    info!("Cold storage: interrupted after moving {} blocks: Process is exiting", moved);
+                    return;
+                }
+                if moved % (COLD_STORAGE_BATCH_SIZE * 40) == 0 {
+                    info!("Cold storage: moved {} blocks...", moved);
+                }
+            }
+        }
+        commit(&mut batch, &mut cold_batch);
+
+        // Set the checkpoint to the new root only after its past was fully moved
+        let mut pruning_point_write = self.pruning_point_store.write();
+        let mut batch = WriteBatch::default();
+        pruning_point_write.set_cold_storage_checkpoint(&mut batch, cold_storage_root).unwrap();
+        self.db.write(batch).unwrap();
+        drop(pruning_point_write);
+
+        info!("Cold storage: moved the data of {} blocks", moved);
+    }
+
     /// Adjusts the retention period root to latest pruning point sample that covers the retention period.
     /// This is the pruning point sample B such that B.timestamp <= retention_period_days_ago. This may return the old hash if
     /// the retention period cannot be covered yet with the node's current history.
     ///
-    /// This function is expected to be called only when a new pruning point is determined and right before
-    /// doing any pruning. Pruning point must be the new pruning point this node is advancing to.
+    /// The root advances by no more than the deletion budget (but by at least a single pruning sample) per call, and the
+    /// remaining distance to the target is reported by the retention stats. Hence a shortened retention period is reached
+    /// gradually over successive calls.
+    ///
+    /// This function is expected to be called only when a new pruning point is determined (or when adjusting the retention
+    /// root in between pruning point movements) and right before doing any pruning. Pruning point must be the new pruning
+    /// point this node is advancing to.
     ///
     /// The returned retention_period_root is guaranteed to be in past(pruning_point) or the pruning point itself.
     fn advance_retention_period_root(&self, retention_period_root: Hash, pruning_point: Hash) -> Hash {
-        match self.config.retention_period_days {
-            // If the retention period wasn't set, immediately default to the pruning point.
-            None => pruning_point,
+        // Collect the samples from the new pruning point down to the prev retention root (exclusive).
+        // Note that prev retention root is always a past pruning point, so we can iterate via pruning samples until we reach it.
+        let mut samples = vec![];
+        let mut sample = pruning_point;
+        while sample != retention_period_root {
+            samples.push(sample);
+            sample = self.pruning_samples_store.pruning_sample_from_pov(sample).unwrap();
+        }
+
+        let target_index = match *self.retention_period_days.read() {
+            // If the retention period wasn't set, target the pruning point.
+            None => 0,
             Some(retention_period_days) => {
                 // The retention period in milliseconds we need to cover
                 // Note: If retention period is set to an amount lower than what the new pruning point would cover
-                // this function will simply return the new pruning point. The new pruning point passed as an argument
+                // this function will simply target the new pruning point. The new pruning point passed as an argument
                 // to this function serves as a clamp.
                 let retention_period_ms = (retention_period_days * 86400.0 * 1000.0).ceil() as u64;
 
@@ -573,32 +809,41 @@ impl PruningProcessor {
                 let sink_timestamp_as_current_time = self.get_sink_timestamp();
                 let retention_period_root_ts_target = sink_timestamp_as_current_time.saturating_sub(retention_period_ms);
 
-                // Iterate from the new pruning point to the prev retention root and search for the first point with enough days above it.
-                // Note that prev retention root is always a past pruning point, so we can iterate via pruning samples until we reach it.
-                let mut new_retention_period_root = pruning_point;
-
                 trace!(
                     "Adjusting the retention period root to cover the required retention period. Target timestamp: {}",
                     retention_period_root_ts_target,
                 );
 
-                while new_retention_period_root != retention_period_root {
-                    let block = new_retention_period_root;
-
-                    let timestamp = self.headers_store.get_timestamp(block).unwrap();
-                    trace!("block | timestamp = {} | {}", block, timestamp);
-                    if timestamp <= retention_period_root_ts_target {
-                        trace!("block {} timestamp {} >= {}", block, timestamp, retention_period_root_ts_target);
-                        // We are now at a pruning point that is at or below our retention period target
-                        break;
-                    }
+                // Search for the first sample with enough days above it, or the prev root if there is none
+                samples
+                    .iter()
+                    .position(|&block| self.headers_store.get_timestamp(block).unwrap() <= retention_period_root_ts_target)
+                    .unwrap_or(samples.len())
+            }
+        };
 
-                    new_retention_period_root = self.pruning_samples_store.pruning_sample_from_pov(block).unwrap();
-                }
+        let Some(&target) = samples.get(target_index) else {
+            self.retention_pending_blocks.store(0, Ordering::Relaxed);
+            return retention_period_root;
+        };
 
-                new_retention_period_root
-            }
-        }
+        // Advance to the latest sample up to the target which is within the deletion budget from the prev root
+        let root_daa_score = self.headers_store.get_daa_score(retention_period_root).unwrap();
+        let budget = RETENTION_ADJUSTMENT_BUDGET * self.config.finality_depth().after();
+        let new_retention_period_root = samples[target_index..]
+            .iter()
+            .copied()
+            .find(|&block| self.headers_store.get_daa_score(block).unwrap().saturating_sub(root_daa_score) <= budget)
+            .unwrap_or(*samples.last().unwrap());
+
+        let pending_blocks = self
+            .headers_store
+            .get_daa_score(target)
+            .unwrap()
+            .saturating_sub(self.headers_store.get_daa_score(new_retention_period_root).unwrap());
+        self.retention_pending_blocks.store(pending_blocks, Ordering::Relaxed);
+
+        new_retention_period_root
     }
 
     fn get_sink_timestamp(&self) -> u64 {
@@ -606,6 +851,10 @@ impl PruningProcessor {
     }
 
     fn get_sink(&self) -> Hash {
+        // Light nodes never resolve virtual, hence their headers selected tip takes the role of the sink
+        if self.config.is_light {
+            return self.headers_selected_tip_store.read().get().unwrap().hash;
+        }
         self.lkg_virtual_state.load().ghostdag_data.selected_parent
     }
 
@@ -631,10 +880,9 @@ impl PruningProcessor {
 
     fn assert_data_rebuilding(&self, ref_data: Arc<PruningPointTrustedData>, new_pruning_point: Hash) {
         info!("Rebuilding pruning point trusted data (sanity test)");
-        let virtual_state = self.lkg_virtual_state.load();
-        let built_data = self
-            .pruning_proof_manager
-            .calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, virtual_state.parents.iter().copied());
+        let top_tips = if self.config.is_light { vec![self.get_sink()] } else { self.lkg_virtual_state.load().parents.clone() };
+        let built_data =
+            self.pruning_proof_manager.calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, top_tips.into_iter());
         assert_eq!(
             ref_data.anticone.iter().copied().collect::<BlockHashSet>(),
             built_data.anticone.iter().copied().collect::<BlockHashSet>()
@@ -650,3 +898,88 @@ impl PruningProcessor {
         info!("Trusted data was rebuilt successfully following pruning");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consensus::test_consensus::TestConsensus, pipeline::virtual_processor::tests_util::TestContext};
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        config::{
+            params::{ForkActivation, MAINNET_PARAMS},
+            ConfigBuilder, MINIMUM_RETENTION_PERIOD_DAYS,
+        },
+    };
+
+    const FINALITY_DEPTH: u64 = 10;
+
+    fn config_builder() -> ConfigBuilder {
+        ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| {
+            p.prior_max_block_parents = 4;
+            p.prior_mergeset_size_limit = 10;
+            p.prior_ghostdag_k = 4;
+            p.prior_finality_depth = FINALITY_DEPTH;
+            p.prior_pruning_depth = FINALITY_DEPTH * 3 - 5;
+            p.crescendo.finality_depth = FINALITY_DEPTH;
+            p.crescendo_activation = ForkActivation::never();
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retention_period_root_adjustment_budget() {
+        // Archival nodes without cold storage keep the retention root at genesis and all the data below the pruning point
+        let config = config_builder().set_archival().build();
+        let mut ctx = TestContext::new(TestConsensus::new(&config));
+        for _ in 0..12 * FINALITY_DEPTH {
+            ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+        }
+        let genesis = config.genesis.hash;
+        let pruning_point = ctx.consensus.pruning_point();
+        let processor = ctx.consensus.pruning_processor.clone();
+        let daa_score = |hash| processor.headers_store.get_daa_score(hash).unwrap();
+        let budget = RETENTION_ADJUSTMENT_BUDGET * FINALITY_DEPTH;
+        assert!(daa_score(pruning_point) - daa_score(genesis) > 2 * budget);
+
+        // Without a retention period the root targets the pruning point, which is reached over several steps, each within
+        // the budget unless a single pruning sample exceeds it
+        let mut root = genesis;
+        let mut steps = 0;
+        while root != pruning_point {
+            let next = processor.advance_retention_period_root(root, pruning_point);
+            assert_ne!(root, next);
+            assert!(
+                daa_score(next) - daa_score(root) <= budget
+                    || processor.pruning_samples_store.pruning_sample_from_pov(next).unwrap() == root
+            );
+            assert_eq!(daa_score(pruning_point) - daa_score(next), processor.retention_stats().pending_blocks);
+            root = next;
+            steps += 1;
+        }
+        assert!(steps > 1);
+        assert_eq!(pruning_point, processor.advance_retention_period_root(pruning_point, pruning_point));
+        assert_eq!(0, processor.retention_stats().pending_blocks);
+
+        // A retention period covering more than the whole history keeps the root in place
+        processor.set_retention_period_days(Some(1000.0));
+        assert_eq!(genesis, processor.advance_retention_period_root(genesis, pruning_point));
+        assert_eq!(0, processor.retention_stats().pending_blocks);
+    }
+
+    #[test]
+    fn test_set_retention_period_days() {
+        let consensus = TestConsensus::new(&config_builder().build());
+        assert!(consensus.set_retention_period_days(Some(f64::NAN)).is_err());
+        assert!(consensus.set_retention_period_days(Some(MINIMUM_RETENTION_PERIOD_DAYS - 0.5)).is_err());
+        assert_eq!(None, consensus.pruning_processor.retention_stats().retention_period_days);
+
+        consensus.set_retention_period_days(Some(MINIMUM_RETENTION_PERIOD_DAYS)).unwrap();
+        assert_eq!(Some(MINIMUM_RETENTION_PERIOD_DAYS), consensus.pruning_processor.retention_stats().retention_period_days);
+        consensus.set_retention_period_days(None).unwrap();
+        assert_eq!(None, consensus.pruning_processor.retention_stats().retention_period_days);
+
+        // Archival nodes without cold storage ignore the retention period, hence setting it is rejected
+        let consensus = TestConsensus::new(&config_builder().set_archival().build());
+        assert!(consensus.set_retention_period_days(Some(MINIMUM_RETENTION_PERIOD_DAYS)).is_err());
+        assert!(consensus.set_retention_period_days(None).is_err());
+    }
+}
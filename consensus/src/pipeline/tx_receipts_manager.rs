@@ -656,7 +656,10 @@ pub fn verify_pchmrs_path(pochm: &LogPathPochm, destination_block_hash: Hash, pc
     /*verify the path from posterity down to chain_purporter:
     iterate downward from posterity block header: for each, verify that leaf hash is in pchmr of ther header */
     pochm.vec.iter().zip(leaf_hashes).all(|(pochm_seg, leaf_hash)| {
-        let pchmr_root_hash = pchmr_store.get(pochm_seg.header.hash).unwrap();
+        // Headers unknown to this node cannot be vouched for
+        let Ok(pchmr_root_hash) = pchmr_store.get(pochm_seg.header.hash) else {
+            return false;
+        };
         let witness = &pochm_seg.leaf_in_pchmr_witness;
         verify_merkle_witness(witness, leaf_hash, pchmr_root_hash)
     })
@@ -27,6 +27,7 @@ use crate::{
             pruning_utxoset::PruningUtxosetStores,
             reachability::DbReachabilityStore,
             relations::{DbRelationsStore, RelationsStoreReader},
+            reorgs::{ChainReorgsStore, DbChainReorgsStore},
             selected_chain::{DbSelectedChainStore, SelectedChainStore},
             statuses::{DbStatusesStore, StatusesStore, StatusesStoreBatchExtensions, StatusesStoreReader},
             tips::{DbTipsStore, TipsStoreReader},
@@ -63,6 +64,7 @@ use kaspa_consensus_core::{
     merkle::calc_hash_merkle_root,
     mining_rules::MiningRules,
     pruning::PruningPointsList,
+    reorg::ChainReorg,
     tx::{MutableTransaction, Transaction},
     utxo::{
         utxo_diff::UtxoDiff,
@@ -72,8 +74,8 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensus_notify::{
     notification::{
-        NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification, UtxosChangedNotification,
-        VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+        ChainReorgNotification, NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification,
+        UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
     },
     root::ConsensusNotificationRoot,
 };
@@ -92,6 +94,7 @@ use super::{
 use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use itertools::Itertools;
 use kaspa_consensus_core::tx::ValidatedTransaction;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_utils::binary_heap::BinaryHeapExtensions;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use rand::{seq::SliceRandom, Rng};
@@ -99,7 +102,6 @@ use rayon::{
     prelude::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator},
     ThreadPool,
 };
-use rocksdb::WriteBatch;
 use std::{
     cmp::min,
     collections::{BinaryHeap, HashMap, VecDeque},
@@ -135,6 +137,7 @@ pub struct VirtualStateProcessor {
     pub(super) body_tips_store: Arc<RwLock<DbTipsStore>>,
     pub(super) depth_store: Arc<DbDepthStore>,
     pub(super) selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
+    pub(super) chain_reorgs_store: Arc<RwLock<DbChainReorgsStore>>,
     pub(super) pruning_samples_store: Arc<DbPruningSamplesStore>,
 
     // Utxo-related stores
@@ -174,6 +177,9 @@ pub struct VirtualStateProcessor {
     // Counters
     counters: Arc<ProcessingCounters>,
 
+    /// Reorgs removing at least this number of chain blocks are notified
+    reorg_notification_depth: u64,
+
     pub(super) crescendo_logger: CrescendoLogger,
 
     // Crescendo hardfork activation score (used here for activating KIPs 9,10)
@@ -197,6 +203,7 @@ impl VirtualStateProcessor {
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
+        reorg_notification_depth: u64,
         mining_rules: Arc<MiningRules>,
     ) -> Self {
         Self {
@@ -220,6 +227,7 @@ impl VirtualStateProcessor {
             body_tips_store: storage.body_tips_store.clone(),
             depth_store: storage.depth_store.clone(),
             selected_chain_store: storage.selected_chain_store.clone(),
+            chain_reorgs_store: storage.chain_reorgs_store.clone(),
             pruning_samples_store: storage.pruning_samples_store.clone(),
             utxo_diffs_store: storage.utxo_diffs_store.clone(),
             utxo_multisets_store: storage.utxo_multisets_store.clone(),
@@ -246,6 +254,7 @@ impl VirtualStateProcessor {
             pruning_lock,
             notification_root,
             counters,
+            reorg_notification_depth,
             crescendo_logger: CrescendoLogger::new(),
             crescendo_activation: params.crescendo_activation,
             mining_rules,
@@ -334,6 +343,8 @@ impl VirtualStateProcessor {
             )
             .expect("all possible rule errors are unexpected here");
 
+        let chain_reorg = self.record_chain_reorg(&chain_path, new_sink);
+
         let compact_sink_ghostdag_data = if let Some(sink_ghostdag_data) = Lazy::get(&sink_ghostdag_data) {
             // If we had to retrieve the full data, we convert it to compact
             sink_ghostdag_data.to_compact()
@@ -363,6 +374,11 @@ impl VirtualStateProcessor {
         self.notification_root
             .notify(Notification::VirtualDaaScoreChanged(VirtualDaaScoreChangedNotification::new(new_virtual_state.daa_score)))
             .expect("expecting an open unbounded channel");
+        if let Some(reorg) = chain_reorg.filter(|reorg| reorg.removed_depth >= self.reorg_notification_depth) {
+            self.notification_root
+                .notify(Notification::ChainReorg(ChainReorgNotification::new(reorg)))
+                .expect("expecting an open unbounded channel");
+        }
         if self.notification_root.has_subscription(EventType::VirtualChainChanged) {
             // check for subscriptions before the heavy lifting
             let added_chain_blocks_acceptance_data =
@@ -377,6 +393,24 @@ impl VirtualStateProcessor {
         }
     }
 
+    /// Records the reorg of the selected chain described by the chain path, if the path removed any chain block.
+    /// Returns the recorded reorg
+    fn record_chain_reorg(&self, chain_path: &ChainPath, new_sink: Hash) -> Option<ChainReorg> {
+        if chain_path.removed.is_empty() {
+            return None;
+        }
+        let sink_daa_score = self.headers_store.get_daa_score(new_sink).unwrap();
+        let reorg = ChainReorg::from_chain_path(chain_path, new_sink, sink_daa_score, unix_now())?;
+        // The reorg record is informational only, hence it is not required to be written atomically with the virtual state
+        self.chain_reorgs_store.write().insert(reorg.clone()).unwrap();
+        self.counters.record_reorg(reorg.removed_depth);
+        debug!(
+            "Selected chain reorg: {} chain blocks removed and {} added, new sink {}",
+            reorg.removed_depth, reorg.added_depth, reorg.added_sink
+        );
+        Some(reorg)
+    }
+
     pub(crate) fn virtual_finality_point(&self, virtual_ghostdag_data: &GhostdagData, pruning_point: Hash) -> Hash {
         let finality_point = self.depth_manager.calc_finality_point(virtual_ghostdag_data, pruning_point);
         if self.reachability_service.is_chain_ancestor_of(pruning_point, finality_point) {
@@ -1127,6 +1161,8 @@ impl VirtualStateProcessor {
             pruning_point_write.set_batch(&mut batch, self.genesis.hash, self.genesis.hash, 0).unwrap();
             pruning_point_write.set_retention_checkpoint(&mut batch, self.genesis.hash).unwrap();
             pruning_point_write.set_retention_period_root(&mut batch, self.genesis.hash).unwrap();
+            pruning_point_write.set_cold_storage_checkpoint(&mut batch, self.genesis.hash).unwrap();
+            pruning_point_write.set_cold_storage_root(&mut batch, self.genesis.hash).unwrap();
             pruning_utxoset_write.set_utxoset_position(&mut batch, self.genesis.hash).unwrap();
             self.db.write(batch).unwrap();
             drop(pruning_point_write);
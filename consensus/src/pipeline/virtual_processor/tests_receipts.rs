@@ -16,8 +16,10 @@ use kaspa_consensus_core::{
         params::{ForkActivation, MAINNET_PARAMS},
         ConfigBuilder,
     },
+    errors::consensus::ConsensusError,
     receipts::Pochm,
 };
+use kaspa_hashes::Hash;
 
 #[tokio::test]
 async fn test_receipts_in_chain() {
@@ -167,6 +169,15 @@ async fn test_receipts_in_chain() {
     for (proof, _) in pops {
         assert!(ctx.consensus.verify_proof_of_pub(&proof));
     }
+
+    // The posterity walk follows the posterity chain up to the requested limit or to the last existing posterity
+    let walk = |low: Hash, limit: usize| {
+        ctx.consensus.get_posterity_headers(low, limit).map(|headers| headers.iter().map(|h| h.hash).collect::<Vec<_>>())
+    };
+    assert_eq!(walk(expected_posterities[PERIODS - 2], 10).unwrap(), expected_posterities[PERIODS - 1..]);
+    assert_eq!(walk(expected_posterities[PERIODS - 2], 1).unwrap(), expected_posterities[PERIODS - 1..PERIODS]);
+    assert!(walk(expected_posterities[PERIODS], 10).unwrap().is_empty());
+    assert!(matches!(walk(Hash::from_u64_word(u64::MAX), 10), Err(ConsensusError::HeaderNotFound(_))));
 }
 #[tokio::test]
 async fn test_receipts_in_random() {
@@ -324,3 +335,78 @@ async fn test_receipts_in_random() {
         assert!(ctx.consensus.verify_proof_of_pub(proof));
     }
 }
+
+#[tokio::test]
+async fn test_tx_confirmations() {
+    const FINALITY_DEPTH: usize = 20;
+    const BPS: f64 = 10.0;
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.prior_max_block_parents = 4;
+            p.prior_mergeset_size_limit = 10;
+            p.prior_ghostdag_k = 4;
+            p.prior_finality_depth = FINALITY_DEPTH as u64;
+            p.prior_target_time_per_block = (1000.0 / BPS) as u64;
+            p.prior_pruning_depth = (FINALITY_DEPTH * 3 - 5) as u64;
+            p.kip6_activation = ForkActivation::new(20);
+        })
+        .build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let accepted_tx =
+        |ctx: &TestContext, block| ctx.consensus.acceptance_data_store.get(block).unwrap()[0].accepted_transactions[0].transaction_id;
+
+    // A chain block accepts the coinbase transaction of its selected parent
+    for _ in 0..FINALITY_DEPTH {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    let posterity = ctx.consensus.get_sink();
+    let tx_id = accepted_tx(&ctx, posterity);
+    let confirmations = ctx.consensus.get_tx_confirmations(tx_id, posterity).unwrap();
+    assert!(confirmations.is_accepted);
+    assert!(!confirmations.is_final && !confirmations.is_posterity_reached && !confirmations.is_receipt_available);
+    assert!(!ctx.consensus.get_tx_confirmations(Hash::from_u64_word(u64::MAX), posterity).unwrap().is_accepted);
+
+    // Receipts are not reported as available before the next posterity is reached with a safety margin
+    for _ in 0..FINALITY_DEPTH {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    let confirmations = ctx.consensus.get_tx_confirmations(tx_id, posterity).unwrap();
+    assert!(confirmations.is_accepted && confirmations.confirmations >= FINALITY_DEPTH as u64);
+    assert!(!confirmations.is_posterity_reached && !confirmations.is_receipt_available);
+
+    for _ in 0..FINALITY_DEPTH / 2 {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    let confirmations = ctx.consensus.get_tx_confirmations(tx_id, posterity).unwrap();
+    assert!(confirmations.is_accepted && confirmations.is_final);
+    assert!(confirmations.is_posterity_reached && confirmations.is_receipt_available);
+
+    // Once the accepting block is pruned down to its header, its acceptance data is gone so acceptance can no longer be verified,
+    // neither of the accepted transaction nor of an arbitrary one
+    for _ in 0..FINALITY_DEPTH * 5 / 2 {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    while ctx.consensus.block_status(posterity) != BlockStatus::StatusHeaderOnly
+        || ctx.consensus.pruning_point_store.read().retention_checkpoint().unwrap()
+            != ctx.consensus.pruning_point_store.read().retention_period_root().unwrap()
+    {
+        sleep(Duration::from_millis(100));
+    }
+    for tx_id in [tx_id, Hash::from_u64_word(u64::MAX)] {
+        assert!(matches!(
+            ctx.consensus.get_tx_confirmations(tx_id, posterity),
+            Err(ConsensusError::AcceptanceDataPruned(block)) if block == posterity
+        ));
+    }
+
+    // A heavier fork reorgs the sink out of the selected chain, so it no longer accepts the transaction
+    let sink = ctx.consensus.get_sink();
+    let selected_parent = ctx.consensus.headers_store.get_header(sink).unwrap().direct_parents()[0];
+    let tx_id = accepted_tx(&ctx, sink);
+    ctx.add_utxo_valid_block_with_parents(Hash::from_u64_word(1), vec![selected_parent], vec![]).await;
+    ctx.add_utxo_valid_block_with_parents(Hash::from_u64_word(2), vec![Hash::from_u64_word(1)], vec![]).await;
+    assert_eq!(ctx.consensus.get_sink(), Hash::from_u64_word(2));
+    assert!(!ctx.consensus.get_tx_confirmations(tx_id, sink).unwrap().is_accepted);
+    assert!(ctx.consensus.get_tx_confirmations(tx_id, Hash::from_u64_word(1)).unwrap().is_accepted);
+}
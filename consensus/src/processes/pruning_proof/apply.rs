@@ -14,10 +14,10 @@ use kaspa_consensus_core::{
     BlockHashMap, BlockHashSet, BlockLevel, HashMapCustomHasher,
 };
 use kaspa_core::{debug, trace};
+use kaspa_database::prelude::WriteBatch;
 use kaspa_hashes::Hash;
 use kaspa_pow::calc_block_level;
 use kaspa_utils::{binary_heap::BinaryHeapExtensions, vec::VecExtensions};
-use rocksdb::WriteBatch;
 
 use crate::{
     model::{
@@ -7,14 +7,19 @@ use std::{
         hash_map::Entry::{self},
         VecDeque,
     },
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
 use itertools::Itertools;
+use kaspa_database::prelude::WriteBatch;
 use parking_lot::{Mutex, RwLock};
-use rocksdb::WriteBatch;
 
 use kaspa_consensus_core::{
+    api::PruningProofStats,
     blockhash::{self, BlockHashExtensions},
     config::params::ForkedParam,
     errors::{
@@ -43,9 +48,10 @@ use crate::{
             depth::DbDepthStore,
             ghostdag::{DbGhostdagStore, GhostdagStoreReader},
             headers::{DbHeadersStore, HeaderStore, HeaderStoreReader},
-            headers_selected_tip::DbHeadersSelectedTipStore,
+            headers_selected_tip::{DbHeadersSelectedTipStore, HeadersSelectedTipStoreReader},
             past_pruning_points::{DbPastPruningPointsStore, PastPruningPointsStore},
             pruning::{DbPruningStore, PruningStoreReader},
+            pruning_proof::{DbPruningProofStore, PruningProofStore, PruningProofStoreReader},
             pruning_samples::{DbPruningSamplesStore, PruningSamplesStore},
             reachability::DbReachabilityStore,
             relations::{DbRelationsStore, RelationsStoreReader},
@@ -115,6 +121,7 @@ pub struct PruningProofManager {
     depth_store: Arc<DbDepthStore>,
     selected_chain_store: Arc<RwLock<DbSelectedChainStore>>,
     pruning_samples_store: Arc<DbPruningSamplesStore>,
+    pruning_proof_store: Arc<RwLock<DbPruningProofStore>>,
 
     ghostdag_manager: DbGhostdagManager,
     traversal_manager: DbDagTraversalManager,
@@ -124,11 +131,15 @@ pub struct PruningProofManager {
     cached_proof: Mutex<Option<CachedPruningPointData<PruningPointProof>>>,
     cached_anticone: Mutex<Option<CachedPruningPointData<PruningPointTrustedData>>>,
 
+    build_duration_ms: AtomicU64,
+    validation_duration_ms: AtomicU64,
+
     max_block_level: BlockLevel,
     genesis_hash: Hash,
     pruning_proof_m: u64,
     anticone_finalization_depth: ForkedParam<u64>,
     ghostdag_k: ForkedParam<KType>,
+    is_light: bool,
 
     is_consensus_exiting: Arc<AtomicBool>,
 }
@@ -148,6 +159,7 @@ impl PruningProofManager {
         pruning_proof_m: u64,
         anticone_finalization_depth: ForkedParam<u64>,
         ghostdag_k: ForkedParam<KType>,
+        is_light: bool,
         is_consensus_exiting: Arc<AtomicBool>,
     ) -> Self {
         Self {
@@ -166,6 +178,7 @@ impl PruningProofManager {
             selected_chain_store: storage.selected_chain_store.clone(),
             depth_store: storage.depth_store.clone(),
             pruning_samples_store: storage.pruning_samples_store.clone(),
+            pruning_proof_store: storage.pruning_proof_store.clone(),
 
             traversal_manager,
             window_manager,
@@ -174,11 +187,15 @@ impl PruningProofManager {
             cached_proof: Mutex::new(None),
             cached_anticone: Mutex::new(None),
 
+            build_duration_ms: AtomicU64::new(0),
+            validation_duration_ms: AtomicU64::new(0),
+
             max_block_level,
             genesis_hash,
             pruning_proof_m,
             anticone_finalization_depth,
             ghostdag_k,
+            is_light,
             ghostdag_manager,
 
             is_consensus_exiting,
@@ -226,6 +243,8 @@ impl PruningProofManager {
         pruning_point_write.set_batch(&mut batch, new_pruning_point, new_pruning_point, (pruning_points.len() - 1) as u64).unwrap();
         pruning_point_write.set_retention_checkpoint(&mut batch, new_pruning_point).unwrap();
         pruning_point_write.set_retention_period_root(&mut batch, new_pruning_point).unwrap();
+        pruning_point_write.set_cold_storage_checkpoint(&mut batch, new_pruning_point).unwrap();
+        pruning_point_write.set_cold_storage_root(&mut batch, new_pruning_point).unwrap();
         self.db.write(batch).unwrap();
         drop(pruning_point_write);
 
@@ -383,16 +402,41 @@ impl PruningProofManager {
                 return cache.data;
             }
         }
+
+        // The proof is persisted per pruning point, so it needs to be rebuilt only once the pruning point moves
+        if let Some(stored) = self.pruning_proof_store.read().get().unwrap_option() {
+            if stored.pruning_point == pp {
+                cache_lock.replace(CachedPruningPointData { pruning_point: pp, data: stored.proof.clone() });
+                return stored.proof;
+            }
+        }
+
+        let start = Instant::now();
         let proof = Arc::new(self.build_pruning_point_proof(pp));
+        let build_duration = start.elapsed();
+        self.build_duration_ms.store(build_duration.as_millis() as u64, Ordering::Relaxed);
         info!(
-            "Built headers proof with overall {} headers ({} unique)",
+            "Built headers proof with overall {} headers ({} unique) in {:.2}s",
             proof.iter().map(|l| l.len()).sum::<usize>(),
-            proof.iter().flatten().unique_by(|h| h.hash).count()
+            proof.iter().flatten().unique_by(|h| h.hash).count(),
+            build_duration.as_secs_f64()
         );
+
+        // An empty proof (when the pruning point is genesis) is trivial to rebuild, so we avoid persisting it
+        if !proof.is_empty() {
+            self.pruning_proof_store.write().set(pp, proof.clone()).unwrap();
+        }
         cache_lock.replace(CachedPruningPointData { pruning_point: pp, data: proof.clone() });
         proof
     }
 
+    pub fn proof_stats(&self) -> PruningProofStats {
+        PruningProofStats {
+            build_duration_ms: self.build_duration_ms.load(Ordering::Relaxed),
+            validation_duration_ms: self.validation_duration_ms.load(Ordering::Relaxed),
+        }
+    }
+
     pub fn get_pruning_point_anticone_and_trusted_data(&self) -> ConsensusResult<Arc<PruningPointTrustedData>> {
         let pp = self.pruning_point_store.read().pruning_point().unwrap();
         let mut cache_lock = self.cached_anticone.lock();
@@ -402,7 +446,14 @@ impl PruningProofManager {
             }
         }
 
-        let virtual_state = self.virtual_stores.read().state.get().unwrap();
+        // Light nodes never resolve virtual, so the headers selected tip stands in for it
+        let (top_blue_score, top_tips) = if self.is_light {
+            let hst = self.headers_selected_tip_store.read().get().unwrap().hash;
+            (self.headers_store.get_blue_score(hst).unwrap(), vec![hst])
+        } else {
+            let virtual_state = self.virtual_stores.read().state.get().unwrap();
+            (virtual_state.ghostdag_data.blue_score, virtual_state.parents.clone())
+        };
         let pp_bs = self.headers_store.get_blue_score(pp).unwrap();
 
         // [Crescendo]: use pruning point DAA score for activation. This means that only after sufficient time
@@ -410,8 +461,8 @@ impl PruningProofManager {
         let pruning_point_daa_score = self.headers_store.get_daa_score(pp).unwrap();
 
         // The anticone is considered final only if the pruning point is at sufficient depth from virtual
-        if virtual_state.ghostdag_data.blue_score >= pp_bs + self.anticone_finalization_depth.get(pruning_point_daa_score) {
-            let anticone = Arc::new(self.calculate_pruning_point_anticone_and_trusted_data(pp, virtual_state.parents.iter().copied()));
+        if top_blue_score >= pp_bs + self.anticone_finalization_depth.get(pruning_point_daa_score) {
+            let anticone = Arc::new(self.calculate_pruning_point_anticone_and_trusted_data(pp, top_tips.into_iter()));
             cache_lock.replace(CachedPruningPointData { pruning_point: pp, data: anticone.clone() });
             Ok(anticone)
         } else {
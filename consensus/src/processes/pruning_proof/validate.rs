@@ -1,6 +1,7 @@
 use std::{
     ops::DerefMut,
     sync::{atomic::Ordering, Arc},
+    time::Instant,
 };
 
 use itertools::Itertools;
@@ -12,12 +13,13 @@ use kaspa_consensus_core::{
     BlockLevel,
 };
 use kaspa_core::info;
+use kaspa_database::prelude::WriteBatch;
 use kaspa_database::prelude::{CachePolicy, ConnBuilder, StoreResultEmptyTuple, StoreResultExtensions};
 use kaspa_hashes::Hash;
 use kaspa_pow::{calc_block_level, calc_block_level_check_pow};
 use kaspa_utils::vec::VecExtensions;
 use parking_lot::lock_api::RwLock;
-use rocksdb::WriteBatch;
+use rayon::prelude::*;
 
 use crate::{
     model::{
@@ -41,18 +43,31 @@ impl PruningProofManager {
         &self,
         proof: &PruningPointProof,
         proof_metadata: &PruningProofMetadata,
+    ) -> PruningImportResult<()> {
+        let start = Instant::now();
+        let res = self.validate_pruning_point_proof_inner(proof, proof_metadata);
+        let validation_duration = start.elapsed();
+        self.validation_duration_ms.store(validation_duration.as_millis() as u64, Ordering::Relaxed);
+        info!("Pruning point proof validation took {:.2}s", validation_duration.as_secs_f64());
+        res
+    }
+
+    fn validate_pruning_point_proof_inner(
+        &self,
+        proof: &PruningPointProof,
+        proof_metadata: &PruningProofMetadata,
     ) -> PruningImportResult<()> {
         if proof.len() != self.max_block_level as usize + 1 {
             return Err(PruningImportError::ProofNotEnoughLevels(self.max_block_level as usize + 1));
         }
 
         // Initialize the stores for the proof
-        let mut proof_stores_and_processes = self.init_validate_pruning_point_proof_stores_and_processes(proof)?;
+        let proof_stores_and_processes = self.init_validate_pruning_point_proof_stores_and_processes(proof)?;
         let proof_pp_header = proof[0].last().expect("checked if empty");
         let proof_pp = proof_pp_header.hash;
         let proof_pp_level = calc_block_level(proof_pp_header, self.max_block_level);
         let proof_selected_tip_by_level =
-            self.populate_stores_for_validate_pruning_point_proof(proof, &mut proof_stores_and_processes, true)?;
+            self.populate_stores_for_validate_pruning_point_proof(proof, &proof_stores_and_processes, true)?;
         let proof_ghostdag_stores = proof_stores_and_processes.ghostdag_stores;
 
         // Get the proof for the current consensus and recreate the stores for it
@@ -64,11 +79,11 @@ impl PruningProofManager {
             let genesis_header = self.headers_store.get_header(self.genesis_hash).unwrap();
             current_consensus_proof = Arc::new((0..=self.max_block_level).map(|_| vec![genesis_header.clone()]).collect_vec());
         }
-        let mut current_consensus_stores_and_processes =
+        let current_consensus_stores_and_processes =
             self.init_validate_pruning_point_proof_stores_and_processes(&current_consensus_proof)?;
         let _ = self.populate_stores_for_validate_pruning_point_proof(
             &current_consensus_proof,
-            &mut current_consensus_stores_and_processes,
+            &current_consensus_stores_and_processes,
             false,
         )?;
         let current_consensus_ghostdag_stores = current_consensus_stores_and_processes.ghostdag_stores;
@@ -235,113 +250,121 @@ impl PruningProofManager {
     fn populate_stores_for_validate_pruning_point_proof(
         &self,
         proof: &PruningPointProof,
-        ctx: &mut TempProofContext,
+        ctx: &TempProofContext,
         log_validating: bool,
     ) -> PruningImportResult<Vec<Hash>> {
+        // Each level is populated into its own stores and depends on other levels only through the cross-level
+        // checks below, so levels are populated (and their headers' PoW is checked) in parallel
+        let selected_tip_by_level = (0..=self.max_block_level)
+            .into_par_iter()
+            .map(|level| self.populate_level_for_validate_pruning_point_proof(proof, ctx, level, log_validating))
+            .collect::<PruningImportResult<Vec<Hash>>>()?;
+
+        for level in (0..self.max_block_level).rev() {
+            let level_idx = level as usize;
+            let block_at_depth_m_at_next_level = self
+                .block_at_depth(&*ctx.ghostdag_stores[level_idx + 1], selected_tip_by_level[level_idx + 1], self.pruning_proof_m)
+                .unwrap();
+            if !ctx.relations_stores[level_idx].has(block_at_depth_m_at_next_level).unwrap() {
+                return Err(PruningImportError::PruningProofMissingBlockAtDepthMFromNextLevel(level, level + 1));
+            }
+        }
+
+        Ok(selected_tip_by_level)
+    }
+
+    /// Populates the stores of a single proof level and returns the selected tip of the level
+    fn populate_level_for_validate_pruning_point_proof(
+        &self,
+        proof: &PruningPointProof,
+        ctx: &TempProofContext,
+        level: BlockLevel,
+        log_validating: bool,
+    ) -> PruningImportResult<Hash> {
+        // Before processing this level, check if the process is exiting so we can end early
+        if self.is_consensus_exiting.load(Ordering::Relaxed) {
+            return Err(PruningImportError::PruningValidationInterrupted);
+        }
+
+        let level_idx = level as usize;
         let headers_store = &ctx.headers_store;
-        let ghostdag_stores = &ctx.ghostdag_stores;
-        let mut relations_stores = ctx.relations_stores.clone();
-        let reachability_stores = &ctx.reachability_stores;
-        let ghostdag_managers = &ctx.ghostdag_managers;
+        let ghostdag_store = &ctx.ghostdag_stores[level_idx];
+        let mut relations_store = ctx.relations_stores[level_idx].clone();
+        let reachability_store = &ctx.reachability_stores[level_idx];
+        let ghostdag_manager = &ctx.ghostdag_managers[level_idx];
 
         let proof_pp_header = proof[0].last().expect("checked if empty");
         let proof_pp = proof_pp_header.hash;
 
-        let mut selected_tip_by_level = vec![None; self.max_block_level as usize + 1];
-        for level in (0..=self.max_block_level).rev() {
-            // Before processing this level, check if the process is exiting so we can end early
-            if self.is_consensus_exiting.load(Ordering::Relaxed) {
-                return Err(PruningImportError::PruningValidationInterrupted);
+        if log_validating {
+            info!("Validating level {level} from the pruning point proof ({} headers)", proof[level_idx].len());
+        }
+        let mut selected_tip = None;
+        for (i, header) in proof[level_idx].iter().enumerate() {
+            let (header_level, pow_passes) = calc_block_level_check_pow(header, self.max_block_level);
+            if header_level < level {
+                return Err(PruningImportError::PruningProofWrongBlockLevel(header.hash, header_level, level));
             }
-
-            if log_validating {
-                info!("Validating level {level} from the pruning point proof ({} headers)", proof[level as usize].len());
+            if !pow_passes {
+                return Err(PruningImportError::ProofOfWorkFailed(header.hash, level));
             }
-            let level_idx = level as usize;
-            let mut selected_tip = None;
-            for (i, header) in proof[level as usize].iter().enumerate() {
-                let (header_level, pow_passes) = calc_block_level_check_pow(header, self.max_block_level);
-                if header_level < level {
-                    return Err(PruningImportError::PruningProofWrongBlockLevel(header.hash, header_level, level));
-                }
-                if !pow_passes {
-                    return Err(PruningImportError::ProofOfWorkFailed(header.hash, level));
-                }
-
-                headers_store.insert(header.hash, header.clone(), header_level).unwrap_or_exists();
 
-                let parents = self
-                    .parents_manager
-                    .parents_at_level(header, level)
-                    .iter()
-                    .copied()
-                    .filter(|parent| ghostdag_stores[level_idx].has(*parent).unwrap())
-                    .collect_vec();
+            headers_store.insert(header.hash, header.clone(), header_level).unwrap_or_exists();
 
-                // Only the first block at each level is allowed to have no known parents
-                if parents.is_empty() && i != 0 {
-                    return Err(PruningImportError::PruningProofHeaderWithNoKnownParents(header.hash, level));
-                }
+            let parents = self
+                .parents_manager
+                .parents_at_level(header, level)
+                .iter()
+                .copied()
+                .filter(|parent| ghostdag_store.has(*parent).unwrap())
+                .collect_vec();
 
-                let parents: BlockHashes = parents.push_if_empty(ORIGIN).into();
-
-                if relations_stores[level_idx].has(header.hash).unwrap() {
-                    return Err(PruningImportError::PruningProofDuplicateHeaderAtLevel(header.hash, level));
-                }
-
-                relations_stores[level_idx].insert(header.hash, parents.clone()).unwrap();
-                let ghostdag_data = Arc::new(ghostdag_managers[level_idx].ghostdag(&parents));
-                ghostdag_stores[level_idx].insert(header.hash, ghostdag_data.clone()).unwrap();
-                selected_tip = Some(match selected_tip {
-                    Some(tip) => ghostdag_managers[level_idx].find_selected_parent([tip, header.hash]),
-                    None => header.hash,
-                });
-
-                let mut reachability_mergeset = {
-                    let reachability_read = reachability_stores[level_idx].read();
-                    ghostdag_data
-                        .unordered_mergeset_without_selected_parent()
-                        .filter(|hash| reachability_read.has(*hash).unwrap())
-                        .collect_vec() // We collect to vector so reachability_read can be released and let `reachability::add_block` use a write lock.
-                        .into_iter()
-                };
-                reachability::add_block(
-                    reachability_stores[level_idx].write().deref_mut(),
-                    header.hash,
-                    ghostdag_data.selected_parent,
-                    &mut reachability_mergeset,
-                )
-                .unwrap();
-
-                if selected_tip.unwrap() == header.hash {
-                    reachability::hint_virtual_selected_parent(reachability_stores[level_idx].write().deref_mut(), header.hash)
-                        .unwrap();
-                }
+            // Only the first block at each level is allowed to have no known parents
+            if parents.is_empty() && i != 0 {
+                return Err(PruningImportError::PruningProofHeaderWithNoKnownParents(header.hash, level));
             }
 
-            if level < self.max_block_level {
-                let block_at_depth_m_at_next_level = self
-                    .block_at_depth(
-                        &*ghostdag_stores[level_idx + 1],
-                        selected_tip_by_level[level_idx + 1].unwrap(),
-                        self.pruning_proof_m,
-                    )
-                    .unwrap();
-                if !relations_stores[level_idx].has(block_at_depth_m_at_next_level).unwrap() {
-                    return Err(PruningImportError::PruningProofMissingBlockAtDepthMFromNextLevel(level, level + 1));
-                }
+            let parents: BlockHashes = parents.push_if_empty(ORIGIN).into();
+
+            if relations_store.has(header.hash).unwrap() {
+                return Err(PruningImportError::PruningProofDuplicateHeaderAtLevel(header.hash, level));
             }
 
-            if selected_tip.unwrap() != proof_pp
-                && !self.parents_manager.parents_at_level(proof_pp_header, level).contains(&selected_tip.unwrap())
-            {
-                return Err(PruningImportError::PruningProofMissesBlocksBelowPruningPoint(selected_tip.unwrap(), level));
+            relations_store.insert(header.hash, parents.clone()).unwrap();
+            let ghostdag_data = Arc::new(ghostdag_manager.ghostdag(&parents));
+            ghostdag_store.insert(header.hash, ghostdag_data.clone()).unwrap();
+            selected_tip = Some(match selected_tip {
+                Some(tip) => ghostdag_manager.find_selected_parent([tip, header.hash]),
+                None => header.hash,
+            });
+
+            let mut reachability_mergeset = {
+                let reachability_read = reachability_store.read();
+                ghostdag_data
+                    .unordered_mergeset_without_selected_parent()
+                    .filter(|hash| reachability_read.has(*hash).unwrap())
+                    .collect_vec() // We collect to vector so reachability_read can be released and let `reachability::add_block` use a write lock.
+                    .into_iter()
+            };
+            reachability::add_block(
+                reachability_store.write().deref_mut(),
+                header.hash,
+                ghostdag_data.selected_parent,
+                &mut reachability_mergeset,
+            )
+            .unwrap();
+
+            if selected_tip.unwrap() == header.hash {
+                reachability::hint_virtual_selected_parent(reachability_store.write().deref_mut(), header.hash).unwrap();
             }
+        }
 
-            selected_tip_by_level[level_idx] = selected_tip;
+        let selected_tip = selected_tip.unwrap();
+        if selected_tip != proof_pp && !self.parents_manager.parents_at_level(proof_pp_header, level).contains(&selected_tip) {
+            return Err(PruningImportError::PruningProofMissesBlocksBelowPruningPoint(selected_tip, level));
         }
 
-        Ok(selected_tip_by_level.into_iter().map(|selected_tip| selected_tip.unwrap()).collect())
+        Ok(selected_tip)
     }
 
     fn validate_proof_selected_tip(
@@ -1,15 +1,26 @@
 use kaspa_hashes::{Hash, HasherBase, MerkleBranchHash, ZERO_HASH};
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LeafRoute {
     Left,
     Right,
 }
 pub type MerkleWitness = Vec<WitnessSegment>;
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WitnessSegment {
     companion_hash: Hash,
     leaf_route: LeafRoute,
 }
+impl WitnessSegment {
+    pub fn new(companion_hash: Hash, leaf_route: LeafRoute) -> Self {
+        Self { companion_hash, leaf_route }
+    }
+    pub fn companion_hash(&self) -> Hash {
+        self.companion_hash
+    }
+    pub fn leaf_route(&self) -> LeafRoute {
+        self.leaf_route
+    }
+}
 
 fn derive_merkle_tree(hashes: impl ExactSizeIterator<Item = Hash>) -> Vec<Option<Hash>> {
     if hashes.len() == 0 {
@@ -2,7 +2,7 @@ use crate::{cache::CachePolicy, db::DB, errors::StoreError};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use kaspa_utils::mem_size::MemSizeEstimator;
-use rocksdb::{Direction, IterateBounds, IteratorMode, ReadOptions};
+use rocksdb::IterateBounds;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{collections::hash_map::RandomState, error::Error, hash::BuildHasher, sync::Arc};
 
@@ -30,8 +30,14 @@ where
     TData: Clone + Send + Sync + MemSizeEstimator,
     S: BuildHasher + Default,
 {
-    pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self {
-        Self { db, cache: Cache::new(cache_policy), prefix }
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self
+    where
+        TKey: 'static,
+        TData: 'static,
+        S: Send + Sync + 'static,
+    {
+        let cache = Cache::with_registry(cache_policy, db.cache_registry());
+        Self { db, cache, prefix }
     }
 
     pub fn read_from_cache(&self, key: TKey) -> Option<TData>
@@ -45,19 +51,19 @@ where
     where
         TKey: Clone + AsRef<[u8]>,
     {
-        Ok(self.cache.contains_key(&key) || self.db.get_pinned(DbKey::new(&self.prefix, key))?.is_some())
+        Ok(self.cache.contains_key(&key) || self.db.get(DbKey::new(&self.prefix, key))?.is_some())
     }
 
     pub fn read(&self, key: TKey) -> Result<TData, StoreError>
     where
         TKey: Clone + AsRef<[u8]> + ToString,
-        TData: DeserializeOwned, // We need `DeserializeOwned` since the slice coming from `db.get_pinned` has short lifetime
+        TData: DeserializeOwned, // We need `DeserializeOwned` since the slice coming from `db.get` has short lifetime
     {
         if let Some(data) = self.cache.get(&key) {
             Ok(data)
         } else {
             let db_key = DbKey::new(&self.prefix, key.clone());
-            if let Some(slice) = self.db.get_pinned(&db_key)? {
+            if let Some(slice) = self.db.get(&db_key)? {
                 let data: TData = bincode::deserialize(&slice)?;
                 self.cache.insert(key, data.clone());
                 Ok(data)
@@ -70,19 +76,15 @@ where
     pub fn iterator(&self) -> impl Iterator<Item = KeyDataResult<TData>> + '_
     where
         TKey: Clone + AsRef<[u8]>,
-        TData: DeserializeOwned, // We need `DeserializeOwned` since the slice coming from `db.get_pinned` has short lifetime
+        TData: DeserializeOwned, // We need `DeserializeOwned` since the slice coming from `db.get` has short lifetime
     {
         let prefix_key = DbKey::prefix_only(&self.prefix);
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
-        self.db.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts).map(move |iter_result| {
-            match iter_result {
-                Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
-                    Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
-                    Err(e) => Err(e.into()),
-                },
+        self.db.prefix_iterator(prefix_key.as_ref(), None).map(move |iter_result| match iter_result {
+            Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
+                Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
                 Err(e) => Err(e.into()),
-            }
+            },
+            Err(e) => Err(e.into()),
         })
     }
 
@@ -155,7 +157,7 @@ where
         Ok(())
     }
 
-    /// Deletes all entries in the store using the underlying rocksdb `delete_range` operation
+    /// Deletes all entries in the store using a single `delete_range` operation
     pub fn delete_all(&self, mut writer: impl DbWriter) -> Result<(), StoreError>
     where
         TKey: Clone + AsRef<[u8]>,
@@ -189,15 +191,8 @@ where
             },
         );
 
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(db_key.as_ref()));
-
-        let mut db_iterator = match seek_from {
-            Some(seek_key) => {
-                self.db.iterator_opt(IteratorMode::From(DbKey::new(&self.prefix, seek_key).as_ref(), Direction::Forward), read_opts)
-            }
-            None => self.db.iterator_opt(IteratorMode::Start, read_opts),
-        };
+        let seek_key = seek_from.map(|seek_key| DbKey::new(&self.prefix, seek_key));
+        let mut db_iterator = self.db.prefix_iterator(db_key.as_ref(), seek_key.as_ref().map(AsRef::as_ref));
 
         if skip_first {
             db_iterator.next();
@@ -222,10 +217,9 @@ mod tests {
     use super::*;
     use crate::{
         create_temp_db,
-        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter},
+        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter, WriteBatch},
     };
     use kaspa_hashes::Hash;
-    use rocksdb::WriteBatch;
 
     #[test]
     fn test_delete_all() {
@@ -0,0 +1,67 @@
+use crate::{batch::WriteBatch, errors::StoreResult};
+use rocksdb::DBPinnableSlice;
+use std::{any::Any, ops::Deref, path::Path};
+
+pub use memory::MemoryBackend;
+pub use rocks::RocksDbBackend;
+
+mod memory;
+mod rocks;
+
+/// An iterator over the `(key, value)` entries of a key range, in key order
+pub type DbIterator<'a> = Box<dyn Iterator<Item = StoreResult<(Box<[u8]>, Box<[u8]>)>> + 'a>;
+
+/// A value read from a [`StorageBackend`], either pinned by the backend or owned
+pub enum DbSlice<'a> {
+    Pinned(DBPinnableSlice<'a>),
+    Owned(Box<[u8]>),
+}
+
+impl Deref for DbSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            DbSlice::Pinned(slice) => slice,
+            DbSlice::Owned(bytes) => bytes,
+        }
+    }
+}
+
+impl AsRef<[u8]> for DbSlice<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// The key-value engine underlying a [`crate::prelude::DB`]. All stores share a single ordered keyspace
+/// where each key starts with the prefix of its store (see [`crate::registry::DatabaseStorePrefixes`]),
+/// hence a backend may lay out stores as it sees fit as long as ordering within each prefix is preserved
+pub trait StorageBackend: Send + Sync + 'static {
+    /// Reads the value stored under `key`
+    fn get(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>>;
+
+    /// Iterates over all entries whose keys start with `prefix`, starting from the first key which is
+    /// greater than or equal to `from` (or to `prefix` if `None`)
+    fn iterator(&self, prefix: &[u8], from: Option<&[u8]>) -> DbIterator<'_>;
+
+    /// Applies all operations of `batch` atomically. If `sync` is set, the call returns only after
+    /// the batch is persisted
+    fn write(&self, batch: WriteBatch, sync: bool) -> StoreResult<()>;
+
+    /// Reclaims the space of entries deleted within the key range `[from, to)`. Blocks until done
+    fn compact_range(&self, from: &[u8], to: &[u8]);
+
+    /// Returns the estimated size in bytes of the entries within the key range `[from, to)`
+    fn estimated_range_size(&self, from: &[u8], to: &[u8]) -> u64;
+
+    /// Returns the estimated overall size in bytes of the stored data
+    fn estimated_size(&self) -> u64;
+
+    /// The directory holding the backend files, if any
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+
+    fn as_any(&self) -> &dyn Any;
+}
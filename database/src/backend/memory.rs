@@ -0,0 +1,116 @@
+use super::{DbIterator, DbSlice, StorageBackend};
+use crate::{
+    batch::{BatchOp, WriteBatch},
+    errors::StoreResult,
+};
+use parking_lot::RwLock;
+use rocksdb::{IterateBounds, PrefixRange};
+use std::{any::Any, collections::BTreeMap, ops::Bound};
+
+type Entries = BTreeMap<Box<[u8]>, Box<[u8]>>;
+
+/// A storage backend keeping all entries in memory, intended for tests where it avoids the cost of
+/// creating and destroying a RocksDB instance on disk. Like RocksDB iterators, iterators of this backend
+/// operate on a snapshot taken on creation, hence they do not observe writes made during iteration.
+/// Compactions are meaningless in memory and are ignored
+#[derive(Default)]
+pub struct MemoryBackend {
+    entries: RwLock<Entries>,
+}
+
+fn range_size(entries: &Entries, from: &[u8], to: &[u8]) -> u64 {
+    entries.range::<[u8], _>((Bound::Included(from), Bound::Excluded(to))).map(|(key, value)| (key.len() + value.len()) as u64).sum()
+}
+
+impl StorageBackend for MemoryBackend {
+    fn get(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>> {
+        Ok(self.entries.read().get(key).cloned().map(DbSlice::Owned))
+    }
+
+    fn iterator(&self, prefix: &[u8], from: Option<&[u8]>) -> DbIterator<'_> {
+        let (_, end) = PrefixRange(prefix).into_bounds();
+        let range = (Bound::Included(from.unwrap_or(prefix)), end.as_deref().map_or(Bound::Unbounded, Bound::Excluded));
+        // The range is copied under the read lock, which provides the snapshot semantics of RocksDB iterators
+        let snapshot: Vec<StoreResult<_>> =
+            self.entries.read().range::<[u8], _>(range).map(|(key, value)| Ok((key.clone(), value.clone()))).collect();
+        Box::new(snapshot.into_iter())
+    }
+
+    fn write(&self, batch: WriteBatch, _sync: bool) -> StoreResult<()> {
+        let mut entries = self.entries.write();
+        for op in batch.iter() {
+            match op {
+                BatchOp::Put(key, value) => {
+                    entries.insert(key.into(), value.into());
+                }
+                BatchOp::Delete(key) => {
+                    entries.remove(key);
+                }
+                BatchOp::DeleteRange(from, to) => {
+                    let mut tail = entries.split_off(from);
+                    entries.append(&mut tail.split_off(to));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn compact_range(&self, _from: &[u8], _to: &[u8]) {}
+
+    fn estimated_range_size(&self, from: &[u8], to: &[u8]) -> u64 {
+        range_size(&self.entries.read(), from, to)
+    }
+
+    fn estimated_size(&self) -> u64 {
+        self.entries.read().iter().map(|(key, value)| (key.len() + value.len()) as u64).sum()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DbSetAccess, DirectDbWriter, WriteBatch, DB};
+    use kaspa_hashes::Hash;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_memory_backend() {
+        let db = Arc::new(DB::in_memory());
+        let access = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Empty, vec![1, 2]);
+        let neighbour = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Empty, vec![1, 3]);
+        access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), i))).unwrap();
+        neighbour.write_many(DirectDbWriter::new(&db), &mut (0..4).map(|i| (i.into(), i))).unwrap();
+        assert_eq!(16, access.iterator().count());
+        assert_eq!(7, access.read(7.into()).unwrap());
+        assert_eq!(6, access.seek_iterator(None, Some(10.into()), usize::MAX, false).count());
+
+        access.delete(DirectDbWriter::new(&db), 7.into()).unwrap();
+        assert!(!access.has(7.into()).unwrap());
+        assert_eq!(15, access.iterator().count());
+
+        let mut batch = WriteBatch::default();
+        access.delete_all(BatchDbWriter::new(&mut batch)).unwrap();
+        assert_eq!(15, access.iterator().count());
+        db.write(batch).unwrap();
+        assert_eq!(0, access.iterator().count());
+        assert_eq!(4, neighbour.iterator().count());
+
+        let set_access = DbSetAccess::<Hash, u64>::new(db.clone(), vec![2]);
+        for i in 0..4 {
+            set_access.write(DirectDbWriter::new(&db), 1.into(), i).unwrap();
+        }
+        assert_eq!(4, set_access.bucket_iterator(1.into()).count());
+        assert_eq!(0, set_access.bucket_iterator(2.into()).count());
+
+        // Iterators operate on a snapshot taken on creation
+        let iterator = neighbour.iterator();
+        neighbour.write(DirectDbWriter::new(&db), 8.into(), 8).unwrap();
+        neighbour.delete(DirectDbWriter::new(&db), 0.into()).unwrap();
+        assert_eq!(4, iterator.count());
+        assert_eq!(4, neighbour.iterator().count());
+        assert!(!neighbour.has(0.into()).unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+use super::{DbIterator, DbSlice, StorageBackend};
+use crate::{
+    batch::{BatchOp, WriteBatch},
+    errors::{StoreError, StoreResult},
+    registry::{DatabaseStorePrefixes, StoreFamily},
+};
+use kaspa_utils::fd_budget::FDGuard;
+use num_traits::FromPrimitive;
+use rocksdb::{
+    properties, BoundColumnFamily, CompactOptions, DBWithThreadMode, Direction, IteratorMode, MultiThreaded, PrefixRange, Range,
+    ReadOptions, WriteOptions, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use std::{any::Any, ops::Deref, path::Path, sync::Arc};
+
+/// The number of entries moved in each atomic batch by [`RocksDbBackend::migrate_to_column_families`]
+const MIGRATION_CHUNK_SIZE: usize = 10_000;
+
+/// The default storage backend, keeping stores in a RocksDB instance on disk
+pub struct RocksDbBackend {
+    inner: DBWithThreadMode<MultiThreaded>,
+    /// Whether stores are kept in per-[`StoreFamily`] column families
+    column_families: bool,
+    /// The column family of each store prefix byte, resolved once on creation
+    families: [Option<StoreFamily>; 256],
+    _fd_guard: FDGuard,
+}
+
+/// Column family handles looked up lazily and reused across the operations of a single write batch
+struct BatchColumnFamilies<'a> {
+    backend: &'a RocksDbBackend,
+    /// The handle of the default column family followed by those of [`StoreFamily::ALL`]
+    handles: [Option<Arc<BoundColumnFamily<'a>>>; StoreFamily::ALL.len() + 1],
+}
+
+impl<'a> BatchColumnFamilies<'a> {
+    fn new(backend: &'a RocksDbBackend) -> Self {
+        Self { backend, handles: Default::default() }
+    }
+
+    fn get(&mut self, key: &[u8]) -> &Arc<BoundColumnFamily<'a>> {
+        let family = self.backend.family(key);
+        let backend = self.backend;
+        self.handles[family.map_or(0, |family| family as usize + 1)].get_or_insert_with(|| backend.cf(family))
+    }
+}
+
+impl RocksDbBackend {
+    pub fn new(inner: DBWithThreadMode<MultiThreaded>, column_families: bool, fd_guard: FDGuard) -> Self {
+        let families = std::array::from_fn(|prefix| {
+            DatabaseStorePrefixes::from_u8(prefix as u8).and_then(DatabaseStorePrefixes::family).filter(|_| column_families)
+        });
+        Self { inner, column_families, families, _fd_guard: fd_guard }
+    }
+
+    /// Returns whether stores are kept in per-[`StoreFamily`] column families
+    pub fn has_column_families(&self) -> bool {
+        self.column_families
+    }
+
+    /// Returns the column family holding the entries of the store with the given (possibly compound) `prefix`.
+    /// Stores which do not belong to a [`StoreFamily`], or all stores if the DB was opened without column
+    /// families, reside in the default column family
+    pub fn store_cf(&self, prefix: &[u8]) -> Arc<BoundColumnFamily<'_>> {
+        self.cf(self.family(prefix))
+    }
+
+    fn family(&self, prefix: &[u8]) -> Option<StoreFamily> {
+        prefix.first().and_then(|&prefix| self.families[prefix as usize])
+    }
+
+    fn cf(&self, family: Option<StoreFamily>) -> Arc<BoundColumnFamily<'_>> {
+        let name = family.map_or(DEFAULT_COLUMN_FAMILY_NAME, StoreFamily::name);
+        self.inner.cf_handle(name).unwrap_or_else(|| panic!("column family {name} is expected to be open"))
+    }
+
+    /// Moves the entries of all stores which belong to a [`StoreFamily`] from the default column family (where a DB
+    /// created without column families keeps all stores) into their family column families. Entries are moved in
+    /// atomic chunks, so the migration can be safely resumed if interrupted. `progress` is called with the overall
+    /// number of entries moved so far after each chunk. Returns the overall number of entries moved.
+    pub fn migrate_to_column_families(&self, mut progress: impl FnMut(u64)) -> Result<u64, rocksdb::Error> {
+        assert!(self.column_families, "the DB must be opened with column families in order to be migrated");
+        let default_cf = self.cf(None);
+        let mut moved = 0u64;
+        for family in StoreFamily::ALL {
+            let family_cf = self.cf(Some(family));
+            for prefix in family.prefixes() {
+                let mut read_opts = ReadOptions::default();
+                read_opts.set_iterate_range(PrefixRange([prefix as u8].as_slice()));
+                let mut iter =
+                    self.inner.iterator_cf_opt(&default_cf, read_opts, IteratorMode::From(&[prefix as u8], Direction::Forward));
+                loop {
+                    let mut batch = rocksdb::WriteBatch::default();
+                    for item in iter.by_ref().take(MIGRATION_CHUNK_SIZE) {
+                        let (key, value) = item?;
+                        batch.put_cf(&family_cf, &key, value);
+                        batch.delete_cf(&default_cf, key);
+                    }
+                    let count = batch.len() as u64 / 2;
+                    if count == 0 {
+                        break;
+                    }
+                    self.inner.write(batch)?;
+                    moved += count;
+                    progress(moved);
+                }
+            }
+        }
+        // Reclaim the space of the moved entries
+        let mut options = CompactOptions::default();
+        options.set_exclusive_manual_compaction(false);
+        self.inner.compact_range_cf_opt(&default_cf, None::<&[u8]>, None::<&[u8]>, &options);
+        Ok(moved)
+    }
+}
+
+impl StorageBackend for RocksDbBackend {
+    fn get(&self, key: &[u8]) -> StoreResult<Option<DbSlice<'_>>> {
+        Ok(self.inner.get_pinned_cf(&self.store_cf(key), key)?.map(DbSlice::Pinned))
+    }
+
+    fn iterator(&self, prefix: &[u8], from: Option<&[u8]>) -> DbIterator<'_> {
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_iterate_range(PrefixRange(prefix));
+        let mode = IteratorMode::From(from.unwrap_or(prefix), Direction::Forward);
+        Box::new(self.inner.iterator_cf_opt(&self.store_cf(prefix), read_opts, mode).map(|item| item.map_err(StoreError::from)))
+    }
+
+    fn write(&self, batch: WriteBatch, sync: bool) -> StoreResult<()> {
+        let mut rocks_batch = rocksdb::WriteBatch::default();
+        let mut cfs = BatchColumnFamilies::new(self);
+        for op in batch.iter() {
+            match op {
+                BatchOp::Put(key, value) => rocks_batch.put_cf(cfs.get(key), key, value),
+                BatchOp::Delete(key) => rocks_batch.delete_cf(cfs.get(key), key),
+                BatchOp::DeleteRange(from, to) => rocks_batch.delete_range_cf(cfs.get(from), from, to),
+            }
+        }
+        let mut write_opts = WriteOptions::default();
+        write_opts.set_sync(sync);
+        Ok(self.inner.write_opt(rocks_batch, &write_opts)?)
+    }
+
+    fn compact_range(&self, from: &[u8], to: &[u8]) {
+        let mut options = CompactOptions::default();
+        options.set_exclusive_manual_compaction(false);
+        self.inner.compact_range_cf_opt(&self.store_cf(from), Some(from), Some(to), &options);
+    }
+
+    fn estimated_range_size(&self, from: &[u8], to: &[u8]) -> u64 {
+        self.inner.get_approximate_sizes_cf(&self.store_cf(from), &[Range::new(from, to)]).first().copied().unwrap_or_default()
+    }
+
+    /// Returns the total size in bytes of the DB files (of all column families) as reported by RocksDB
+    fn estimated_size(&self) -> u64 {
+        let families = if self.column_families { StoreFamily::ALL.map(Some).to_vec() } else { vec![] };
+        std::iter::once(None)
+            .chain(families)
+            .map(|family| {
+                self.inner.property_int_value_cf(&self.cf(family), properties::TOTAL_SST_FILES_SIZE).ok().flatten().unwrap_or_default()
+            })
+            .sum()
+    }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self.inner.path())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Exposes the underlying RocksDB instance for engine-specific operations such as DB upgrades
+impl Deref for RocksDbBackend {
+    type Target = DBWithThreadMode<MultiThreaded>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::{CachePolicy, CachedDbAccess, ConnBuilder, DirectDbWriter},
+        registry::DatabaseStorePrefixes,
+        utils::{get_kaspa_tempdir, DbLifetime},
+    };
+    use kaspa_hashes::Hash;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_migrate_to_column_families() {
+        let db_tempdir = get_kaspa_tempdir();
+        let db_path = db_tempdir.path().to_owned();
+        let headers_prefix = vec![DatabaseStorePrefixes::Headers as u8];
+        let other_prefix = vec![DatabaseStorePrefixes::UtxoIndex as u8];
+
+        // Populate a DB with a single keyspace
+        let db = ConnBuilder::default().with_db_path(db_path.clone()).with_files_limit(10).build().unwrap();
+        for prefix in [&headers_prefix, &other_prefix] {
+            let access = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Empty, prefix.clone());
+            access.write_many(DirectDbWriter::new(&db), &mut (0..16).map(|i| (i.into(), i))).unwrap();
+        }
+        drop(db);
+
+        let db = ConnBuilder::default().with_db_path(db_path).with_files_limit(10).with_column_families(true).build().unwrap();
+        let lifetime = DbLifetime::new(db_tempdir, Arc::downgrade(&db));
+        let headers = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Empty, headers_prefix);
+        let other = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Empty, other_prefix);
+        assert_eq!(0, headers.iterator().count());
+        assert_eq!(16, other.iterator().count());
+
+        let backend = db.rocksdb().unwrap();
+        let mut reported = 0;
+        assert_eq!(16, backend.migrate_to_column_families(|moved| reported = moved).unwrap());
+        assert_eq!(16, reported);
+        assert_eq!(16, headers.iterator().count());
+        assert_eq!(7, headers.read(7.into()).unwrap());
+        assert_eq!(16, other.iterator().count());
+
+        // Migration is idempotent
+        assert_eq!(0, backend.migrate_to_column_families(|_| {}).unwrap());
+        drop((headers, other, db));
+        drop(lifetime);
+    }
+}
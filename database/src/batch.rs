@@ -0,0 +1,102 @@
+/// A single write operation within a [`WriteBatch`], borrowing its keys and values from the batch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchOp<'a> {
+    Put(&'a [u8], &'a [u8]),
+    Delete(&'a [u8]),
+    /// Deletes all keys in the range `[from, to)`
+    DeleteRange(&'a [u8], &'a [u8]),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum OpKind {
+    Put,
+    Delete,
+    DeleteRange,
+}
+
+/// A batch of write operations which is applied atomically by [`crate::prelude::DB::write`], regardless
+/// of the storage backend. Keys and values are appended to a single buffer, so adding an operation does
+/// not allocate per key or value
+#[derive(Clone, Debug, Default)]
+pub struct WriteBatch {
+    /// The keys and values of all operations, laid out back to back in order
+    data: Vec<u8>,
+    /// The kind of each operation along with the end offsets of its first and second byte strings within `data`
+    ops: Vec<(OpKind, usize, usize)>,
+}
+
+impl WriteBatch {
+    pub fn put<K, V>(&mut self, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.push(OpKind::Put, key.as_ref(), value.as_ref());
+    }
+
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) {
+        self.push(OpKind::Delete, key.as_ref(), &[]);
+    }
+
+    pub fn delete_range<K: AsRef<[u8]>>(&mut self, from: K, to: K) {
+        self.push(OpKind::DeleteRange, from.as_ref(), to.as_ref());
+    }
+
+    fn push(&mut self, kind: OpKind, first: &[u8], second: &[u8]) {
+        self.data.extend_from_slice(first);
+        let first_end = self.data.len();
+        self.data.extend_from_slice(second);
+        self.ops.push((kind, first_end, self.data.len()));
+    }
+
+    /// The number of operations in the batch
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.ops.clear();
+    }
+
+    /// Returns the operations of the batch in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = BatchOp<'_>> {
+        let mut start = 0;
+        self.ops.iter().map(move |&(kind, first_end, second_end)| {
+            let (first, second) = (&self.data[start..first_end], &self.data[first_end..second_end]);
+            start = second_end;
+            match kind {
+                OpKind::Put => BatchOp::Put(first, second),
+                OpKind::Delete => BatchOp::Delete(first),
+                OpKind::DeleteRange => BatchOp::DeleteRange(first, second),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_batch_ops() {
+        let mut batch = WriteBatch::default();
+        batch.put([1u8, 2], [3u8]);
+        batch.delete([4u8]);
+        batch.put([5u8], [0u8; 0]);
+        batch.delete_range([6u8, 0], [6, 1]);
+        assert_eq!(batch.len(), 4);
+        assert_eq!(
+            batch.iter().collect::<Vec<_>>(),
+            vec![BatchOp::Put(&[1, 2], &[3]), BatchOp::Delete(&[4]), BatchOp::Put(&[5], &[]), BatchOp::DeleteRange(&[6, 0], &[6, 1])]
+        );
+
+        batch.clear();
+        assert!(batch.is_empty());
+        assert_eq!(batch.iter().count(), 0);
+    }
+}
@@ -1,8 +1,15 @@
 use indexmap::IndexMap;
 use kaspa_utils::mem_size::{MemMode, MemSizeEstimator};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rand::Rng;
-use std::{collections::hash_map::RandomState, hash::BuildHasher, sync::Arc};
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum CachePolicy {
@@ -12,8 +19,164 @@ pub enum CachePolicy {
     Count(usize),
     /// Items are tracked by size with a `max_size` limit overall. The cache will pass this limit
     /// if there are no more than `min_items` items in the cache. `mem_mode` determines whether
-    /// items are tracked by bytes or by units
-    Tracked { max_size: usize, min_items: usize, mem_mode: MemMode },
+    /// items are tracked by bytes or by units, where `unit_bytes` is the approximate size of
+    /// a unit in bytes (expected to be 1 in bytes mode). The effective limit may be scaled at
+    /// runtime via the [`TrackedCacheRegistry`] the cache is registered with
+    Tracked { max_size: usize, min_items: usize, mem_mode: MemMode, unit_bytes: usize },
+}
+
+/// The limit and current size of a tracked cache, shared with the registry the cache is registered
+/// with so that the overall memory usage of tracked caches can be observed and bounded at runtime
+struct TrackedLimit {
+    /// The max size the cache was configured with
+    configured_max_size: usize,
+    /// Approximate size of a tracked unit in bytes
+    unit_bytes: usize,
+    /// The effective max size, i.e., the configured size scaled by the current registry scale
+    max_size: AtomicUsize,
+    /// Mirrors the tracked size of the cache, updated following each cache mutation
+    tracked_size: AtomicUsize,
+}
+
+impl TrackedLimit {
+    fn new(configured_max_size: usize, unit_bytes: usize) -> Self {
+        Self { configured_max_size, unit_bytes, max_size: AtomicUsize::new(configured_max_size), tracked_size: AtomicUsize::new(0) }
+    }
+
+    fn scale(&self, scale: f64) {
+        // Never scale a non-empty limit down to zero, since zero-sized caches are special-cased as empty
+        let max_size = ((self.configured_max_size as f64 * scale) as usize).max(1);
+        self.max_size.store(max_size, Ordering::Relaxed);
+    }
+}
+
+/// A tracked cache as seen by the [`TrackedCacheRegistry`] it is registered with
+trait RegisteredCache: Send + Sync {
+    fn limit(&self) -> &TrackedLimit;
+
+    /// Returns whether the cache is still in use, i.e., was not dropped
+    fn is_live(&self) -> bool;
+
+    /// Evicts items until the cache meets its current limit
+    fn evict(&self);
+}
+
+struct RegisteredInner<TKey, TData, S>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+{
+    inner: Weak<RwLock<Inner<TKey, TData, S>>>,
+    policy: CachePolicyInner,
+}
+
+impl<TKey, TData, S> RegisteredCache for RegisteredInner<TKey, TData, S>
+where
+    TKey: Clone + std::hash::Hash + Eq + Send + Sync,
+    TData: Clone + Send + Sync + MemSizeEstimator,
+    S: BuildHasher + Default + Send + Sync,
+{
+    fn limit(&self) -> &TrackedLimit {
+        self.policy.limit.as_deref().expect("only caches with a tracked limit are registered")
+    }
+
+    fn is_live(&self) -> bool {
+        self.inner.strong_count() > 0
+    }
+
+    fn evict(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.write().tracked_evict(&self.policy);
+        }
+    }
+}
+
+struct RegistryInner {
+    caches: Vec<Arc<dyn RegisteredCache>>,
+    scale: f64,
+}
+
+/// A registry of tracked caches (see [`CachePolicy::Tracked`]) whose limits are scaled together at runtime, e.g., by a
+/// [`crate::prelude::MemoryGovernor`]. The registry is shared by cloning. Caches are registered on creation (see
+/// [`Cache::with_registry`]) and are dropped from the registry once they are dropped themselves
+#[derive(Clone)]
+pub struct TrackedCacheRegistry {
+    inner: Arc<Mutex<RegistryInner>>,
+}
+
+impl std::fmt::Debug for TrackedCacheRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("TrackedCacheRegistry").field("caches", &inner.caches.len()).field("scale", &inner.scale).finish()
+    }
+}
+
+impl Default for TrackedCacheRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrackedCacheRegistry {
+    pub fn new() -> Self {
+        Self { inner: Arc::new(Mutex::new(RegistryInner { caches: Vec::new(), scale: 1.0 })) }
+    }
+
+    fn register(&self, cache: Arc<dyn RegisteredCache>) {
+        let mut inner = self.inner.lock();
+        if inner.scale != 1.0 {
+            cache.limit().scale(inner.scale);
+        }
+        inner.caches.retain(|cache| cache.is_live());
+        inner.caches.push(cache);
+    }
+
+    /// Returns the aggregated sizes of all live registered caches
+    pub fn size(&self) -> TrackedCachesSize {
+        let mut inner = self.inner.lock();
+        inner.caches.retain(|cache| cache.is_live());
+        inner.caches.iter().fold(TrackedCachesSize::default(), |mut size, cache| {
+            let limit = cache.limit();
+            size.tracked_bytes += limit.tracked_size.load(Ordering::Relaxed) * limit.unit_bytes;
+            size.configured_bytes += limit.configured_max_size * limit.unit_bytes;
+            size.max_bytes += limit.max_size.load(Ordering::Relaxed) * limit.unit_bytes;
+            size
+        })
+    }
+
+    /// Returns the scale currently applied to the configured limits of registered caches
+    pub fn scale(&self) -> f64 {
+        self.inner.lock().scale
+    }
+
+    /// Scales the limits of all live and future registered caches relative to their configured limits. When the
+    /// limits shrink, caches exceeding their new limit evict items right away, so that the memory is reclaimed also
+    /// from caches which are rarely mutated
+    pub fn set_scale(&self, scale: f64) {
+        let (shrunk, caches) = {
+            let mut inner = self.inner.lock();
+            let shrunk = scale < inner.scale;
+            inner.scale = scale;
+            inner.caches.retain(|cache| cache.is_live());
+            inner.caches.iter().for_each(|cache| cache.limit().scale(scale));
+            (shrunk, inner.caches.clone())
+        };
+        // Evicting requires the write lock of each cache, so it is done without holding the registry lock
+        if shrunk {
+            caches.iter().for_each(|cache| cache.evict());
+        }
+    }
+}
+
+/// Aggregated sizes of the live caches of a [`TrackedCacheRegistry`], in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrackedCachesSize {
+    /// The overall (approximate) size of the items currently held by tracked caches
+    pub tracked_bytes: usize,
+    /// The overall limit tracked caches were configured with
+    pub configured_bytes: usize,
+    /// The overall effective limit of tracked caches following runtime scaling
+    pub max_bytes: usize,
 }
 
 #[derive(Clone)]
@@ -27,15 +190,36 @@ struct CachePolicyInner {
     min_items: usize,
     /// Indicates whether tracking is in bytes mode, units mode or undefined
     mem_mode: MemMode,
+    /// The runtime limit of a tracked cache
+    limit: Option<Arc<TrackedLimit>>,
+}
+
+impl CachePolicyInner {
+    /// The effective max size of a tracked cache
+    fn tracked_max_size(&self) -> usize {
+        self.limit.as_ref().map_or(self.max_size, |limit| limit.max_size.load(Ordering::Relaxed))
+    }
+
+    fn update_tracked_size(&self, tracked_size: usize) {
+        if let Some(limit) = self.limit.as_ref() {
+            limit.tracked_size.store(tracked_size, Ordering::Relaxed);
+        }
+    }
 }
 
 impl From<CachePolicy> for CachePolicyInner {
     fn from(policy: CachePolicy) -> Self {
         match policy {
-            CachePolicy::Empty => CachePolicyInner { tracked: false, max_size: 0, min_items: 0, mem_mode: MemMode::Undefined },
-            CachePolicy::Count(max_size) => CachePolicyInner { tracked: false, max_size, min_items: 0, mem_mode: MemMode::Undefined },
-            CachePolicy::Tracked { max_size, min_items, mem_mode } => {
-                CachePolicyInner { tracked: true, max_size, min_items, mem_mode }
+            CachePolicy::Empty => {
+                CachePolicyInner { tracked: false, max_size: 0, min_items: 0, mem_mode: MemMode::Undefined, limit: None }
+            }
+            CachePolicy::Count(max_size) => {
+                CachePolicyInner { tracked: false, max_size, min_items: 0, mem_mode: MemMode::Undefined, limit: None }
+            }
+            CachePolicy::Tracked { max_size, min_items, mem_mode, unit_bytes } => {
+                // Empty caches are never resized so there's no need to track their limit
+                let limit = (max_size > 0).then(|| Arc::new(TrackedLimit::new(max_size, unit_bytes)));
+                CachePolicyInner { tracked: true, max_size, min_items, mem_mode, limit }
             }
         }
     }
@@ -60,11 +244,13 @@ where
     /// Evicts items until meeting cache policy requirements (in tracked mode)
     fn tracked_evict(&mut self, policy: &CachePolicyInner) {
         // We allow passing tracked size limit as long as there are no more than min_items items
-        while self.tracked_size > policy.max_size && self.map.len() > policy.min_items {
+        let max_size = policy.tracked_max_size();
+        while self.tracked_size > max_size && self.map.len() > policy.min_items {
             if let Some((_, v)) = self.map.swap_remove_index(rand::thread_rng().gen_range(0..self.map.len())) {
                 self.tracked_size -= v.estimate_size(policy.mem_mode)
             }
         }
+        policy.update_tracked_size(self.tracked_size);
     }
 
     fn insert(&mut self, policy: &CachePolicyInner, key: TKey, data: TData) {
@@ -104,6 +290,7 @@ where
             Some(data) => {
                 if policy.tracked {
                     self.tracked_size -= data.estimate_size(policy.mem_mode);
+                    policy.update_tracked_size(self.tracked_size);
                 }
                 Some(data)
             }
@@ -145,6 +332,21 @@ where
         Self { inner: Arc::new(RwLock::new(Inner::new(prealloc_size))), policy }
     }
 
+    /// Creates a cache which, if tracked, is registered with `registry` so that its limit is scaled along with
+    /// the limits of all caches registered with it
+    pub fn with_registry(policy: CachePolicy, registry: Option<&TrackedCacheRegistry>) -> Self
+    where
+        TKey: 'static,
+        TData: 'static,
+        S: Send + Sync + 'static,
+    {
+        let cache = Self::new(policy);
+        if let (Some(registry), Some(_)) = (registry, cache.policy.limit.as_ref()) {
+            registry.register(Arc::new(RegisteredInner { inner: Arc::downgrade(&cache.inner), policy: cache.policy.clone() }));
+        }
+        cache
+    }
+
     pub fn get(&self, key: &TKey) -> Option<TData> {
         self.inner.read().map.get(key).cloned()
     }
@@ -206,6 +408,45 @@ where
         inner.map.clear();
         if self.policy.tracked {
             inner.tracked_size = 0;
+            self.policy.update_tracked_size(0);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_scaling() {
+        let registry = TrackedCacheRegistry::new();
+        let policy = CachePolicy::Tracked { max_size: 10, min_items: 0, mem_mode: MemMode::Units, unit_bytes: 8 };
+        let cache = Cache::<u64, Vec<u8>>::with_registry(policy, Some(&registry));
+        (0..10).for_each(|i| cache.insert(i, vec![0]));
+        assert_eq!(registry.size(), TrackedCachesSize { tracked_bytes: 80, configured_bytes: 80, max_bytes: 80 });
+
+        // Shrinking evicts right away, without waiting for the next mutation of the cache
+        registry.set_scale(0.5);
+        assert_eq!(cache.inner.read().map.len(), 5);
+        assert_eq!(registry.size(), TrackedCachesSize { tracked_bytes: 40, configured_bytes: 80, max_bytes: 40 });
+
+        // Caches created later on are scaled as well
+        let other = Cache::<u64, Vec<u8>>::with_registry(policy, Some(&registry));
+        (0..10).for_each(|i| other.insert(i, vec![0]));
+        assert_eq!(other.inner.read().map.len(), 5);
+
+        // Growing does not affect the cached items
+        registry.set_scale(2.0);
+        assert_eq!(registry.size(), TrackedCachesSize { tracked_bytes: 80, configured_bytes: 160, max_bytes: 320 });
+
+        // Dropped caches leave the registry
+        drop(other);
+        assert_eq!(registry.size().configured_bytes, 80);
+
+        // Unregistered caches keep their configured limit
+        let unregistered = Cache::<u64, Vec<u8>>::new(policy);
+        (0..10).for_each(|i| unregistered.insert(i, vec![0]));
+        assert_eq!(unregistered.inner.read().map.len(), 10);
+        assert_eq!(registry.size().configured_bytes, 80);
+    }
+}
@@ -1,36 +1,100 @@
+use crate::{
+    backend::{DbIterator, DbSlice, MemoryBackend, RocksDbBackend, StorageBackend},
+    batch::WriteBatch,
+    cache::TrackedCacheRegistry,
+    errors::StoreResult,
+    registry::DatabaseStorePrefixes,
+};
 use rocksdb::{DBWithThreadMode, MultiThreaded};
-use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use conn_builder::ConnBuilder;
-use kaspa_utils::fd_budget::FDGuard;
 
 mod conn_builder;
 
-/// The DB type used for Kaspad stores
+/// The DB type used for Kaspad stores. All operations are dispatched to the underlying [`StorageBackend`],
+/// which is RocksDB when built via [`ConnBuilder`]
 pub struct DB {
-    inner: DBWithThreadMode<MultiThreaded>,
-    _fd_guard: FDGuard,
+    backend: Box<dyn StorageBackend>,
+    cache_registry: Option<TrackedCacheRegistry>,
 }
 
 impl DB {
-    pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
-        Self { inner, _fd_guard: fd_guard }
+    pub fn new(backend: impl StorageBackend) -> Self {
+        Self { backend: Box::new(backend), cache_registry: None }
     }
-}
 
-impl DerefMut for DB {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+    pub fn with_cache_registry(self, cache_registry: Option<TrackedCacheRegistry>) -> Self {
+        Self { cache_registry, ..self }
+    }
+
+    /// The registry which tracked caches of stores built over this DB are registered with, if any
+    pub fn cache_registry(&self) -> Option<&TrackedCacheRegistry> {
+        self.cache_registry.as_ref()
     }
-}
 
-impl Deref for DB {
-    type Target = DBWithThreadMode<MultiThreaded>;
+    /// Creates a DB which keeps all data in memory (see [`MemoryBackend`])
+    pub fn in_memory() -> Self {
+        Self::new(MemoryBackend::default())
+    }
+
+    /// Returns the RocksDB backend of this DB, or `None` if the DB uses a different backend
+    pub fn rocksdb(&self) -> Option<&RocksDbBackend> {
+        self.backend.as_any().downcast_ref::<RocksDbBackend>()
+    }
+
+    /// The directory holding the DB files, or `None` if the DB is not persisted
+    pub fn path(&self) -> Option<&Path> {
+        self.backend.path()
+    }
+
+    pub fn get(&self, key: impl AsRef<[u8]>) -> StoreResult<Option<DbSlice<'_>>> {
+        self.backend.get(key.as_ref())
+    }
+
+    /// Iterates over all entries whose keys start with `prefix`, starting from `from` if provided
+    pub fn prefix_iterator(&self, prefix: &[u8], from: Option<&[u8]>) -> DbIterator<'_> {
+        self.backend.iterator(prefix, from)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+    pub fn write(&self, batch: WriteBatch) -> StoreResult<()> {
+        self.backend.write(batch, false)
     }
+
+    /// Writes `batch` and waits for it to be persisted
+    pub fn write_sync(&self, batch: WriteBatch) -> StoreResult<()> {
+        self.backend.write(batch, true)
+    }
+
+    /// Reclaims the space of entries deleted within the key range `[from, to)`. Blocks until done
+    pub fn compact_range(&self, from: &[u8], to: &[u8]) {
+        self.backend.compact_range(from, to)
+    }
+
+    /// Compacts the key range of all stores under `prefix` (including all block levels of leveled stores), so that
+    /// the space of deleted entries is reclaimed without waiting for RocksDB to compact the range on its own.
+    /// This call blocks until the compaction completes, however it does not block automatic compactions.
+    pub fn compact_prefix(&self, prefix: DatabaseStorePrefixes) {
+        let (start, end) = prefix_range(prefix);
+        self.backend.compact_range(&start, &end)
+    }
+
+    /// Returns the estimated on-disk size in bytes of the entries of all stores under `prefix`
+    pub fn estimated_prefix_size(&self, prefix: DatabaseStorePrefixes) -> u64 {
+        let (start, end) = prefix_range(prefix);
+        self.backend.estimated_range_size(&start, &end)
+    }
+
+    /// Returns the total size in bytes of the DB files as reported by the backend
+    pub fn estimated_size(&self) -> u64 {
+        self.backend.estimated_size()
+    }
+}
+
+/// The key range holding the entries of all stores under `prefix`
+fn prefix_range(prefix: DatabaseStorePrefixes) -> ([u8; 1], [u8; 1]) {
+    // Note that the separator is never used as a prefix, hence the range end cannot overflow
+    ([prefix as u8], [prefix as u8 + 1])
 }
 
 /// Deletes an existing DB if it exists
@@ -1,5 +1,6 @@
-use crate::db::DB;
-use rocksdb::{DBWithThreadMode, MultiThreaded};
+use crate::{backend::RocksDbBackend, cache::TrackedCacheRegistry, db::DB, registry::StoreFamily};
+use kaspa_utils::fd_budget::FDGuard;
+use rocksdb::{BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, DBWithThreadMode, MultiThreaded, Options};
 use std::{path::PathBuf, sync::Arc};
 
 #[derive(Debug)]
@@ -12,7 +13,10 @@ pub struct ConnBuilder<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> {
     parallelism: usize,
     files_limit: FDLimit,
     mem_budget: usize,
+    compression: bool,
+    column_families: bool,
     stats_period: StatsPeriod,
+    cache_registry: Option<TrackedCacheRegistry>,
 }
 
 impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
@@ -22,8 +26,11 @@ impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
             create_if_missing: true,
             parallelism: 1,
             mem_budget: 64 * 1024 * 1024,
+            compression: false,
+            column_families: false,
             stats_period: Unspecified,
             files_limit: Unspecified,
+            cache_registry: None,
         }
     }
 }
@@ -36,6 +43,9 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             create_if_missing: self.create_if_missing,
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
+            compression: self.compression,
+            column_families: self.column_families,
+            cache_registry: self.cache_registry,
             stats_period: self.stats_period,
         }
     }
@@ -48,6 +58,23 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
     pub fn with_mem_budget(self, mem_budget: impl Into<usize>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
         ConnBuilder { mem_budget: mem_budget.into(), ..self }
     }
+    /// Compresses all levels with zstd. Intended for rarely read, append-only data such as archival cold storage
+    pub fn with_compression(self, compression: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { compression, ..self }
+    }
+    /// Keeps each [`StoreFamily`] in a dedicated column family with options tuned to its access pattern. Note that a DB
+    /// which already has these column families is always opened with them, and that an existing DB without them must be
+    /// migrated (see [`RocksDbBackend::migrate_to_column_families`]) before use
+    pub fn with_column_families(self, column_families: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { column_families, ..self }
+    }
+    /// Registers the tracked caches of stores built over the DB with `cache_registry` (see [`DB::cache_registry`])
+    pub fn with_cache_registry(
+        self,
+        cache_registry: Option<TrackedCacheRegistry>,
+    ) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { cache_registry, ..self }
+    }
     pub fn with_files_limit(self, files_limit: impl Into<i32>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, i32> {
         ConnBuilder {
             db_path: self.db_path,
@@ -55,6 +82,9 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             create_if_missing: self.create_if_missing,
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
+            compression: self.compression,
+            column_families: self.column_families,
+            cache_registry: self.cache_registry,
             stats_period: self.stats_period,
         }
     }
@@ -68,6 +98,9 @@ impl<Path, FDLimit> ConnBuilder<Path, false, Unspecified, FDLimit> {
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
+            compression: self.compression,
+            column_families: self.column_families,
+            cache_registry: self.cache_registry,
             stats_period: self.stats_period,
         }
     }
@@ -81,6 +114,9 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
+            compression: self.compression,
+            column_families: self.column_families,
+            cache_registry: self.cache_registry,
             stats_period: Unspecified,
         }
     }
@@ -91,6 +127,9 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
+            compression: self.compression,
+            column_families: self.column_families,
+            cache_registry: self.cache_registry,
             stats_period: stats_period.into(),
         }
     }
@@ -107,15 +146,84 @@ macro_rules! default_opts {
         let guard = kaspa_utils::fd_budget::acquire_guard($self.files_limit)?;
         opts.set_max_open_files($self.files_limit);
         opts.create_if_missing($self.create_if_missing);
+        if $self.compression {
+            opts.set_compression_type(rocksdb::DBCompressionType::Zstd);
+            opts.set_bottommost_compression_type(rocksdb::DBCompressionType::Zstd);
+        }
         Ok((opts, guard))
     }};
 }
 
+/// The share of the memory budget given to the memtables and block cache of each column family, where `None` stands for the
+/// default column family. Headers and the UTXO set are read by most operations, hence they are given the largest shares
+fn mem_budget_share(family: Option<StoreFamily>) -> f64 {
+    match family {
+        Some(StoreFamily::Headers) => 0.35,
+        Some(StoreFamily::UtxoSet) => 0.25,
+        Some(StoreFamily::BlockData) => 0.15,
+        Some(StoreFamily::UtxoDiffs) => 0.1,
+        None => 0.15,
+    }
+}
+
+impl<const STATS_ENABLED: bool, StatsPeriod> ConnBuilder<PathBuf, STATS_ENABLED, StatsPeriod, i32> {
+    fn open(&self, mut opts: Options, guard: FDGuard) -> Arc<DB> {
+        let path = self.db_path.to_str().unwrap();
+        // All existing column families must be opened, hence a DB created with column families is opened with them regardless
+        let existing = <DBWithThreadMode<MultiThreaded>>::list_cf(&opts, path).unwrap_or_default();
+        let column_families =
+            self.column_families || StoreFamily::ALL.iter().any(|family| existing.iter().any(|cf| cf == family.name()));
+
+        let mut descriptors = vec![ColumnFamilyDescriptor::new(rocksdb::DEFAULT_COLUMN_FAMILY_NAME, self.family_options(None, &opts))];
+        if column_families {
+            opts.create_missing_column_families(true);
+            descriptors.extend(
+                StoreFamily::ALL.map(|family| ColumnFamilyDescriptor::new(family.name(), self.family_options(Some(family), &opts))),
+            );
+        }
+        let inner = <DBWithThreadMode<MultiThreaded>>::open_cf_descriptors(&opts, path, descriptors).unwrap();
+        Arc::new(DB::new(RocksDbBackend::new(inner, column_families, guard)).with_cache_registry(self.cache_registry.clone()))
+    }
+
+    fn family_options(&self, family: Option<StoreFamily>, opts: &Options) -> Options {
+        if !self.column_families && family.is_none() {
+            // A single keyspace shares the DB options
+            return opts.clone();
+        }
+        let mem_budget = (self.mem_budget as f64 * mem_budget_share(family)) as usize;
+        let mut opts = opts.clone();
+        opts.optimize_level_style_compaction(mem_budget);
+        let mut table_opts = BlockBasedOptions::default();
+        table_opts.set_block_cache(&Cache::new_lru_cache(mem_budget));
+        match family {
+            Some(StoreFamily::Headers) | Some(StoreFamily::UtxoSet) => {
+                // Point lookups dominate, and many of them miss (e.g., when checking for unknown blocks or spent outputs)
+                table_opts.set_bloom_filter(10.0, false);
+                table_opts.set_cache_index_and_filter_blocks(true);
+                table_opts.set_pin_l0_filter_and_index_blocks_in_cache(true);
+                opts.set_compression_type(DBCompressionType::Lz4);
+            }
+            Some(StoreFamily::BlockData) | Some(StoreFamily::UtxoDiffs) => {
+                // Large entries which are mostly read in bulk, hence larger blocks compress better
+                table_opts.set_block_size(64 * 1024);
+                opts.set_compression_type(DBCompressionType::Lz4);
+                opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+            }
+            None => {}
+        }
+        if self.compression {
+            opts.set_compression_type(DBCompressionType::Zstd);
+            opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+        }
+        opts.set_block_based_table_factory(&table_opts);
+        opts
+    }
+}
+
 impl ConnBuilder<PathBuf, false, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (opts, guard) = default_opts!(self)?;
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
-        Ok(db)
+        Ok(self.open(opts, guard))
     }
 }
 
@@ -123,8 +231,7 @@ impl ConnBuilder<PathBuf, true, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (mut opts, guard) = default_opts!(self)?;
         opts.enable_statistics();
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
-        Ok(db)
+        Ok(self.open(opts, guard))
     }
 }
 
@@ -134,7 +241,6 @@ impl ConnBuilder<PathBuf, true, u32, i32> {
         opts.enable_statistics();
         opts.set_report_bg_io_stats(true);
         opts.set_stats_dump_period_sec(self.stats_period);
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
-        Ok(db)
+        Ok(self.open(opts, guard))
     }
 }
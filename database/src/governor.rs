@@ -0,0 +1,131 @@
+use crate::cache::{TrackedCacheRegistry, TrackedCachesSize};
+
+/// Above this fraction of the ceiling, tracked caches are shrunk
+const HIGH_WATERMARK: f64 = 0.9;
+/// Tracked caches are shrunk such that the process is expected to fall back to this fraction of the ceiling,
+/// and are only grown while the process (including the growth) is expected to remain below it
+const LOW_WATERMARK: f64 = 0.8;
+/// Tracked caches are only grown when they are at least this full, i.e., when they are actually under pressure
+const FULL_RATIO: f64 = 0.9;
+/// The multiplicative growth step applied to the scale of tracked caches per adjustment
+const GROWTH_STEP: f64 = 1.1;
+const MIN_SCALE: f64 = 0.1;
+const MAX_SCALE: f64 = 4.0;
+
+/// A runtime memory governor which grows or shrinks the tracked caches (see [`crate::prelude::CachePolicy::Tracked`])
+/// of a [`TrackedCacheRegistry`] in order to keep the overall process memory under a configured ceiling. The governor is expected to be
+/// periodically fed with the process resident set size (RSS) via [`MemoryGovernor::adjust`].
+///
+/// Cache limits are scaled uniformly relative to their configured limits, so the relative memory budgets set at
+/// startup are preserved. Shrinking is applied right away, i.e., caches passing their new limit evict items immediately.
+pub struct MemoryGovernor {
+    ceiling_bytes: usize,
+    registry: TrackedCacheRegistry,
+}
+
+impl MemoryGovernor {
+    pub fn new(ceiling_bytes: usize, registry: TrackedCacheRegistry) -> Self {
+        Self { ceiling_bytes, registry }
+    }
+
+    pub fn ceiling_bytes(&self) -> usize {
+        self.ceiling_bytes
+    }
+
+    /// The current scale applied to the configured limits of tracked caches
+    pub fn scale(&self) -> f64 {
+        self.registry.scale()
+    }
+
+    /// The aggregated sizes of all live tracked caches
+    pub fn caches_size(&self) -> TrackedCachesSize {
+        self.registry.size()
+    }
+
+    /// Adjusts the limits of tracked caches given the current process resident set size. Returns the new
+    /// scale if it was changed
+    pub fn adjust(&self, resident_set_size: u64) -> Option<f64> {
+        let size = self.registry.size();
+        let current = self.registry.scale();
+        let scale = self.next_scale(current, resident_set_size as f64, size);
+        if scale != current {
+            self.registry.set_scale(scale);
+            Some(scale)
+        } else {
+            None
+        }
+    }
+
+    fn next_scale(&self, current: f64, rss: f64, size: TrackedCachesSize) -> f64 {
+        if size.configured_bytes == 0 {
+            return current;
+        }
+        let ceiling = self.ceiling_bytes as f64;
+        let configured = size.configured_bytes as f64;
+        let scale = if rss > ceiling * HIGH_WATERMARK {
+            // Memory not held by tracked caches is out of our control, so we shrink the caches to the
+            // share of the low watermark that remains available to them
+            let untracked = (rss - size.tracked_bytes as f64).max(0.0);
+            let available = (ceiling * LOW_WATERMARK - untracked).max(0.0);
+            current.min(available / configured)
+        } else if size.tracked_bytes as f64 >= size.max_bytes as f64 * FULL_RATIO {
+            // Grow only if the caches filling up their extended limits is not expected to pass the low watermark
+            let grown = current * GROWTH_STEP;
+            let growth_bytes = configured * (grown - current);
+            if rss + growth_bytes < ceiling * LOW_WATERMARK {
+                grown
+            } else {
+                current
+            }
+        } else {
+            current
+        };
+        scale.clamp(MIN_SCALE, MAX_SCALE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: usize = 1 << 20;
+
+    fn size(tracked_mb: usize, configured_mb: usize, scale: f64) -> TrackedCachesSize {
+        TrackedCachesSize {
+            tracked_bytes: tracked_mb * MB,
+            configured_bytes: configured_mb * MB,
+            max_bytes: (configured_mb as f64 * scale) as usize * MB,
+        }
+    }
+
+    #[test]
+    fn test_memory_governor_scale() {
+        let governor = MemoryGovernor::new(1000 * MB, TrackedCacheRegistry::new());
+
+        // Within the watermarks: no change
+        assert_eq!(governor.next_scale(1.0, (850 * MB) as f64, size(400, 400, 1.0)), 1.0);
+
+        // Above the high watermark: untracked memory is 950 - 400 = 550MB, so caches may hold 800 - 550 = 250MB
+        let scale = governor.next_scale(1.0, (950 * MB) as f64, size(400, 400, 1.0));
+        assert!((scale - 250.0 / 400.0).abs() < 1e-9);
+
+        // Untracked memory alone passing the ceiling: shrink to the minimal scale
+        assert_eq!(governor.next_scale(1.0, (1500 * MB) as f64, size(100, 400, 1.0)), MIN_SCALE);
+
+        // Below the low watermark with full caches: grow by a single step
+        let scale = governor.next_scale(1.0, (500 * MB) as f64, size(400, 400, 1.0));
+        assert!((scale - GROWTH_STEP).abs() < 1e-9);
+
+        // Below the low watermark but caches are not under pressure: no change
+        assert_eq!(governor.next_scale(1.0, (500 * MB) as f64, size(100, 400, 1.0)), 1.0);
+
+        // Growth which is expected to pass the low watermark is avoided
+        assert_eq!(governor.next_scale(1.0, (780 * MB) as f64, size(400, 400, 1.0)), 1.0);
+
+        // Growth is capped
+        assert_eq!(governor.next_scale(MAX_SCALE, (100 * MB) as f64, size(40, 10, MAX_SCALE)), MAX_SCALE);
+
+        // No tracked caches: nothing to adjust
+        assert_eq!(governor.next_scale(1.0, (2000 * MB) as f64, TrackedCachesSize::default()), 1.0);
+    }
+}
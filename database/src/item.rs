@@ -33,7 +33,7 @@ impl<T> CachedDbItem<T> {
         if let Some(item) = self.cached_item.read().clone() {
             return Ok(item);
         }
-        if let Some(slice) = self.db.get_pinned(&self.key)? {
+        if let Some(slice) = self.db.get(&self.key)? {
             let item: T = bincode::deserialize(&slice)?;
             *self.cached_item.write() = Some(item.clone());
             Ok(item)
@@ -67,7 +67,7 @@ where {
         let mut guard = self.cached_item.write();
         let mut item = if let Some(item) = guard.take() {
             item
-        } else if let Some(slice) = self.db.get_pinned(&self.key)? {
+        } else if let Some(slice) = self.db.get(&self.key)? {
             let item: T = bincode::deserialize(&slice)?;
             item
         } else {
@@ -1,7 +1,10 @@
 mod access;
+mod backend;
+mod batch;
 mod cache;
 mod db;
 mod errors;
+mod governor;
 mod item;
 mod key;
 mod writer;
@@ -14,7 +17,10 @@ pub mod prelude {
     use crate::{db, errors};
 
     pub use super::access::CachedDbAccess;
-    pub use super::cache::{Cache, CachePolicy};
+    pub use super::backend::{DbIterator, DbSlice, MemoryBackend, RocksDbBackend, StorageBackend};
+    pub use super::batch::{BatchOp, WriteBatch};
+    pub use super::cache::{Cache, CachePolicy, TrackedCacheRegistry, TrackedCachesSize};
+    pub use super::governor::MemoryGovernor;
     pub use super::item::{CachedDbItem, CachedDbSetItem};
     pub use super::key::DbKey;
     pub use super::set_access::{CachedDbSetAccess, DbSetAccess, ReadLock};
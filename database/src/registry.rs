@@ -1,4 +1,5 @@
 use enum_primitive_derive::Primitive;
+use num_traits::FromPrimitive;
 
 /// We use `u8::MAX` which is never a valid block level. Also note that through
 /// the [`DatabaseStorePrefixes`] enum we make sure it is not used as a prefix as well
@@ -49,13 +50,28 @@ pub enum DatabaseStorePrefixes {
     // ---- Retention Period Root ----
     RetentionPeriodRoot = 50,
 
+    // ---- Archival cold storage ----
+    ColdStorageRoot = 51,
+    ColdStorageCheckpoint = 52,
+
+    // ---- Staging IBD progress ----
+    IbdCheckpoint = 60,
+
+    // ---- Pruning point proof ----
+    PruningPointProof = 61,
+
+    // ---- Selected chain reorgs ----
+    ChainReorgs = 62,
+    ChainReorgsCount = 63,
+
     // ---- Metadata ----
     MultiConsensusMetadata = 124,
     ConsensusEntries = 125,
 
     // ---- Components ----
-    Addresses = 128,
+    Addresses = 128, // Legacy, upgraded to `AddressRecords`
     BannedAddresses = 129,
+    AddressRecords = 131,
 
     // ---- Indexes ----
     UtxoIndex = 192,
@@ -69,6 +85,71 @@ pub enum DatabaseStorePrefixes {
     Separator = SEPARATOR,
 }
 
+impl DatabaseStorePrefixes {
+    /// Iterates over all store prefixes, excluding the separator
+    pub fn iter() -> impl Iterator<Item = Self> {
+        (0..SEPARATOR).filter_map(Self::from_u8)
+    }
+
+    /// The family of stores this store belongs to, or `None` if the store resides in the default column family
+    pub fn family(self) -> Option<StoreFamily> {
+        use DatabaseStorePrefixes::*;
+        match self {
+            Headers
+            | HeadersCompact
+            | Ghostdag
+            | GhostdagCompact
+            | RelationsParents
+            | RelationsChildren
+            | Reachability
+            | ReachabilityRelations
+            | ReachabilityTreeChildren
+            | ReachabilityFutureCoveringSet
+            | Statuses
+            | BlockDepth
+            | NonDaaMergeset
+            | PruningSamples => Some(StoreFamily::Headers),
+            BlockTransactions | AcceptanceData => Some(StoreFamily::BlockData),
+            UtxoDiffs | UtxoMultisets => Some(StoreFamily::UtxoDiffs),
+            VirtualUtxoset | PruningUtxoset => Some(StoreFamily::UtxoSet),
+            _ => None,
+        }
+    }
+}
+
+/// Groups of stores with similar access patterns, each kept in a dedicated RocksDB column family so that it can be tuned
+/// separately (see [`crate::prelude::ConnBuilder::with_column_families`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreFamily {
+    /// Headers and DAG metadata: small entries frequently read by point lookups
+    Headers,
+    /// Block transactions and acceptance data: large, rarely updated entries
+    BlockData,
+    /// UTXO diffs and multisets: large entries which are mostly read only when the virtual moves far
+    UtxoDiffs,
+    /// The virtual and pruning point UTXO sets: small entries read by point lookups and full iteration
+    UtxoSet,
+}
+
+impl StoreFamily {
+    pub const ALL: [StoreFamily; 4] = [StoreFamily::Headers, StoreFamily::BlockData, StoreFamily::UtxoDiffs, StoreFamily::UtxoSet];
+
+    /// The column family name
+    pub fn name(self) -> &'static str {
+        match self {
+            StoreFamily::Headers => "headers",
+            StoreFamily::BlockData => "block-data",
+            StoreFamily::UtxoDiffs => "utxo-diffs",
+            StoreFamily::UtxoSet => "utxo-set",
+        }
+    }
+
+    /// The stores belonging to this family
+    pub fn prefixes(self) -> impl Iterator<Item = DatabaseStorePrefixes> {
+        DatabaseStorePrefixes::iter().filter(move |prefix| prefix.family() == Some(self))
+    }
+}
+
 impl From<DatabaseStorePrefixes> for Vec<u8> {
     fn from(value: DatabaseStorePrefixes) -> Self {
         [value as u8].to_vec()
@@ -110,4 +191,22 @@ mod tests {
             "DatabaseStorePrefixes is expected to have the same memory layout of u8"
         );
     }
+
+    #[test]
+    fn test_store_families() {
+        for family in StoreFamily::ALL {
+            assert!(family.prefixes().count() > 0);
+        }
+        assert_eq!(DatabaseStorePrefixes::Headers.family(), Some(StoreFamily::Headers));
+        assert_eq!(DatabaseStorePrefixes::UtxoIndex.family(), None);
+        assert_eq!(DatabaseStorePrefixes::MultiConsensusMetadata.family(), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let prefixes = DatabaseStorePrefixes::iter().map(u8::from).collect::<Vec<_>>();
+        assert_eq!(prefixes.first(), Some(&(DatabaseStorePrefixes::AcceptanceData as u8)));
+        assert_eq!(prefixes.last(), Some(&(DatabaseStorePrefixes::PochmMerkleRoots as u8)));
+        assert!(prefixes.windows(2).all(|w| w[0] < w[1]));
+    }
 }
@@ -2,7 +2,7 @@ use crate::{cache::CachePolicy, db::DB, errors::StoreError};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use parking_lot::{RwLock, RwLockReadGuard};
-use rocksdb::{IterateBounds, IteratorMode, ReadOptions};
+use rocksdb::IterateBounds;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::{hash_map::RandomState, HashSet},
@@ -54,8 +54,15 @@ where
     S: BuildHasher + Default,
     W: BuildHasher + Default + Send + Sync,
 {
-    pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self {
-        Self { inner: DbSetAccess::new(db, prefix), cache: Cache::new(cache_policy) }
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self
+    where
+        TKey: 'static,
+        TData: 'static,
+        S: Send + Sync + 'static,
+        W: 'static,
+    {
+        let cache = Cache::with_registry(cache_policy, db.cache_registry());
+        Self { inner: DbSetAccess::new(db, prefix), cache }
     }
 
     pub fn read_from_cache(&self, key: TKey) -> Option<ReadLock<HashSet<TData, W>>> {
@@ -162,10 +169,7 @@ where
         TData: DeserializeOwned,
     {
         let db_key = DbKey::new_with_bucket(&self.prefix, &key, []);
-        let mut read_opts = ReadOptions::default();
-        read_opts.set_iterate_range(rocksdb::PrefixRange(db_key.as_ref()));
-
-        let mut db_iterator = self.db.iterator_opt(IteratorMode::Start, read_opts);
+        let mut db_iterator = self.db.prefix_iterator(db_key.as_ref(), None);
 
         if skip_first {
             db_iterator.next();
@@ -198,10 +202,9 @@ mod tests {
     use super::*;
     use crate::{
         create_temp_db,
-        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter},
+        prelude::{BatchDbWriter, ConnBuilder, DirectDbWriter, WriteBatch},
     };
     use kaspa_hashes::Hash;
-    use rocksdb::WriteBatch;
 
     #[test]
     fn test_delete_bucket() {
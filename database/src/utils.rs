@@ -61,6 +61,16 @@ macro_rules! create_temp_db {
     }};
 }
 
+/// Creates a DB which keeps all data in memory (see [`crate::prelude::MemoryBackend`]).
+/// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB instance to exist.
+#[macro_export]
+macro_rules! create_memory_db {
+    () => {{
+        let db = std::sync::Arc::new($crate::prelude::DB::in_memory());
+        ($crate::utils::DbLifetime::without_destroy(std::sync::Arc::downgrade(&db)), db)
+    }};
+}
+
 /// Creates a DB within the provided directory path.
 /// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB instance to exist.
 #[macro_export]
@@ -1,16 +1,14 @@
+use crate::prelude::{StoreError, WriteBatch, DB};
 use kaspa_utils::refs::Refs;
-use rocksdb::WriteBatch;
-
-use crate::prelude::DB;
 
 /// Abstraction over direct/batched DB writing
 pub trait DbWriter {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>;
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error>;
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError>;
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>;
 }
@@ -34,19 +32,23 @@ impl<'a> DirectDbWriter<'a> {
 }
 
 impl DbWriter for DirectDbWriter<'_> {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
-        self.db.put(key, value)
+        let mut batch = WriteBatch::default();
+        batch.put(key, value);
+        self.db.write(batch)
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
-        self.db.delete(key)
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
+        let mut batch = WriteBatch::default();
+        batch.delete(key);
+        self.db.write(batch)
     }
 
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -69,7 +71,7 @@ impl<'a> BatchDbWriter<'a> {
 }
 
 impl DbWriter for BatchDbWriter<'_> {
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -78,12 +80,12 @@ impl DbWriter for BatchDbWriter<'_> {
         Ok(())
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
         self.batch.delete(key);
         Ok(())
     }
 
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -94,7 +96,7 @@ impl DbWriter for BatchDbWriter<'_> {
 
 impl<T: DbWriter> DbWriter for &mut T {
     #[inline]
-    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, key: K, value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -103,12 +105,12 @@ impl<T: DbWriter> DbWriter for &mut T {
     }
 
     #[inline]
-    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), StoreError> {
         (*self).delete(key)
     }
 
     #[inline]
-    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, from: K, to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
@@ -123,7 +125,7 @@ impl<T: DirectWriter> DirectWriter for &mut T {}
 pub struct MemoryWriter;
 
 impl DbWriter for MemoryWriter {
-    fn put<K, V>(&mut self, _key: K, _value: V) -> Result<(), rocksdb::Error>
+    fn put<K, V>(&mut self, _key: K, _value: V) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
@@ -131,11 +133,11 @@ impl DbWriter for MemoryWriter {
         Ok(())
     }
 
-    fn delete<K: AsRef<[u8]>>(&mut self, _key: K) -> Result<(), rocksdb::Error> {
+    fn delete<K: AsRef<[u8]>>(&mut self, _key: K) -> Result<(), StoreError> {
         Ok(())
     }
 
-    fn delete_range<K>(&mut self, _from: K, _to: K) -> Result<(), rocksdb::Error>
+    fn delete_range<K>(&mut self, _from: K, _to: K) -> Result<(), StoreError>
     where
         K: AsRef<[u8]>,
     {
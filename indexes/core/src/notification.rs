@@ -6,7 +6,7 @@ use kaspa_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{OverallSubscription, TransactionReplacedSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -53,6 +53,14 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_transaction_replaced_subscription(
+        &self,
+        _subscription: &TransactionReplacedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
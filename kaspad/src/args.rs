@@ -1,10 +1,18 @@
 use clap::{arg, Arg, ArgAction, Command};
 use kaspa_consensus_core::{
-    config::Config,
+    config::{Config, TrustedPruningPoint, DEFAULT_REORG_NOTIFICATION_DEPTH},
     network::{NetworkId, NetworkType},
+    subnets::SubnetworkId,
 };
 use kaspa_core::kaspad_env::version;
+use kaspa_hashes::Hash;
+use kaspa_mining::mempool::config::{
+    PayloadSubnetworkFilter, RbfMode, DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE, DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS,
+};
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_p2p_lib::{OutboundProxies, Socks5Proxy, DEFAULT_SOCKS_PORT};
+use kaspa_p2p_mining::rules::sync_rate_rule::{SYNC_RATE_THRESHOLD, SYNC_RATE_WINDOW_DURATION, SYNC_RATE_WINDOW_MIN_DURATION};
+use kaspa_stratum::config::{DEFAULT_SHARE_DIFFICULTY, DEFAULT_STRATUM_PORT};
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
@@ -69,6 +77,8 @@ pub struct Args {
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    pub cold_storage_dir: Option<String>,
+    pub light: bool,
     pub sanity: bool,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
@@ -89,8 +99,47 @@ pub struct Args {
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
+    #[serde(rename = "txrecon")]
+    pub tx_reconciliation: bool,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub proxy: Option<ContextualNetAddress>,
+    #[serde(rename = "proxyuser")]
+    pub proxy_user: Option<String>,
+    #[serde(rename = "proxypass")]
+    pub proxy_pass: Option<String>,
+    #[serde(rename = "onion")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub onion_proxy: Option<ContextualNetAddress>,
+    #[serde(rename = "onionuser")]
+    pub onion_user: Option<String>,
+    #[serde(rename = "onionpass")]
+    pub onion_pass: Option<String>,
     pub ram_scale: f64,
+    pub memory_ceiling_gb: Option<f64>,
     pub retention_period_days: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub trusted_pruning_point: Option<Hash>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub trusted_utxo_commitment: Option<Hash>,
+    pub reorg_notification_depth: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratum_listen: Option<ContextualNetAddress>,
+    pub stratum_pay_address: Option<String>,
+    pub stratum_difficulty: u64,
+    pub sync_rate_threshold: f64,
+    pub sync_rate_window: u64,
+    pub sync_rate_min_window: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub rbf_mode: RbfMode,
+    pub rbf_min_fee_increment: Option<u64>,
+    pub rbf_min_feerate_increment: f64,
+    pub rbf_max_evictions: usize,
+    pub max_payload_size: usize,
+    pub payload_fee_multiplier: f64,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub payload_allowed_subnetworks: Vec<SubnetworkId>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub payload_denied_subnetworks: Vec<SubnetworkId>,
 }
 
 impl Default for Args {
@@ -115,6 +164,8 @@ impl Default for Args {
             devnet: false,
             simnet: false,
             archival: false,
+            cold_storage_dir: None,
+            light: false,
             sanity: false,
             logdir: None,
             rpclisten: None,
@@ -140,8 +191,33 @@ impl Default for Args {
             disable_upnp: false,
             disable_dns_seeding: false,
             disable_grpc: false,
+            tx_reconciliation: false,
+            proxy: None,
+            proxy_user: None,
+            proxy_pass: None,
+            onion_proxy: None,
+            onion_user: None,
+            onion_pass: None,
             ram_scale: 1.0,
+            memory_ceiling_gb: None,
             retention_period_days: None,
+            trusted_pruning_point: None,
+            trusted_utxo_commitment: None,
+            reorg_notification_depth: DEFAULT_REORG_NOTIFICATION_DEPTH,
+            stratum_listen: None,
+            stratum_pay_address: None,
+            stratum_difficulty: DEFAULT_SHARE_DIFFICULTY,
+            sync_rate_threshold: SYNC_RATE_THRESHOLD,
+            sync_rate_window: SYNC_RATE_WINDOW_DURATION,
+            sync_rate_min_window: SYNC_RATE_WINDOW_MIN_DURATION,
+            rbf_mode: RbfMode::default(),
+            rbf_min_fee_increment: None,
+            rbf_min_feerate_increment: 0.0,
+            rbf_max_evictions: DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS,
+            max_payload_size: DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE,
+            payload_fee_multiplier: 0.0,
+            payload_allowed_subnetworks: vec![],
+            payload_denied_subnetworks: vec![],
         }
     }
 }
@@ -154,6 +230,8 @@ impl Args {
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
         config.is_archival = self.archival;
+        config.is_light = self.light;
+        config.enable_tx_reconciliation = self.tx_reconciliation;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
@@ -162,6 +240,11 @@ impl Args {
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
         config.retention_period_days = self.retention_period_days;
+        config.trusted_pruning_point = self
+            .trusted_pruning_point
+            .zip(self.trusted_utxo_commitment)
+            .map(|(hash, utxo_commitment)| TrustedPruningPoint { hash, utxo_commitment });
+        config.reorg_notification_depth = self.reorg_notification_depth;
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -192,6 +275,28 @@ impl Args {
             _ => panic!("only a single net should be activated"),
         }
     }
+
+    /// Returns the SOCKS5 proxies for outbound p2p connections
+    pub fn outbound_proxies(&self) -> OutboundProxies {
+        let proxy = |address: Option<ContextualNetAddress>, user: &Option<String>, pass: &Option<String>| {
+            address.map(|address| Socks5Proxy::new(address.normalize(DEFAULT_SOCKS_PORT).into(), user.clone().zip(pass.clone())))
+        };
+        OutboundProxies::new(
+            proxy(self.proxy, &self.proxy_user, &self.proxy_pass),
+            proxy(self.onion_proxy, &self.onion_user, &self.onion_pass),
+        )
+    }
+
+    /// Returns the mempool payload subnetwork filter, expecting at most one of the allow and deny lists to be set
+    pub fn payload_subnetwork_filter(&self) -> PayloadSubnetworkFilter {
+        if !self.payload_allowed_subnetworks.is_empty() {
+            PayloadSubnetworkFilter::Allow(self.payload_allowed_subnetworks.clone())
+        } else if !self.payload_denied_subnetworks.is_empty() {
+            PayloadSubnetworkFilter::Deny(self.payload_denied_subnetworks.clone())
+        } else {
+            PayloadSubnetworkFilter::AllowAll
+        }
+    }
 }
 
 pub fn cli() -> Command {
@@ -335,6 +440,8 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(arg!(--"cold-storage-dir" <COLD_STORAGE_DIR> "Archival nodes only: move block data older than the retention period (see --retention-period-days) to a compressed store in this directory, e.g. on a cheaper disk"))
+        .arg(arg!(--light "Run as a header-only light node: follows the selected chain and verifies PoW without downloading block bodies or maintaining the UTXO set"))
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
         .arg(
@@ -351,7 +458,7 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .require_equals(true)
                 .default_missing_value(None)
                 .value_parser(clap::value_parser!(ContextualNetAddress))
-                .help("Add a socket address(ip:port) to the list of local addresses we claim to listen on to peers"),
+                .help("Add a socket address(ip:port) or an onion service address(<address>.onion:port) to the list of local addresses we claim to listen on to peers"),
         )
         .arg(arg!(--"perf-metrics" "Enable performance metrics: cpu, memory, disk io usage"))
         .arg(
@@ -364,6 +471,51 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
+        .arg(arg!(--"txrecon" "Relay transactions to supporting outbound peers by periodic set reconciliation instead of flooding"))
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help(format!("Connect to peers via a SOCKS5 proxy (default port: {}). Disables DNS seeding.", DEFAULT_SOCKS_PORT)),
+        )
+        .arg(
+            Arg::new("proxyuser")
+                .long("proxyuser")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Username for the SOCKS5 proxy."),
+        )
+        .arg(
+            Arg::new("proxypass")
+                .long("proxypass")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Password for the SOCKS5 proxy."),
+        )
+        .arg(
+            Arg::new("onion")
+                .long("onion")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help(format!("Connect to onion peers via a separate SOCKS5 proxy such as Tor (default port: {}, default: --proxy).", DEFAULT_SOCKS_PORT)),
+        )
+        .arg(
+            Arg::new("onionuser")
+                .long("onionuser")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Username for the onion SOCKS5 proxy."),
+        )
+        .arg(
+            Arg::new("onionpass")
+                .long("onionpass")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Password for the onion SOCKS5 proxy."),
+        )
         .arg(
             Arg::new("ram-scale")
                 .long("ram-scale")
@@ -372,6 +524,13 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("memory-ceiling-gb")
+                .long("memory-ceiling-gb")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Total process memory (in GB) which the node tries to stay under by resizing its caches at runtime. When unset, cache sizes are fixed at startup according to --ram-scale."),
+        )
         .arg(
             Arg::new("retention-period-days")
                 .long("retention-period-days")
@@ -379,6 +538,134 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
                 .value_parser(clap::value_parser!(f64))
                 .help("The number of total days of data to keep.")
         )
+        .arg(
+            Arg::new("trusted-pruning-point")
+                .long("trusted-pruning-point")
+                .value_name("HASH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(Hash))
+                .help("Sync from this pruning point without validating the headers proof, trusting the syncer (e.g., a node under your control) instead. Requires --trusted-utxo-commitment."),
+        )
+        .arg(
+            Arg::new("trusted-utxo-commitment")
+                .long("trusted-utxo-commitment")
+                .value_name("HASH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(Hash))
+                .help("The expected UTXO commitment of the pruning point set by --trusted-pruning-point."),
+        )
+        .arg(
+            Arg::new("reorg-notification-depth")
+                .long("reorg-notification-depth")
+                .value_name("DEPTH")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(format!("Minimum number of selected chain blocks a reorg must remove to trigger a ChainReorg notification (default: {}).", DEFAULT_REORG_NOTIFICATION_DEPTH)),
+        )
+        .arg(
+            Arg::new("stratum-listen")
+                .long("stratum-listen")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help(format!("Interface:port to listen for Stratum mining connections (default port: {}). Requires --stratum-pay-address.", DEFAULT_STRATUM_PORT)),
+        )
+        .arg(
+            Arg::new("stratum-pay-address")
+                .long("stratum-pay-address")
+                .value_name("ADDRESS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Address receiving the coinbase rewards of blocks mined through the Stratum server."),
+        )
+        .arg(
+            Arg::new("stratum-difficulty")
+                .long("stratum-difficulty")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Share difficulty assigned to Stratum miners (default: {}).", DEFAULT_SHARE_DIFFICULTY)),
+        )
+        .arg(
+            Arg::new("sync-rate-threshold")
+                .long("sync-rate-threshold")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help(format!("Ratio of received to expected blocks below which mining is allowed while the node is not nearly synced, as long as the finality point is recent (default: {}).", SYNC_RATE_THRESHOLD)),
+        )
+        .arg(
+            Arg::new("sync-rate-window")
+                .long("sync-rate-window")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Duration in seconds of the window over which the sync rate is measured (default: {}).", SYNC_RATE_WINDOW_DURATION)),
+        )
+        .arg(
+            Arg::new("sync-rate-min-window")
+                .long("sync-rate-min-window")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Duration in seconds of samples required before the sync rate rule is evaluated (default: {}).", SYNC_RATE_WINDOW_MIN_DURATION)),
+        )
+        .arg(
+            Arg::new("rbf-mode")
+                .long("rbf-mode")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(RbfMode))
+                .help(format!("Replace by fee mode of the mempool: disabled, opt-in (only transactions with an input sequence signaling replaceability can be replaced) or full (default: {}).", RbfMode::default())),
+        )
+        .arg(
+            Arg::new("rbf-min-fee-increment")
+                .long("rbf-min-fee-increment")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("When set, a replacement transaction must pay at least the fees of all the transactions it evicts plus this amount in sompi."),
+        )
+        .arg(
+            Arg::new("rbf-min-feerate-increment")
+                .long("rbf-min-feerate-increment")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Minimum feerate increment in sompi/gram a replacement transaction must add to the feerate of every replaced transaction (default: 0)."),
+        )
+        .arg(
+            Arg::new("rbf-max-evictions")
+                .long("rbf-max-evictions")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Maximum number of mempool transactions, descendants included, a single replacement can evict (default: {}).", DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS)),
+        )
+        .arg(
+            Arg::new("max-payload-size")
+                .long("max-payload-size")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Maximum size in bytes of a transaction payload accepted by the mempool (default: {}).", DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE)),
+        )
+        .arg(
+            Arg::new("payload-fee-multiplier")
+                .long("payload-fee-multiplier")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Multiplier of the minimum relay feerate a transaction must additionally pay for every byte of its payload (default: 0)."),
+        )
+        .arg(
+            Arg::new("payload-allowed-subnetworks")
+                .long("payload-allow-subnetwork")
+                .value_name("SUBNETWORK_ID")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(SubnetworkId))
+                .help("Accept transactions carrying a payload only on the specified subnetworks. Cannot be used with --payload-deny-subnetwork."),
+        )
+        .arg(
+            Arg::new("payload-denied-subnetworks")
+                .long("payload-deny-subnetwork")
+                .value_name("SUBNETWORK_ID")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(SubnetworkId))
+                .help("Reject transactions carrying a payload on the specified subnetworks. Cannot be used with --payload-allow-subnetwork."),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -446,6 +733,8 @@ impl Args {
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            cold_storage_dir: m.get_one::<String>("cold-storage-dir").cloned().or(defaults.cold_storage_dir),
+            light: arg_match_unwrap_or::<bool>(&m, "light", defaults.light),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
@@ -457,8 +746,41 @@ impl Args {
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
+            tx_reconciliation: arg_match_unwrap_or::<bool>(&m, "txrecon", defaults.tx_reconciliation),
+            proxy: m.get_one::<ContextualNetAddress>("proxy").cloned().or(defaults.proxy),
+            proxy_user: m.get_one::<String>("proxyuser").cloned().or(defaults.proxy_user),
+            proxy_pass: m.get_one::<String>("proxypass").cloned().or(defaults.proxy_pass),
+            onion_proxy: m.get_one::<ContextualNetAddress>("onion").cloned().or(defaults.onion_proxy),
+            onion_user: m.get_one::<String>("onionuser").cloned().or(defaults.onion_user),
+            onion_pass: m.get_one::<String>("onionpass").cloned().or(defaults.onion_pass),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            memory_ceiling_gb: m.get_one::<f64>("memory-ceiling-gb").cloned().or(defaults.memory_ceiling_gb),
             retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
+            trusted_pruning_point: m.get_one::<Hash>("trusted-pruning-point").cloned().or(defaults.trusted_pruning_point),
+            trusted_utxo_commitment: m.get_one::<Hash>("trusted-utxo-commitment").cloned().or(defaults.trusted_utxo_commitment),
+            reorg_notification_depth: arg_match_unwrap_or::<u64>(&m, "reorg-notification-depth", defaults.reorg_notification_depth),
+            stratum_listen: m.get_one::<ContextualNetAddress>("stratum-listen").cloned().or(defaults.stratum_listen),
+            stratum_pay_address: m.get_one::<String>("stratum-pay-address").cloned().or(defaults.stratum_pay_address),
+            stratum_difficulty: arg_match_unwrap_or::<u64>(&m, "stratum-difficulty", defaults.stratum_difficulty),
+            sync_rate_threshold: arg_match_unwrap_or::<f64>(&m, "sync-rate-threshold", defaults.sync_rate_threshold),
+            sync_rate_window: arg_match_unwrap_or::<u64>(&m, "sync-rate-window", defaults.sync_rate_window),
+            sync_rate_min_window: arg_match_unwrap_or::<u64>(&m, "sync-rate-min-window", defaults.sync_rate_min_window),
+            rbf_mode: arg_match_unwrap_or::<RbfMode>(&m, "rbf-mode", defaults.rbf_mode),
+            rbf_min_fee_increment: m.get_one::<u64>("rbf-min-fee-increment").cloned().or(defaults.rbf_min_fee_increment),
+            rbf_min_feerate_increment: arg_match_unwrap_or::<f64>(&m, "rbf-min-feerate-increment", defaults.rbf_min_feerate_increment),
+            rbf_max_evictions: arg_match_unwrap_or::<usize>(&m, "rbf-max-evictions", defaults.rbf_max_evictions),
+            max_payload_size: arg_match_unwrap_or::<usize>(&m, "max-payload-size", defaults.max_payload_size),
+            payload_fee_multiplier: arg_match_unwrap_or::<f64>(&m, "payload-fee-multiplier", defaults.payload_fee_multiplier),
+            payload_allowed_subnetworks: arg_match_many_unwrap_or::<SubnetworkId>(
+                &m,
+                "payload-allowed-subnetworks",
+                defaults.payload_allowed_subnetworks,
+            ),
+            payload_denied_subnetworks: arg_match_many_unwrap_or::<SubnetworkId>(
+                &m,
+                "payload-denied-subnetworks",
+                defaults.payload_denied_subnetworks,
+            ),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -1,24 +1,32 @@
 use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
 
 use async_channel::unbounded;
+use kaspa_addresses::{Address, Prefix};
 use kaspa_consensus_core::{
-    config::ConfigBuilder,
+    config::{ConfigBuilder, MINIMUM_RETENTION_PERIOD_DAYS},
     constants::TRANSIENT_BYTE_TO_MASS_FACTOR,
     errors::config::{ConfigError, ConfigResult},
     mining_rules::MiningRules,
 };
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use kaspa_core::{core::Core, debug, info, trace};
+use kaspa_core::{core::Core, debug, info, trace, warn};
 use kaspa_core::{kaspad_env::version, task::tick::TickService};
 use kaspa_database::{
-    prelude::{CachePolicy, DbWriter, DirectDbWriter},
+    prelude::{CachePolicy, DbWriter, DirectDbWriter, MemoryGovernor, TrackedCacheRegistry},
     registry::DatabaseStorePrefixes,
 };
 use kaspa_grpc_server::service::GrpcService;
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
 use kaspa_p2p_lib::Hub;
-use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
+use kaspa_p2p_mining::{
+    rule_engine::{MiningRuleEngine, SNAPSHOT_INTERVAL},
+    rules::sync_rate_rule::SyncRateRuleConfig,
+};
 use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_stratum::{
+    config::{Config as StratumConfig, DEFAULT_STRATUM_PORT},
+    service::StratumService,
+};
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::git;
 use kaspa_utils::networking::ContextualNetAddress;
@@ -35,6 +43,7 @@ use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_index_processor::service::IndexService;
 use kaspa_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::config::{PayloadConfig, ReplaceByFeeConfig},
     monitor::MiningMonitor,
     MiningCounters,
 };
@@ -54,10 +63,9 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-/// If set, the retention period days must be at least this value
-/// (otherwise it is meaningless since pruning periods are typically at least 2 days long)
-const MINIMUM_RETENTION_PERIOD_DAYS: f64 = 2.0;
 const ONE_GIGABYTE: f64 = 1_000_000_000.0;
+/// The lowest memory ceiling the memory governor can be configured with
+const MIN_MEMORY_CEILING_GB: f64 = 1.0;
 
 use crate::args::Args;
 
@@ -66,6 +74,7 @@ const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
+const UPGRADE_DB_FILE_LIMIT: i32 = 64;
 const DEFAULT_LOG_DIR: &str = "logs";
 
 fn get_home_dir() -> PathBuf {
@@ -107,9 +116,75 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.ram_scale > 10.0 {
         return Err(ConfigError::RamScaleTooHigh);
     }
+    if args.memory_ceiling_gb.is_some_and(|ceiling| ceiling < MIN_MEMORY_CEILING_GB) {
+        return Err(ConfigError::MemoryCeilingTooLow(MIN_MEMORY_CEILING_GB));
+    }
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.stratum_listen.is_some() {
+        let Some(pay_address) = args.stratum_pay_address.as_deref() else {
+            return Err(ConfigError::MissingStratumPayAddress);
+        };
+        if !Address::try_from(pay_address).is_ok_and(|address| address.prefix == Prefix::from(args.network())) {
+            return Err(ConfigError::InvalidStratumPayAddress(pay_address.to_owned()));
+        }
+        if args.stratum_difficulty == 0 {
+            return Err(ConfigError::StratumDifficultyZero);
+        }
+    }
+    if !(args.sync_rate_threshold > 0.0 && args.sync_rate_threshold <= 1.0) {
+        return Err(ConfigError::SyncRateThresholdOutOfRange);
+    }
+    if args.sync_rate_window < SNAPSHOT_INTERVAL {
+        return Err(ConfigError::SyncRateWindowTooShort(SNAPSHOT_INTERVAL));
+    }
+    if args.sync_rate_min_window < SNAPSHOT_INTERVAL || args.sync_rate_min_window > args.sync_rate_window {
+        return Err(ConfigError::SyncRateMinWindowOutOfRange(SNAPSHOT_INTERVAL));
+    }
+    if !(args.rbf_min_feerate_increment.is_finite() && args.rbf_min_feerate_increment >= 0.0) {
+        return Err(ConfigError::RbfMinFeerateIncrementOutOfRange);
+    }
+    if args.rbf_max_evictions == 0 {
+        return Err(ConfigError::RbfMaxEvictionsZero);
+    }
+    if !(args.payload_fee_multiplier.is_finite() && args.payload_fee_multiplier >= 0.0) {
+        return Err(ConfigError::PayloadFeeMultiplierOutOfRange);
+    }
+    if !args.payload_allowed_subnetworks.is_empty() && !args.payload_denied_subnetworks.is_empty() {
+        return Err(ConfigError::PayloadSubnetworkAllowAndDeny);
+    }
+    for (name, proxy, user, pass) in
+        [("proxy", &args.proxy, &args.proxy_user, &args.proxy_pass), ("onion", &args.onion_proxy, &args.onion_user, &args.onion_pass)]
+    {
+        if proxy.is_some_and(|proxy| proxy.is_onion()) {
+            return Err(ConfigError::ProxyNotAnIpAddress(name));
+        }
+        if user.is_some() != pass.is_some() {
+            return Err(ConfigError::ProxyCredentialsMismatch(name));
+        }
+    }
+    if args.cold_storage_dir.is_some() && !args.archival {
+        return Err(ConfigError::ColdStorageRequiresArchival);
+    }
+    if args.trusted_pruning_point.is_some() != args.trusted_utxo_commitment.is_some() {
+        return Err(ConfigError::TrustedPruningPointMismatch);
+    }
+    if args.light {
+        if args.archival {
+            return Err(ConfigError::LightModeConflict("--archival"));
+        }
+        if args.utxoindex {
+            return Err(ConfigError::LightModeConflict("--utxoindex"));
+        }
+        if args.stratum_listen.is_some() {
+            return Err(ConfigError::LightModeConflict("--stratum-listen"));
+        }
+        // The trusted pruning point is verified against its UTXO commitment, which requires importing the UTXO set
+        if args.trusted_pruning_point.is_some() {
+            return Err(ConfigError::LightModeConflict("--trusted-pruning-point"));
+        }
+    }
     Ok(())
 }
 
@@ -263,6 +338,9 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
+    // Archival data moved out of the consensus DBs is kept per network in a similar layout
+    let cold_storage_db_dir =
+        args.cold_storage_dir.as_ref().map(|dir| PathBuf::from(dir).join(network.to_prefixed()).join(CONSENSUS_DB));
 
     let mut is_db_reset_needed = args.reset_db;
 
@@ -273,16 +351,23 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         get_user_approval_or_exit(msg, args.yes);
         info!("Deleting databases");
         fs::remove_dir_all(&db_dir).unwrap();
+        if let Some(cold_storage_db_dir) = cold_storage_db_dir.as_ref().filter(|dir| dir.exists()) {
+            fs::remove_dir_all(cold_storage_db_dir).unwrap();
+        }
     }
 
     fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
     fs::create_dir_all(meta_db_dir.as_path()).unwrap();
+    if let Some(cold_storage_db_dir) = cold_storage_db_dir.as_ref() {
+        info!("Cold storage directory {}", cold_storage_db_dir.display());
+        fs::create_dir_all(cold_storage_db_dir.as_path()).unwrap();
+    }
     if args.utxoindex {
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
 
-    if !args.archival && args.retention_period_days.is_some() {
+    if (!args.archival || args.cold_storage_dir.is_some()) && args.retention_period_days.is_some() {
         let retention_period_days = args.retention_period_days.unwrap();
         // Look only at post-fork values (which are the worst-case)
         let finality_depth = config.finality_depth().after();
@@ -305,6 +390,14 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         }
     }
 
+    if let Some(trusted) = config.trusted_pruning_point {
+        warn!(
+            "Trusted pruning point {} (UTXO commitment {}) is configured. The headers proof of this pruning point will not be \
+validated, so the node relies on its syncers to provide the correct chain",
+            trusted.hash, trusted.utxo_commitment
+        );
+    }
+
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
         .with_db_path(meta_db_dir.clone())
@@ -347,11 +440,11 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     // Reset Condition: Need to reset if we're upgrading from kaspad DB version
     // TEMP: upgrade from Alpha version or any version before this one
     if !is_db_reset_needed
-        && (meta_db.get_pinned(b"multi-consensus-metadata-key").is_ok_and(|r| r.is_some())
+        && (meta_db.get(b"multi-consensus-metadata-key").is_ok_and(|r| r.is_some())
             || MultiConsensusManagementStore::new(meta_db.clone()).should_upgrade().unwrap())
     {
         let mut mcms = MultiConsensusManagementStore::new(meta_db.clone());
-        let version = mcms.version().unwrap();
+        let mut version = mcms.version().unwrap();
 
         // TODO: Update this entire section to a more robust implementation that allows applying multiple upgrade strategies.
         // If I'm at version 3 and latest version is 7, I need to be able to upgrade to that version following the intermediate
@@ -379,7 +472,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                     let ghostdag_prefix = ghostdag_prefix_vec.as_slice();
 
                     // This section is used to count the records to be deleted. It's not used for the actual delete.
-                    for result in consensus_db.iterator(rocksdb::IteratorMode::From(ghostdag_prefix, rocksdb::Direction::Forward)) {
+                    for result in consensus_db.prefix_iterator(&[DatabaseStorePrefixes::Ghostdag.into()], Some(ghostdag_prefix)) {
                         let (key, _) = result.unwrap();
                         if !key.starts_with(&[DatabaseStorePrefixes::Ghostdag.into()]) {
                             break;
@@ -391,7 +484,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                     let compact_prefix_vec = DatabaseStorePrefixes::GhostdagCompact.into_iter().chain(start_level_bytes).collect_vec();
                     let compact_prefix = compact_prefix_vec.as_slice();
 
-                    for result in consensus_db.iterator(rocksdb::IteratorMode::From(compact_prefix, rocksdb::Direction::Forward)) {
+                    for result in consensus_db.prefix_iterator(&[DatabaseStorePrefixes::GhostdagCompact.into()], Some(compact_prefix))
+                    {
                         let (key, _) = result.unwrap();
                         if !key.starts_with(&[DatabaseStorePrefixes::GhostdagCompact.into()]) {
                             break;
@@ -427,11 +521,12 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                     writer.delete_range(start_compact_prefix_vec.clone(), end_compact_prefix_vec.clone()).unwrap();
 
                     // Compact the deleted rangeto apply the delete immediately
-                    consensus_db.compact_range(Some(start_ghostdag_prefix_vec.as_slice()), Some(end_ghostdag_prefix_vec.as_slice()));
-                    consensus_db.compact_range(Some(start_compact_prefix_vec.as_slice()), Some(end_compact_prefix_vec.as_slice()));
+                    consensus_db.compact_range(&start_ghostdag_prefix_vec, &end_ghostdag_prefix_vec);
+                    consensus_db.compact_range(&start_compact_prefix_vec, &end_compact_prefix_vec);
 
                     // Also update the version to one higher:
-                    mcms.set_version(version + 1).unwrap();
+                    version += 1;
+                    mcms.set_version(version).unwrap();
                 }
                 None => {
                     let msg =
@@ -441,7 +536,50 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                     is_db_reset_needed = true;
                 }
             }
-        } else {
+        }
+
+        if version == 4 {
+            // Apply soft upgrade logic: move the stores of each store family out of the shared keyspace into
+            // a dedicated column family, and then update DB version to 5
+            let msg = "Node database currently at version 4. Upgrade process to version 5 needs to be applied. Continue? (y/n)";
+            get_user_approval_or_exit(msg, args.yes);
+
+            // A staging consensus retained for resuming an interrupted IBD is migrated as well, since its IBD checkpoint
+            // lives in the default column family and would otherwise refer to stores which appear empty
+            let consensus_dbs = [mcms.active_consensus_dir_name().unwrap(), mcms.staging_consensus_dir_name().unwrap()];
+            for consensus_db in consensus_dbs.into_iter().flatten() {
+                // Block data moved to cold storage is kept in a consensus DB of the same name
+                let cold_consensus_db_dir = cold_storage_db_dir.as_ref().map(|dir| dir.join(&consensus_db)).filter(|dir| dir.exists());
+                for (db_path, compression) in
+                    std::iter::once((consensus_db_dir.join(&consensus_db), false)).chain(cold_consensus_db_dir.map(|dir| (dir, true)))
+                {
+                    let consensus_db = kaspa_database::prelude::ConnBuilder::default()
+                        .with_db_path(db_path.clone())
+                        .with_files_limit(UPGRADE_DB_FILE_LIMIT)
+                        .with_compression(compression)
+                        .with_column_families(true)
+                        .build()
+                        .unwrap();
+                    info!("Moving consensus stores at {} into column families", db_path.display());
+
+                    let mut reported = 0;
+                    let moved = consensus_db
+                        .rocksdb()
+                        .expect("consensus DBs are built with the RocksDB backend")
+                        .migrate_to_column_families(|moved| {
+                            if moved >= reported + 1_000_000 {
+                                info!("Moved {} records", moved);
+                                reported = moved;
+                            }
+                        })
+                        .unwrap();
+                    info!("Moved {} records into column families", moved);
+                }
+            }
+
+            version += 1;
+            mcms.set_version(version).unwrap();
+        } else if version != 3 {
             let msg =
                 "Node database is from a different Kaspad *DB* version and needs to be fully deleted, do you confirm the delete? (y/n)";
             get_user_approval_or_exit(msg, args.yes);
@@ -452,6 +590,14 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         }
     }
 
+    // Reset Condition: A light node database lacks the block bodies and UTXO set required by a full node
+    if !is_db_reset_needed && !args.light && MultiConsensusManagementStore::new(meta_db.clone()).is_light_node().unwrap() {
+        let msg = "Node database was created by a light node (--light) and lacks the block bodies and UTXO set of a full node, hence it needs to be fully deleted, do you confirm the delete? (y/n)";
+        get_user_approval_or_exit(msg, args.yes);
+
+        is_db_reset_needed = true;
+    }
+
     // Will be true if any of the other condition above except args.reset_db
     // has set is_db_reset_needed to true
     if is_db_reset_needed && !args.reset_db {
@@ -460,10 +606,16 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
 
         // Delete
         fs::remove_dir_all(db_dir.clone()).unwrap();
+        if let Some(cold_storage_db_dir) = cold_storage_db_dir.as_ref().filter(|dir| dir.exists()) {
+            fs::remove_dir_all(cold_storage_db_dir).unwrap();
+        }
 
         // Recreate the empty folders
         fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
         fs::create_dir_all(meta_db_dir.as_path()).unwrap();
+        if let Some(cold_storage_db_dir) = cold_storage_db_dir.as_ref() {
+            fs::create_dir_all(cold_storage_db_dir.as_path()).unwrap();
+        }
 
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
@@ -487,7 +639,9 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     // connect_peers means no DNS seeding and no outbound/inbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
     let inbound_limit = if connect_peers.is_empty() { args.inbound_limit } else { 0 };
-    let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
+    // DNS seeding is skipped when connecting through a proxy, since seeders are resolved locally and would leak queries
+    let dns_seeders =
+        if connect_peers.is_empty() && !args.disable_dns_seeding && args.proxy.is_none() { config.dns_seeders } else { &[] };
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
 
@@ -511,16 +665,20 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     // Use `num_cpus` background threads for the consensus database as recommended by rocksdb
     let mining_rules = Arc::new(MiningRules::default());
     let consensus_db_parallelism = num_cpus::get();
+    // Tracked caches are only registered when their limits are governed at runtime
+    let cache_registry = args.memory_ceiling_gb.is_some().then(TrackedCacheRegistry::new);
     let consensus_factory = Arc::new(ConsensusFactory::new(
         meta_db.clone(),
         &config,
         consensus_db_dir,
+        cold_storage_db_dir,
         consensus_db_parallelism,
         notification_root.clone(),
         processing_counters.clone(),
         tx_script_cache_counters.clone(),
         fd_remaining,
         mining_rules.clone(),
+        cache_registry.clone(),
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
@@ -528,12 +686,32 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let perf_monitor_builder = PerfMonitorBuilder::new()
         .with_fetch_interval(Duration::from_secs(args.perf_metrics_interval_sec))
         .with_tick_service(tick_service.clone());
-    let perf_monitor = if args.perf_metrics {
+    let memory_governor = args.memory_ceiling_gb.zip(cache_registry).map(|(ceiling, cache_registry)| {
+        let governor = MemoryGovernor::new((ceiling * ONE_GIGABYTE) as usize, cache_registry);
+        info!("Memory governor is enabled with a {:.2} GB memory ceiling", ceiling);
+        governor
+    });
+    let perf_metrics = args.perf_metrics;
+    let perf_monitor = if perf_metrics || memory_governor.is_some() {
         let cb = move |counters: CountersSnapshot| {
-            debug!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_process_metrics_display());
-            debug!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_io_metrics_display());
-            #[cfg(feature = "heap")]
-            debug!("[{}] heap stats: {:?}", kaspa_perf_monitor::SERVICE_NAME, dhat::HeapStats::get());
+            if perf_metrics {
+                debug!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_process_metrics_display());
+                debug!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_io_metrics_display());
+                #[cfg(feature = "heap")]
+                debug!("[{}] heap stats: {:?}", kaspa_perf_monitor::SERVICE_NAME, dhat::HeapStats::get());
+            }
+            if let Some(governor) = memory_governor.as_ref() {
+                if let Some(scale) = governor.adjust(counters.resident_set_size) {
+                    let size = governor.caches_size();
+                    info!(
+                        "Memory governor: resident set size {:.2} GB, cache limits scaled to {:.2}x ({:.2} GB, currently holding {:.2} GB)",
+                        counters.resident_set_size as f64 / ONE_GIGABYTE,
+                        scale,
+                        size.max_bytes as f64 / ONE_GIGABYTE,
+                        size.tracked_bytes as f64 / ONE_GIGABYTE
+                    );
+                }
+            }
         };
         Arc::new(perf_monitor_builder.with_fetch_cb(cb).build())
     } else {
@@ -564,6 +742,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         false,
         config.max_block_mass,
         config.ram_scale,
+        ReplaceByFeeConfig::new(args.rbf_mode, args.rbf_min_fee_increment, args.rbf_min_feerate_increment, args.rbf_max_evictions),
+        PayloadConfig::new(args.max_payload_size, args.payload_fee_multiplier, args.payload_subnetwork_filter()),
         config.block_template_cache_lifetime,
         mining_counters.clone(),
     )));
@@ -583,6 +763,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         tick_service.clone(),
         hub.clone(),
         mining_rules,
+        SyncRateRuleConfig::new(args.sync_rate_threshold, args.sync_rate_window, args.sync_rate_min_window),
     ));
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
@@ -594,6 +775,17 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         hub.clone(),
         mining_rule_engine.clone(),
     ));
+    let stratum_service = args.stratum_listen.map(|stratum_listen| {
+        // The pay address was verified by `validate_args`
+        let pay_address = Address::try_from(args.stratum_pay_address.as_deref().unwrap()).unwrap();
+        let stratum_config = StratumConfig::new(
+            stratum_listen.normalize(DEFAULT_STRATUM_PORT),
+            pay_address,
+            args.stratum_difficulty,
+            format!("{}/stratum", version()).into_bytes(),
+        );
+        Arc::new(StratumService::new(stratum_config, flow_context.clone(), &notify_service.notifier(), tick_service.clone()))
+    });
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
         connect_peers,
@@ -604,6 +796,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         dns_seeders,
         config.default_p2p_port(),
         p2p_tower_counters.clone(),
+        args.outbound_proxies(),
     ));
 
     let rpc_core_service = Arc::new(RpcCoreService::new(
@@ -654,6 +847,9 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         async_runtime.register(grpc_service)
     }
     async_runtime.register(p2p_service);
+    if let Some(stratum_service) = stratum_service {
+        async_runtime.register(stratum_service)
+    };
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
     async_runtime.register(perf_monitor);
@@ -10,6 +10,7 @@ use workflow_core::enums::Describe;
 pub enum MetricGroup {
     System,
     Storage,
+    Database,
     Bandwidth,
     Connections,
     Network,
@@ -20,6 +21,7 @@ impl std::fmt::Display for MetricGroup {
         match self {
             MetricGroup::System => write!(f, "system"),
             MetricGroup::Storage => write!(f, "storage"),
+            MetricGroup::Database => write!(f, "database"),
             MetricGroup::Bandwidth => write!(f, "bandwidth"),
             MetricGroup::Connections => write!(f, "connections"),
             MetricGroup::Network => write!(f, "network"),
@@ -32,6 +34,7 @@ impl MetricGroup {
         match self {
             MetricGroup::System => "System",
             MetricGroup::Storage => "Storage",
+            MetricGroup::Database => "Database",
             MetricGroup::Bandwidth => "Bandwidth",
             MetricGroup::Connections => "Connections",
             MetricGroup::Network => "Network",
@@ -59,9 +62,23 @@ impl MetricGroup {
             ]
             .as_slice()
             .iter(),
+            MetricGroup::Database => [
+                Metric::NodeStoreHeadersBytes,
+                Metric::NodeStoreBlockTransactionsBytes,
+                Metric::NodeStoreAcceptanceDataBytes,
+                Metric::NodeStoreUtxoDiffsBytes,
+                Metric::NodeStoreUtxoSetBytes,
+                Metric::NodeStoreGhostdagBytes,
+                Metric::NodeStoreReachabilityBytes,
+                Metric::NodeStoreRelationsBytes,
+                Metric::NodeStoreOtherBytes,
+            ]
+            .as_slice()
+            .iter(),
             MetricGroup::Bandwidth => [
                 Metric::NodeTotalBytesTx,
                 Metric::NodeTotalBytesTxPerSecond,
+                Metric::NodeP2pTxRelayBytesSaved,
                 Metric::NodeTotalBytesRx,
                 Metric::NodeTotalBytesRxPerSecond,
                 Metric::NodeBorshBytesTx,
@@ -85,6 +102,8 @@ impl MetricGroup {
             .iter(),
             MetricGroup::Connections => [
                 Metric::NodeActivePeers,
+                Metric::NodeEncryptedPeers,
+                Metric::NodeP2pInboundEvictions,
                 Metric::NodeBorshLiveConnections,
                 Metric::NodeBorshConnectionAttempts,
                 Metric::NodeBorshHandshakeFailures,
@@ -102,6 +121,17 @@ impl MetricGroup {
                 Metric::NodeTransactionsProcessedCount,
                 Metric::NodeChainBlocksProcessedCount,
                 Metric::NodeMassProcessedCount,
+                Metric::NodeTriggeredMiningRulesCount,
+                Metric::NodeMiningRuleTransitionsCount,
+                Metric::NodeRetentionPendingBlocksCount,
+                Metric::NodeRetentionPrunedBlocksCount,
+                Metric::NodePruningProofBuildDuration,
+                Metric::NodePruningProofValidationDuration,
+                Metric::NodeReorgsDepth1Count,
+                Metric::NodeReorgsDepth2To3Count,
+                Metric::NodeReorgsDepth4To7Count,
+                Metric::NodeReorgsDepth8OrMoreCount,
+                Metric::NodeMaxReorgDepth,
                 Metric::NodeDatabaseBlocksCount,
                 Metric::NodeDatabaseHeadersCount,
                 Metric::NetworkMempoolSize,
@@ -130,13 +160,25 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeDiskIoWritePerSec
             | Metric::NodeStorageSizeBytes => MetricGroup::Storage,
             // --
+            Metric::NodeStoreHeadersBytes
+            | Metric::NodeStoreBlockTransactionsBytes
+            | Metric::NodeStoreAcceptanceDataBytes
+            | Metric::NodeStoreUtxoDiffsBytes
+            | Metric::NodeStoreUtxoSetBytes
+            | Metric::NodeStoreGhostdagBytes
+            | Metric::NodeStoreReachabilityBytes
+            | Metric::NodeStoreRelationsBytes
+            | Metric::NodeStoreOtherBytes => MetricGroup::Database,
+            // --
             Metric::NodeBorshLiveConnections
             | Metric::NodeBorshConnectionAttempts
             | Metric::NodeBorshHandshakeFailures
             | Metric::NodeJsonLiveConnections
             | Metric::NodeJsonConnectionAttempts
             | Metric::NodeJsonHandshakeFailures
-            | Metric::NodeActivePeers => MetricGroup::Connections,
+            | Metric::NodeActivePeers
+            | Metric::NodeEncryptedPeers
+            | Metric::NodeP2pInboundEvictions => MetricGroup::Connections,
             // --
             Metric::NodeBorshBytesRx
             | Metric::NodeBorshBytesTx
@@ -157,7 +199,8 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeGrpcUserBytesTxPerSecond
             | Metric::NodeGrpcUserBytesRxPerSecond
             | Metric::NodeTotalBytesRxPerSecond
-            | Metric::NodeTotalBytesTxPerSecond => MetricGroup::Bandwidth,
+            | Metric::NodeTotalBytesTxPerSecond
+            | Metric::NodeP2pTxRelayBytesSaved => MetricGroup::Bandwidth,
             // --
             Metric::NodeBlocksSubmittedCount
             | Metric::NodeHeadersProcessedCount
@@ -166,6 +209,17 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeTransactionsProcessedCount
             | Metric::NodeChainBlocksProcessedCount
             | Metric::NodeMassProcessedCount
+            | Metric::NodeTriggeredMiningRulesCount
+            | Metric::NodeMiningRuleTransitionsCount
+            | Metric::NodeRetentionPendingBlocksCount
+            | Metric::NodeRetentionPrunedBlocksCount
+            | Metric::NodePruningProofBuildDuration
+            | Metric::NodePruningProofValidationDuration
+            | Metric::NodeReorgsDepth1Count
+            | Metric::NodeReorgsDepth2To3Count
+            | Metric::NodeReorgsDepth4To7Count
+            | Metric::NodeReorgsDepth8OrMoreCount
+            | Metric::NodeMaxReorgDepth
             // --
             | Metric::NodeDatabaseBlocksCount
             | Metric::NodeDatabaseHeadersCount
@@ -197,7 +251,19 @@ pub enum Metric {
     NodeDiskIoWritePerSec,
     NodeStorageSizeBytes,
     // ---
+    NodeStoreHeadersBytes,
+    NodeStoreBlockTransactionsBytes,
+    NodeStoreAcceptanceDataBytes,
+    NodeStoreUtxoDiffsBytes,
+    NodeStoreUtxoSetBytes,
+    NodeStoreGhostdagBytes,
+    NodeStoreReachabilityBytes,
+    NodeStoreRelationsBytes,
+    NodeStoreOtherBytes,
+    // ---
     NodeActivePeers,
+    NodeEncryptedPeers,
+    NodeP2pInboundEvictions,
     NodeBorshLiveConnections,
     NodeBorshConnectionAttempts,
     NodeBorshHandshakeFailures,
@@ -209,6 +275,7 @@ pub enum Metric {
     NodeTotalBytesRx,
     NodeTotalBytesTxPerSecond,
     NodeTotalBytesRxPerSecond,
+    NodeP2pTxRelayBytesSaved,
 
     NodeP2pBytesTx,
     NodeP2pBytesRx,
@@ -238,6 +305,17 @@ pub enum Metric {
     NodeTransactionsProcessedCount,
     NodeChainBlocksProcessedCount,
     NodeMassProcessedCount,
+    NodeTriggeredMiningRulesCount,
+    NodeMiningRuleTransitionsCount,
+    NodeRetentionPendingBlocksCount,
+    NodeRetentionPrunedBlocksCount,
+    NodePruningProofBuildDuration,
+    NodePruningProofValidationDuration,
+    NodeReorgsDepth1Count,
+    NodeReorgsDepth2To3Count,
+    NodeReorgsDepth4To7Count,
+    NodeReorgsDepth8OrMoreCount,
+    NodeMaxReorgDepth,
     // --
     NodeDatabaseBlocksCount,
     NodeDatabaseHeadersCount,
@@ -270,6 +348,7 @@ impl Metric {
                 | Metric::NodeGrpcUserBytesRx
                 | Metric::NodeTotalBytesTx
                 | Metric::NodeTotalBytesRx
+                | Metric::NodeP2pTxRelayBytesSaved
                 | Metric::NodeBorshBytesTxPerSecond
                 | Metric::NodeBorshBytesRxPerSecond
                 | Metric::NodeP2pBytesTxPerSecond
@@ -279,6 +358,7 @@ impl Metric {
                 | Metric::NodeTotalBytesTxPerSecond
                 | Metric::NodeTotalBytesRxPerSecond
                 | Metric::NodeActivePeers
+                | Metric::NodeEncryptedPeers
                 | Metric::NetworkMempoolSize
                 | Metric::NetworkTipHashesCount
                 | Metric::NetworkTransactionsPerSecond
@@ -307,6 +387,16 @@ impl Metric {
             Metric::NodeDiskIoWritePerSec => format!("{}/s", as_data_size(f, si)),
             Metric::NodeStorageSizeBytes => as_gb(f, si, short),
             // --
+            Metric::NodeStoreHeadersBytes => as_mb(f, si, short),
+            Metric::NodeStoreBlockTransactionsBytes => as_mb(f, si, short),
+            Metric::NodeStoreAcceptanceDataBytes => as_mb(f, si, short),
+            Metric::NodeStoreUtxoDiffsBytes => as_mb(f, si, short),
+            Metric::NodeStoreUtxoSetBytes => as_mb(f, si, short),
+            Metric::NodeStoreGhostdagBytes => as_mb(f, si, short),
+            Metric::NodeStoreReachabilityBytes => as_mb(f, si, short),
+            Metric::NodeStoreRelationsBytes => as_mb(f, si, short),
+            Metric::NodeStoreOtherBytes => as_mb(f, si, short),
+            // --
             Metric::NodeBorshLiveConnections => f.trunc().separated_string(),
             Metric::NodeBorshConnectionAttempts => f.trunc().separated_string(),
             Metric::NodeBorshHandshakeFailures => f.trunc().separated_string(),
@@ -314,6 +404,8 @@ impl Metric {
             Metric::NodeJsonConnectionAttempts => f.trunc().separated_string(),
             Metric::NodeJsonHandshakeFailures => f.trunc().separated_string(),
             Metric::NodeActivePeers => f.trunc().separated_string(),
+            Metric::NodeEncryptedPeers => f.trunc().separated_string(),
+            Metric::NodeP2pInboundEvictions => f.trunc().separated_string(),
             // --
             Metric::NodeBorshBytesTx => as_data_size(f, si),
             Metric::NodeBorshBytesRx => as_data_size(f, si),
@@ -325,6 +417,7 @@ impl Metric {
             Metric::NodeGrpcUserBytesRx => as_data_size(f, si),
             Metric::NodeTotalBytesTx => as_data_size(f, si),
             Metric::NodeTotalBytesRx => as_data_size(f, si),
+            Metric::NodeP2pTxRelayBytesSaved => as_data_size(f, si),
             // --
             Metric::NodeBorshBytesTxPerSecond => format!("{}/s", as_kb(f, si, short)),
             Metric::NodeBorshBytesRxPerSecond => format!("{}/s", as_kb(f, si, short)),
@@ -344,6 +437,17 @@ impl Metric {
             Metric::NodeTransactionsProcessedCount => format_as_float(f, short),
             Metric::NodeChainBlocksProcessedCount => format_as_float(f, short),
             Metric::NodeMassProcessedCount => format_as_float(f, short),
+            Metric::NodeTriggeredMiningRulesCount => format_as_float(f, short),
+            Metric::NodeMiningRuleTransitionsCount => format_as_float(f, short),
+            Metric::NodeRetentionPendingBlocksCount => format_as_float(f, short),
+            Metric::NodeRetentionPrunedBlocksCount => format_as_float(f, short),
+            Metric::NodePruningProofBuildDuration => format!("{} ms", format_as_float(f, short)),
+            Metric::NodePruningProofValidationDuration => format!("{} ms", format_as_float(f, short)),
+            Metric::NodeReorgsDepth1Count => format_as_float(f, short),
+            Metric::NodeReorgsDepth2To3Count => format_as_float(f, short),
+            Metric::NodeReorgsDepth4To7Count => format_as_float(f, short),
+            Metric::NodeReorgsDepth8OrMoreCount => format_as_float(f, short),
+            Metric::NodeMaxReorgDepth => format_as_float(f, short),
             // --
             Metric::NodeDatabaseHeadersCount => format_as_float(f, short),
             Metric::NodeDatabaseBlocksCount => format_as_float(f, short),
@@ -371,7 +475,19 @@ impl Metric {
             Metric::NodeDiskIoWritePerSec => ("Storage Write/s", "Stor Write"),
             Metric::NodeStorageSizeBytes => ("Storage Size", "Stor Size"),
             // --
+            Metric::NodeStoreHeadersBytes => ("Header Store Size", "Headers DB"),
+            Metric::NodeStoreBlockTransactionsBytes => ("Block Transactions Store Size", "Txs DB"),
+            Metric::NodeStoreAcceptanceDataBytes => ("Acceptance Data Store Size", "Acceptance DB"),
+            Metric::NodeStoreUtxoDiffsBytes => ("UTXO Diffs Store Size", "Diffs DB"),
+            Metric::NodeStoreUtxoSetBytes => ("UTXO Set Store Size", "UTXO DB"),
+            Metric::NodeStoreGhostdagBytes => ("GHOSTDAG Store Size", "GD DB"),
+            Metric::NodeStoreReachabilityBytes => ("Reachability Store Size", "Reach DB"),
+            Metric::NodeStoreRelationsBytes => ("Relations Store Size", "Relations DB"),
+            Metric::NodeStoreOtherBytes => ("Other Stores Size", "Other DB"),
+            // --
             Metric::NodeActivePeers => ("Active p2p Peers", "Peers"),
+            Metric::NodeEncryptedPeers => ("Encrypted p2p Peers", "Enc Peers"),
+            Metric::NodeP2pInboundEvictions => ("p2p Inbound Evictions", "Evictions"),
             Metric::NodeBorshLiveConnections => ("Borsh Active Connections", "Borsh Conn"),
             Metric::NodeBorshConnectionAttempts => ("Borsh Connection Attempts", "Borsh Conn Att"),
             Metric::NodeBorshHandshakeFailures => ("Borsh Handshake Failures", "Borsh Failures"),
@@ -389,6 +505,7 @@ impl Metric {
             Metric::NodeGrpcUserBytesRx => ("gRPC Rx", "gRPC Rx"),
             Metric::NodeTotalBytesTx => ("Total Tx", "Total Tx"),
             Metric::NodeTotalBytesRx => ("Total Rx", "Total Rx"),
+            Metric::NodeP2pTxRelayBytesSaved => ("p2p Tx Relay Saved", "Relay Saved"),
             // --
             Metric::NodeBorshBytesTxPerSecond => ("wRPC Borsh Tx/s", "Borsh Tx/s"),
             Metric::NodeBorshBytesRxPerSecond => ("wRPC Borsh Rx/s", "Borsh Rx/s"),
@@ -408,6 +525,17 @@ impl Metric {
             Metric::NodeTransactionsProcessedCount => ("Processed Transactions", "Transactions"),
             Metric::NodeChainBlocksProcessedCount => ("Chain Blocks", "Chain Blocks"),
             Metric::NodeMassProcessedCount => ("Processed Mass Counts", "Mass Processed"),
+            Metric::NodeTriggeredMiningRulesCount => ("Triggered Mining Rules", "Mining Rules"),
+            Metric::NodeMiningRuleTransitionsCount => ("Mining Rule Transitions", "Rule Transitions"),
+            Metric::NodeRetentionPendingBlocksCount => ("Retention Pending Blocks", "Pending Prune"),
+            Metric::NodeRetentionPrunedBlocksCount => ("Retention Pruned Blocks", "Pruned"),
+            Metric::NodePruningProofBuildDuration => ("Pruning Proof Build Time", "Proof Build"),
+            Metric::NodePruningProofValidationDuration => ("Pruning Proof Validation Time", "Proof Valid"),
+            Metric::NodeReorgsDepth1Count => ("Reorgs (Depth 1)", "Reorgs 1"),
+            Metric::NodeReorgsDepth2To3Count => ("Reorgs (Depth 2-3)", "Reorgs 2-3"),
+            Metric::NodeReorgsDepth4To7Count => ("Reorgs (Depth 4-7)", "Reorgs 4-7"),
+            Metric::NodeReorgsDepth8OrMoreCount => ("Reorgs (Depth 8+)", "Reorgs 8+"),
+            Metric::NodeMaxReorgDepth => ("Max Reorg Depth", "Max Reorg"),
             // --
             Metric::NodeDatabaseBlocksCount => ("Database Blocks", "DB Blocks"),
             Metric::NodeDatabaseHeadersCount => ("Database Headers", "DB Headers"),
@@ -423,6 +551,53 @@ impl Metric {
     }
 }
 
+/// Prefixes of the consensus stores reported by the database metrics. These are the values of
+/// `kaspa_database::registry::DatabaseStorePrefixes`, which are persisted and hence never change
+/// (the database crate is not a dependency since it does not build for wasm)
+mod store_prefixes {
+    pub const ACCEPTANCE_DATA: u8 = 1;
+    pub const BLOCK_TRANSACTIONS: u8 = 2;
+    pub const GHOSTDAG: u8 = 5;
+    pub const GHOSTDAG_COMPACT: u8 = 6;
+    pub const HEADERS: u8 = 8;
+    pub const HEADERS_COMPACT: u8 = 9;
+    pub const PRUNING_UTXOSET: u8 = 11;
+    pub const REACHABILITY: u8 = 15;
+    pub const REACHABILITY_RELATIONS: u8 = 17;
+    pub const RELATIONS_PARENTS: u8 = 18;
+    pub const RELATIONS_CHILDREN: u8 = 19;
+    pub const UTXO_DIFFS: u8 = 25;
+    pub const UTXO_MULTISETS: u8 = 26;
+    pub const VIRTUAL_UTXOSET: u8 = 27;
+    pub const REACHABILITY_TREE_CHILDREN: u8 = 30;
+    pub const REACHABILITY_FUTURE_COVERING_SET: u8 = 31;
+}
+
+// The consensus stores reported by each of the database metrics
+const HEADERS_STORES: &[u8] = &[store_prefixes::HEADERS, store_prefixes::HEADERS_COMPACT];
+const BLOCK_TRANSACTIONS_STORES: &[u8] = &[store_prefixes::BLOCK_TRANSACTIONS];
+const ACCEPTANCE_DATA_STORES: &[u8] = &[store_prefixes::ACCEPTANCE_DATA];
+const UTXO_DIFFS_STORES: &[u8] = &[store_prefixes::UTXO_DIFFS, store_prefixes::UTXO_MULTISETS];
+const UTXO_SET_STORES: &[u8] = &[store_prefixes::VIRTUAL_UTXOSET, store_prefixes::PRUNING_UTXOSET];
+const GHOSTDAG_STORES: &[u8] = &[store_prefixes::GHOSTDAG, store_prefixes::GHOSTDAG_COMPACT];
+const REACHABILITY_STORES: &[u8] = &[
+    store_prefixes::REACHABILITY,
+    store_prefixes::REACHABILITY_RELATIONS,
+    store_prefixes::REACHABILITY_TREE_CHILDREN,
+    store_prefixes::REACHABILITY_FUTURE_COVERING_SET,
+];
+const RELATIONS_STORES: &[u8] = &[store_prefixes::RELATIONS_PARENTS, store_prefixes::RELATIONS_CHILDREN];
+const CATEGORIZED_STORES: &[&[u8]] = &[
+    HEADERS_STORES,
+    BLOCK_TRANSACTIONS_STORES,
+    ACCEPTANCE_DATA_STORES,
+    UTXO_DIFFS_STORES,
+    UTXO_SET_STORES,
+    GHOSTDAG_STORES,
+    REACHABILITY_STORES,
+    RELATIONS_STORES,
+];
+
 #[derive(Default, Debug, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub struct MetricsData {
     pub unixtime_millis: f64,
@@ -440,6 +615,16 @@ pub struct MetricsData {
     pub node_disk_io_write_per_sec: f32,
     pub node_storage_size_bytes: u64,
     // ---
+    pub node_store_headers_bytes: u64,
+    pub node_store_block_transactions_bytes: u64,
+    pub node_store_acceptance_data_bytes: u64,
+    pub node_store_utxo_diffs_bytes: u64,
+    pub node_store_utxo_set_bytes: u64,
+    pub node_store_ghostdag_bytes: u64,
+    pub node_store_reachability_bytes: u64,
+    pub node_store_relations_bytes: u64,
+    pub node_store_other_bytes: u64,
+    // ---
     pub node_borsh_live_connections: u32,
     pub node_borsh_connection_attempts: u64,
     pub node_borsh_handshake_failures: u64,
@@ -447,6 +632,8 @@ pub struct MetricsData {
     pub node_json_connection_attempts: u64,
     pub node_json_handshake_failures: u64,
     pub node_active_peers: u32,
+    pub node_encrypted_peers: u32,
+    pub node_p2p_inbound_evictions: u64,
     // ---
     pub node_borsh_bytes_tx: u64,
     pub node_borsh_bytes_rx: u64,
@@ -458,6 +645,7 @@ pub struct MetricsData {
     pub node_grpc_user_bytes_rx: u64,
     pub node_total_bytes_tx: u64,
     pub node_total_bytes_rx: u64,
+    pub node_p2p_tx_relay_bytes_saved: u64,
     // ---
     pub node_blocks_submitted_count: u64,
     pub node_headers_processed_count: u64,
@@ -466,6 +654,17 @@ pub struct MetricsData {
     pub node_transactions_processed_count: u64,
     pub node_chain_blocks_processed_count: u64,
     pub node_mass_processed_count: u64,
+    pub node_triggered_mining_rules_count: u32,
+    pub node_mining_rule_transitions_count: u64,
+    pub node_retention_pending_blocks_count: u64,
+    pub node_retention_pruned_blocks_count: u64,
+    pub node_pruning_proof_build_duration_ms: u64,
+    pub node_pruning_proof_validation_duration_ms: u64,
+    pub node_reorgs_depth_1_count: u64,
+    pub node_reorgs_depth_2_to_3_count: u64,
+    pub node_reorgs_depth_4_to_7_count: u64,
+    pub node_reorgs_depth_8_or_more_count: u64,
+    pub node_max_reorg_depth: u64,
     // ---
     pub node_database_blocks_count: u64,
     pub node_database_headers_count: u64,
@@ -502,6 +701,9 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
         let bandwidth_metrics = bandwidth_metrics.ok_or(Error::MissingData("Bandwidth Metrics"))?;
         let process_metrics = process_metrics.ok_or(Error::MissingData("Process Metrics"))?;
         let storage_metrics = storage_metrics.ok_or(Error::MissingData("Storage Metrics"))?;
+        let store_size = |prefixes: &[u8]| {
+            storage_metrics.store_sizes.iter().filter(|store| prefixes.contains(&store.prefix)).map(|store| store.size_bytes).sum()
+        };
 
         Ok(MetricsData {
             unixtime_millis: server_time as f64,
@@ -513,6 +715,17 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_transactions_processed_count: consensus_metrics.node_transactions_processed_count,
             node_chain_blocks_processed_count: consensus_metrics.node_chain_blocks_processed_count,
             node_mass_processed_count: consensus_metrics.node_mass_processed_count,
+            node_triggered_mining_rules_count: consensus_metrics.node_triggered_mining_rules_count,
+            node_mining_rule_transitions_count: consensus_metrics.node_mining_rule_transitions_count,
+            node_retention_pending_blocks_count: consensus_metrics.node_retention_pending_blocks_count,
+            node_retention_pruned_blocks_count: consensus_metrics.node_retention_pruned_blocks_count,
+            node_pruning_proof_build_duration_ms: consensus_metrics.node_pruning_proof_build_duration_ms,
+            node_pruning_proof_validation_duration_ms: consensus_metrics.node_pruning_proof_validation_duration_ms,
+            node_reorgs_depth_1_count: consensus_metrics.node_reorgs_depth_1_count,
+            node_reorgs_depth_2_to_3_count: consensus_metrics.node_reorgs_depth_2_to_3_count,
+            node_reorgs_depth_4_to_7_count: consensus_metrics.node_reorgs_depth_4_to_7_count,
+            node_reorgs_depth_8_or_more_count: consensus_metrics.node_reorgs_depth_8_or_more_count,
+            node_max_reorg_depth: consensus_metrics.node_max_reorg_depth,
             // --
             node_database_blocks_count: consensus_metrics.node_database_blocks_count,
             node_database_headers_count: consensus_metrics.node_database_headers_count,
@@ -530,6 +743,8 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_json_connection_attempts: connection_metrics.json_connection_attempts,
             node_json_handshake_failures: connection_metrics.json_handshake_failures,
             node_active_peers: connection_metrics.active_peers,
+            node_encrypted_peers: connection_metrics.encrypted_peers,
+            node_p2p_inbound_evictions: connection_metrics.inbound_evictions,
 
             node_borsh_bytes_tx: bandwidth_metrics.borsh_bytes_tx,
             node_borsh_bytes_rx: bandwidth_metrics.borsh_bytes_rx,
@@ -550,6 +765,8 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
                 + bandwidth_metrics.p2p_bytes_rx
                 + bandwidth_metrics.grpc_bytes_rx,
 
+            node_p2p_tx_relay_bytes_saved: bandwidth_metrics.p2p_tx_relay_bytes_saved,
+
             node_resident_set_size_bytes: process_metrics.resident_set_size,
             node_virtual_memory_size_bytes: process_metrics.virtual_memory_size,
             node_cpu_cores: process_metrics.core_num,
@@ -561,6 +778,16 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_disk_io_write_per_sec: process_metrics.disk_io_write_per_sec,
 
             node_storage_size_bytes: storage_metrics.storage_size_bytes,
+            node_store_headers_bytes: store_size(HEADERS_STORES),
+            node_store_block_transactions_bytes: store_size(BLOCK_TRANSACTIONS_STORES),
+            node_store_acceptance_data_bytes: store_size(ACCEPTANCE_DATA_STORES),
+            node_store_utxo_diffs_bytes: store_size(UTXO_DIFFS_STORES),
+            node_store_utxo_set_bytes: store_size(UTXO_SET_STORES),
+            node_store_ghostdag_bytes: store_size(GHOSTDAG_STORES),
+            node_store_reachability_bytes: store_size(REACHABILITY_STORES),
+            node_store_relations_bytes: store_size(RELATIONS_STORES),
+            node_store_other_bytes: storage_metrics.store_sizes.iter().map(|store| store.size_bytes).sum::<u64>()
+                - store_size(&CATEGORIZED_STORES.concat()),
         })
     }
 }
@@ -590,6 +817,8 @@ pub struct MetricsSnapshot {
     pub node_json_connection_attempts: f64,
     pub node_json_handshake_failures: f64,
     pub node_active_peers: f64,
+    pub node_encrypted_peers: f64,
+    pub node_p2p_inbound_evictions: f64,
     // ---
     pub node_borsh_bytes_tx: f64,
     pub node_borsh_bytes_rx: f64,
@@ -601,6 +830,7 @@ pub struct MetricsSnapshot {
     pub node_grpc_user_bytes_rx: f64,
     pub node_total_bytes_tx: f64,
     pub node_total_bytes_rx: f64,
+    pub node_p2p_tx_relay_bytes_saved: f64,
 
     pub node_borsh_bytes_tx_per_second: f64,
     pub node_borsh_bytes_rx_per_second: f64,
@@ -621,6 +851,17 @@ pub struct MetricsSnapshot {
     pub node_transactions_processed_count: f64,
     pub node_chain_blocks_processed_count: f64,
     pub node_mass_processed_count: f64,
+    pub node_triggered_mining_rules_count: f64,
+    pub node_mining_rule_transitions_count: f64,
+    pub node_retention_pending_blocks_count: f64,
+    pub node_retention_pruned_blocks_count: f64,
+    pub node_pruning_proof_build_duration_ms: f64,
+    pub node_pruning_proof_validation_duration_ms: f64,
+    pub node_reorgs_depth_1_count: f64,
+    pub node_reorgs_depth_2_to_3_count: f64,
+    pub node_reorgs_depth_4_to_7_count: f64,
+    pub node_reorgs_depth_8_or_more_count: f64,
+    pub node_max_reorg_depth: f64,
     // ---
     pub network_mempool_size: f64,
     pub network_transactions_per_second: f64,
@@ -633,6 +874,15 @@ pub struct MetricsSnapshot {
     pub network_virtual_daa_score: f64,
     // ---
     pub node_storage_size_bytes: f64,
+    pub node_store_headers_bytes: f64,
+    pub node_store_block_transactions_bytes: f64,
+    pub node_store_acceptance_data_bytes: f64,
+    pub node_store_utxo_diffs_bytes: f64,
+    pub node_store_utxo_set_bytes: f64,
+    pub node_store_ghostdag_bytes: f64,
+    pub node_store_reachability_bytes: f64,
+    pub node_store_relations_bytes: f64,
+    pub node_store_other_bytes: f64,
 }
 
 impl MetricsSnapshot {
@@ -649,7 +899,19 @@ impl MetricsSnapshot {
             Metric::NodeDiskIoWritePerSec => self.node_disk_io_write_per_sec,
             Metric::NodeStorageSizeBytes => self.node_storage_size_bytes,
             // ---
+            Metric::NodeStoreHeadersBytes => self.node_store_headers_bytes,
+            Metric::NodeStoreBlockTransactionsBytes => self.node_store_block_transactions_bytes,
+            Metric::NodeStoreAcceptanceDataBytes => self.node_store_acceptance_data_bytes,
+            Metric::NodeStoreUtxoDiffsBytes => self.node_store_utxo_diffs_bytes,
+            Metric::NodeStoreUtxoSetBytes => self.node_store_utxo_set_bytes,
+            Metric::NodeStoreGhostdagBytes => self.node_store_ghostdag_bytes,
+            Metric::NodeStoreReachabilityBytes => self.node_store_reachability_bytes,
+            Metric::NodeStoreRelationsBytes => self.node_store_relations_bytes,
+            Metric::NodeStoreOtherBytes => self.node_store_other_bytes,
+            // ---
             Metric::NodeActivePeers => self.node_active_peers,
+            Metric::NodeEncryptedPeers => self.node_encrypted_peers,
+            Metric::NodeP2pInboundEvictions => self.node_p2p_inbound_evictions,
             Metric::NodeBorshLiveConnections => self.node_borsh_active_connections,
             Metric::NodeBorshConnectionAttempts => self.node_borsh_connection_attempts,
             Metric::NodeBorshHandshakeFailures => self.node_borsh_handshake_failures,
@@ -667,6 +929,7 @@ impl MetricsSnapshot {
             Metric::NodeGrpcUserBytesRx => self.node_grpc_user_bytes_rx,
             Metric::NodeTotalBytesTx => self.node_total_bytes_tx,
             Metric::NodeTotalBytesRx => self.node_total_bytes_rx,
+            Metric::NodeP2pTxRelayBytesSaved => self.node_p2p_tx_relay_bytes_saved,
 
             Metric::NodeBorshBytesTxPerSecond => self.node_borsh_bytes_tx_per_second,
             Metric::NodeBorshBytesRxPerSecond => self.node_borsh_bytes_rx_per_second,
@@ -686,6 +949,17 @@ impl MetricsSnapshot {
             Metric::NodeTransactionsProcessedCount => self.node_transactions_processed_count,
             Metric::NodeChainBlocksProcessedCount => self.node_chain_blocks_processed_count,
             Metric::NodeMassProcessedCount => self.node_mass_processed_count,
+            Metric::NodeTriggeredMiningRulesCount => self.node_triggered_mining_rules_count,
+            Metric::NodeMiningRuleTransitionsCount => self.node_mining_rule_transitions_count,
+            Metric::NodeRetentionPendingBlocksCount => self.node_retention_pending_blocks_count,
+            Metric::NodeRetentionPrunedBlocksCount => self.node_retention_pruned_blocks_count,
+            Metric::NodePruningProofBuildDuration => self.node_pruning_proof_build_duration_ms,
+            Metric::NodePruningProofValidationDuration => self.node_pruning_proof_validation_duration_ms,
+            Metric::NodeReorgsDepth1Count => self.node_reorgs_depth_1_count,
+            Metric::NodeReorgsDepth2To3Count => self.node_reorgs_depth_2_to_3_count,
+            Metric::NodeReorgsDepth4To7Count => self.node_reorgs_depth_4_to_7_count,
+            Metric::NodeReorgsDepth8OrMoreCount => self.node_reorgs_depth_8_or_more_count,
+            Metric::NodeMaxReorgDepth => self.node_max_reorg_depth,
             // --
             Metric::NodeDatabaseBlocksCount => self.node_database_blocks_count,
             Metric::NodeDatabaseHeadersCount => self.node_database_headers_count,
@@ -745,6 +1019,15 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_disk_io_read_per_sec: b.node_disk_io_read_per_sec as f64,
             node_disk_io_write_per_sec: b.node_disk_io_write_per_sec as f64,
             node_storage_size_bytes: b.node_storage_size_bytes as f64,
+            node_store_headers_bytes: b.node_store_headers_bytes as f64,
+            node_store_block_transactions_bytes: b.node_store_block_transactions_bytes as f64,
+            node_store_acceptance_data_bytes: b.node_store_acceptance_data_bytes as f64,
+            node_store_utxo_diffs_bytes: b.node_store_utxo_diffs_bytes as f64,
+            node_store_utxo_set_bytes: b.node_store_utxo_set_bytes as f64,
+            node_store_ghostdag_bytes: b.node_store_ghostdag_bytes as f64,
+            node_store_reachability_bytes: b.node_store_reachability_bytes as f64,
+            node_store_relations_bytes: b.node_store_relations_bytes as f64,
+            node_store_other_bytes: b.node_store_other_bytes as f64,
             // ---
             node_borsh_active_connections: b.node_borsh_live_connections as f64,
             node_borsh_connection_attempts: b.node_borsh_connection_attempts as f64,
@@ -753,6 +1036,8 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_json_connection_attempts: b.node_json_connection_attempts as f64,
             node_json_handshake_failures: b.node_json_handshake_failures as f64,
             node_active_peers: b.node_active_peers as f64,
+            node_encrypted_peers: b.node_encrypted_peers as f64,
+            node_p2p_inbound_evictions: b.node_p2p_inbound_evictions as f64,
             // ---
             node_borsh_bytes_tx: b.node_borsh_bytes_tx as f64,
             node_borsh_bytes_rx: b.node_borsh_bytes_rx as f64,
@@ -764,6 +1049,7 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_grpc_user_bytes_rx: b.node_grpc_user_bytes_rx as f64,
             node_total_bytes_tx: b.node_total_bytes_tx as f64,
             node_total_bytes_rx: b.node_total_bytes_rx as f64,
+            node_p2p_tx_relay_bytes_saved: b.node_p2p_tx_relay_bytes_saved as f64,
 
             node_borsh_bytes_tx_per_second,
             node_borsh_bytes_rx_per_second,
@@ -783,6 +1069,17 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_transactions_processed_count: b.node_transactions_processed_count as f64,
             node_chain_blocks_processed_count: b.node_chain_blocks_processed_count as f64,
             node_mass_processed_count: b.node_mass_processed_count as f64,
+            node_triggered_mining_rules_count: b.node_triggered_mining_rules_count as f64,
+            node_mining_rule_transitions_count: b.node_mining_rule_transitions_count as f64,
+            node_retention_pending_blocks_count: b.node_retention_pending_blocks_count as f64,
+            node_retention_pruned_blocks_count: b.node_retention_pruned_blocks_count as f64,
+            node_pruning_proof_build_duration_ms: b.node_pruning_proof_build_duration_ms as f64,
+            node_pruning_proof_validation_duration_ms: b.node_pruning_proof_validation_duration_ms as f64,
+            node_reorgs_depth_1_count: b.node_reorgs_depth_1_count as f64,
+            node_reorgs_depth_2_to_3_count: b.node_reorgs_depth_2_to_3_count as f64,
+            node_reorgs_depth_4_to_7_count: b.node_reorgs_depth_4_to_7_count as f64,
+            node_reorgs_depth_8_or_more_count: b.node_reorgs_depth_8_or_more_count as f64,
+            node_max_reorg_depth: b.node_max_reorg_depth as f64,
             // ---
             node_database_blocks_count: b.node_database_blocks_count as f64,
             node_database_headers_count: b.node_database_headers_count as f64,
@@ -893,3 +1190,29 @@ fn format_with_precision(f: f64) -> String {
         separated_float!(format!("{:.2}", f))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::store_prefixes::*;
+    use kaspa_database::registry::DatabaseStorePrefixes;
+
+    #[test]
+    fn test_store_prefixes_match_the_database_registry() {
+        assert_eq!(ACCEPTANCE_DATA, DatabaseStorePrefixes::AcceptanceData as u8);
+        assert_eq!(BLOCK_TRANSACTIONS, DatabaseStorePrefixes::BlockTransactions as u8);
+        assert_eq!(GHOSTDAG, DatabaseStorePrefixes::Ghostdag as u8);
+        assert_eq!(GHOSTDAG_COMPACT, DatabaseStorePrefixes::GhostdagCompact as u8);
+        assert_eq!(HEADERS, DatabaseStorePrefixes::Headers as u8);
+        assert_eq!(HEADERS_COMPACT, DatabaseStorePrefixes::HeadersCompact as u8);
+        assert_eq!(PRUNING_UTXOSET, DatabaseStorePrefixes::PruningUtxoset as u8);
+        assert_eq!(REACHABILITY, DatabaseStorePrefixes::Reachability as u8);
+        assert_eq!(REACHABILITY_RELATIONS, DatabaseStorePrefixes::ReachabilityRelations as u8);
+        assert_eq!(RELATIONS_PARENTS, DatabaseStorePrefixes::RelationsParents as u8);
+        assert_eq!(RELATIONS_CHILDREN, DatabaseStorePrefixes::RelationsChildren as u8);
+        assert_eq!(UTXO_DIFFS, DatabaseStorePrefixes::UtxoDiffs as u8);
+        assert_eq!(UTXO_MULTISETS, DatabaseStorePrefixes::UtxoMultisets as u8);
+        assert_eq!(VIRTUAL_UTXOSET, DatabaseStorePrefixes::VirtualUtxoset as u8);
+        assert_eq!(REACHABILITY_TREE_CHILDREN, DatabaseStorePrefixes::ReachabilityTreeChildren as u8);
+        assert_eq!(REACHABILITY_FUTURE_COVERING_SET, DatabaseStorePrefixes::ReachabilityFutureCoveringSet as u8);
+    }
+}
@@ -1,5 +1,6 @@
 use kaspa_consensus_core::{
     errors::tx::TxRuleError,
+    subnets::SubnetworkId,
     tx::{TransactionId, TransactionOutpoint},
 };
 use thiserror::Error;
@@ -33,6 +34,33 @@ pub enum RuleError {
     #[error("replace by fee found more than one double spending transaction in the mempool")]
     RejectRbfTooManyDoubleSpendingTransactions,
 
+    #[error("replace by fee is disabled on this node")]
+    RejectRbfDisabled,
+
+    #[error("replace by fee is opt-in and the double spent transaction {0} in the mempool does not signal replaceability")]
+    RejectRbfNotSignaled(TransactionId),
+
+    #[error("replace by fee would evict {0} transactions from the mempool which is more than the allowed max of {1}")]
+    RejectRbfTooManyEvictions(usize, usize),
+
+    #[error("replace by fee transaction has {0} fees which is under the required amount of {1}")]
+    RejectRbfInsufficientFee(u64, u64),
+
+    #[error("replace by fee transaction feerate does not exceed the feerate of the double spent transaction {0} in the mempool by the required minimum increment")]
+    RejectRbfInsufficientFeerateIncrement(TransactionId),
+
+    #[error("transaction {0} would have {1} ancestors in the mempool, itself included, which is more than the allowed max of {2}")]
+    RejectTooManyAncestors(TransactionId, usize, usize),
+
+    #[error("transaction {0} would have ancestors in the mempool with a total mass of {1}, itself included, which is larger than the allowed max of {2}")]
+    RejectAncestorMassTooHigh(TransactionId, u64, u64),
+
+    #[error("transaction {0} would bring its mempool ancestor {1} to {2} descendants, itself included, which is more than the allowed max of {3}")]
+    RejectTooManyDescendants(TransactionId, TransactionId, usize, usize),
+
+    #[error("transaction {0} would bring its mempool ancestor {1} to descendants with a total mass of {2}, itself included, which is larger than the allowed max of {3}")]
+    RejectDescendantMassTooHigh(TransactionId, TransactionId, u64, u64),
+
     /// a transaction is rejected if the mempool is full
     #[error("transaction could not be added to the mempool because it's full with transactions with higher priority")]
     RejectMempoolIsFull,
@@ -132,6 +160,15 @@ pub enum NonStandardError {
 
     #[error("transaction input #{1} has {2} signature operations which is more than the allowed max amount of {3}")]
     RejectSignatureCount(TransactionId, usize, u64, u8),
+
+    #[error("transaction payload size of {1} bytes is larger than the maximum allowed size of {2} bytes")]
+    RejectPayloadSize(TransactionId, usize, usize),
+
+    #[error("transaction payload is not allowed on subnetwork {1}")]
+    RejectPayloadSubnetwork(TransactionId, SubnetworkId),
+
+    #[error("transaction with a payload of {1} bytes has {2} fees which is under the required amount of {3}")]
+    RejectInsufficientPayloadFee(TransactionId, usize, u64, u64),
 }
 
 impl NonStandardError {
@@ -148,6 +185,9 @@ impl NonStandardError {
             NonStandardError::RejectInputScriptClass(id, _) => id,
             NonStandardError::RejectInsufficientFee(id, _, _) => id,
             NonStandardError::RejectSignatureCount(id, _, _, _) => id,
+            NonStandardError::RejectPayloadSize(id, _, _) => id,
+            NonStandardError::RejectPayloadSubnetwork(id, _) => id,
+            NonStandardError::RejectInsufficientPayloadFee(id, _, _, _) => id,
         }
     }
 }
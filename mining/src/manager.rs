@@ -4,7 +4,7 @@ use crate::{
     errors::MiningManagerResult,
     feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
     mempool::{
-        config::Config,
+        config::{Config, PayloadConfig, ReplaceByFeeConfig},
         model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
@@ -13,9 +13,11 @@ use crate::{
         Mempool,
     },
     model::{
+        chain_stats::TransactionChainStats,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        short_id::{ShortIdSalt, ShortTransactionId},
         topological_sort::IntoIterTopologically,
-        tx_insert::TransactionInsertion,
+        tx_insert::{TransactionBatchInsertion, TransactionInsertion},
         tx_query::TransactionQuery,
     },
     MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
@@ -59,16 +61,21 @@ impl MiningManager {
         Self::with_config(config, cache_lifetime, counters)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_extended_config(
         target_time_per_block: ForkedParam<u64>,
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        replace_by_fee: ReplaceByFeeConfig,
+        payload: PayloadConfig,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .apply_replace_by_fee(replace_by_fee)
+            .apply_payload(payload);
         Self::with_config(config, cache_lifetime, counters)
     }
 
@@ -316,7 +323,7 @@ impl MiningManager {
         // write lock on mempool
         let mut mempool = self.mempool.write();
         match mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy)? {
-            TransactionPostValidation { removed, accepted: Some(accepted_transaction) } => {
+            TransactionPostValidation { replacement, accepted: Some(accepted_transaction) } => {
                 let unorphaned_transactions = mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
                 drop(mempool);
 
@@ -324,22 +331,28 @@ impl MiningManager {
                 let mut accepted_transactions = Vec::with_capacity(unorphaned_transactions.len() + 1);
                 // We include the original accepted transaction as well
                 accepted_transactions.push(accepted_transaction);
-                accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
+                let (unorphaned_transactions, unorphaned_replacements) =
+                    self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
+                accepted_transactions.extend(unorphaned_transactions);
                 self.counters.increase_tx_counts(1, priority);
 
-                Ok(TransactionInsertion::new(removed, accepted_transactions))
+                Ok(TransactionInsertion::new(replacement, unorphaned_replacements, accepted_transactions))
             }
-            TransactionPostValidation { removed, accepted: None } => Ok(TransactionInsertion::new(removed, vec![])),
+            TransactionPostValidation { replacement, accepted: None } => Ok(TransactionInsertion::new(replacement, vec![], vec![])),
         }
     }
 
+    /// Validates and inserts the unorphaned transactions, along with the transactions they unorphan in turn.
+    ///
+    /// Returns the accepted transactions and the replacements they performed.
     fn validate_and_insert_unorphaned_transactions(
         &self,
         consensus: &dyn ConsensusApi,
         mut incoming_transactions: Vec<MempoolTransaction>,
-    ) -> Vec<Arc<Transaction>> {
+    ) -> (Vec<Arc<Transaction>>, Vec<TransactionReplacement>) {
         // The capacity used here may be exceeded (see next comment).
         let mut accepted_transactions = Vec::with_capacity(incoming_transactions.len());
+        let mut replacements = vec![];
         // The validation args map is immutably empty since unorphaned transactions do not require pre processing so there
         // are no feerate thresholds to use. Instead, we rely on this being checked during post processing.
         let args = TransactionValidationBatchArgs::new();
@@ -383,12 +396,13 @@ impl MiningManager {
                         Orphan::Forbidden,
                         rbf_policy,
                     ) {
-                        Ok(TransactionPostValidation { removed: _, accepted: Some(accepted_transaction) }) => {
+                        Ok(TransactionPostValidation { replacement, accepted: Some(accepted_transaction) }) => {
                             accepted_transactions.push(accepted_transaction.clone());
+                            replacements.extend(replacement);
                             self.counters.increase_tx_counts(1, priority);
                             mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                         }
-                        Ok(TransactionPostValidation { removed: _, accepted: None }) => vec![],
+                        Ok(TransactionPostValidation { replacement: _, accepted: None }) => vec![],
                         Err(err) => {
                             debug!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
                             vec![]
@@ -398,7 +412,7 @@ impl MiningManager {
                 .collect::<Vec<_>>();
             drop(mempool);
         }
-        accepted_transactions
+        (accepted_transactions, replacements)
     }
 
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
@@ -408,7 +422,8 @@ impl MiningManager {
     /// to double spends in the mempool. For more information, see [`RbfPolicy`].
     ///
     /// Returns transactions that where unorphaned following the insertion of the provided
-    /// transactions. The returned transactions are references of objects owned by the mempool.
+    /// transactions, along with the replacements performed by the provided transactions.
+    /// The returned transactions are references of objects owned by the mempool.
     pub fn validate_and_insert_transaction_batch(
         &self,
         consensus: &dyn ConsensusApi,
@@ -416,11 +431,12 @@ impl MiningManager {
         priority: Priority,
         orphan: Orphan,
         rbf_policy: RbfPolicy,
-    ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
+    ) -> TransactionBatchInsertion {
         const TRANSACTION_CHUNK_SIZE: usize = 250;
 
         // The capacity used here may be exceeded since accepted transactions may unorphan other transactions.
         let mut insert_results: Vec<MiningManagerResult<Arc<Transaction>>> = Vec::with_capacity(transactions.len());
+        let mut replacements = vec![];
         let mut unorphaned_transactions = vec![];
         let _swo = Stopwatch::<80>::with_threshold("validate_and_insert_transaction_batch topological_sort op");
         let sorted_transactions = transactions.into_iter().map(MutableTransaction::from_tx).topological_into_iter();
@@ -492,12 +508,13 @@ impl MiningManager {
                     orphan,
                     rbf_policy,
                 ) {
-                    Ok(TransactionPostValidation { removed: _, accepted: Some(accepted_transaction) }) => {
+                    Ok(TransactionPostValidation { replacement, accepted: Some(accepted_transaction) }) => {
                         insert_results.push(Ok(accepted_transaction.clone()));
+                        replacements.extend(replacement);
                         self.counters.increase_tx_counts(1, priority);
                         mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                     }
-                    Ok(TransactionPostValidation { removed: _, accepted: None }) | Err(RuleError::RejectDuplicate(_)) => {
+                    Ok(TransactionPostValidation { replacement: _, accepted: None }) | Err(RuleError::RejectDuplicate(_)) => {
                         // Either orphaned or already existing in the mempool
                         vec![]
                     }
@@ -511,9 +528,11 @@ impl MiningManager {
             unorphaned_transactions.extend(txs);
         }
 
-        insert_results
-            .extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions).into_iter().map(Ok));
-        insert_results
+        let (unorphaned_transactions, unorphaned_replacements) =
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
+        insert_results.extend(unorphaned_transactions.into_iter().map(Ok));
+        replacements.extend(unorphaned_replacements);
+        TransactionBatchInsertion::new(insert_results, replacements)
     }
 
     fn next_transaction_chunk_upper_bound(&self, transactions: &[MutableTransaction], lower_bound: usize) -> Option<usize> {
@@ -541,6 +560,20 @@ impl MiningManager {
         self.mempool.read().get_transaction(transaction_id, query)
     }
 
+    /// Returns the ancestor and descendant statistics of a transaction of the mempool transaction pool.
+    ///
+    /// Orphan transactions have no such statistics.
+    pub fn get_transaction_chain_stats(&self, transaction_id: &TransactionId) -> Option<TransactionChainStats> {
+        self.mempool.read().get_transaction_chain_stats(transaction_id)
+    }
+
+    /// Returns the mempool transactions matching the provided short ids, salted with `salt`, in the same order.
+    ///
+    /// A short id matching no transaction or matching more than one transaction is resolved to `None`.
+    pub fn get_transactions_by_short_ids(&self, salt: ShortIdSalt, short_ids: &[ShortTransactionId]) -> Vec<Option<Arc<Transaction>>> {
+        self.mempool.read().get_transactions_by_short_ids(salt, short_ids)
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         self.mempool.read().has_transaction(transaction_id, query)
@@ -591,7 +624,7 @@ impl MiningManager {
         consensus: &dyn ConsensusApi,
         block_daa_score: u64,
         block_transactions: &[Transaction],
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+    ) -> MiningManagerResult<TransactionInsertion> {
         // TODO: should use tx acceptance data to verify that new block txs are actually accepted into virtual state.
         // TODO: avoid returning a result from this function (and the underlying function). Any possible error is a
         // problem of the internal implementation and unrelated to the caller
@@ -600,9 +633,10 @@ impl MiningManager {
         let unorphaned_transactions = self.mempool.write().handle_new_block_transactions(block_daa_score, block_transactions)?;
 
         // alternate no & write lock on mempool
-        let accepted_transactions = self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
+        let (accepted_transactions, replacements) =
+            self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions);
 
-        Ok(accepted_transactions)
+        Ok(TransactionInsertion::new(None, replacements, accepted_transactions))
     }
 
     pub fn expire_low_priority_transactions(&self, consensus: &dyn ConsensusApi) {
@@ -903,7 +937,8 @@ impl MiningManagerProxy {
     /// to double spends in the mempool. For more information, see [`RbfPolicy`].
     ///
     /// Returns transactions that where unorphaned following the insertion of the provided
-    /// transactions. The returned transactions are references of objects owned by the mempool.
+    /// transactions, along with the replacements performed by the provided transactions.
+    /// The returned transactions are references of objects owned by the mempool.
     pub async fn validate_and_insert_transaction_batch(
         self,
         consensus: &ConsensusProxy,
@@ -911,7 +946,7 @@ impl MiningManagerProxy {
         priority: Priority,
         orphan: Orphan,
         rbf_policy: RbfPolicy,
-    ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
+    ) -> TransactionBatchInsertion {
         consensus
             .clone()
             .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan, rbf_policy))
@@ -923,7 +958,7 @@ impl MiningManagerProxy {
         consensus: &ConsensusProxy,
         block_daa_score: u64,
         block_transactions: Arc<Vec<Transaction>>,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+    ) -> MiningManagerResult<TransactionInsertion> {
         consensus
             .clone()
             .spawn_blocking(move |c| self.inner.handle_new_block_transactions(c, block_daa_score, &block_transactions))
@@ -949,6 +984,24 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_transaction(&transaction_id, query)).await.unwrap()
     }
 
+    /// Returns the ancestor and descendant statistics of a transaction of the mempool transaction pool.
+    ///
+    /// Orphan transactions have no such statistics.
+    pub async fn get_transaction_chain_stats(self, transaction_id: TransactionId) -> Option<TransactionChainStats> {
+        spawn_blocking(move || self.inner.get_transaction_chain_stats(&transaction_id)).await.unwrap()
+    }
+
+    /// Returns the mempool transactions matching the provided short ids, salted with `salt`, in the same order.
+    ///
+    /// A short id matching no transaction or matching more than one transaction is resolved to `None`.
+    pub async fn get_transactions_by_short_ids(
+        self,
+        salt: ShortIdSalt,
+        short_ids: Vec<ShortTransactionId>,
+    ) -> Vec<Option<Arc<Transaction>>> {
+        spawn_blocking(move || self.inner.get_transactions_by_short_ids(salt, &short_ids)).await.unwrap()
+    }
+
     /// Returns whether the mempool holds this transaction in any form.
     pub async fn has_transaction(self, transaction_id: TransactionId, query: TransactionQuery) -> bool {
         spawn_blocking(move || self.inner.has_transaction(&transaction_id, query)).await.unwrap()
@@ -5,12 +5,14 @@ mod tests {
         errors::{MiningManagerError, MiningManagerResult},
         manager::MiningManager,
         mempool::{
-            config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+            config::{Config, RbfMode, ReplaceByFeeConfig, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE, MAX_RBF_OPT_IN_SEQUENCE},
             errors::RuleError,
             model::frontier::selectors::TakeAllSelector,
             tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        model::{
+            chain_stats::TransactionChainStats, short_id::ShortIdSalt, tx_insert::TransactionInsertion, tx_query::TransactionQuery,
+        },
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -429,13 +431,21 @@ mod tests {
                 if expected {
                     assert!(result.is_ok(), "[{}, {:?}] mempool should accept a RBF transaction", self.name, rbf_policy,);
                     let tx_insertion = result.unwrap();
+                    let replacement = tx_insertion.replacement.as_ref().unwrap();
                     assert_eq!(
-                        tx_insertion.removed.as_ref().unwrap().id(),
+                        replacement.replaced[0].id(),
                         transactions[0].id(),
                         "[{}, {:?}] RBF should return the removed transaction",
                         self.name,
                         rbf_policy,
                     );
+                    assert_eq!(
+                        replacement.evicted.len(),
+                        tx_count + 1 - expected_tx_count,
+                        "[{}, {:?}] RBF should list all the evicted transactions",
+                        self.name,
+                        rbf_policy,
+                    );
                     transactions.iter().for_each(|x| {
                         assert!(
                             !mining_manager.has_transaction(&x.id(), TransactionQuery::All),
@@ -544,6 +554,317 @@ mod tests {
         }
     }
 
+    /// test_replace_by_fee_rules verifies that the node-wide replace by fee mode and rules are applied
+    /// to a transaction double spending a mempool transaction having some descendants.
+    #[test]
+    fn test_replace_by_fee_rules() {
+        const BASE_FEE: u64 = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        // The replaced transaction has 2 outputs, each with a binary tree of descendants of depth 2
+        const EVICTED_COUNT: usize = 7;
+
+        struct Test {
+            name: &'static str,
+            replace_by_fee: ReplaceByFeeConfig,
+            /// The replaced transaction signals replaceability
+            signal: bool,
+            rbf_policy: RbfPolicy,
+            replacement_fee: u64,
+            /// Number of evicted transactions on success, the rule error given the replaced transaction otherwise
+            expected: Result<usize, fn(&Transaction) -> RuleError>,
+        }
+
+        let tests = vec![
+            Test {
+                name: "full mode, increased fee",
+                replace_by_fee: ReplaceByFeeConfig::default(),
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Ok(EVICTED_COUNT),
+            },
+            Test {
+                name: "disabled mode, mandatory RBF",
+                replace_by_fee: ReplaceByFeeConfig { mode: RbfMode::Disabled, ..Default::default() },
+                signal: true,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|_| RuleError::RejectRbfDisabled),
+            },
+            Test {
+                name: "disabled mode, allowed RBF",
+                replace_by_fee: ReplaceByFeeConfig { mode: RbfMode::Disabled, ..Default::default() },
+                signal: true,
+                rbf_policy: RbfPolicy::Allowed,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|tx| RuleError::RejectDoubleSpendInMempool(tx.inputs[0].previous_outpoint, tx.id())),
+            },
+            Test {
+                name: "opt-in mode, replaced transaction not signaling",
+                replace_by_fee: ReplaceByFeeConfig { mode: RbfMode::OptIn, ..Default::default() },
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|tx| RuleError::RejectRbfNotSignaled(tx.id())),
+            },
+            Test {
+                name: "opt-in mode, replaced transaction signaling",
+                replace_by_fee: ReplaceByFeeConfig { mode: RbfMode::OptIn, ..Default::default() },
+                signal: true,
+                rbf_policy: RbfPolicy::Allowed,
+                replacement_fee: BASE_FEE * 2,
+                expected: Ok(EVICTED_COUNT),
+            },
+            Test {
+                name: "too many evictions",
+                replace_by_fee: ReplaceByFeeConfig { maximum_evicted_transactions: EVICTED_COUNT - 1, ..Default::default() },
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|_| RuleError::RejectRbfTooManyEvictions(EVICTED_COUNT, EVICTED_COUNT - 1)),
+            },
+            Test {
+                name: "fee under the evicted fees plus increment",
+                replace_by_fee: ReplaceByFeeConfig { minimum_fee_increment: Some(BASE_FEE), ..Default::default() },
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|_| RuleError::RejectRbfInsufficientFee(BASE_FEE * 2, BASE_FEE * (EVICTED_COUNT as u64 + 1))),
+            },
+            Test {
+                name: "fee matching the evicted fees plus increment",
+                replace_by_fee: ReplaceByFeeConfig { minimum_fee_increment: Some(BASE_FEE), ..Default::default() },
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * (EVICTED_COUNT as u64 + 1),
+                expected: Ok(EVICTED_COUNT),
+            },
+            Test {
+                name: "feerate under the required increment",
+                replace_by_fee: ReplaceByFeeConfig { minimum_feerate_increment: 1_000.0, ..Default::default() },
+                signal: false,
+                rbf_policy: RbfPolicy::Mandatory,
+                replacement_fee: BASE_FEE * 2,
+                expected: Err(|tx| RuleError::RejectRbfInsufficientFeerateIncrement(tx.id())),
+            },
+        ];
+
+        for test in tests {
+            let consensus = Arc::new(ConsensusMock::new());
+            let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+            config.replace_by_fee = test.replace_by_fee.clone();
+            let counters = Arc::new(MiningCounters::default());
+            let mining_manager = MiningManager::with_config(config, None, counters);
+            let funding_transactions = create_and_add_funding_transactions(&consensus, 1);
+
+            let mut transaction = create_funded_transaction(
+                select_transactions(&funding_transactions, &[0]),
+                vec![0],
+                Some(900 * SOMPI_PER_KASPA),
+                BASE_FEE,
+            );
+            if test.signal {
+                transaction.inputs[0].sequence = MAX_RBF_OPT_IN_SEQUENCE;
+                transaction.finalize();
+            }
+            let children = create_children_tree(&transaction, 2);
+            validate_and_insert_transactions(
+                &mining_manager,
+                consensus.as_ref(),
+                once(&transaction).chain(children.iter()),
+                Priority::High,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert_transaction_count(&mining_manager, EVICTED_COUNT, &format!("[{}] initial state", test.name));
+
+            let transaction_replacement = create_funded_transaction(
+                select_transactions(&funding_transactions, &[0]),
+                vec![0],
+                Some(900 * SOMPI_PER_KASPA),
+                test.replacement_fee,
+            );
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction_replacement.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                test.rbf_policy,
+            );
+            match test.expected {
+                Ok(evicted_count) => {
+                    let replacement =
+                        result.unwrap().replacement.unwrap_or_else(|| panic!("[{}] a replacement is expected", test.name));
+                    assert_eq!(
+                        replacement.transaction_id,
+                        transaction_replacement.id(),
+                        "[{}] wrong replacing transaction",
+                        test.name
+                    );
+                    assert_eq!(
+                        replacement.replaced.iter().map(|x| x.id()).collect_vec(),
+                        vec![transaction.id()],
+                        "[{}] wrong replaced transactions",
+                        test.name
+                    );
+                    assert_eq!(replacement.evicted.len(), evicted_count, "[{}] wrong evicted transaction count", test.name);
+                    assert_eq!(replacement.evicted[0], transaction.id(), "[{}] the replaced transaction should come first", test.name);
+                    assert!(
+                        children.iter().all(|x| replacement.evicted.contains(&x.id())),
+                        "[{}] all descendants should be evicted",
+                        test.name
+                    );
+                    assert_transaction_count(&mining_manager, 1, &format!("[{}] successful replacement", test.name));
+                }
+                Err(expected_error) => {
+                    assert_eq!(into_mempool_result(result), Err(expected_error(&transaction)), "[{}] wrong rule error", test.name);
+                    assert_transaction_count(&mining_manager, EVICTED_COUNT, &format!("[{}] failed replacement", test.name));
+                }
+            }
+        }
+    }
+
+    /// test_transaction_chain_limits verifies that the mempool enforces the ancestor and descendant limits of its config,
+    /// reports matching chain statistics and ignores the transactions evicted by a replacement when enforcing the limits.
+    #[test]
+    fn test_transaction_chain_limits() {
+        let insert = |mining_manager: &MiningManager, consensus: &ConsensusMock, transaction: &Transaction, rbf_policy: RbfPolicy| {
+            into_mempool_result(mining_manager.validate_and_insert_transaction(
+                consensus,
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                rbf_policy,
+            ))
+        };
+        // The consensus mock computes the mass of a transaction as its serialized size
+        let mass = transaction_estimated_serialized_size;
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let funding_transactions = create_and_add_funding_transactions(&consensus, 1);
+
+        // Build a chain t0 <- t1 <- t2 <- t3 linked by the first output, all transactions having a change output
+        let mut chain: Vec<Transaction> = vec![];
+        for i in 0..4 {
+            let parent = if i == 0 { &funding_transactions[0] } else { &chain[i - 1] };
+            let child = create_funded_transaction(
+                once(parent),
+                vec![0],
+                Some(parent.outputs[0].value / 2),
+                DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+            );
+            chain.push(child);
+        }
+        // Siblings spending the change outputs of t0 and t1
+        let s0 = create_funded_transaction(once(&chain[0]), vec![1], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let s1 = create_funded_transaction(once(&chain[1]), vec![1], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+
+        // Count limits
+        let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+        config.maximum_ancestor_count = 3;
+        config.maximum_descendant_count = 4;
+        let mining_manager = MiningManager::with_config(config, None, Arc::new(MiningCounters::default()));
+        for transaction in chain.iter().take(3) {
+            assert_eq!(Ok(()), insert(&mining_manager, &consensus, transaction, RbfPolicy::Forbidden));
+        }
+        assert_eq!(
+            Err(RuleError::RejectTooManyAncestors(chain[3].id(), 4, 3)),
+            insert(&mining_manager, &consensus, &chain[3], RbfPolicy::Forbidden)
+        );
+        assert_eq!(Ok(()), insert(&mining_manager, &consensus, &s0, RbfPolicy::Forbidden));
+        assert_eq!(
+            Err(RuleError::RejectTooManyDescendants(s1.id(), chain[0].id(), 5, 4)),
+            insert(&mining_manager, &consensus, &s1, RbfPolicy::Forbidden)
+        );
+        assert_transaction_count(&mining_manager, 4, "after rejecting transactions exceeding the chain limits,");
+
+        let stats = mining_manager.get_transaction_chain_stats(&chain[1].id()).expect("the transaction is in the mempool");
+        assert_eq!(TransactionChainStats::new(2, mass(&chain[0]) + mass(&chain[1]), 2, mass(&chain[1]) + mass(&chain[2])), stats);
+        let stats = mining_manager.get_transaction_chain_stats(&chain[0].id()).expect("the transaction is in the mempool");
+        assert_eq!((1, 4), (stats.ancestor_count, stats.descendant_count));
+
+        // t0 has reached the descendant limit but a replacement of one of its descendants is still accepted
+        let s0_replacement = create_funded_transaction(once(&chain[0]), vec![1], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * 2);
+        assert_eq!(Ok(()), insert(&mining_manager, &consensus, &s0_replacement, RbfPolicy::Allowed));
+        assert!(mining_manager.get_transaction_chain_stats(&s0.id()).is_none(), "the replaced transaction should be evicted");
+        assert_transaction_count(&mining_manager, 4, "after replacing a transaction,");
+
+        // Ancestor mass limit
+        let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+        config.maximum_ancestor_mass = mass(&chain[0]) + mass(&chain[1]);
+        let mining_manager = MiningManager::with_config(config.clone(), None, Arc::new(MiningCounters::default()));
+        for transaction in chain.iter().take(2) {
+            assert_eq!(Ok(()), insert(&mining_manager, &consensus, transaction, RbfPolicy::Forbidden));
+        }
+        assert_eq!(
+            Err(RuleError::RejectAncestorMassTooHigh(
+                chain[2].id(),
+                mass(&chain[0]) + mass(&chain[1]) + mass(&chain[2]),
+                config.maximum_ancestor_mass
+            )),
+            insert(&mining_manager, &consensus, &chain[2], RbfPolicy::Forbidden)
+        );
+
+        // Descendant mass limit
+        let mut config = Config::build_default(ForkedParam::new_const(TARGET_TIME_PER_BLOCK), false, MAX_BLOCK_MASS);
+        config.maximum_descendant_mass = mass(&chain[0]) + mass(&chain[1]);
+        let mining_manager = MiningManager::with_config(config.clone(), None, Arc::new(MiningCounters::default()));
+        for transaction in chain.iter().take(2) {
+            assert_eq!(Ok(()), insert(&mining_manager, &consensus, transaction, RbfPolicy::Forbidden));
+        }
+        assert_eq!(
+            Err(RuleError::RejectDescendantMassTooHigh(
+                s0.id(),
+                chain[0].id(),
+                mass(&chain[0]) + mass(&chain[1]) + mass(&s0),
+                config.maximum_descendant_mass
+            )),
+            insert(&mining_manager, &consensus, &s0, RbfPolicy::Forbidden)
+        );
+        assert_transaction_count(&mining_manager, 2, "after rejecting transactions exceeding the mass limits,");
+    }
+
+    /// test_get_transactions_by_short_ids verifies that mempool transactions are found by their short ids
+    /// and that unknown short ids are resolved to `None`.
+    #[test]
+    fn test_get_transactions_by_short_ids() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let transactions = create_and_add_funding_transactions(&consensus, 2)
+            .iter()
+            .map(|parent| create_funded_transaction(once(parent), vec![0], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE))
+            .collect_vec();
+        for transaction in transactions.iter() {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction should succeed");
+        }
+
+        let salt = ShortIdSalt::from_block(TransactionId::from_u64_word(7), 11);
+        let unknown_short_id = salt.short_id(&TransactionId::from_u64_word(u64::MAX));
+        let short_ids = vec![salt.short_id(&transactions[1].id()), unknown_short_id, salt.short_id(&transactions[0].id())];
+        let found = mining_manager.get_transactions_by_short_ids(salt, &short_ids);
+        assert_eq!(3, found.len());
+        assert_eq!(Some(transactions[1].id()), found[0].as_ref().map(|tx| tx.id()));
+        assert!(found[1].is_none(), "an unknown short id should not be resolved");
+        assert_eq!(Some(transactions[0].id()), found[2].as_ref().map(|tx| tx.id()));
+
+        // Transactions removed from the mempool are no longer resolved
+        mining_manager
+            .handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&transactions[1])))
+            .unwrap();
+        assert!(mining_manager.get_transactions_by_short_ids(salt, &short_ids)[0].is_none());
+
+        // Short ids salted differently are not resolved
+        let other_salt = ShortIdSalt::from_block(TransactionId::from_u64_word(7), 12);
+        assert!(mining_manager.get_transactions_by_short_ids(other_salt, &short_ids).iter().all(Option::is_none));
+    }
+
     /// test_handle_new_block_transactions verifies that all the transactions in the block were successfully removed from the mempool.
     #[test]
     fn test_handle_new_block_transactions() {
@@ -696,7 +1017,7 @@ mod tests {
         let result =
             mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(added_parent_txs.iter()));
         assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
-        let unorphaned_txs = result.unwrap();
+        let unorphaned_txs = result.unwrap().accepted;
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
         assert_eq!(
             unorphaned_txs.len(), child_txs.len() - SKIPPED_TXS,
@@ -782,7 +1103,7 @@ mod tests {
             mining_manager.handle_new_block_transactions(consensus.as_ref(), 4, &build_block_transactions(added_child_txs.iter()));
         assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
 
-        let unorphaned_txs = result.unwrap();
+        let unorphaned_txs = result.unwrap().accepted;
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
         assert_eq!(
             0,
@@ -840,6 +1161,86 @@ mod tests {
         assert_eq!(0, orphans.len(), "the orphan pool is expected to be empty: {}, got: {}", 0, orphans.len());
     }
 
+    /// test_unorphaned_replace_by_fee verifies that the replacements performed by unorphaned transactions are reported
+    /// whichever way the orphans get unorphaned.
+    #[test]
+    fn test_unorphaned_replace_by_fee() {
+        #[derive(Debug)]
+        enum Unorphaning {
+            Insert,
+            BatchInsert,
+            Block,
+        }
+
+        const BASE_FEE: u64 = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        for unorphaning in [Unorphaning::Insert, Unorphaning::BatchInsert, Unorphaning::Block] {
+            let consensus = Arc::new(ConsensusMock::new());
+            let counters = Arc::new(MiningCounters::default());
+            let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+            let funding_transactions = create_and_add_funding_transactions(&consensus, 2);
+
+            // A mempool transaction, double spent by an orphan paying a much higher fee and spending the output of a missing parent
+            let transaction = create_funded_transaction(select_transactions(&funding_transactions, &[0]), vec![0], None, BASE_FEE);
+            let parent = create_funded_transaction(select_transactions(&funding_transactions, &[1]), vec![1], None, BASE_FEE);
+            let orphan = create_funded_transaction(once(&funding_transactions[0]).chain(once(&parent)), vec![0], None, 100 * BASE_FEE);
+            validate_and_insert_transactions(
+                &mining_manager,
+                consensus.as_ref(),
+                [transaction.clone(), orphan.clone()].iter(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Allowed,
+            );
+            assert!(
+                mining_manager.has_transaction(&orphan.id(), TransactionQuery::OrphansOnly),
+                "[{unorphaning:?}] the double spending transaction should be an orphan"
+            );
+
+            let replacements = match unorphaning {
+                Unorphaning::Insert => {
+                    let insertion = mining_manager
+                        .validate_and_insert_transaction(
+                            consensus.as_ref(),
+                            parent.clone(),
+                            Priority::Low,
+                            Orphan::Forbidden,
+                            RbfPolicy::Allowed,
+                        )
+                        .unwrap();
+                    assert!(insertion.replacement.is_none(), "[{unorphaning:?}] the parent should replace no transaction");
+                    insertion.replacements().cloned().collect_vec()
+                }
+                Unorphaning::BatchInsert => {
+                    mining_manager
+                        .validate_and_insert_transaction_batch(
+                            consensus.as_ref(),
+                            vec![parent.clone()],
+                            Priority::Low,
+                            Orphan::Forbidden,
+                            RbfPolicy::Allowed,
+                        )
+                        .replacements
+                }
+                Unorphaning::Block => {
+                    consensus.add_transaction(parent.clone(), 1);
+                    mining_manager
+                        .handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&parent)))
+                        .unwrap()
+                        .replacements()
+                        .cloned()
+                        .collect_vec()
+                }
+            };
+
+            assert_eq!(1, replacements.len(), "[{unorphaning:?}] the unorphaned transaction should report its replacement");
+            assert_eq!(orphan.id(), replacements[0].transaction_id);
+            assert_eq!(vec![transaction.id()], replacements[0].replaced.iter().map(|x| x.id()).collect_vec());
+            assert_eq!(vec![transaction.id()], replacements[0].evicted);
+            assert!(mining_manager.has_transaction(&orphan.id(), TransactionQuery::TransactionsOnly));
+            assert!(!mining_manager.has_transaction(&transaction.id(), TransactionQuery::All));
+        }
+    }
+
     /// test_high_priority_transactions verifies that inserting a high priority orphan transaction when the orphan pool is full
     /// evicts a low-priority transaction, if available, or fails if the pool is already filled with high priority transactions.
     #[test]
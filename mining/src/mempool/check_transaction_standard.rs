@@ -69,6 +69,19 @@ impl Mempool {
             return Err(NonStandardError::RejectTransientMass(transaction_id, transient_mass, MAXIMUM_STANDARD_TRANSACTION_MASS));
         }
 
+        // A transaction payload must comply with the node payload policy.
+        //
+        // These rules are node-configurable and enforced by the mempool only, never by consensus.
+        let payload_len = transaction.tx.payload.len();
+        if payload_len > 0 {
+            if payload_len > self.config.payload.maximum_size {
+                return Err(NonStandardError::RejectPayloadSize(transaction_id, payload_len, self.config.payload.maximum_size));
+            }
+            if !self.config.payload.subnetwork_filter.allows(&transaction.tx.subnetwork_id) {
+                return Err(NonStandardError::RejectPayloadSubnetwork(transaction_id, transaction.tx.subnetwork_id.clone()));
+            }
+        }
+
         for (i, input) in transaction.tx.inputs.iter().enumerate() {
             // Each transaction input signature script must not exceed the
             // maximum size allowed for a standard transaction.
@@ -207,9 +220,36 @@ impl Mempool {
             }
         }
 
+        // A transaction carrying a payload must pay an additional fee for every byte of its payload
+        let payload_len = transaction.tx.payload.len();
+        let payload_fee = self.minimum_required_payload_fee(payload_len);
+        if payload_fee > 0 {
+            let minimum_fee = self
+                .minimum_required_transaction_relay_fee(transaction.calculated_non_contextual_masses.unwrap().compute_mass)
+                .saturating_add(payload_fee)
+                .min(MAX_SOMPI);
+            if transaction.calculated_fee.unwrap() < minimum_fee {
+                return Err(NonStandardError::RejectInsufficientPayloadFee(
+                    transaction_id,
+                    payload_len,
+                    transaction.calculated_fee.unwrap(),
+                    minimum_fee,
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// minimum_required_payload_fee returns the fee a transaction with a payload of the passed
+    /// size must pay on top of its minimum relay fee.
+    fn minimum_required_payload_fee(&self, payload_len: usize) -> u64 {
+        // The multiplier applies to the minimum relay feerate in sompi/gram, so the
+        // surcharge is expressed as if every payload byte weighed `multiplier` grams.
+        let payload_fee = (payload_len as f64 * self.config.payload.fee_per_byte_multiplier * self.config.minimum_feerate()).ceil();
+        (payload_fee as u64).min(MAX_SOMPI)
+    }
+
     /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
     /// for a transaction with the passed mass to be accepted into the mempool and relayed.
     fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
@@ -235,17 +275,17 @@ impl Mempool {
 mod tests {
     use super::*;
     use crate::{
-        mempool::config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+        mempool::config::{Config, PayloadConfig, PayloadSubnetworkFilter, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
         MiningCounters,
     };
     use kaspa_addresses::{Address, Prefix, Version};
     use kaspa_consensus_core::{
-        config::params::Params,
+        config::params::{ForkedParam, Params},
         constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA, TX_VERSION},
         mass::NonContextualMasses,
         network::NetworkType,
-        subnets::SUBNETWORK_ID_NATIVE,
-        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+        subnets::{SubnetworkId, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
     };
     use kaspa_txscript::{
         opcodes::codes::{OpReturn, OpTrue},
@@ -584,4 +624,66 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_check_transaction_payload_standard() {
+        let dummy_prev_out = TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 1);
+        let dummy_tx_input = TransactionInput::new(dummy_prev_out, vec![0u8; 65], MAX_TX_IN_SEQUENCE_NUM, 1);
+        let addr = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let dummy_script_public_key = kaspa_txscript::pay_to_address_script(&addr);
+        let other_subnetwork_id = SubnetworkId::from_byte(3);
+
+        // All transactions have a compute mass of 1000 grams, requiring a minimum fee of 1000 sompi under the default config
+        let new_mtx = |payload_len: usize, subnetwork_id: SubnetworkId, fee: u64| {
+            let tx = Transaction::new(
+                TX_VERSION,
+                vec![dummy_tx_input.clone()],
+                vec![TransactionOutput::new(SOMPI_PER_KASPA, dummy_script_public_key.clone())],
+                0,
+                subnetwork_id,
+                0,
+                vec![0u8; payload_len],
+            );
+            let entry = UtxoEntry::new(SOMPI_PER_KASPA + fee, dummy_script_public_key.clone(), 0, false);
+            let mut mtx = MutableTransaction::with_entries(Arc::new(tx), vec![entry]);
+            mtx.calculated_non_contextual_masses = Some(NonContextualMasses::new(1000, 1000));
+            mtx.calculated_fee = Some(fee);
+            mtx
+        };
+        let check = |payload: PayloadConfig, mtx: &MutableTransaction| {
+            let mut config = Config::build_default(ForkedParam::new_const(1000), false, 500_000);
+            config.payload = payload;
+            let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+            mempool.check_transaction_standard_in_isolation(mtx).and_then(|_| mempool.check_transaction_standard_in_context(mtx))
+        };
+
+        // Default policy
+        assert_eq!(Ok(()), check(PayloadConfig::default(), &new_mtx(1000, SUBNETWORK_ID_NATIVE, 1000)));
+
+        // Maximum size
+        let payload = PayloadConfig::new(100, 0.0, PayloadSubnetworkFilter::AllowAll);
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(100, SUBNETWORK_ID_NATIVE, 1000)));
+        let mtx = new_mtx(101, SUBNETWORK_ID_NATIVE, 1000);
+        assert_eq!(Err(NonStandardError::RejectPayloadSize(mtx.id(), 101, 100)), check(payload, &mtx));
+
+        // Subnetwork allow list
+        let payload = PayloadConfig::new(1000, 0.0, PayloadSubnetworkFilter::Allow(vec![other_subnetwork_id.clone()]));
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(100, other_subnetwork_id.clone(), 1000)));
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(0, SUBNETWORK_ID_NATIVE, 1000)), "the filter only applies to payloads");
+        let mtx = new_mtx(100, SUBNETWORK_ID_NATIVE, 1000);
+        assert_eq!(Err(NonStandardError::RejectPayloadSubnetwork(mtx.id(), SUBNETWORK_ID_NATIVE)), check(payload, &mtx));
+
+        // Subnetwork deny list
+        let payload = PayloadConfig::new(1000, 0.0, PayloadSubnetworkFilter::Deny(vec![SUBNETWORK_ID_NATIVE]));
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(100, other_subnetwork_id, 1000)));
+        let mtx = new_mtx(100, SUBNETWORK_ID_NATIVE, 1000);
+        assert_eq!(Err(NonStandardError::RejectPayloadSubnetwork(mtx.id(), SUBNETWORK_ID_NATIVE)), check(payload, &mtx));
+
+        // Fee per payload byte, on top of the minimum relay fee of 1000 sompi
+        let payload = PayloadConfig::new(1000, 2.0, PayloadSubnetworkFilter::AllowAll);
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(100, SUBNETWORK_ID_NATIVE, 1200)));
+        assert_eq!(Ok(()), check(payload.clone(), &new_mtx(0, SUBNETWORK_ID_NATIVE, 1000)));
+        let mtx = new_mtx(100, SUBNETWORK_ID_NATIVE, 1199);
+        assert_eq!(Err(NonStandardError::RejectInsufficientPayloadFee(mtx.id(), 100, 1199, 1200)), check(payload, &mtx));
+    }
 }
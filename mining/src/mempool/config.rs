@@ -1,4 +1,9 @@
-use kaspa_consensus_core::{config::params::ForkedParam, constants::TX_VERSION};
+use kaspa_consensus_core::{
+    config::params::ForkedParam,
+    constants::{MAX_TX_IN_SEQUENCE_NUM, TX_VERSION},
+    subnets::SubnetworkId,
+};
+use std::{fmt::Display, str::FromStr};
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: usize = 1_000_000;
 pub(crate) const DEFAULT_MEMPOOL_SIZE_LIMIT: usize = 1_000_000_000;
@@ -25,6 +30,151 @@ pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 pub(crate) const DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 
+/// DEFAULT_MAXIMUM_ANCESTOR_COUNT and DEFAULT_MAXIMUM_DESCENDANT_COUNT specify the maximum number of transactions,
+/// the transaction itself included, of a chain of dependent transactions the mempool accepts above or below any of
+/// its transactions.
+pub(crate) const DEFAULT_MAXIMUM_ANCESTOR_COUNT: usize = 100;
+pub(crate) const DEFAULT_MAXIMUM_DESCENDANT_COUNT: usize = 100;
+
+/// DEFAULT_MAXIMUM_ANCESTOR_MASS and DEFAULT_MAXIMUM_DESCENDANT_MASS specify the maximum total mass, in grams, of
+/// the same ancestor and descendant sets.
+pub(crate) const DEFAULT_MAXIMUM_ANCESTOR_MASS: u64 = 2_000_000;
+pub(crate) const DEFAULT_MAXIMUM_DESCENDANT_MASS: u64 = 2_000_000;
+
+/// DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS specifies the maximum number of mempool transactions, descendants
+/// included, a single replace by fee is allowed to evict.
+pub const DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS: usize = 100;
+
+/// MAX_RBF_OPT_IN_SEQUENCE is the highest transaction input sequence signaling, under the [`RbfMode::OptIn`]
+/// mode, that the transaction may be replaced by fee. Inputs using one of the two highest sequence numbers,
+/// [`MAX_TX_IN_SEQUENCE_NUM`] included, do not signal replaceability.
+pub const MAX_RBF_OPT_IN_SEQUENCE: u64 = MAX_TX_IN_SEQUENCE_NUM - 2;
+
+/// Node-wide replace by fee (RBF) mode, applied on top of the [`RbfPolicy`](super::tx::RbfPolicy) of every insertion
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RbfMode {
+    /// No mempool transaction can ever be replaced
+    Disabled,
+    /// Only mempool transactions with at least one input sequence not above [`MAX_RBF_OPT_IN_SEQUENCE`] can be replaced
+    OptIn,
+    /// Any mempool transaction can be replaced
+    #[default]
+    Full,
+}
+
+impl Display for RbfMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RbfMode::Disabled => write!(f, "disabled"),
+            RbfMode::OptIn => write!(f, "opt-in"),
+            RbfMode::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for RbfMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disabled" => Ok(RbfMode::Disabled),
+            "opt-in" => Ok(RbfMode::OptIn),
+            "full" => Ok(RbfMode::Full),
+            _ => Err(format!("unknown replace by fee mode '{s}', expected one of: disabled, opt-in, full")),
+        }
+    }
+}
+
+/// Replace by fee (RBF) rules applied by the mempool
+#[derive(Clone, Debug)]
+pub struct ReplaceByFeeConfig {
+    pub mode: RbfMode,
+    /// When set, the replacement transaction must pay at least the total fees of all the evicted transactions
+    /// increased by this amount of sompi
+    pub minimum_fee_increment: Option<u64>,
+    /// The fee/mass ratio (in sompi/gram) by which the replacement transaction must exceed every replaced transaction
+    pub minimum_feerate_increment: f64,
+    /// Maximum number of mempool transactions, descendants included, a single replacement can evict
+    pub maximum_evicted_transactions: usize,
+}
+
+impl ReplaceByFeeConfig {
+    pub fn new(
+        mode: RbfMode,
+        minimum_fee_increment: Option<u64>,
+        minimum_feerate_increment: f64,
+        maximum_evicted_transactions: usize,
+    ) -> Self {
+        Self { mode, minimum_fee_increment, minimum_feerate_increment, maximum_evicted_transactions }
+    }
+}
+
+impl Default for ReplaceByFeeConfig {
+    fn default() -> Self {
+        Self {
+            mode: RbfMode::default(),
+            minimum_fee_increment: None,
+            minimum_feerate_increment: 0.0,
+            maximum_evicted_transactions: DEFAULT_RBF_MAXIMUM_EVICTED_TRANSACTIONS,
+        }
+    }
+}
+
+/// DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE is the maximum size in bytes of a standard transaction payload.
+/// By default, a payload is effectively only bounded by the maximum standard transaction mass.
+pub const DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE: usize = 100_000;
+
+/// Subnetworks on which the mempool accepts transactions carrying a payload
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum PayloadSubnetworkFilter {
+    /// Payloads are accepted on every subnetwork
+    #[default]
+    AllowAll,
+    /// Payloads are accepted on the listed subnetworks only
+    Allow(Vec<SubnetworkId>),
+    /// Payloads are accepted on every subnetwork but the listed ones
+    Deny(Vec<SubnetworkId>),
+}
+
+impl PayloadSubnetworkFilter {
+    pub fn allows(&self, subnetwork_id: &SubnetworkId) -> bool {
+        match self {
+            PayloadSubnetworkFilter::AllowAll => true,
+            PayloadSubnetworkFilter::Allow(subnetworks) => subnetworks.contains(subnetwork_id),
+            PayloadSubnetworkFilter::Deny(subnetworks) => !subnetworks.contains(subnetwork_id),
+        }
+    }
+}
+
+/// Standardness rules applied by the mempool to transactions carrying a payload.
+///
+/// These rules are a node policy and are never enforced by consensus.
+#[derive(Clone, Debug)]
+pub struct PayloadConfig {
+    /// Maximum size in bytes of a transaction payload
+    pub maximum_size: usize,
+    /// Multiplier of the minimum relay feerate a transaction must pay, on top of its minimum fee,
+    /// for every byte of its payload
+    pub fee_per_byte_multiplier: f64,
+    pub subnetwork_filter: PayloadSubnetworkFilter,
+}
+
+impl PayloadConfig {
+    pub fn new(maximum_size: usize, fee_per_byte_multiplier: f64, subnetwork_filter: PayloadSubnetworkFilter) -> Self {
+        Self { maximum_size, fee_per_byte_multiplier, subnetwork_filter }
+    }
+}
+
+impl Default for PayloadConfig {
+    fn default() -> Self {
+        Self {
+            maximum_size: DEFAULT_MAXIMUM_STANDARD_PAYLOAD_SIZE,
+            fee_per_byte_multiplier: 0.0,
+            subnetwork_filter: PayloadSubnetworkFilter::default(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub maximum_transaction_count: usize,
@@ -46,6 +196,12 @@ pub struct Config {
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
     pub network_blocks_per_second: ForkedParam<u64>,
+    pub replace_by_fee: ReplaceByFeeConfig,
+    pub maximum_ancestor_count: usize,
+    pub maximum_ancestor_mass: u64,
+    pub maximum_descendant_count: usize,
+    pub maximum_descendant_mass: u64,
+    pub payload: PayloadConfig,
 }
 
 impl Config {
@@ -70,6 +226,12 @@ impl Config {
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
         network_blocks_per_second: ForkedParam<u64>,
+        replace_by_fee: ReplaceByFeeConfig,
+        maximum_ancestor_count: usize,
+        maximum_ancestor_mass: u64,
+        maximum_descendant_count: usize,
+        maximum_descendant_mass: u64,
+        payload: PayloadConfig,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -91,6 +253,12 @@ impl Config {
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
             network_blocks_per_second,
+            replace_by_fee,
+            maximum_ancestor_count,
+            maximum_ancestor_mass,
+            maximum_descendant_count,
+            maximum_descendant_mass,
+            payload,
         }
     }
 
@@ -126,6 +294,12 @@ impl Config {
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
             network_blocks_per_second: target_milliseconds_per_block.map(|v| 1000 / v),
+            replace_by_fee: ReplaceByFeeConfig::default(),
+            maximum_ancestor_count: DEFAULT_MAXIMUM_ANCESTOR_COUNT,
+            maximum_ancestor_mass: DEFAULT_MAXIMUM_ANCESTOR_MASS,
+            maximum_descendant_count: DEFAULT_MAXIMUM_DESCENDANT_COUNT,
+            maximum_descendant_mass: DEFAULT_MAXIMUM_DESCENDANT_MASS,
+            payload: PayloadConfig::default(),
         }
     }
 
@@ -136,6 +310,16 @@ impl Config {
         self
     }
 
+    pub fn apply_replace_by_fee(mut self, replace_by_fee: ReplaceByFeeConfig) -> Self {
+        self.replace_by_fee = replace_by_fee;
+        self
+    }
+
+    pub fn apply_payload(mut self, payload: PayloadConfig) -> Self {
+        self.payload = payload;
+        self
+    }
+
     /// Returns the minimum standard fee/mass ratio currently required by the mempool
     pub(crate) fn minimum_feerate(&self) -> f64 {
         // The parameter minimum_relay_transaction_fee is in sompi/kg units so divide by 1000 to get sompi/gram
@@ -1,7 +1,9 @@
 use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     model::{
+        chain_stats::TransactionChainStats,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        short_id::{ShortIdSalt, ShortTransactionId},
         tx_query::TransactionQuery,
     },
     MiningCounters,
@@ -14,7 +16,7 @@ use self::{
 };
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
-    tx::{MutableTransaction, TransactionId},
+    tx::{MutableTransaction, Transaction, TransactionId},
 };
 use kaspa_core::time::Stopwatch;
 use std::sync::Arc;
@@ -72,6 +74,10 @@ impl Mempool {
         transaction.map(|x| x.mtx.clone())
     }
 
+    pub(crate) fn get_transaction_chain_stats(&self, transaction_id: &TransactionId) -> Option<TransactionChainStats> {
+        self.transaction_pool.get_chain_stats(transaction_id)
+    }
+
     pub(crate) fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         (query.include_transaction_pool() && self.transaction_pool.has(transaction_id))
             || (query.include_orphan_pool() && self.orphan_pool.has(transaction_id))
@@ -83,6 +89,26 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    /// Returns the transactions of both pools matching the provided short ids, salted with `salt`, in the same order.
+    ///
+    /// A short id matching no transaction or matching more than one transaction is resolved to `None`.
+    pub(crate) fn get_transactions_by_short_ids(
+        &self,
+        salt: ShortIdSalt,
+        short_ids: &[ShortTransactionId],
+    ) -> Vec<Option<Arc<Transaction>>> {
+        let transaction_index = self.transaction_pool.short_id_index(salt);
+        let orphan_index = self.orphan_pool.short_id_index(salt);
+        short_ids
+            .iter()
+            .map(|short_id| match (transaction_index.get(short_id), orphan_index.get(short_id)) {
+                ([id], []) => self.transaction_pool.get(id).map(|tx| tx.mtx.tx.clone()),
+                ([], [id]) => self.orphan_pool.get(id).map(|tx| tx.mtx.tx.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
         let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
         let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
@@ -178,6 +204,9 @@ pub mod tx {
     }
 
     /// Replace by Fee (RBF) policy
+    ///
+    /// The policy is further restricted by the node-wide [`RbfMode`](super::config::RbfMode) which may disable replacements
+    /// altogether or limit them to transactions signaling replaceability.
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum RbfPolicy {
         /// ### RBF is forbidden
@@ -1,5 +1,5 @@
 use crate::{block_template::selector::ALPHA, mempool::model::tx::MempoolTransaction};
-use kaspa_consensus_core::{mass::ContextualMasses, tx::Transaction};
+use kaspa_consensus_core::tx::Transaction;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -81,8 +81,7 @@ impl From<&MempoolTransaction> for FeerateTransactionKey {
         //       single one-dimension value (making it easier to select transactions for block templates).
         // Future mempool improvements are expected to refine this behavior and use the multi-dimension values
         // in order to optimize and increase block space usage.
-        let mass = ContextualMasses::new(tx.mtx.tx.mass())
-            .max(tx.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"));
+        let mass = tx.mass();
         let fee = tx.mtx.calculated_fee.expect("fee is expected to be populated");
         Self::new(fee, mass, tx.mtx.tx.clone())
     }
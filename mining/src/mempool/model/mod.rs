@@ -3,6 +3,7 @@ pub(crate) mod frontier;
 pub(crate) mod map;
 pub(crate) mod orphan_pool;
 pub(crate) mod pool;
+pub(crate) mod short_id_index;
 pub(crate) mod transactions_pool;
 pub(crate) mod tx;
 pub(crate) mod utxo_set;
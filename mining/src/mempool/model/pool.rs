@@ -1,10 +1,11 @@
 use crate::{
     mempool::{
-        model::{map::MempoolTransactionCollection, tx::MempoolTransaction},
+        model::{map::MempoolTransactionCollection, short_id_index::ShortIdIndex, tx::MempoolTransaction},
         tx::Priority,
     },
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        short_id::ShortIdSalt,
         topological_index::TopologicalIndex,
         TransactionIdSet,
     },
@@ -19,6 +20,11 @@ pub(crate) trait Pool {
 
     fn chained(&self) -> &TransactionsEdges;
 
+    /// Builds the index of the pool transactions by their short ids under `salt`
+    fn short_id_index(&self, salt: ShortIdSalt) -> ShortIdIndex {
+        ShortIdIndex::new(salt, self.all().keys())
+    }
+
     fn has(&self, transaction_id: &TransactionId) -> bool {
         self.all().contains_key(transaction_id)
     }
@@ -60,6 +66,27 @@ pub(crate) trait Pool {
         parents
     }
 
+    /// Returns the ids of all transactions `transaction` is directly and indirectly chained to
+    /// and existing in the pool.
+    ///
+    /// The transactions are traversed in BFS mode. The returned order is not guaranteed to be
+    /// topological.
+    fn get_ancestor_ids_in_pool(&self, transaction: &MutableTransaction) -> Vec<TransactionId> {
+        let mut visited = TransactionIdSet::new();
+        let mut ancestors = vec![];
+        let mut queue = VecDeque::new();
+        queue.push_back(transaction);
+        while let Some(transaction) = queue.pop_front() {
+            for parent_id in self.get_parent_transaction_ids_in_pool(transaction) {
+                if visited.insert(parent_id) {
+                    ancestors.push(parent_id);
+                    queue.push_back(&self.get(&parent_id).unwrap().mtx);
+                }
+            }
+        }
+        ancestors
+    }
+
     /// Returns the ids of all transactions being directly and indirectly chained to `transaction_id`
     /// and existing in the pool.
     ///
@@ -0,0 +1,46 @@
+use crate::model::short_id::{ShortIdSalt, ShortTransactionId};
+use kaspa_consensus_core::tx::TransactionId;
+use std::collections::HashMap;
+
+/// Index of the transactions of a pool by their short ids under a single salt.
+///
+/// Since the salt of every compact block is randomized by its sender, the index is built out of a single
+/// scan of the pool per compact block and is dropped once all its short ids are resolved.
+pub(crate) struct ShortIdIndex {
+    /// Maps each short id to the transactions sharing it, which is almost always a single one
+    index: HashMap<ShortTransactionId, Vec<TransactionId>>,
+}
+
+impl ShortIdIndex {
+    pub(crate) fn new<'a>(salt: ShortIdSalt, transaction_ids: impl ExactSizeIterator<Item = &'a TransactionId>) -> Self {
+        let mut index: HashMap<ShortTransactionId, Vec<TransactionId>> = HashMap::with_capacity(transaction_ids.len());
+        transaction_ids.for_each(|id| index.entry(salt.short_id(id)).or_default().push(*id));
+        Self { index }
+    }
+
+    /// Returns the transactions matching `short_id`
+    pub(crate) fn get(&self, short_id: &ShortTransactionId) -> &[TransactionId] {
+        self.index.get(short_id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_id_index() {
+        let (salt, other_salt) = (ShortIdSalt::new(1, 2), ShortIdSalt::new(3, 4));
+        let ids = (0..4).map(TransactionId::from_u64_word).collect::<Vec<_>>();
+
+        let index = ShortIdIndex::new(salt, ids[..2].iter());
+        assert_eq!([ids[0]], index.get(&salt.short_id(&ids[0])));
+        assert_eq!([ids[1]], index.get(&salt.short_id(&ids[1])));
+        assert!(index.get(&salt.short_id(&ids[2])).is_empty());
+
+        // Short ids are only resolved under the salt the index was built with
+        let index = ShortIdIndex::new(other_salt, ids[1..].iter());
+        assert_eq!([ids[3]], index.get(&other_salt.short_id(&ids[3])));
+        assert!(index.get(&salt.short_id(&ids[3])).is_empty());
+    }
+}
@@ -6,12 +6,12 @@ use crate::{
         model::{
             map::MempoolTransactionCollection,
             pool::{Pool, TransactionsEdges},
-            tx::{DoubleSpend, MempoolTransaction},
+            tx::{mempool_mass, DoubleSpend, MempoolTransaction},
             utxo_set::MempoolUtxoSet,
         },
         tx::Priority,
     },
-    model::{topological_index::TopologicalIndex, TransactionIdSet},
+    model::{chain_stats::TransactionChainStats, topological_index::TopologicalIndex, TransactionIdSet},
     Policy,
 };
 use kaspa_consensus_core::{
@@ -277,6 +277,73 @@ impl TransactionsPool {
         Err(RuleError::RejectMempoolIsFull)
     }
 
+    /// Returns the ancestor and descendant statistics of a transaction in the pool
+    pub(crate) fn get_chain_stats(&self, transaction_id: &TransactionId) -> Option<TransactionChainStats> {
+        let transaction = self.get(transaction_id)?;
+        let mass = transaction.mass();
+        let no_exclusion = TransactionIdSet::new();
+        let (ancestor_count, ancestor_mass) = self.get_count_and_mass(&self.get_ancestor_ids_in_pool(&transaction.mtx), &no_exclusion);
+        let (descendant_count, descendant_mass) =
+            self.get_count_and_mass(&self.get_redeemer_ids_in_pool(transaction_id), &no_exclusion);
+        Some(TransactionChainStats::new(ancestor_count + 1, ancestor_mass + mass, descendant_count + 1, descendant_mass + mass))
+    }
+
+    /// Checks that adding `transaction` to the pool keeps it and each of its ancestors within the ancestor
+    /// and descendant limits of the mempool config.
+    ///
+    /// The transactions in `excluded` are ignored since they are about to be evicted, notably by a replace by fee.
+    ///
+    /// NOTE: the traversal of the descendants of every ancestor is bounded by the very limits being enforced here
+    pub(crate) fn check_chain_limits(&self, transaction: &MutableTransaction, excluded: &TransactionIdSet) -> RuleResult<()> {
+        let transaction_id = transaction.id();
+        let transaction_mass = mempool_mass(transaction);
+
+        let ancestors = self.get_ancestor_ids_in_pool(transaction);
+        let (ancestor_count, ancestor_mass) = self.get_count_and_mass(&ancestors, excluded);
+        let (ancestor_count, ancestor_mass) = (ancestor_count + 1, ancestor_mass.saturating_add(transaction_mass));
+        if ancestor_count > self.config.maximum_ancestor_count {
+            return Err(RuleError::RejectTooManyAncestors(transaction_id, ancestor_count, self.config.maximum_ancestor_count));
+        }
+        if ancestor_mass > self.config.maximum_ancestor_mass {
+            return Err(RuleError::RejectAncestorMassTooHigh(transaction_id, ancestor_mass, self.config.maximum_ancestor_mass));
+        }
+
+        for ancestor in ancestors.iter().filter(|x| !excluded.contains(x)).map(|x| self.get(x).unwrap()) {
+            let (descendant_count, descendant_mass) =
+                self.get_count_and_mass(&self.get_redeemer_ids_in_pool(&ancestor.id()), excluded);
+            // Both the ancestor and the added transaction are accounted for
+            let descendant_count = descendant_count + 2;
+            let descendant_mass = descendant_mass.saturating_add(ancestor.mass()).saturating_add(transaction_mass);
+            if descendant_count > self.config.maximum_descendant_count {
+                return Err(RuleError::RejectTooManyDescendants(
+                    transaction_id,
+                    ancestor.id(),
+                    descendant_count,
+                    self.config.maximum_descendant_count,
+                ));
+            }
+            if descendant_mass > self.config.maximum_descendant_mass {
+                return Err(RuleError::RejectDescendantMassTooHigh(
+                    transaction_id,
+                    ancestor.id(),
+                    descendant_mass,
+                    self.config.maximum_descendant_mass,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number and the total mass of the transactions of the pool listed in `transaction_ids`,
+    /// ignoring those in `excluded`
+    fn get_count_and_mass(&self, transaction_ids: &[TransactionId], excluded: &TransactionIdSet) -> (usize, u64) {
+        transaction_ids
+            .iter()
+            .filter(|x| !excluded.contains(x))
+            .filter_map(|x| self.get(x))
+            .fold((0, 0), |(count, mass), transaction| (count + 1, mass + transaction.mass()))
+    }
+
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.estimated_size
     }
@@ -1,5 +1,11 @@
-use crate::mempool::tx::{Priority, RbfPolicy};
-use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
+use crate::{
+    mempool::tx::{Priority, RbfPolicy},
+    model::tx_insert::TransactionReplacement,
+};
+use kaspa_consensus_core::{
+    mass::ContextualMasses,
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint},
+};
 use kaspa_mining_errors::mempool::RuleError;
 use std::{
     fmt::{Display, Formatter},
@@ -25,6 +31,17 @@ impl MempoolTransaction {
     pub(crate) fn feerate(&self) -> f64 {
         self.mtx.calculated_feerate().unwrap()
     }
+
+    pub(crate) fn mass(&self) -> u64 {
+        mempool_mass(&self.mtx)
+    }
+}
+
+/// Returns the single-dimension mass of a transaction as used by the mempool, ie. the max over all its masses.
+///
+/// NOTE: This is a mempool simplification with no consensus meaning, see [`ContextualMasses::max`].
+pub(crate) fn mempool_mass(mtx: &MutableTransaction) -> u64 {
+    ContextualMasses::new(mtx.tx.mass()).max(mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"))
 }
 
 impl RbfPolicy {
@@ -68,7 +85,7 @@ pub(crate) struct TransactionPreValidation {
 
 #[derive(Default)]
 pub(crate) struct TransactionPostValidation {
-    pub removed: Option<Arc<Transaction>>,
+    pub replacement: Option<TransactionReplacement>,
     pub accepted: Option<Arc<Transaction>>,
 }
 
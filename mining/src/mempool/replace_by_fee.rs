@@ -1,11 +1,17 @@
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::tx::{DoubleSpend, MempoolTransaction, TxRemovalReason},
-    tx::RbfPolicy,
-    Mempool,
+use crate::{
+    mempool::{
+        config::{RbfMode, MAX_RBF_OPT_IN_SEQUENCE},
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{DoubleSpend, MempoolTransaction, TxRemovalReason},
+        },
+        tx::RbfPolicy,
+        Mempool,
+    },
+    model::{tx_insert::TransactionReplacement, TransactionIdSet},
 };
-use kaspa_consensus_core::tx::{MutableTransaction, Transaction};
-use std::sync::Arc;
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
 
 impl Mempool {
     /// Returns the replace by fee (RBF) constraint fee/mass threshold for an incoming transaction and a policy.
@@ -18,7 +24,7 @@ impl Mempool {
         transaction: &MutableTransaction,
         rbf_policy: RbfPolicy,
     ) -> RuleResult<Option<f64>> {
-        match rbf_policy {
+        match self.get_effective_rbf_policy(rbf_policy)? {
             RbfPolicy::Forbidden => {
                 // When RBF is forbidden, fails early on any double spend
                 self.transaction_pool.check_double_spends(transaction)?;
@@ -26,17 +32,19 @@ impl Mempool {
             }
 
             RbfPolicy::Allowed => {
-                // When RBF is allowed, never fails since both insertion and replacement are possible
+                // When RBF is allowed, never fails since both insertion and replacement are possible,
+                // unless some double spending transaction cannot be replaced under the node RBF mode
                 let double_spends = self.transaction_pool.get_double_spend_transaction_ids(transaction);
                 if double_spends.is_empty() {
                     Ok(None)
                 } else {
                     let mut feerate_threshold = 0f64;
                     for double_spend in double_spends {
+                        self.validate_double_spend_replaceability(&double_spend)?;
                         // We take the max over all double spends as the required threshold
                         feerate_threshold = feerate_threshold.max(self.get_double_spend_feerate(&double_spend)?);
                     }
-                    Ok(Some(feerate_threshold))
+                    Ok(Some(feerate_threshold + self.config.replace_by_fee.minimum_feerate_increment))
                 }
             }
 
@@ -46,8 +54,9 @@ impl Mempool {
                 match double_spends.len() {
                     0 => Err(RuleError::RejectRbfNoDoubleSpend),
                     1 => {
+                        self.validate_double_spend_replaceability(&double_spends[0])?;
                         let feerate_threshold = self.get_double_spend_feerate(&double_spends[0])?;
-                        Ok(Some(feerate_threshold))
+                        Ok(Some(feerate_threshold + self.config.replace_by_fee.minimum_feerate_increment))
                     }
                     _ => Err(RuleError::RejectRbfTooManyDoubleSpendingTransactions),
                 }
@@ -61,13 +70,13 @@ impl Mempool {
     ///
     /// On success, `transaction` is guaranteed to embed no double spend with the mempool.
     ///
-    /// On success with the [`RbfPolicy::Mandatory`] policy, some removed transaction is always returned.
+    /// On success with the [`RbfPolicy::Mandatory`] policy, some replacement is always returned.
     pub(super) fn execute_replace_by_fee(
         &mut self,
         transaction: &MutableTransaction,
         rbf_policy: RbfPolicy,
-    ) -> RuleResult<Option<Arc<Transaction>>> {
-        match rbf_policy {
+    ) -> RuleResult<Option<TransactionReplacement>> {
+        match self.get_effective_rbf_policy(rbf_policy)? {
             RbfPolicy::Forbidden => {
                 self.transaction_pool.check_double_spends(transaction)?;
                 Ok(None)
@@ -77,23 +86,7 @@ impl Mempool {
                 let double_spends = self.transaction_pool.get_double_spend_transaction_ids(transaction);
                 match double_spends.is_empty() {
                     true => Ok(None),
-                    false => {
-                        let removed = self.validate_double_spending_transaction(transaction, &double_spends[0])?.mtx.tx.clone();
-                        for double_spend in double_spends.iter().skip(1) {
-                            // Validate the feerate threshold is passed for all double spends
-                            self.validate_double_spending_transaction(transaction, double_spend)?;
-                        }
-                        // We apply consequences such as removal only after we fully validate against all double spends
-                        for double_spend in double_spends {
-                            self.remove_transaction(
-                                &double_spend.owner_id,
-                                true,
-                                TxRemovalReason::ReplacedByFee,
-                                format!("by {}", transaction.id()).as_str(),
-                            )?;
-                        }
-                        Ok(Some(removed))
-                    }
+                    false => Ok(Some(self.replace_double_spending_transactions(transaction, &double_spends)?)),
                 }
             }
 
@@ -101,22 +94,100 @@ impl Mempool {
                 let double_spends = self.transaction_pool.get_double_spend_transaction_ids(transaction);
                 match double_spends.len() {
                     0 => Err(RuleError::RejectRbfNoDoubleSpend),
-                    1 => {
-                        let removed = self.validate_double_spending_transaction(transaction, &double_spends[0])?.mtx.tx.clone();
-                        self.remove_transaction(
-                            &double_spends[0].owner_id,
-                            true,
-                            TxRemovalReason::ReplacedByFee,
-                            format!("by {}", transaction.id()).as_str(),
-                        )?;
-                        Ok(Some(removed))
-                    }
+                    1 => Ok(Some(self.replace_double_spending_transactions(transaction, &double_spends)?)),
                     _ => Err(RuleError::RejectRbfTooManyDoubleSpendingTransactions),
                 }
             }
         }
     }
 
+    /// Returns the RBF policy actually applicable to an insertion under the node-wide [`RbfMode`].
+    ///
+    /// When RBF is disabled, an allowed replacement degrades into a forbidden one and a mandatory replacement fails.
+    fn get_effective_rbf_policy(&self, rbf_policy: RbfPolicy) -> RuleResult<RbfPolicy> {
+        match (self.config.replace_by_fee.mode, rbf_policy) {
+            (RbfMode::Disabled, RbfPolicy::Allowed) => Ok(RbfPolicy::Forbidden),
+            (RbfMode::Disabled, RbfPolicy::Mandatory) => Err(RuleError::RejectRbfDisabled),
+            _ => Ok(rbf_policy),
+        }
+    }
+
+    /// Validates `transaction` against all `double_spends` and, on success only, removes all the
+    /// double spending mempool transactions and their descendants.
+    fn replace_double_spending_transactions(
+        &mut self,
+        transaction: &MutableTransaction,
+        double_spends: &[DoubleSpend],
+    ) -> RuleResult<TransactionReplacement> {
+        // Validate the feerate threshold is passed for all double spends
+        let mut replaced = Vec::with_capacity(double_spends.len());
+        for double_spend in double_spends.iter() {
+            replaced.push(self.validate_double_spending_transaction(transaction, double_spend)?.mtx.tx.clone());
+        }
+        let evicted = self.get_replace_by_fee_evictions(double_spends);
+        self.validate_replace_by_fee_evictions(transaction, &evicted)?;
+        self.transaction_pool.check_chain_limits(transaction, &evicted.iter().copied().collect())?;
+
+        // We apply consequences such as removal only after we fully validate against all double spends
+        for double_spend in double_spends {
+            self.remove_transaction(
+                &double_spend.owner_id,
+                true,
+                TxRemovalReason::ReplacedByFee,
+                format!("by {}", transaction.id()).as_str(),
+            )?;
+        }
+        Ok(TransactionReplacement::new(transaction.id(), replaced, evicted))
+    }
+
+    /// Returns the ids of the mempool transactions owning `double_spends` followed by the ids of all their descendants
+    fn get_replace_by_fee_evictions(&self, double_spends: &[DoubleSpend]) -> Vec<TransactionId> {
+        let mut visited = TransactionIdSet::new();
+        let mut evicted: Vec<TransactionId> = double_spends.iter().map(|x| x.owner_id).filter(|x| visited.insert(*x)).collect();
+        for double_spend in double_spends {
+            let descendants = self.transaction_pool.get_redeemer_ids_in_pool(&double_spend.owner_id);
+            evicted.extend(descendants.into_iter().filter(|x| visited.insert(*x)));
+        }
+        evicted
+    }
+
+    /// Validates the eviction count and the absolute fee rules of a replacement
+    fn validate_replace_by_fee_evictions(&self, transaction: &MutableTransaction, evicted: &[TransactionId]) -> RuleResult<()> {
+        let config = &self.config.replace_by_fee;
+        if evicted.len() > config.maximum_evicted_transactions {
+            return Err(RuleError::RejectRbfTooManyEvictions(evicted.len(), config.maximum_evicted_transactions));
+        }
+        if let Some(minimum_fee_increment) = config.minimum_fee_increment {
+            let evicted_fees: u64 = evicted
+                .iter()
+                .filter_map(|transaction_id| self.transaction_pool.get(transaction_id))
+                .map(|x| x.mtx.calculated_fee.unwrap_or_default())
+                .sum();
+            let required_fee = evicted_fees.saturating_add(minimum_fee_increment);
+            // Getting a transaction with no calculated fee is unexpected at this stage but nonetheless we fail gracefully
+            let fee = transaction.calculated_fee.unwrap_or_default();
+            if fee < required_fee {
+                return Err(RuleError::RejectRbfInsufficientFee(fee, required_fee));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if the node RBF mode forbids the replacement of the mempool transaction owning `double_spend`
+    fn validate_double_spend_replaceability(&self, double_spend: &DoubleSpend) -> RuleResult<()> {
+        match self.config.replace_by_fee.mode {
+            RbfMode::Disabled => Err(RuleError::RejectRbfDisabled),
+            RbfMode::OptIn => {
+                let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
+                match signals_replaceability(&owner.mtx.tx) {
+                    true => Ok(()),
+                    false => Err(RuleError::RejectRbfNotSignaled(double_spend.owner_id)),
+                }
+            }
+            RbfMode::Full => Ok(()),
+        }
+    }
+
     fn get_double_spend_feerate(&self, double_spend: &DoubleSpend) -> RuleResult<f64> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         match owner.mtx.calculated_feerate() {
@@ -132,14 +203,15 @@ impl Mempool {
         transaction: &MutableTransaction,
         double_spend: &DoubleSpend,
     ) -> RuleResult<&'a MempoolTransaction> {
+        self.validate_double_spend_replaceability(double_spend)?;
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         if let (Some(transaction_feerate), Some(double_spend_feerate)) =
             (transaction.calculated_feerate(), owner.mtx.calculated_feerate())
         {
-            if transaction_feerate > double_spend_feerate {
+            if transaction_feerate > double_spend_feerate + self.config.replace_by_fee.minimum_feerate_increment {
                 return Ok(owner);
             } else {
-                return Err(double_spend.into());
+                return Err(RuleError::RejectRbfInsufficientFeerateIncrement(double_spend.owner_id));
             }
         }
         // Getting here is unexpected since both txs should be populated with
@@ -147,3 +219,8 @@ impl Mempool {
         Err(double_spend.into())
     }
 }
+
+/// Returns whether some input of `transaction` signals replaceability under the [`RbfMode::OptIn`] mode
+fn signals_replaceability(transaction: &Transaction) -> bool {
+    transaction.inputs.iter().any(|input| input.sequence <= MAX_RBF_OPT_IN_SEQUENCE)
+}
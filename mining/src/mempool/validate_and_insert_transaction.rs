@@ -1,13 +1,16 @@
 use std::sync::atomic::Ordering;
 
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        },
+        tx::{Orphan, Priority, RbfPolicy},
+        Mempool,
     },
-    tx::{Orphan, Priority, RbfPolicy},
-    Mempool,
+    model::TransactionIdSet,
 };
 use kaspa_consensus_core::{
     api::ConsensusApi,
@@ -73,7 +76,12 @@ impl Mempool {
         self.validate_transaction_in_context(&transaction)?;
 
         // Check double spends and try to remove them if the RBF policy requires it
-        let removed_transaction = self.execute_replace_by_fee(&transaction, rbf_policy)?;
+        let replacement = self.execute_replace_by_fee(&transaction, rbf_policy)?;
+
+        // Check the ancestor and descendant limits, unless a replacement already did so prior to any eviction
+        if replacement.is_none() {
+            self.transaction_pool.check_chain_limits(&transaction, &TransactionIdSet::new())?;
+        }
 
         //
         // Note: there exists a case below where `limit_transaction_count` returns an error signaling that
@@ -126,7 +134,7 @@ impl Mempool {
             .mtx
             .tx
             .clone();
-        Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
+        Ok(TransactionPostValidation { replacement, accepted: Some(accepted_transaction) })
     }
 
     /// Validates that the transaction wasn't already accepted into the DAG
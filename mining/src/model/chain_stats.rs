@@ -0,0 +1,20 @@
+/// Statistics about the chains of dependent transactions a mempool transaction belongs to
+///
+/// Both the ancestor and the descendant sets include the transaction itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionChainStats {
+    /// Number of in-mempool ancestors
+    pub ancestor_count: usize,
+    /// Total mass of the in-mempool ancestors
+    pub ancestor_mass: u64,
+    /// Number of in-mempool descendants
+    pub descendant_count: usize,
+    /// Total mass of the in-mempool descendants
+    pub descendant_mass: u64,
+}
+
+impl TransactionChainStats {
+    pub fn new(ancestor_count: usize, ancestor_mass: u64, descendant_count: usize, descendant_mass: u64) -> Self {
+        Self { ancestor_count, ancestor_mass, descendant_count, descendant_mass }
+    }
+}
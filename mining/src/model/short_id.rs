@@ -0,0 +1,91 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_hashes::Hash;
+
+/// A short transaction id, used by compact block relay in order to reference transactions
+/// which are expected to already reside in the mempool of the receiving peer
+pub type ShortTransactionId = u64;
+
+/// The salt short ids are computed with, keying a SipHash-2-4 of the transaction id (as in BIP152).
+///
+/// Transaction ids are hashes so short ids are well distributed, but collisions are still possible. Salting makes
+/// them unpredictable, so that transactions cannot be crafted to collide on all peers at once. Consumers must still
+/// treat ambiguous short ids as missing, and compact block reconstruction must verify the merkle root of the
+/// reconstructed block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShortIdSalt {
+    k0: u64,
+    k1: u64,
+}
+
+impl ShortIdSalt {
+    pub fn new(k0: u64, k1: u64) -> Self {
+        Self { k0, k1 }
+    }
+
+    /// The salt of the short ids of a compact block, keyed by the block hash and a nonce drawn at random by the sending peer for every compact block
+    pub fn from_block(block_hash: Hash, nonce: u64) -> Self {
+        let words = block_hash.to_le_u64();
+        Self::new(words[0] ^ nonce, words[1])
+    }
+
+    pub fn short_id(&self, transaction_id: &TransactionId) -> ShortTransactionId {
+        siphash_2_4(self.k0, self.k1, &transaction_id.as_bytes())
+    }
+}
+
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+fn siphash_2_4(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    let mut v = [k0 ^ 0x736f6d6570736575, k1 ^ 0x646f72616e646f6d, k0 ^ 0x6c7967656e657261, k1 ^ 0x7465646279746573];
+    let mut compress = |m: u64| {
+        v[3] ^= m;
+        sip_round(&mut v);
+        sip_round(&mut v);
+        v[0] ^= m;
+    };
+    let chunks = data.chunks_exact(8);
+    let tail = chunks.remainder();
+    chunks.for_each(|chunk| compress(u64::from_le_bytes(chunk.try_into().unwrap())));
+    compress(tail.iter().enumerate().fold((data.len() as u64) << 56, |last, (i, &byte)| last | (byte as u64) << (8 * i)));
+
+    v[2] ^= 0xff;
+    (0..4).for_each(|_| sip_round(&mut v));
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siphash_reference_vectors() {
+        // Vectors of the SipHash reference implementation, with the key 00..0f and the messages 00..(len - 1)
+        let (k0, k1) = (0x0706050403020100, 0x0f0e0d0c0b0a0908);
+        let message = (0..64u8).collect::<Vec<_>>();
+        assert_eq!(0x726fdb47dd0e0e31, siphash_2_4(k0, k1, &message[..0]));
+        assert_eq!(0x74f839c593dc67fd, siphash_2_4(k0, k1, &message[..1]));
+        assert_eq!(0xa129ca6149be45e5, siphash_2_4(k0, k1, &message[..15]));
+        assert_eq!(0x3f2acc7f57c29bdb, siphash_2_4(k0, k1, &message[..16]));
+        assert_eq!(0x958a324ceb064572, siphash_2_4(k0, k1, &message[..63]));
+    }
+
+    #[test]
+    fn test_short_id_salts() {
+        let id = TransactionId::from_u64_word(7);
+        let (block_hash, other_block_hash) = (Hash::from_le_u64([3, 5, 0, 0]), Hash::from_le_u64([4, 5, 0, 0]));
+        assert_eq!(ShortIdSalt::from_block(block_hash, 1).short_id(&id), ShortIdSalt::from_block(block_hash, 1).short_id(&id));
+        assert_ne!(ShortIdSalt::from_block(block_hash, 1).short_id(&id), ShortIdSalt::from_block(block_hash, 2).short_id(&id));
+        assert_ne!(ShortIdSalt::from_block(block_hash, 1).short_id(&id), ShortIdSalt::from_block(other_block_hash, 1).short_id(&id));
+    }
+}
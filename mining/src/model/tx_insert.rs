@@ -1,14 +1,57 @@
-use kaspa_consensus_core::tx::Transaction;
+use crate::errors::MiningManagerResult;
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
 use std::sync::Arc;
 
+/// A replace by fee (RBF) performed by the mempool while inserting a transaction
+#[derive(Debug, Clone)]
+pub struct TransactionReplacement {
+    /// Id of the inserted transaction
+    pub transaction_id: TransactionId,
+    /// The mempool transactions double spent by the inserted transaction
+    pub replaced: Vec<Arc<Transaction>>,
+    /// Ids of all the transactions evicted from the mempool, ie. the replaced transactions followed by their descendants
+    pub evicted: Vec<TransactionId>,
+}
+
+impl TransactionReplacement {
+    pub fn new(transaction_id: TransactionId, replaced: Vec<Arc<Transaction>>, evicted: Vec<TransactionId>) -> Self {
+        Self { transaction_id, replaced, evicted }
+    }
+}
+
 #[derive(Debug)]
 pub struct TransactionInsertion {
-    pub removed: Option<Arc<Transaction>>,
+    /// The replacement performed by the inserted transaction
+    pub replacement: Option<TransactionReplacement>,
+    /// The replacements performed by the transactions unorphaned following the insertion
+    pub unorphaned_replacements: Vec<TransactionReplacement>,
     pub accepted: Vec<Arc<Transaction>>,
 }
 
 impl TransactionInsertion {
-    pub fn new(removed: Option<Arc<Transaction>>, accepted: Vec<Arc<Transaction>>) -> Self {
-        Self { removed, accepted }
+    pub fn new(
+        replacement: Option<TransactionReplacement>,
+        unorphaned_replacements: Vec<TransactionReplacement>,
+        accepted: Vec<Arc<Transaction>>,
+    ) -> Self {
+        Self { replacement, unorphaned_replacements, accepted }
+    }
+
+    /// Returns all the replacements performed by the insertion, unorphaned transactions included
+    pub fn replacements(&self) -> impl Iterator<Item = &TransactionReplacement> {
+        self.replacement.iter().chain(self.unorphaned_replacements.iter())
+    }
+}
+
+pub struct TransactionBatchInsertion {
+    /// The insertion result of every processed transaction, unorphaned transactions included
+    pub results: Vec<MiningManagerResult<Arc<Transaction>>>,
+    /// The replacements performed by the processed transactions, unorphaned transactions included
+    pub replacements: Vec<TransactionReplacement>,
+}
+
+impl TransactionBatchInsertion {
+    pub fn new(results: Vec<MiningManagerResult<Arc<Transaction>>>, replacements: Vec<TransactionReplacement>) -> Self {
+        Self { results, replacements }
     }
 }
@@ -0,0 +1,32 @@
+use kaspa_addresses::Address;
+use kaspa_utils::networking::NetAddress;
+
+/// Default port of the stratum listener (matches the common stratum bridge default)
+pub const DEFAULT_STRATUM_PORT: u16 = 5555;
+
+/// Default share difficulty assigned to stratum miners
+pub const DEFAULT_SHARE_DIFFICULTY: u64 = 4096;
+
+/// Interval in seconds between worker hashrate reports
+pub const HASHRATE_REPORT_INTERVAL: u64 = 60;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Interface and port the stratum server listens on
+    pub listen_address: NetAddress,
+
+    /// Address receiving the coinbase rewards of blocks mined through the stratum server
+    pub pay_address: Address,
+
+    /// Fixed share difficulty assigned to every connected miner
+    pub share_difficulty: u64,
+
+    /// Extra data appended to the coinbase payload of stratum templates
+    pub extra_data: Vec<u8>,
+}
+
+impl Config {
+    pub fn new(listen_address: NetAddress, pay_address: Address, share_difficulty: u64, extra_data: Vec<u8>) -> Self {
+        Self { listen_address, pay_address, share_difficulty, extra_data }
+    }
+}
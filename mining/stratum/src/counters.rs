@@ -0,0 +1,151 @@
+use crate::difficulty::estimate_hashrate;
+use parking_lot::RwLock;
+use std::{collections::HashMap, time::Instant};
+
+/// Per-worker share statistics
+#[derive(Debug, Clone)]
+pub struct WorkerStats {
+    pub worker: String,
+    pub first_seen: Instant,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub blocks_found: u64,
+    /// Accepted shares since the last hashrate sample
+    window_shares: u64,
+    window_start: Instant,
+}
+
+impl WorkerStats {
+    fn new(worker: String, now: Instant) -> Self {
+        Self { worker, first_seen: now, accepted_shares: 0, rejected_shares: 0, blocks_found: 0, window_shares: 0, window_start: now }
+    }
+}
+
+/// A point-in-time hashrate report for a single worker
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerHashrate {
+    pub worker: String,
+    pub hashrate: f64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub blocks_found: u64,
+}
+
+/// Tracks share statistics per connection of an authorized worker. Worker names are chosen by the miners, so
+/// statistics are keyed by connection and removed on disconnect to keep the memory bounded by the connections
+#[derive(Default)]
+pub struct WorkerStatsTracker {
+    workers: RwLock<HashMap<u64, WorkerStats>>,
+}
+
+impl WorkerStatsTracker {
+    /// Starts tracking the worker authorized over connection `id`, resetting the statistics if it was already tracked
+    pub fn register(&self, id: u64, worker: String) {
+        self.workers.write().insert(id, WorkerStats::new(worker, Instant::now()));
+    }
+
+    pub fn remove(&self, id: u64) {
+        self.workers.write().remove(&id);
+    }
+
+    pub fn record_accepted(&self, id: u64) {
+        if let Some(stats) = self.workers.write().get_mut(&id) {
+            stats.accepted_shares += 1;
+            stats.window_shares += 1;
+        }
+    }
+
+    pub fn record_block(&self, id: u64) {
+        if let Some(stats) = self.workers.write().get_mut(&id) {
+            stats.blocks_found += 1;
+        }
+    }
+
+    pub fn record_rejected(&self, id: u64) {
+        if let Some(stats) = self.workers.write().get_mut(&id) {
+            stats.rejected_shares += 1;
+        }
+    }
+
+    /// Computes the hashrate of every worker over the window since the previous sample and starts a new window
+    pub fn sample_hashrates(&self, share_difficulty: u64) -> Vec<WorkerHashrate> {
+        let now = Instant::now();
+        let mut workers = self.workers.write();
+        let mut hashrates = workers
+            .values_mut()
+            .map(|stats| {
+                let elapsed = now.duration_since(stats.window_start).as_secs_f64();
+                let hashrate = estimate_hashrate(stats.window_shares, share_difficulty, elapsed);
+                stats.window_shares = 0;
+                stats.window_start = now;
+                WorkerHashrate {
+                    worker: stats.worker.clone(),
+                    hashrate,
+                    accepted_shares: stats.accepted_shares,
+                    rejected_shares: stats.rejected_shares,
+                    blocks_found: stats.blocks_found,
+                }
+            })
+            .collect::<Vec<_>>();
+        hashrates.sort_by(|a, b| a.worker.cmp(&b.worker));
+        hashrates
+    }
+}
+
+/// Formats a hashrate with the largest fitting unit
+pub fn format_hashrate(hashrate: f64) -> String {
+    const UNITS: [&str; 7] = ["H/s", "KH/s", "MH/s", "GH/s", "TH/s", "PH/s", "EH/s"];
+    let mut value = hashrate;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_hashrate() {
+        assert_eq!(format_hashrate(0.0), "0.00 H/s");
+        assert_eq!(format_hashrate(999.0), "999.00 H/s");
+        assert_eq!(format_hashrate(1_500_000.0), "1.50 MH/s");
+        assert_eq!(format_hashrate(2.0e21), "2000.00 EH/s");
+    }
+
+    #[test]
+    fn test_worker_tracker() {
+        let tracker = WorkerStatsTracker::default();
+        tracker.register(0, "rig1".to_owned());
+        tracker.register(1, "rig2".to_owned());
+        tracker.record_accepted(0);
+        tracker.record_accepted(0);
+        tracker.record_block(0);
+        tracker.record_rejected(1);
+        // Shares of untracked connections are ignored
+        tracker.record_accepted(2);
+
+        let hashrates = tracker.sample_hashrates(1);
+        assert_eq!(hashrates.len(), 2);
+        assert_eq!(hashrates[0].worker, "rig1");
+        assert_eq!(hashrates[0].accepted_shares, 2);
+        assert_eq!(hashrates[0].blocks_found, 1);
+        assert_eq!(hashrates[1].worker, "rig2");
+        assert_eq!(hashrates[1].rejected_shares, 1);
+        assert_eq!(hashrates[1].hashrate, 0.0);
+
+        // A new sample window starts after each sample
+        let hashrates = tracker.sample_hashrates(1);
+        assert_eq!(hashrates[0].hashrate, 0.0);
+        assert_eq!(hashrates[0].accepted_shares, 2);
+
+        // Statistics are dropped along with the connection
+        tracker.remove(0);
+        let hashrates = tracker.sample_hashrates(1);
+        assert_eq!(hashrates.len(), 1);
+        assert_eq!(hashrates[0].worker, "rig2");
+    }
+}
@@ -0,0 +1,42 @@
+use kaspa_math::Uint256;
+
+/// The number of hashes represented by a single share of difficulty 1 (2^32).
+const HASHES_PER_UNIT_DIFFICULTY: f64 = 4_294_967_296.0;
+
+/// Converts a stratum share difficulty into the matching share target.
+///
+/// Difficulty 1 corresponds to a target of `2^224 - 1`, meaning a share of difficulty `d`
+/// is expected to require `d * 2^32` hashes. This is the convention used by the existing
+/// Kaspa stratum bridges, so pool-side accounting remains compatible.
+pub fn share_target(difficulty: u64) -> Uint256 {
+    (Uint256::MAX >> 32) / difficulty.max(1)
+}
+
+/// Estimates the hashrate (in hashes per second) backing a number of accepted shares at
+/// a given difficulty over the elapsed period.
+pub fn estimate_hashrate(accepted_shares: u64, difficulty: u64, elapsed_secs: f64) -> f64 {
+    if elapsed_secs <= 0.0 {
+        return 0.0;
+    }
+    accepted_shares as f64 * difficulty as f64 * HASHES_PER_UNIT_DIFFICULTY / elapsed_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_target() {
+        assert_eq!(share_target(1), Uint256::MAX >> 32);
+        assert_eq!(share_target(0), share_target(1), "difficulty zero should be treated as difficulty one");
+        assert!(share_target(2) < share_target(1));
+        assert_eq!(share_target(1 << 16), Uint256::MAX >> 48);
+    }
+
+    #[test]
+    fn test_estimate_hashrate() {
+        assert_eq!(estimate_hashrate(10, 1, 0.0), 0.0);
+        assert_eq!(estimate_hashrate(1, 1, 1.0), HASHES_PER_UNIT_DIFFICULTY);
+        assert_eq!(estimate_hashrate(60, 4, 60.0), 4.0 * HASHES_PER_UNIT_DIFFICULTY);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::protocol::ErrorCode;
+use kaspa_mining::errors::MiningManagerError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StratumError {
+    #[error("malformed request: {0}")]
+    MalformedRequest(#[from] serde_json::Error),
+
+    #[error("invalid {0} params")]
+    InvalidParams(&'static str),
+
+    #[error("unknown method {0}")]
+    UnknownMethod(String),
+
+    #[error("worker is not authorized")]
+    Unauthorized,
+
+    #[error("miner is not subscribed")]
+    NotSubscribed,
+
+    #[error("job {0} not found")]
+    JobNotFound(String),
+
+    #[error("duplicate share")]
+    DuplicateShare,
+
+    #[error("share does not meet the share difficulty")]
+    LowDifficultyShare,
+
+    #[error("block template error: {0}")]
+    BlockTemplate(#[from] MiningManagerError),
+}
+
+impl StratumError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            StratumError::Unauthorized => ErrorCode::Unauthorized,
+            StratumError::NotSubscribed => ErrorCode::NotSubscribed,
+            StratumError::JobNotFound(_) => ErrorCode::JobNotFound,
+            StratumError::DuplicateShare => ErrorCode::DuplicateShare,
+            StratumError::LowDifficultyShare => ErrorCode::LowDifficultyShare,
+            _ => ErrorCode::Unknown,
+        }
+    }
+}
+
+pub type StratumResult<T> = std::result::Result<T, StratumError>;
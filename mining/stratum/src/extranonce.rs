@@ -0,0 +1,49 @@
+/// Hands out the per-connection nonce prefixes, so that no two connected miners share a prefix.
+/// Prefixes of closed connections are recycled.
+#[derive(Debug, Default)]
+pub struct ExtranoncePool {
+    /// The lowest prefix never allocated so far
+    next: u32,
+    /// Released prefixes available for reuse
+    released: Vec<u16>,
+}
+
+impl ExtranoncePool {
+    /// Allocates a free prefix, or returns `None` if all prefixes are in use
+    pub fn allocate(&mut self) -> Option<u16> {
+        if let Some(extranonce) = self.released.pop() {
+            return Some(extranonce);
+        }
+        let extranonce = u16::try_from(self.next).ok()?;
+        self.next += 1;
+        Some(extranonce)
+    }
+
+    pub fn release(&mut self, extranonce: u16) {
+        debug_assert!((extranonce as u32) < self.next && !self.released.contains(&extranonce));
+        self.released.push(extranonce);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extranonce_pool() {
+        let mut pool = ExtranoncePool::default();
+        assert_eq!(pool.allocate(), Some(0));
+        assert_eq!(pool.allocate(), Some(1));
+        pool.release(0);
+        assert_eq!(pool.allocate(), Some(0));
+
+        // Exhaust all prefixes
+        (2..=u16::MAX).for_each(|i| assert_eq!(pool.allocate(), Some(i)));
+        assert_eq!(pool.allocate(), None);
+
+        // A released prefix becomes available again
+        pool.release(42);
+        assert_eq!(pool.allocate(), Some(42));
+        assert_eq!(pool.allocate(), None);
+    }
+}
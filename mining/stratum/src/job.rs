@@ -0,0 +1,168 @@
+use kaspa_consensus_core::{block::Block, hashing, header::Header, tx::Transaction};
+use kaspa_math::Uint256;
+use kaspa_pow::State;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+
+/// Number of recent jobs for which shares are still accepted. At 10 BPS templates are
+/// replaced frequently, so miners are allowed to lag behind by a few seconds of jobs.
+pub const MAX_ACTIVE_JOBS: usize = 64;
+
+/// The outcome of checking a submitted nonce against a job
+#[derive(Debug)]
+pub enum ShareValidation {
+    /// The nonce solves the block. The finalized block is returned for submission.
+    Block(Block),
+    /// The nonce satisfies the share target but not the block target
+    Share,
+    /// The nonce does not satisfy the share target
+    LowDifficulty,
+    /// The nonce was already submitted for this job
+    Duplicate,
+}
+
+/// A mining job derived from a single block template
+pub struct Job {
+    pub id: u64,
+    header: Header,
+    transactions: Arc<Vec<Transaction>>,
+    state: State,
+    pre_pow_hash_words: [u64; 4],
+    submitted_nonces: Mutex<HashSet<u64>>,
+}
+
+impl Job {
+    pub fn new(id: u64, header: Header, transactions: Vec<Transaction>) -> Self {
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&header, 0, 0);
+        let state = State::new(&header);
+        Self {
+            id,
+            header,
+            transactions: Arc::new(transactions),
+            state,
+            pre_pow_hash_words: pre_pow_hash.to_le_u64(),
+            submitted_nonces: Default::default(),
+        }
+    }
+
+    pub fn pre_pow_hash_words(&self) -> [u64; 4] {
+        self.pre_pow_hash_words
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.header.timestamp
+    }
+
+    pub fn daa_score(&self) -> u64 {
+        self.header.daa_score
+    }
+
+    /// Validates a submitted nonce against the share target and the block target
+    pub fn validate(&self, nonce: u64, share_target: &Uint256) -> ShareValidation {
+        if !self.submitted_nonces.lock().insert(nonce) {
+            return ShareValidation::Duplicate;
+        }
+        let (is_block, pow) = self.state.check_pow(nonce);
+        if is_block {
+            let mut header = self.header.clone();
+            header.nonce = nonce;
+            header.finalize();
+            ShareValidation::Block(Block::from_arcs(Arc::new(header), self.transactions.clone()))
+        } else if pow <= *share_target {
+            ShareValidation::Share
+        } else {
+            ShareValidation::LowDifficulty
+        }
+    }
+}
+
+/// Keeps the most recent jobs so that shares on slightly outdated jobs can still be credited
+#[derive(Default)]
+pub struct JobStore {
+    next_id: u64,
+    jobs: VecDeque<Arc<Job>>,
+}
+
+impl JobStore {
+    /// Registers a new job built from the provided template. Returns `None` if the template
+    /// is identical to the latest job.
+    pub fn push(&mut self, header: Header, transactions: Vec<Transaction>) -> Option<Arc<Job>> {
+        if let Some(latest) = self.jobs.back() {
+            if latest.header.hash == header.hash {
+                return None;
+            }
+        }
+        self.next_id += 1;
+        let job = Arc::new(Job::new(self.next_id, header, transactions));
+        self.jobs.push_back(job.clone());
+        while self.jobs.len() > MAX_ACTIVE_JOBS {
+            self.jobs.pop_front();
+        }
+        Some(job)
+    }
+
+    pub fn get(&self, id: u64) -> Option<Arc<Job>> {
+        // Jobs are stored in ascending id order with no gaps
+        let oldest = self.jobs.front()?.id;
+        if id < oldest {
+            return None;
+        }
+        self.jobs.get((id - oldest) as usize).cloned()
+    }
+
+    pub fn latest(&self) -> Option<Arc<Job>> {
+        self.jobs.back().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{header::Header, BlueWorkType};
+    use kaspa_hashes::Hash;
+
+    fn header(nonce_seed: u64) -> Header {
+        Header::new_finalized(
+            1,
+            vec![vec![Hash::from_u64_word(1)]],
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            nonce_seed,
+            0x207fffff,
+            0,
+            0,
+            BlueWorkType::from_u64(0),
+            0,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_job_store_retention() {
+        let mut store = JobStore::default();
+        for i in 0..(MAX_ACTIVE_JOBS as u64 + 10) {
+            assert!(store.push(header(i), vec![]).is_some());
+        }
+        assert!(store.get(1).is_none(), "evicted jobs should not be found");
+        assert!(store.get(11).is_some());
+        assert_eq!(store.get(11).unwrap().id, 11);
+        assert_eq!(store.latest().unwrap().id, MAX_ACTIVE_JOBS as u64 + 10);
+        assert!(store.get(MAX_ACTIVE_JOBS as u64 + 11).is_none());
+
+        // Pushing the same template again is a no-op
+        assert!(store.push(header(MAX_ACTIVE_JOBS as u64 + 9), vec![]).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_share() {
+        let job = Job::new(1, header(0), vec![]);
+        // Every nonce satisfies the maximal share target
+        assert!(matches!(job.validate(7, &Uint256::MAX), ShareValidation::Block(_) | ShareValidation::Share));
+        assert!(matches!(job.validate(7, &Uint256::MAX), ShareValidation::Duplicate));
+        assert!(matches!(job.validate(8, &Uint256::ZERO), ShareValidation::Block(_) | ShareValidation::LowDifficulty));
+    }
+}
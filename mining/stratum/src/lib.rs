@@ -0,0 +1,8 @@
+pub mod config;
+pub mod counters;
+pub mod difficulty;
+pub mod error;
+pub mod extranonce;
+pub mod job;
+pub mod protocol;
+pub mod service;
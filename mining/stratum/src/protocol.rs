@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const METHOD_SUBSCRIBE: &str = "mining.subscribe";
+pub const METHOD_EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const METHOD_AUTHORIZE: &str = "mining.authorize";
+pub const METHOD_SUBMIT: &str = "mining.submit";
+pub const METHOD_NOTIFY: &str = "mining.notify";
+pub const METHOD_SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const METHOD_SET_EXTRANONCE: &str = "mining.set_extranonce";
+
+/// The stratum protocol version reported to miners on subscription
+pub const PROTOCOL_VERSION: &str = "EthereumStratum/1.0.0";
+
+/// Size in bytes of the per-connection nonce prefix assigned to each miner
+pub const EXTRANONCE_SIZE: usize = 2;
+
+/// Stratum error codes, as used by the common stratum implementations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unknown = 20,
+    JobNotFound = 21,
+    DuplicateShare = 22,
+    LowDifficultyShare = 23,
+    Unauthorized = 24,
+    NotSubscribed = 25,
+}
+
+/// A JSON-RPC request sent by a miner
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+/// A JSON-RPC response to a miner request
+#[derive(Debug, Clone, Serialize)]
+pub struct Response {
+    pub id: Value,
+    pub result: Value,
+    pub error: Value,
+}
+
+impl Response {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { id, result, error: Value::Null }
+    }
+
+    pub fn err(id: Value, code: ErrorCode, message: &str) -> Self {
+        Self { id, result: Value::Null, error: json!([code as i32, message, Value::Null]) }
+    }
+}
+
+/// A server-initiated JSON-RPC notification
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub id: Value,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl Notification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { id: Value::Null, method, params }
+    }
+
+    /// Builds a `mining.notify` notification in the array job format understood by Kaspa miners:
+    /// the pre-PoW hash split into four little-endian `u64` words, followed by the header timestamp
+    pub fn job(job_id: &str, pre_pow_words: [u64; 4], timestamp: u64) -> Self {
+        Self::new(METHOD_NOTIFY, json!([job_id, pre_pow_words, timestamp]))
+    }
+
+    pub fn set_difficulty(difficulty: u64) -> Self {
+        Self::new(METHOD_SET_DIFFICULTY, json!([difficulty]))
+    }
+
+    pub fn set_extranonce(extranonce: u16) -> Self {
+        Self::new(METHOD_SET_EXTRANONCE, json!([extranonce_hex(extranonce), 8 - EXTRANONCE_SIZE]))
+    }
+}
+
+pub fn extranonce_hex(extranonce: u16) -> String {
+    format!("{:0width$x}", extranonce, width = EXTRANONCE_SIZE * 2)
+}
+
+/// Parses a hex nonce as submitted by a miner. Miners working with an extranonce only submit the
+/// remaining nonce bytes, in which case the connection extranonce is prepended.
+pub fn parse_nonce(nonce: &str, extranonce: u16) -> Option<u64> {
+    let nonce = nonce.strip_prefix("0x").unwrap_or(nonce);
+    if nonce.is_empty() || nonce.len() > 16 {
+        return None;
+    }
+    let value = u64::from_str_radix(nonce, 16).ok()?;
+    if nonce.len() <= (8 - EXTRANONCE_SIZE) * 2 {
+        Some(((extranonce as u64) << ((8 - EXTRANONCE_SIZE) * 8)) | value)
+    } else {
+        Some(value)
+    }
+}
+
+/// Splits an `address.worker` authorization login into its worker name part.
+/// Logins without a worker suffix are reported under the login itself.
+pub fn worker_name(login: &str) -> String {
+    match login.rsplit_once('.') {
+        Some((_, worker)) if !worker.is_empty() => worker.to_owned(),
+        _ => login.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nonce() {
+        assert_eq!(parse_nonce("0x0000000000000001", 0xabcd), Some(1));
+        assert_eq!(parse_nonce("ffffffffffffffff", 0xabcd), Some(u64::MAX));
+        assert_eq!(parse_nonce("000000000001", 0xabcd), Some(0xabcd_0000_0000_0001));
+        assert_eq!(parse_nonce("", 0), None);
+        assert_eq!(parse_nonce("0x", 0), None);
+        assert_eq!(parse_nonce("zz", 0), None);
+        assert_eq!(parse_nonce("00000000000000000", 0), None);
+    }
+
+    #[test]
+    fn test_worker_name() {
+        assert_eq!(worker_name("kaspa:qr0lr4ml9fn3chekrqmjdkergxl93l4wrk3dankcgvjq776s9wn9jkdskewva.rig1"), "rig1");
+        assert_eq!(worker_name("rig2"), "rig2");
+        assert_eq!(worker_name("rig3."), "rig3.");
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let notification = Notification::job("1", [1, 2, 3, 4], 5);
+        assert_eq!(
+            serde_json::to_string(&notification).unwrap(),
+            r#"{"id":null,"method":"mining.notify","params":["1",[1,2,3,4],5]}"#
+        );
+        let response = Response::err(json!(7), ErrorCode::JobNotFound, "job not found");
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"id":7,"result":null,"error":[21,"job not found",null]}"#);
+        let request: Request = serde_json::from_str(r#"{"id":1,"method":"mining.subscribe","params":["miner/1.0"]}"#).unwrap();
+        assert_eq!(request.method, METHOD_SUBSCRIBE);
+        assert_eq!(request.params.len(), 1);
+    }
+}
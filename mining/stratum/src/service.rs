@@ -0,0 +1,414 @@
+use crate::{
+    config::{Config, HASHRATE_REPORT_INTERVAL},
+    counters::{format_hashrate, WorkerStatsTracker},
+    difficulty::share_target,
+    error::{StratumError, StratumResult},
+    extranonce::ExtranoncePool,
+    job::{Job, JobStore, ShareValidation},
+    protocol::{self, Notification, Request, Response},
+};
+use kaspa_consensus_core::{block::Block, coinbase::MinerData};
+use kaspa_consensus_notify::{
+    connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification, notifier::ConsensusNotifier,
+};
+use kaspa_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use kaspa_math::Uint256;
+use kaspa_notify::{
+    connection::ChannelType,
+    listener::ListenerLifespan,
+    scope::NewBlockTemplateScope,
+    subscription::{MutationPolicies, UtxosChangedMutationPolicy},
+};
+use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+};
+use triggered::Listener;
+
+/// Maximum size of a single miner message. Stratum messages are small JSON lines so
+/// anything larger is considered abusive and the connection is dropped.
+const MAX_MESSAGE_SIZE: u64 = 16 * 1024;
+
+/// A connected stratum miner
+struct Client {
+    id: u64,
+    address: SocketAddr,
+    extranonce: u16,
+    subscribed: AtomicBool,
+    worker: RwLock<Option<String>>,
+    outgoing: UnboundedSender<String>,
+}
+
+impl Client {
+    fn new(id: u64, address: SocketAddr, extranonce: u16, outgoing: UnboundedSender<String>) -> Self {
+        Self { id, address, extranonce, subscribed: AtomicBool::new(false), worker: RwLock::new(None), outgoing }
+    }
+
+    fn send<T: Serialize>(&self, message: &T) {
+        match serde_json::to_string(message) {
+            Ok(mut line) => {
+                line.push('\n');
+                // A send error means the connection is closing, so the message can be dropped
+                let _ = self.outgoing.send(line);
+            }
+            Err(err) => warn!("[{}] failed serializing a message for {}: {}", StratumService::IDENT, self.address, err),
+        }
+    }
+
+    fn send_job(&self, job: &Job) {
+        self.send(&Notification::job(&job.id.to_string(), job.pre_pow_hash_words(), job.timestamp()));
+    }
+
+    fn is_ready(&self) -> bool {
+        self.subscribed.load(Ordering::Relaxed) && self.worker.read().is_some()
+    }
+}
+
+/// A Stratum v1 server serving block templates of the node as mining jobs and
+/// submitting found blocks directly to consensus
+pub struct StratumService {
+    config: Config,
+    flow_context: Arc<FlowContext>,
+    tick_service: Arc<TickService>,
+    consensus_notify_channel: Channel<ConsensusNotification>,
+    miner_data: MinerData,
+    share_target: Uint256,
+    jobs: Mutex<JobStore>,
+    clients: Mutex<HashMap<u64, Arc<Client>>>,
+    next_client_id: AtomicU64,
+    extranonces: Mutex<ExtranoncePool>,
+    workers: WorkerStatsTracker,
+    shutdown: SingleTrigger,
+}
+
+impl StratumService {
+    pub const IDENT: &'static str = "stratum-service";
+
+    pub fn new(
+        config: Config,
+        flow_context: Arc<FlowContext>,
+        consensus_notifier: &Arc<ConsensusNotifier>,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        // Listen to new block template notifications in order to refresh the mining job
+        let consensus_notify_channel = Channel::<ConsensusNotification>::default();
+        let listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(Self::IDENT, consensus_notify_channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Static(MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard)),
+        );
+        consensus_notifier
+            .try_start_notify(listener_id, NewBlockTemplateScope::default().into())
+            .expect("the subscription always succeeds");
+
+        let script_public_key = kaspa_txscript::pay_to_address_script(&config.pay_address);
+        let miner_data = MinerData::new(script_public_key, config.extra_data.clone());
+        let share_target = share_target(config.share_difficulty);
+
+        Self {
+            config,
+            flow_context,
+            tick_service,
+            consensus_notify_channel,
+            miner_data,
+            share_target,
+            jobs: Default::default(),
+            clients: Default::default(),
+            next_client_id: AtomicU64::new(0),
+            extranonces: Default::default(),
+            workers: Default::default(),
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    async fn accept_connections(self: Arc<Self>, listener: TcpListener, shutdown: Listener) {
+        loop {
+            tokio::select! {
+                _ = shutdown.clone() => break,
+                result = listener.accept() => match result {
+                    Ok((stream, address)) => {
+                        tokio::spawn(self.clone().handle_connection(stream, address, shutdown.clone()));
+                    }
+                    Err(err) => warn!("[{}] failed accepting a connection: {}", Self::IDENT, err),
+                }
+            }
+        }
+    }
+
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream, address: SocketAddr, shutdown: Listener) {
+        // Each connection gets a distinct nonce prefix so concurrent miners do not overlap in their search space
+        let Some(extranonce) = self.extranonces.lock().allocate() else {
+            warn!("[{}] rejecting miner {}: all nonce prefixes are in use", Self::IDENT, address);
+            return;
+        };
+        let (reader, mut writer) = stream.into_split();
+        let (outgoing, mut outgoing_receiver) = unbounded_channel::<String>();
+        let id = self.next_client_id.fetch_add(1, Ordering::Relaxed);
+        let client = Arc::new(Client::new(id, address, extranonce, outgoing));
+        self.clients.lock().insert(id, client.clone());
+        debug!("[{}] miner connected from {}", Self::IDENT, address);
+
+        let writer_task = tokio::spawn(async move {
+            while let Some(line) = outgoing_receiver.recv().await {
+                if writer.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = tokio::select! {
+                _ = shutdown.clone() => break,
+                read = (&mut reader).take(MAX_MESSAGE_SIZE).read_line(&mut line) => read,
+            };
+            match read {
+                Ok(0) => break,
+                Ok(_) if !line.ends_with('\n') => {
+                    debug!("[{}] dropping miner {}: message exceeds {} bytes", Self::IDENT, address, MAX_MESSAGE_SIZE);
+                    break;
+                }
+                Ok(_) => {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    if let Err(err) = self.handle_message(&client, &line).await {
+                        debug!("[{}] dropping miner {}: {}", Self::IDENT, address, err);
+                        break;
+                    }
+                }
+                Err(err) => {
+                    debug!("[{}] connection error with miner {}: {}", Self::IDENT, address, err);
+                    break;
+                }
+            }
+        }
+
+        self.clients.lock().remove(&id);
+        self.workers.remove(id);
+        self.extranonces.lock().release(extranonce);
+        writer_task.abort();
+        debug!("[{}] miner {} disconnected", Self::IDENT, address);
+    }
+
+    /// Handles a single miner message. Returns an error only if the connection should be dropped.
+    async fn handle_message(&self, client: &Client, message: &str) -> StratumResult<()> {
+        let request: Request = serde_json::from_str(message)?;
+        trace!("[{}] {} from {}", Self::IDENT, request.method, client.address);
+        let id = request.id.clone();
+        let response = match self.handle_request(client, request).await {
+            Ok(result) => Response::ok(id, result),
+            Err(err) => Response::err(id, err.code(), &err.to_string()),
+        };
+        client.send(&response);
+        Ok(())
+    }
+
+    async fn handle_request(&self, client: &Client, request: Request) -> StratumResult<Value> {
+        match request.method.as_str() {
+            protocol::METHOD_SUBSCRIBE => {
+                client.subscribed.store(true, Ordering::Relaxed);
+                client.send(&Notification::set_extranonce(client.extranonce));
+                Ok(json!([true, protocol::PROTOCOL_VERSION]))
+            }
+            protocol::METHOD_EXTRANONCE_SUBSCRIBE => Ok(json!(true)),
+            protocol::METHOD_AUTHORIZE => {
+                let login =
+                    request.params.first().and_then(Value::as_str).ok_or(StratumError::InvalidParams(protocol::METHOD_AUTHORIZE))?;
+                let worker = protocol::worker_name(login);
+                self.workers.register(client.id, worker.clone());
+                *client.worker.write() = Some(worker.clone());
+                info!("[{}] worker {} authorized from {}", Self::IDENT, worker, client.address);
+
+                client.send(&Notification::set_difficulty(self.config.share_difficulty));
+                let latest_job = self.jobs.lock().latest();
+                if let Some(job) = latest_job {
+                    client.send_job(&job);
+                }
+                Ok(json!(true))
+            }
+            protocol::METHOD_SUBMIT => self.handle_submit(client, &request.params).await,
+            _ => Err(StratumError::UnknownMethod(request.method)),
+        }
+    }
+
+    async fn handle_submit(&self, client: &Client, params: &[Value]) -> StratumResult<Value> {
+        // The nonce prefix is only known to the miner after subscribing, so earlier shares cannot be trusted
+        if !client.subscribed.load(Ordering::Relaxed) {
+            return Err(StratumError::NotSubscribed);
+        }
+        let worker = client.worker.read().clone().ok_or(StratumError::Unauthorized)?;
+        let (Some(job_id), Some(nonce)) = (params.get(1).and_then(Value::as_str), params.get(2).and_then(Value::as_str)) else {
+            return Err(StratumError::InvalidParams(protocol::METHOD_SUBMIT));
+        };
+        let nonce = protocol::parse_nonce(nonce, client.extranonce).ok_or(StratumError::InvalidParams(protocol::METHOD_SUBMIT))?;
+
+        let job = job_id.parse::<u64>().ok().and_then(|id| self.jobs.lock().get(id));
+        let Some(job) = job else {
+            self.workers.record_rejected(client.id);
+            return Err(StratumError::JobNotFound(job_id.to_owned()));
+        };
+
+        match job.validate(nonce, &self.share_target) {
+            ShareValidation::Block(block) => {
+                self.workers.record_accepted(client.id);
+                self.submit_block(client.id, &worker, block).await;
+                Ok(json!(true))
+            }
+            ShareValidation::Share => {
+                self.workers.record_accepted(client.id);
+                Ok(json!(true))
+            }
+            ShareValidation::LowDifficulty => {
+                self.workers.record_rejected(client.id);
+                Err(StratumError::LowDifficultyShare)
+            }
+            ShareValidation::Duplicate => {
+                self.workers.record_rejected(client.id);
+                Err(StratumError::DuplicateShare)
+            }
+        }
+    }
+
+    async fn submit_block(&self, client_id: u64, worker: &str, block: Block) {
+        let hash = block.hash();
+        let session = self.flow_context.consensus().unguarded_session();
+        match self.flow_context.submit_rpc_block(&session, block).await {
+            Ok(_) => {
+                self.workers.record_block(client_id);
+                info!("[{}] worker {} found block {}", Self::IDENT, worker, hash);
+            }
+            Err(err) => {
+                warn!("[{}] block {} found by worker {} was rejected: {}", Self::IDENT, hash, worker, err);
+            }
+        }
+    }
+
+    async fn refresh_job(&self) -> StratumResult<()> {
+        let session = self.flow_context.consensus().unguarded_session();
+        if !self.flow_context.should_mine(&session).await {
+            trace!("[{}] node is not synced, skipping job refresh", Self::IDENT);
+            return Ok(());
+        }
+        let template = self.flow_context.mining_manager().clone().get_block_template(&session, self.miner_data.clone()).await?;
+        let job = self.jobs.lock().push(template.block.header, template.block.transactions);
+        if let Some(job) = job {
+            let clients = self.clients.lock().values().filter(|client| client.is_ready()).cloned().collect::<Vec<_>>();
+            trace!("[{}] broadcasting job {} (DAA score {}) to {} miners", Self::IDENT, job.id, job.daa_score(), clients.len());
+            clients.iter().for_each(|client| client.send_job(&job));
+        }
+        Ok(())
+    }
+
+    async fn refresh_jobs(self: Arc<Self>, shutdown: Listener) {
+        if let Err(err) = self.refresh_job().await {
+            debug!("[{}] failed building the initial job: {}", Self::IDENT, err);
+        }
+        loop {
+            tokio::select! {
+                _ = shutdown.clone() => break,
+                notification = self.consensus_notify_channel.recv() => match notification {
+                    Ok(ConsensusNotification::NewBlockTemplate(_)) => {
+                        // Notifications received meanwhile are all covered by a single fresh template
+                        while self.consensus_notify_channel.try_recv().is_ok() {}
+                        if let Err(err) = self.refresh_job().await {
+                            warn!("[{}] failed refreshing the mining job: {}", Self::IDENT, err);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            }
+        }
+    }
+
+    async fn report_hashrates(self: Arc<Self>) {
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(Duration::from_secs(HASHRATE_REPORT_INTERVAL)).await {
+                break;
+            }
+            let hashrates = self.workers.sample_hashrates(self.config.share_difficulty);
+            let total = hashrates.iter().map(|worker| worker.hashrate).sum::<f64>();
+            if total > 0.0 {
+                info!("[{}] {} connected miners, total hashrate {}", Self::IDENT, self.clients.lock().len(), format_hashrate(total));
+                for worker in hashrates.iter().filter(|worker| worker.hashrate > 0.0) {
+                    info!(
+                        "[{}] worker {}: {} ({} accepted, {} rejected shares, {} blocks)",
+                        Self::IDENT,
+                        worker.worker,
+                        format_hashrate(worker.hashrate),
+                        worker.accepted_shares,
+                        worker.rejected_shares,
+                        worker.blocks_found
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl AsyncService for StratumService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let address: SocketAddr = self.config.listen_address.into();
+            let listener = TcpListener::bind(address)
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("failed binding the stratum listener to {}: {}", address, err)))?;
+            info!("Stratum server listening on {}", address);
+
+            let accept_task = tokio::spawn(self.clone().accept_connections(listener, shutdown_signal.clone()));
+            let jobs_task = tokio::spawn(self.clone().refresh_jobs(shutdown_signal.clone()));
+            let report_task = tokio::spawn(self.clone().report_hashrates());
+
+            // Keep the server running until a service shutdown signal is received
+            shutdown_signal.await;
+            self.consensus_notify_channel.close();
+            let _ = tokio::join!(accept_task, jobs_task, report_task);
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
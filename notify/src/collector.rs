@@ -128,7 +128,9 @@ mod tests {
         notifier::test_helpers::NotifyMock,
         subscription::{
             context::SubscriptionContext,
-            single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+            single::{
+                OverallSubscription, TransactionReplacedSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription,
+            },
         },
     };
     use derive_more::Display;
@@ -171,6 +173,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn apply_transaction_replaced_subscription(
+            &self,
+            _: &TransactionReplacedSubscription,
+            _: &SubscriptionContext,
+        ) -> Option<Self> {
+            unimplemented!()
+        }
+
         fn event_type(&self) -> EventType {
             unimplemented!()
         }
@@ -2,6 +2,7 @@ use super::events::EventType;
 use borsh::{BorshDeserialize, BorshSerialize};
 use derive_more::Display;
 use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::TransactionId;
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -45,6 +46,8 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    TransactionReplaced,
+    ChainReorg,
 }
 }
 
@@ -266,3 +269,69 @@ impl Deserializer for NewBlockTemplateScope {
         Ok(Self {})
     }
 }
+
+/// Scope of TransactionReplaced notifications, restricted to replacements involving any of `transaction_ids`
+/// either as the inserted, a replaced or an evicted transaction. An empty set means all replacements.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct TransactionReplacedScope {
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+impl std::fmt::Display for TransactionReplacedScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let transaction_ids = match self.transaction_ids.len() {
+            0 => "all".to_string(),
+            1 => format!("{}", self.transaction_ids[0]),
+            n => format!("{} transactions", n),
+        };
+        write!(f, "TransactionReplacedScope ({})", transaction_ids)
+    }
+}
+
+impl PartialEq for TransactionReplacedScope {
+    fn eq(&self, other: &Self) -> bool {
+        self.transaction_ids.len() == other.transaction_ids.len()
+            && self.transaction_ids.iter().all(|x| other.transaction_ids.contains(x))
+    }
+}
+
+impl Eq for TransactionReplacedScope {}
+
+impl TransactionReplacedScope {
+    pub fn new(transaction_ids: Vec<TransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+impl Serializer for TransactionReplacedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<TransactionId>, &self.transaction_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TransactionReplacedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<TransactionId>, reader)?;
+        Ok(Self { transaction_ids })
+    }
+}
+
+#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ChainReorgScope {}
+
+impl Serializer for ChainReorgScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ChainReorgScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
     address::{error::Result, tracker::Counters},
     events::EventType,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{Scope, TransactionReplacedScope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{context::SubscriptionContext, Command, Compounded, Mutation, Subscription},
 };
 use itertools::Itertools;
 use kaspa_addresses::{Address, Prefix};
+use kaspa_consensus_core::tx::TransactionId;
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct OverallSubscription {
@@ -149,6 +151,107 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct TransactionReplacedSubscription {
+    all: usize,
+    transaction_ids: BTreeMap<TransactionId, usize>,
+}
+
+impl TransactionReplacedSubscription {
+    /// Registers `transaction_ids`, returning those which were not registered yet
+    fn register(&mut self, transaction_ids: Vec<TransactionId>) -> Vec<TransactionId> {
+        transaction_ids
+            .into_iter()
+            .filter(|id| {
+                let count = self.transaction_ids.entry(*id).or_default();
+                *count += 1;
+                *count == 1
+            })
+            .collect()
+    }
+
+    /// Unregisters `transaction_ids`, returning those which are no longer registered
+    fn unregister(&mut self, transaction_ids: Vec<TransactionId>) -> Vec<TransactionId> {
+        transaction_ids
+            .into_iter()
+            .filter(|id| match self.transaction_ids.get_mut(id) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    false
+                }
+                Some(_) => {
+                    self.transaction_ids.remove(id);
+                    true
+                }
+                None => false,
+            })
+            .collect()
+    }
+}
+
+impl Compounded for TransactionReplacedSubscription {
+    fn compound(&mut self, mutation: Mutation, _context: &SubscriptionContext) -> Option<Mutation> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        if let Scope::TransactionReplaced(scope) = mutation.scope {
+            match mutation.command {
+                Command::Start => {
+                    if scope.transaction_ids.is_empty() {
+                        // Add All
+                        self.all += 1;
+                        if self.all == 1 {
+                            return Some(Mutation::new(Command::Start, TransactionReplacedScope::default().into()));
+                        }
+                    } else {
+                        // Add(A)
+                        let added = self.register(scope.transaction_ids);
+                        if !added.is_empty() && self.all == 0 {
+                            return Some(Mutation::new(Command::Start, TransactionReplacedScope::new(added).into()));
+                        }
+                    }
+                }
+                Command::Stop => {
+                    if !scope.transaction_ids.is_empty() {
+                        // Remove(R)
+                        let removed = self.unregister(scope.transaction_ids);
+                        if !removed.is_empty() && self.all == 0 {
+                            return Some(Mutation::new(Command::Stop, TransactionReplacedScope::new(removed).into()));
+                        }
+                    } else {
+                        // Remove All
+                        assert!(self.all > 0);
+                        self.all -= 1;
+                        if self.all == 0 {
+                            let transaction_ids = self.transaction_ids.keys().copied().collect_vec();
+                            if !transaction_ids.is_empty() {
+                                return Some(Mutation::new(Command::Start, TransactionReplacedScope::new(transaction_ids).into()));
+                            } else {
+                                return Some(Mutation::new(Command::Stop, TransactionReplacedScope::default().into()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Subscription for TransactionReplacedSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::TransactionReplaced
+    }
+
+    fn active(&self) -> bool {
+        self.all > 0 || !self.transaction_ids.is_empty()
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        let transaction_ids = if self.all > 0 { vec![] } else { self.transaction_ids.keys().copied().collect_vec() };
+        Scope::TransactionReplaced(TransactionReplacedScope::new(transaction_ids))
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 pub struct UtxosChangedSubscription {
     all: usize,
@@ -413,4 +516,58 @@ mod tests {
         // let result = std::panic::catch_unwind(AssertUnwindSafe(|| state.compound(remove_0(), &test.context)));
         // assert!(result.is_err(), "{}: trying to remove an address when its counter is zero must panic", test.name);
     }
+
+    #[test]
+    #[allow(clippy::redundant_clone)]
+    fn test_transaction_replaced_compounding() {
+        let t_stock = [TransactionId::from_u64_word(1), TransactionId::from_u64_word(2)];
+
+        let m = |command: Command, indexes: &[usize]| -> Mutation {
+            Mutation {
+                command,
+                scope: Scope::TransactionReplaced(TransactionReplacedScope::new(indexes.iter().map(|idx| t_stock[*idx]).collect())),
+            }
+        };
+        let none = Box::<TransactionReplacedSubscription>::default;
+
+        let add_all = || m(Command::Start, &[]);
+        let remove_all = || m(Command::Stop, &[]);
+        let add_0 = || m(Command::Start, &[0]);
+        let add_1 = || m(Command::Start, &[1]);
+        let add_01 = || m(Command::Start, &[0, 1]);
+        let remove_0 = || m(Command::Stop, &[0]);
+        let remove_1 = || m(Command::Stop, &[1]);
+
+        let test = Test {
+            name: "TransactionReplaced",
+            context: SubscriptionContext::new(),
+            initial_state: none(),
+            steps: vec![
+                Step { name: "add all 1", mutation: add_all(), result: Some(add_all()) },
+                Step { name: "add all 2", mutation: add_all(), result: None },
+                Step { name: "remove all 2", mutation: remove_all(), result: None },
+                Step { name: "remove all 1", mutation: remove_all(), result: Some(remove_all()) },
+                Step { name: "add t0 1", mutation: add_0(), result: Some(add_0()) },
+                Step { name: "add t0 2", mutation: add_0(), result: None },
+                Step { name: "add t1 1", mutation: add_1(), result: Some(add_1()) },
+                Step { name: "remove t0 2", mutation: remove_0(), result: None },
+                Step { name: "remove t1 1", mutation: remove_1(), result: Some(remove_1()) },
+                Step { name: "remove t0 1", mutation: remove_0(), result: Some(remove_0()) },
+                // Interleaved all and transaction id set
+                Step { name: "add all 1", mutation: add_all(), result: Some(add_all()) },
+                Step { name: "add t0t1, masked by all", mutation: add_01(), result: None },
+                Step { name: "remove all 1, revealing t0t1", mutation: remove_all(), result: Some(add_01()) },
+                Step { name: "add all 1, masking t0t1", mutation: add_all(), result: Some(add_all()) },
+                Step { name: "remove t1, masked by all", mutation: remove_1(), result: None },
+                Step { name: "remove all 1, revealing t0", mutation: remove_all(), result: Some(add_0()) },
+                Step { name: "remove t0", mutation: remove_0(), result: Some(remove_0()) },
+            ],
+            final_state: none(),
+        };
+        let mut state = test.run();
+
+        // Removing once more must panic
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| state.compound(remove_all(), &test.context)));
+        assert!(result.is_err(), "{}: trying to remove all when counter is zero must panic", test.name);
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{Scope, TransactionReplacedScope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{
         context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
         Single, Subscription, UtxosChangedMutationPolicy,
@@ -11,11 +11,11 @@ use crate::{
 };
 use itertools::Itertools;
 use kaspa_addresses::{Address, Prefix};
-use kaspa_consensus_core::tx::ScriptPublicKey;
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId};
 use kaspa_core::trace;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{
-    collections::hash_set,
+    collections::{hash_set, BTreeSet},
     fmt::{Debug, Display},
     hash::{Hash, Hasher},
     sync::{
@@ -172,6 +172,132 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
+/// Subscription to TransactionReplaced notifications
+///
+/// The subscription is either inactive (None), active on a set of selected transactions (Selected) or active on all
+/// replacements (All). A replacement matches a selected set if any of its inserted, replaced or evicted transactions
+/// is in the set.
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
+pub struct TransactionReplacedSubscription {
+    active: bool,
+    transaction_ids: BTreeSet<TransactionId>,
+}
+
+impl TransactionReplacedSubscription {
+    pub fn new(active: bool, transaction_ids: BTreeSet<TransactionId>) -> Self {
+        Self { active, transaction_ids }
+    }
+
+    pub fn to_all(&self) -> bool {
+        self.active && self.transaction_ids.is_empty()
+    }
+
+    /// Returns whether a replacement involving `transaction_ids` must be notified to this subscription
+    pub fn matches<'a>(&self, mut transaction_ids: impl Iterator<Item = &'a TransactionId>) -> bool {
+        self.active && (self.transaction_ids.is_empty() || transaction_ids.any(|id| self.transaction_ids.contains(id)))
+    }
+
+    fn scope_of(transaction_ids: &BTreeSet<TransactionId>) -> Scope {
+        TransactionReplacedScope::new(transaction_ids.iter().copied().collect()).into()
+    }
+}
+
+impl Single for TransactionReplacedSubscription {
+    fn apply_mutation(
+        &self,
+        _: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        _: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let Scope::TransactionReplaced(ref scope) = mutation.scope else { return Ok(MutationOutcome::new()) };
+        let result = match (mutation.command, scope.transaction_ids.is_empty()) {
+            (Command::Start, true) => {
+                if self.to_all() {
+                    // State All + Mutation All => No change
+                    None
+                } else if !self.active {
+                    // State None + Mutation All => Mutated new state All
+                    Some((Self::new(true, BTreeSet::new()), vec![mutation]))
+                } else {
+                    // State Selected(S) + Mutation All => Mutated new state All
+                    Some((
+                        Self::new(true, BTreeSet::new()),
+                        vec![Mutation::new(Command::Stop, Self::scope_of(&self.transaction_ids)), mutation],
+                    ))
+                }
+            }
+            (Command::Start, false) => {
+                let added: BTreeSet<_> =
+                    scope.transaction_ids.iter().filter(|id| !self.transaction_ids.contains(id)).copied().collect();
+                if self.to_all() {
+                    // State All + Mutation Add(A) => Mutated new state Selected(A)
+                    Some((
+                        Self::new(true, added.clone()),
+                        vec![
+                            Mutation::new(Command::Start, Self::scope_of(&added)),
+                            Mutation::new(Command::Stop, TransactionReplacedScope::default().into()),
+                        ],
+                    ))
+                } else if added.is_empty() {
+                    // State None or Selected(S) + Mutation Add(A) with A ⊆ S => No change
+                    None
+                } else {
+                    // State None or Selected(S) + Mutation Add(A) => Mutated new state Selected(A ∪ S)
+                    let transaction_ids = self.transaction_ids.union(&added).copied().collect();
+                    Some((Self::new(true, transaction_ids), vec![Mutation::new(Command::Start, Self::scope_of(&added))]))
+                }
+            }
+            (Command::Stop, true) => {
+                if !self.active {
+                    // State None + Mutation None => No change
+                    None
+                } else {
+                    // State Selected(S) or All + Mutation None => Mutated new state None
+                    Some((Self::default(), vec![Mutation::new(Command::Stop, Self::scope_of(&self.transaction_ids))]))
+                }
+            }
+            (Command::Stop, false) => {
+                let removed: BTreeSet<_> =
+                    scope.transaction_ids.iter().filter(|id| self.transaction_ids.contains(id)).copied().collect();
+                if removed.is_empty() {
+                    // State None or All + Mutation Remove(R), or State Selected(S) + Mutation Remove(R) with R ∩ S = ∅ => No change
+                    None
+                } else {
+                    // State Selected(S) + Mutation Remove(R) => Mutated state Selected(S – R) or mutated new state None
+                    let transaction_ids: BTreeSet<_> = self.transaction_ids.difference(&removed).copied().collect();
+                    Some((
+                        Self::new(!transaction_ids.is_empty(), transaction_ids),
+                        vec![Mutation::new(Command::Stop, Self::scope_of(&removed))],
+                    ))
+                }
+            }
+        };
+        let outcome = match result {
+            Some((mutated, mutations)) => MutationOutcome::with_mutated(Arc::new(mutated), mutations),
+            None => MutationOutcome::new(),
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for TransactionReplacedSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::TransactionReplaced
+    }
+
+    #[inline(always)]
+    fn active(&self) -> bool {
+        self.active
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        Self::scope_of(&self.transaction_ids)
+    }
+}
+
 static UTXOS_CHANGED_SUBSCRIPTIONS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1076,4 +1202,123 @@ mod tests {
         ]);
         tests.run(&context)
     }
+
+    #[test]
+    fn test_transaction_replaced_mutation() {
+        let context = SubscriptionContext::new();
+        let t_stock = [TransactionId::from_u64_word(1), TransactionId::from_u64_word(2)];
+
+        let t = |indexes: &[usize]| indexes.iter().map(|idx| t_stock[*idx]).collect::<Vec<_>>();
+        let s = |active: bool, indexes: &[usize]| -> DynSubscription {
+            Arc::new(TransactionReplacedSubscription::new(active, t(indexes).into_iter().collect()))
+        };
+        let m = |command: Command, indexes: &[usize]| -> Mutation {
+            Mutation { command, scope: Scope::TransactionReplaced(TransactionReplacedScope::new(t(indexes))) }
+        };
+
+        // Subscriptions
+        let none = || s(false, &[]);
+        let selected_0 = || s(true, &[0]);
+        let selected_01 = || s(true, &[0, 1]);
+        let all = || s(true, &[]);
+
+        // Mutations
+        let start_all = || m(Command::Start, &[]);
+        let stop_all = || m(Command::Stop, &[]);
+        let start_0 = || m(Command::Start, &[0]);
+        let start_1 = || m(Command::Start, &[1]);
+        let start_01 = || m(Command::Start, &[0, 1]);
+        let stop_0 = || m(Command::Stop, &[0]);
+        let stop_1 = || m(Command::Stop, &[1]);
+        let stop_01 = || m(Command::Stop, &[0, 1]);
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "TransactionReplacedSubscription None to All",
+                state: none(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription None to Selected 0",
+                state: none(),
+                mutation: start_0(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![start_0()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription None to None (stop set)",
+                state: none(),
+                mutation: stop_0(),
+                new_state: none(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 0 to Selected 01 (add set)",
+                state: selected_0(),
+                mutation: start_01(),
+                new_state: selected_01(),
+                outcome: MutationOutcome::with_mutated(selected_01(), vec![start_1()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 01 to Selected 01 (add included set)",
+                state: selected_01(),
+                mutation: start_0(),
+                new_state: selected_01(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 01 to Selected 0 (remove set)",
+                state: selected_01(),
+                mutation: stop_1(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![stop_1()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 0 to None (remove superset)",
+                state: selected_0(),
+                mutation: stop_01(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_0()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 01 to All",
+                state: selected_01(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![stop_01(), start_all()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription Selected 01 to None (remove all)",
+                state: selected_01(),
+                mutation: stop_all(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_01()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription All to Selected 01 (add set)",
+                state: all(),
+                mutation: start_01(),
+                new_state: selected_01(),
+                outcome: MutationOutcome::with_mutated(selected_01(), vec![start_01(), stop_all()]),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription All to All (remove set)",
+                state: all(),
+                mutation: stop_01(),
+                new_state: all(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "TransactionReplacedSubscription All to None (remove all)",
+                state: all(),
+                mutation: stop_all(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+        ]);
+        tests.run(&context)
+    }
 }
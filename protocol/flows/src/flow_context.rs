@@ -2,9 +2,9 @@ use crate::{
     flowcontext::{
         orphans::{OrphanBlocksPool, OrphanOutput},
         process_queue::ProcessQueue,
-        transactions::TransactionsSpread,
+        transactions::{TransactionsSpread, TxReconciliationCounters},
     },
-    v7,
+    v7, v8,
 };
 use crate::{v5, v6};
 use async_trait::async_trait;
@@ -13,6 +13,7 @@ use kaspa_addressmanager::AddressManager;
 use kaspa_connectionmanager::ConnectionManager;
 use kaspa_consensus_core::block::Block;
 use kaspa_consensus_core::config::Config;
+use kaspa_consensus_core::daa_score_timestamp::DaaScoreTimestamp;
 use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::tx::{Transaction, TransactionId};
 use kaspa_consensus_core::{
@@ -20,7 +21,7 @@ use kaspa_consensus_core::{
     network::NetworkType,
 };
 use kaspa_consensus_notify::{
-    notification::{Notification, PruningPointUtxoSetOverrideNotification},
+    notification::{Notification, PruningPointUtxoSetOverrideNotification, TransactionReplacedNotification},
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusInstance, ConsensusManager, ConsensusProxy, ConsensusSessionOwned};
@@ -32,20 +33,23 @@ use kaspa_core::{
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::mempool::tx::{Orphan, Priority};
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy};
+use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy, model::tx_insert::TransactionReplacement};
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
-    convert::model::version::Version,
+    convert::model::version::{
+        Version, ARCHIVAL_SERVICE_FLAG, LIGHT_SERVICE_FLAG, ONION_ADDRESSES_SERVICE_FLAG, RECEIPTS_SERVICE_FLAG,
+        TX_RECONCILIATION_SERVICE_FLAG, UTXOINDEX_SERVICE_FLAG,
+    },
     make_message,
     pb::{kaspad_message::Payload, InvRelayBlockMessage},
     ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
 };
 use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
 use kaspa_utils::iter::IterExtensions;
-use kaspa_utils::networking::PeerId;
+use kaspa_utils::networking::{AddressRecord, PeerId};
 use parking_lot::{Mutex, RwLock};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use std::{collections::hash_map::Entry, fmt::Display};
 use std::{
@@ -65,7 +69,7 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
 /// The P2P protocol version.
-const PROTOCOL_VERSION: u32 = 7;
+const PROTOCOL_VERSION: u32 = 8;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -223,6 +227,7 @@ pub struct FlowContextInner {
     orphans_pool: AsyncRwLock<OrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
+    tx_reconciliation_counters: Arc<TxReconciliationCounters>,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
@@ -326,13 +331,15 @@ impl FlowContext {
         // of how many orphans there can possibly be on average bounded by an upper bound.
         let max_orphans =
             (2u64.pow(orphan_resolution_range) as usize * config.ghostdag_k().upper_bound() as usize).min(MAX_ORPHANS_UPPER_BOUND);
+        let tx_reconciliation_counters = Arc::new(TxReconciliationCounters::default());
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
                 consensus_manager,
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone(), tx_reconciliation_counters.clone())),
+                tx_reconciliation_counters,
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
@@ -571,12 +578,14 @@ impl FlowContext {
         let hash = block.hash();
         let mut blocks = self.unorphan_blocks(consensus, hash).await;
 
-        // Broadcast unorphaned blocks
-        let msgs = blocks
-            .iter()
-            .map(|(b, _)| make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(b.hash().into()) }))
-            .collect();
-        self.hub.broadcast_many(msgs).await;
+        // Broadcast unorphaned blocks. Light nodes cannot serve block bodies hence do not relay blocks
+        if !self.config.is_light {
+            let msgs = blocks
+                .iter()
+                .map(|(b, _)| make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(b.hash().into()) }))
+                .collect();
+            self.hub.broadcast_many(msgs).await;
+        }
 
         // Process blocks in topological order
         blocks.sort_by(|a, b| a.0.header.blue_work.partial_cmp(&b.0.header.blue_work).unwrap());
@@ -586,13 +595,14 @@ impl FlowContext {
             // We only care about waiting for virtual to process the block at this point, before proceeding with post-processing
             // actions such as updating the mempool. We know this will not err since `block_task` already completed w/o error
             let _ = virtual_state_task.await;
-            if let Ok(txs) = self
+            if let Ok(insertion) = self
                 .mining_manager()
                 .clone()
                 .handle_new_block_transactions(consensus, block.header.daa_score, block.transactions.clone())
                 .await
             {
-                transactions_to_broadcast.enqueue_chunk(txs.into_iter().map(|x| x.id()));
+                self.on_transactions_replaced(insertion.replacements());
+                transactions_to_broadcast.enqueue_chunk(insertion.accepted.into_iter().map(|x| x.id()));
             }
         }
 
@@ -637,13 +647,24 @@ impl FlowContext {
     }
 
     pub async fn is_nearly_synced(&self, session: &ConsensusSessionOwned) -> bool {
-        let sink_daa_score_and_timestamp = session.async_get_sink_daa_score_timestamp().await;
-        self.mining_rule_engine.is_nearly_synced(sink_daa_score_and_timestamp)
+        let sync_daa_score_and_timestamp = self.get_sync_daa_score_timestamp(session).await;
+        self.mining_rule_engine.is_nearly_synced(sync_daa_score_and_timestamp)
     }
 
     pub async fn should_mine(&self, session: &ConsensusSessionOwned) -> bool {
-        let sink_daa_score_and_timestamp = session.async_get_sink_daa_score_timestamp().await;
-        self.mining_rule_engine.should_mine(sink_daa_score_and_timestamp)
+        let sync_daa_score_and_timestamp = self.get_sync_daa_score_timestamp(session).await;
+        self.mining_rule_engine.should_mine(sync_daa_score_and_timestamp)
+    }
+
+    /// Returns the DAA score and timestamp the sync status is derived from. Light nodes never resolve virtual,
+    /// hence their sink does not move and the headers selected tip is used instead
+    async fn get_sync_daa_score_timestamp(&self, session: &ConsensusSessionOwned) -> DaaScoreTimestamp {
+        if self.config.is_light {
+            let header = session.async_get_header(session.async_get_headers_selected_tip().await).await.unwrap();
+            DaaScoreTimestamp { daa_score: header.daa_score, timestamp: header.timestamp }
+        } else {
+            session.async_get_sink_daa_score_timestamp().await
+        }
     }
 
     /// Notifies that the UTXO set was reset due to pruning point change via IBD.
@@ -653,6 +674,20 @@ impl FlowContext {
         let _ = self.notification_root.notify(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}));
     }
 
+    /// Notifies that some mempool transactions were replaced by fee.
+    pub fn on_transactions_replaced<'a>(&self, replacements: impl IntoIterator<Item = &'a TransactionReplacement>) {
+        for replacement in replacements {
+            let notification = TransactionReplacedNotification::new(
+                replacement.transaction_id,
+                Arc::new(replacement.replaced.iter().map(|x| x.id()).collect()),
+                Arc::new(replacement.evicted.clone()),
+            );
+            // Notifications from the flow context might be ignored if the inner channel is already closing
+            // due to global shutdown, hence we ignore the possible error
+            let _ = self.notification_root.notify(Notification::TransactionReplaced(notification));
+        }
+    }
+
     /// Notifies that a transaction has been added to the mempool.
     pub async fn on_transaction_added_to_mempool(&self) {
         // TODO: call a handler function or a predefined registered service
@@ -679,12 +714,14 @@ impl FlowContext {
             false, // RPC transactions are considered high priority, so we don't want to throttle them
         )
         .await;
+        // Transactions unorphaned by the submitted one may replace others by fee
+        self.on_transactions_replaced(transaction_insertion.replacements());
         Ok(())
     }
 
     /// Replaces the rpc-submitted transaction into the mempool and propagates it to peers.
     ///
-    /// Returns the removed mempool transaction on successful replace by fee.
+    /// Returns the replacement, listing the replaced and all the evicted mempool transactions, on successful replace by fee.
     ///
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
@@ -693,7 +730,7 @@ impl FlowContext {
         &self,
         consensus: &ConsensusProxy,
         transaction: Transaction,
-    ) -> Result<Arc<Transaction>, ProtocolError> {
+    ) -> Result<TransactionReplacement, ProtocolError> {
         let transaction_insertion = self
             .mining_manager()
             .clone()
@@ -704,9 +741,10 @@ impl FlowContext {
             false, // RPC transactions are considered high priority, so we don't want to throttle them
         )
         .await;
+        self.on_transactions_replaced(transaction_insertion.replacements());
         // The combination of args above of Orphan::Forbidden and RbfPolicy::Mandatory should always result
-        // in a removed transaction returned, however we prefer failing gracefully in case of future internal mempool changes
-        transaction_insertion.removed.ok_or(ProtocolError::Other(
+        // in a replacement returned, however we prefer failing gracefully in case of future internal mempool changes
+        transaction_insertion.replacement.ok_or(ProtocolError::Other(
             "Replacement transaction was actually accepted but the *replaced* transaction was not returned from the mempool",
         ))
     }
@@ -737,6 +775,33 @@ impl FlowContext {
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
         self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
     }
+
+    /// Returns true if transactions are relayed to `router` by set reconciliation, namely if reconciliation
+    /// is enabled locally and was advertised by the peer in its version message
+    pub fn is_tx_reconciliation_peer(&self, router: &Router) -> bool {
+        let properties = router.properties();
+        self.config.enable_tx_reconciliation
+            && !self.config.is_light
+            && !properties.disable_relay_tx
+            && properties.services & TX_RECONCILIATION_SERVICE_FLAG != 0
+    }
+
+    pub async fn register_tx_reconciliation_peer(&self, peer: PeerKey) {
+        self.transactions_spread.write().await.register_reconciliation_peer(peer)
+    }
+
+    pub async fn unregister_tx_reconciliation_peer(&self, peer: PeerKey) {
+        self.transactions_spread.write().await.unregister_reconciliation_peer(peer)
+    }
+
+    /// Takes the transactions withheld from `peer` since its previous reconciliation round
+    pub async fn take_tx_reconciliation_set(&self, peer: PeerKey) -> HashSet<TransactionId> {
+        self.transactions_spread.write().await.take_reconciliation_set(peer)
+    }
+
+    pub fn tx_reconciliation_counters(&self) -> &Arc<TxReconciliationCounters> {
+        &self.tx_reconciliation_counters
+    }
 }
 
 #[async_trait]
@@ -756,8 +821,23 @@ impl ConnectionInitializer for FlowContext {
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
-        // TODO: get number of live services
-        // TODO: disable_relay_tx from config/cmd
+        self_version_message.services |= ONION_ADDRESSES_SERVICE_FLAG;
+        if self.config.is_archival {
+            self_version_message.services |= ARCHIVAL_SERVICE_FLAG;
+        }
+        if self.config.utxoindex {
+            self_version_message.services |= UTXOINDEX_SERVICE_FLAG;
+        }
+        if self.config.is_light {
+            // Light nodes have no UTXO set to validate transactions against
+            self_version_message.disable_relay_tx = true;
+            self_version_message.services |= LIGHT_SERVICE_FLAG;
+        } else {
+            self_version_message.services |= RECEIPTS_SERVICE_FLAG;
+            if self.config.enable_tx_reconciliation {
+                self_version_message.services |= TX_RECONCILIATION_SERVICE_FLAG;
+            }
+        }
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message.into()).await?;
@@ -786,12 +866,15 @@ impl ConnectionInitializer for FlowContext {
 
         let (flows, applied_protocol_version) = if connect_only_new_versions {
             match peer_version.protocol_version {
-                v if v >= PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                v if v >= PROTOCOL_VERSION => (v8::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                // Peers which do not support compact blocks are still served with full block relay
+                7 => (v7::register(self.clone(), router.clone()), 7),
                 v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
             }
         } else {
             match peer_version.protocol_version {
-                v if v >= PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                v if v >= PROTOCOL_VERSION => (v8::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+                7 => (v7::register(self.clone(), router.clone()), 7),
                 6 => (v6::register(self.clone(), router.clone()), 6),
                 5 => (v5::register(self.clone(), router.clone()), 5),
                 v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
@@ -801,6 +884,7 @@ impl ConnectionInitializer for FlowContext {
         // Build and register the peer properties
         let peer_properties = Arc::new(PeerProperties {
             user_agent: peer_version.user_agent.to_owned(),
+            services: peer_version.services,
             advertised_protocol_version: peer_version.protocol_version,
             protocol_version: applied_protocol_version,
             disable_relay_tx: peer_version.disable_relay_tx,
@@ -812,7 +896,12 @@ impl ConnectionInitializer for FlowContext {
         // Send and receive the ready signal
         handshake.exchange_ready_messages().await?;
 
-        info!("Registering p2p flows for peer {} for protocol version {}", router, applied_protocol_version);
+        info!(
+            "Registering p2p flows for peer {} for protocol version {} (encrypted transport: {})",
+            router,
+            applied_protocol_version,
+            router.is_encrypted()
+        );
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
@@ -822,12 +911,17 @@ impl ConnectionInitializer for FlowContext {
         if router.is_outbound() || peer_version.address.is_some() {
             let mut address_manager = self.address_manager.lock();
 
+            // The services of a peer we are connected to are known first hand, so they override gossiped ones
             if router.is_outbound() {
-                address_manager.add_address(router.net_address().into());
+                address_manager.add_first_hand_address_record(AddressRecord::new(
+                    router.net_address().into(),
+                    peer_version.services,
+                    unix_now(),
+                ));
             }
 
             if let Some(peer_ip_address) = peer_version.address {
-                address_manager.add_address(peer_ip_address);
+                address_manager.add_first_hand_address_record(AddressRecord::new(peer_ip_address, peer_version.services, unix_now()));
             }
         }
 
@@ -5,9 +5,17 @@ use kaspa_core::debug;
 use kaspa_p2p_lib::{
     make_message,
     pb::{kaspad_message::Payload, InvTransactionsMessage, KaspadMessage},
-    Hub,
+    Hub, PeerKey,
+};
+use rand::seq::IteratorRandom;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
-use std::time::{Duration, Instant};
 
 /// Interval between mempool scanning tasks (in seconds)
 const SCANNING_TASK_INTERVAL: u64 = 10;
@@ -15,6 +23,33 @@ const REBROADCAST_FREQUENCY: u64 = 3;
 const BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
 pub(crate) const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
 
+/// The number of reconciling peers which still receive each broadcast batch by flooding. Flooding toward
+/// a small random subset keeps propagation fast, while reconciliation takes care of the remaining peers
+const RECONCILIATION_FLOOD_PEERS: usize = 2;
+
+/// The maximal number of transactions withheld from a reconciling peer between reconciliation rounds.
+/// Transactions exceeding this bound are flooded to the peer as usual
+pub(crate) const MAX_RECONCILIATION_SET_SIZE: usize = 8192;
+
+/// The size of a transaction id within an inv message
+pub(crate) const TRANSACTION_ID_BYTES: u64 = 32;
+
+/// Tracks the bandwidth of transaction relay by set reconciliation
+#[derive(Default)]
+pub struct TxReconciliationCounters {
+    /// Bytes of transaction announcements which flooding would have sent to reconciling peers
+    pub withheld_bytes: AtomicU64,
+    /// Bytes sent during reconciliation rounds, including the resulting transaction announcements
+    pub reconciliation_bytes: AtomicU64,
+}
+
+impl TxReconciliationCounters {
+    /// Estimates the outgoing bandwidth saved by relaying transactions through reconciliation rather than by flooding
+    pub fn bytes_saved(&self) -> u64 {
+        self.withheld_bytes.load(Ordering::Relaxed).saturating_sub(self.reconciliation_bytes.load(Ordering::Relaxed))
+    }
+}
+
 pub struct TransactionsSpread {
     hub: Hub,
     last_scanning_time: Instant,
@@ -22,10 +57,14 @@ pub struct TransactionsSpread {
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
+
+    /// Transactions withheld from each reconciling peer since its last reconciliation round
+    reconciliation_sets: HashMap<PeerKey, HashSet<TransactionId>>,
+    reconciliation_counters: Arc<TxReconciliationCounters>,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
+    pub fn new(hub: Hub, reconciliation_counters: Arc<TxReconciliationCounters>) -> Self {
         Self {
             hub,
             last_scanning_time: Instant::now(),
@@ -33,6 +72,8 @@ impl TransactionsSpread {
             scanning_job_count: 0,
             transaction_ids: ProcessQueue::new(),
             last_broadcast_time: Instant::now(),
+            reconciliation_sets: HashMap::new(),
+            reconciliation_counters,
         }
     }
 
@@ -68,8 +109,23 @@ impl TransactionsSpread {
         self.scanning_task_running = false;
     }
 
+    /// Registers a peer with which transactions are relayed by set reconciliation
+    pub fn register_reconciliation_peer(&mut self, peer: PeerKey) {
+        self.reconciliation_sets.insert(peer, HashSet::new());
+    }
+
+    pub fn unregister_reconciliation_peer(&mut self, peer: PeerKey) {
+        self.reconciliation_sets.remove(&peer);
+    }
+
+    /// Takes the transactions withheld from `peer` since its previous reconciliation round, starting a new round
+    pub fn take_reconciliation_set(&mut self, peer: PeerKey) -> HashSet<TransactionId> {
+        self.reconciliation_sets.get_mut(&peer).map(std::mem::take).unwrap_or_default()
+    }
+
     /// Add the given transactions IDs to a set of IDs to broadcast. The IDs will be broadcasted to all peers
-    /// within transaction Inv messages.
+    /// within transaction Inv messages, except for reconciling peers which are not part of the flooding subset
+    /// and receive the IDs through their next reconciliation round instead.
     ///
     /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
     /// every `BROADCAST_INTERVAL` milliseconds or when the queue length is larger than the Inv message
@@ -85,17 +141,53 @@ impl TransactionsSpread {
         }
 
         while !self.transaction_ids.is_empty() {
-            let ids = self.transaction_ids.dequeue_chunk(MAX_INV_PER_TX_INV_MSG).map(|x| x.into()).collect_vec();
-            debug!("Transaction propagation: broadcasting {} transactions", ids.len());
-            let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids });
-            self.broadcast(msg, should_throttle).await;
+            let ids = self.transaction_ids.dequeue_chunk(MAX_INV_PER_TX_INV_MSG).collect_vec();
+            let withheld_from = self.withhold_from_reconciling_peers(&ids);
+            debug!("Transaction propagation: broadcasting {} transactions ({} peers reconciling)", ids.len(), withheld_from.len());
+            let msg =
+                make_message!(Payload::InvTransactions, InvTransactionsMessage { ids: ids.into_iter().map(|x| x.into()).collect() });
+            self.broadcast(msg, should_throttle, &withheld_from).await;
         }
 
         self.last_broadcast_time = Instant::now();
     }
 
-    async fn broadcast(&self, msg: KaspadMessage, should_throttle: bool) {
-        if should_throttle {
+    /// Adds the transactions to the reconciliation sets of all reconciling peers but a small random subset, which
+    /// still receives them by flooding. Returns the peers from which the transactions were withheld
+    fn withhold_from_reconciling_peers(&mut self, ids: &[TransactionId]) -> HashSet<PeerKey> {
+        if self.reconciliation_sets.is_empty() {
+            return HashSet::new();
+        }
+        let flooded: HashSet<PeerKey> = self
+            .reconciliation_sets
+            .keys()
+            .copied()
+            .choose_multiple(&mut rand::thread_rng(), RECONCILIATION_FLOOD_PEERS)
+            .into_iter()
+            .collect();
+        let mut withheld_from = HashSet::new();
+        for (peer, set) in self.reconciliation_sets.iter_mut() {
+            if flooded.contains(peer) || set.len() + ids.len() > MAX_RECONCILIATION_SET_SIZE {
+                continue;
+            }
+            set.extend(ids.iter().copied());
+            withheld_from.insert(*peer);
+        }
+        self.reconciliation_counters
+            .withheld_bytes
+            .fetch_add(withheld_from.len() as u64 * ids.len() as u64 * TRANSACTION_ID_BYTES, Ordering::Relaxed);
+        withheld_from
+    }
+
+    async fn broadcast(&self, msg: KaspadMessage, should_throttle: bool, withheld_from: &HashSet<PeerKey>) {
+        if !withheld_from.is_empty() {
+            let peers = self.hub.active_routers().into_iter().filter(|router| !withheld_from.contains(&router.key()));
+            // TODO: Figure out a better number
+            let peers = if should_throttle { peers.choose_multiple(&mut rand::thread_rng(), 8) } else { peers.collect() };
+            for router in peers {
+                let _ = router.enqueue(msg.clone()).await;
+            }
+        } else if should_throttle {
             // TODO: Figure out a better number
             self.hub.broadcast_to_some_peers(msg, 8).await
         } else {
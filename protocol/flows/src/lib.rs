@@ -5,3 +5,4 @@ pub mod service;
 pub mod v5;
 pub mod v6;
 pub use v6 as v7;
+pub mod v8;
@@ -6,7 +6,7 @@ use kaspa_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace,
 };
-use kaspa_p2p_lib::Adaptor;
+use kaspa_p2p_lib::{convert::model::version::ARCHIVAL_SERVICE_FLAG, Adaptor, OutboundProxies};
 use kaspa_utils::triggers::SingleTrigger;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 
@@ -25,6 +25,7 @@ pub struct P2pService {
     default_port: u16,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    proxies: OutboundProxies,
 }
 
 impl P2pService {
@@ -38,6 +39,7 @@ impl P2pService {
         dns_seeders: &'static [&'static str],
         default_port: u16,
         counters: Arc<TowerConnectionCounters>,
+        proxies: OutboundProxies,
     ) -> Self {
         Self {
             flow_context,
@@ -50,6 +52,7 @@ impl P2pService {
             dns_seeders,
             default_port,
             counters,
+            proxies,
         }
     }
 }
@@ -66,17 +69,31 @@ impl AsyncService for P2pService {
         let shutdown_signal = self.shutdown.listener.clone();
 
         let p2p_adaptor = if self.inbound_limit == 0 {
-            Adaptor::client_only(self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
+            Adaptor::client_only(
+                self.flow_context.hub().clone(),
+                self.flow_context.clone(),
+                self.counters.clone(),
+                self.proxies.clone(),
+            )
         } else {
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap()
+            Adaptor::bidirectional(
+                self.listen,
+                self.flow_context.hub().clone(),
+                self.flow_context.clone(),
+                self.counters.clone(),
+                self.proxies.clone(),
+            )
+            .unwrap()
         };
+        // Archival nodes seek at least one archival peer, which is the only kind able to serve history below the pruning point
+        let preferred_services = if self.flow_context.config.is_archival { ARCHIVAL_SERVICE_FLAG } else { 0 };
         let connection_manager = ConnectionManager::new(
             p2p_adaptor.clone(),
             self.outbound_target,
             self.inbound_limit,
             self.dns_seeders,
             self.default_port,
+            preferred_services,
             self.flow_context.address_manager.clone(),
         );
 
@@ -86,7 +103,7 @@ impl AsyncService for P2pService {
         // Launch the service and wait for a shutdown signal
         Box::pin(async move {
             for peer_address in self.connect_peers.iter().cloned().chain(self.add_peers.iter().cloned()) {
-                connection_manager.add_connection_request(peer_address.into(), true).await;
+                connection_manager.add_connection_request(peer_address, true).await;
             }
 
             // Keep the P2P server running until a service shutdown signal is received
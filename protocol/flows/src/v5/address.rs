@@ -1,13 +1,14 @@
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 use itertools::Itertools;
-use kaspa_addressmanager::NetAddress;
+use kaspa_core::time::unix_now;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::version::ONION_ADDRESSES_SERVICE_FLAG,
     dequeue, dequeue_with_timeout, make_message,
     pb::{kaspad_message::Payload, AddressesMessage, RequestAddressesMessage},
     IncomingRoute, Router,
 };
-use kaspa_utils::networking::IpAddress;
+use kaspa_utils::networking::{AddressNetwork, AddressRecord};
 use rand::seq::SliceRandom;
 use std::sync::Arc;
 
@@ -49,13 +50,15 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
-        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
+        let address_list: Vec<AddressRecord> = msg.try_into()?;
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
+        // Last seen times from the future cannot be trusted to outrank our own observations
+        let now = unix_now();
         let mut amgr_lock = self.ctx.address_manager.lock();
-        for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+        for record in address_list {
+            amgr_lock.add_address_record(AddressRecord { last_seen: record.last_seen.min(now), ..record })
         }
 
         Ok(())
@@ -85,13 +88,19 @@ impl SendAddressesFlow {
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        // Peers which do not know about onion addresses would only see their unreachable OnionCat mapping
+        let include_onion = self.router.properties().services & ONION_ADDRESSES_SERVICE_FLAG != 0;
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
-                .map(|addr| (addr.ip, addr.port).into())
-                .collect();
+            let records = self
+                .ctx
+                .address_manager
+                .lock()
+                .iterate_address_records()
+                .filter(|record| include_onion || record.address.network() != AddressNetwork::Onion)
+                .collect_vec();
+            let address_list =
+                records.choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND).map(|&record| record.into()).collect();
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
         }
     }
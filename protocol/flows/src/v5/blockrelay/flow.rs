@@ -3,14 +3,25 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use kaspa_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use kaspa_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError, merkle::calc_hash_merkle_root,
+    tx::Transaction,
+};
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
+use kaspa_mining::model::short_id::ShortIdSalt;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::{
+        compact::{CompactBlock, PartialBlock},
+        version::LIGHT_SERVICE_FLAG,
+    },
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
@@ -63,6 +74,8 @@ pub struct HandleRelayInvsFlow {
     msg_route: IncomingRoute,
     /// A channel sender for sending blocks to be handled by the IBD flow (of this peer)
     ibd_sender: JobSender<Block>,
+    /// Indicates whether the peer relays blocks as compact blocks
+    compact_blocks: bool,
 }
 
 #[async_trait::async_trait]
@@ -83,8 +96,9 @@ impl HandleRelayInvsFlow {
         invs_route: SharedIncomingRoute,
         msg_route: IncomingRoute,
         ibd_sender: JobSender<Block>,
+        compact_blocks: bool,
     ) -> Self {
-        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender }
+        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender, compact_blocks }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -94,7 +108,9 @@ impl HandleRelayInvsFlow {
             let session = self.ctx.consensus().unguarded_session();
 
             match session.async_get_block_status(inv.hash).await {
-                None | Some(BlockStatus::StatusHeaderOnly) => {} // Continue processing this missing inv
+                None => {} // Continue processing this missing inv
+                // Light nodes only process headers, hence a header-only block is already known to them
+                Some(BlockStatus::StatusHeaderOnly) if !self.ctx.config.is_light => {}
                 Some(BlockStatus::StatusInvalid) => {
                     // Report a protocol error
                     return Err(ProtocolError::OtherOwned(format!("sent inv of an invalid block {}", inv.hash)));
@@ -130,10 +146,13 @@ impl HandleRelayInvsFlow {
             };
             request_scope.report_obtained();
 
-            if block.is_header_only() {
+            if block.is_header_only() && !self.ctx.config.is_light {
                 return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
             }
 
+            // Light nodes discard the relayed body and only process the header
+            let block = if self.ctx.config.is_light { Block::from_header_arc(block.header) } else { block };
+
             let blue_work_threshold = session.async_get_virtual_merge_depth_blue_work_threshold().await;
             // Since `blue_work` respects topology, the negation of this condition means that the relay
             // block is not in the future of virtual's merge depth root, and thus cannot be merged unless
@@ -188,10 +207,14 @@ impl HandleRelayInvsFlow {
                 Err(rule_error) => return Err(rule_error.into()),
             };
 
+            // The peer relayed a new valid block, which protects it from inbound eviction
+            self.router.mark_block_relay();
+
             // As a policy, we only relay blocks who stand a chance to enter past(virtual).
             // The only mining rule which permanently excludes a block is the merge depth bound
-            // (as opposed to "max parents" and "mergeset size limit" rules)
-            if broadcast {
+            // (as opposed to "max parents" and "mergeset size limit" rules).
+            // Light nodes cannot serve block bodies hence do not relay blocks
+            if broadcast && !self.ctx.config.is_light {
                 let msgs = ancestor_batch
                     .blocks
                     .iter()
@@ -236,8 +259,12 @@ impl HandleRelayInvsFlow {
                 request_id
             ))
             .await?;
-        let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
-        let block: Block = msg.try_into()?;
+        let block = if self.compact_blocks {
+            self.receive_compact_block(requested_hash, request_id).await?
+        } else {
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
+            msg.try_into()?
+        };
         if block.hash() != requested_hash {
             Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
         } else {
@@ -245,6 +272,76 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Receives a compact block and reconstructs it from the transactions of the mempool, requesting
+    /// the missing transactions from the peer
+    async fn receive_compact_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Block, ProtocolError> {
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::CompactBlock)?;
+        let compact_block: CompactBlock = msg.try_into()?;
+        if compact_block.header.hash != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested block hash {} but got compact block {}",
+                requested_hash, compact_block.header.hash
+            )));
+        }
+        if self.ctx.config.is_light {
+            // Light nodes only process the header, so there is no need to reconstruct the body
+            return Ok(Block::from_header_arc(compact_block.header));
+        }
+        let header = compact_block.header.clone();
+        let transaction_count = compact_block.transaction_count();
+
+        let salt = ShortIdSalt::from_block(compact_block.header.hash, compact_block.nonce);
+        let resolved = self.ctx.mining_manager().clone().get_transactions_by_short_ids(salt, compact_block.short_ids.clone()).await;
+        let mut partial_block = PartialBlock::new(compact_block, resolved)?;
+        let missing_indexes = partial_block.missing_indexes();
+        if !missing_indexes.is_empty() {
+            let transactions = self.request_block_transactions(requested_hash, missing_indexes.clone(), request_id).await?;
+            partial_block.fill(&missing_indexes, transactions)?;
+        }
+        let block = partial_block.try_into_block().expect("all missing transactions were filled");
+
+        // Short id collisions might have resolved the wrong mempool transactions, in which case we fall
+        // back to requesting all the block transactions
+        let include_mass_field = self.ctx.config.crescendo_activation.is_active(header.daa_score);
+        if calc_hash_merkle_root(block.transactions.iter(), include_mass_field) != header.hash_merkle_root {
+            debug!("Compact block {} could not be reconstructed from the mempool, requesting all its transactions", requested_hash);
+            let transactions =
+                self.request_block_transactions(requested_hash, (0..transaction_count as u32).collect(), request_id).await?;
+            return Ok(Block::from_arcs(header, Arc::new(transactions)));
+        }
+        Ok(block)
+    }
+
+    async fn request_block_transactions(
+        &mut self,
+        block_hash: Hash,
+        indexes: Vec<u32>,
+        request_id: u32,
+    ) -> Result<Vec<Transaction>, ProtocolError> {
+        let expected_count = indexes.len();
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestBlockTransactions,
+                RequestBlockTransactionsMessage { block_hash: Some(block_hash.into()), indexes },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockTransactions)?;
+        let (hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+        if hash != block_hash {
+            return Err(ProtocolError::OtherOwned(format!("requested transactions of block {} but got block {}", block_hash, hash)));
+        }
+        if transactions.len() != expected_count {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested {} transactions of block {} but got {}",
+                expected_count,
+                block_hash,
+                transactions.len()
+            )));
+        }
+        Ok(transactions)
+    }
+
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
     /// the batch includes ancestor blocks and their consensus processing batch. This indicates a retry is recommended.
     async fn process_orphan(
@@ -289,6 +386,9 @@ impl HandleRelayInvsFlow {
                 }
                 None | Some(OrphanOutput::Unknown) => {}
             }
+        } else if !self.ctx.config.is_light && self.router.properties().services & LIGHT_SERVICE_FLAG != 0 {
+            // Light peers cannot serve block bodies, so IBD is left to the full peers relaying the block as well
+            debug!("Skipping IBD with light peer {} for relay block {}", self.router, block.hash());
         } else {
             // Send the block to IBD flow via the dedicated job channel. If the channel has a pending job, we prefer
             // the block with higher blue work, since it is usually more recent
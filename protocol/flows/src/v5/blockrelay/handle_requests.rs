@@ -1,7 +1,9 @@
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 use kaspa_core::debug;
+use kaspa_mining::model::short_id::ShortIdSalt;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact::CompactBlock,
     dequeue_with_request_id, make_message, make_response,
     pb::{kaspad_message::Payload, InvRelayBlockMessage},
     IncomingRoute, Router,
@@ -12,6 +14,8 @@ pub struct HandleRelayBlockRequests {
     ctx: FlowContext,
     router: Arc<Router>,
     incoming_route: IncomingRoute,
+    /// Indicates whether blocks are relayed to this peer as compact blocks
+    compact_blocks: bool,
 }
 
 #[async_trait::async_trait]
@@ -26,8 +30,8 @@ impl Flow for HandleRelayBlockRequests {
 }
 
 impl HandleRelayBlockRequests {
-    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
-        Self { ctx, router, incoming_route }
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, compact_blocks: bool) -> Self {
+        Self { ctx, router, incoming_route, compact_blocks }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -43,7 +47,14 @@ impl HandleRelayBlockRequests {
 
             for hash in hashes {
                 let block = session.async_get_block(hash).await?;
-                self.router.enqueue(make_response!(Payload::Block, (&block).into(), request_id)).await?;
+                if self.compact_blocks {
+                    let nonce = rand::random();
+                    let salt = ShortIdSalt::from_block(hash, nonce);
+                    let compact_block = CompactBlock::from_block(&block, nonce, |id| salt.short_id(id));
+                    self.router.enqueue(make_response!(Payload::CompactBlock, (&compact_block).into(), request_id)).await?;
+                } else {
+                    self.router.enqueue(make_response!(Payload::Block, (&block).into(), request_id)).await?;
+                }
                 debug!("relayed block with hash {} to peer {}", hash, self.router);
             }
         }
@@ -0,0 +1,337 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use super::{progress::ProgressReporter, IbdFlow, IBD_BATCH_SIZE};
+use futures::{
+    future::{select, try_join_all, Either},
+    stream::FuturesUnordered,
+    StreamExt,
+};
+use itertools::Itertools;
+use kaspa_consensus_core::{api::BlockValidationFuture, block::Block, config::params::ForkActivation, merkle::calc_hash_merkle_root};
+use kaspa_consensusmanager::ConsensusProxy;
+use kaspa_core::{debug, info, warn};
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::version::LIGHT_SERVICE_FLAG,
+    dequeue_with_timeout, make_message,
+    pb::{kaspad_message::Payload, RequestIbdBlocksMessage},
+    IncomingRoute, Router, BLANK_ROUTE_ID,
+};
+use tokio::time::{sleep, timeout};
+
+/// The maximal number of peers (including the syncer) from which block bodies are concurrently downloaded
+const IBD_BODIES_MAX_PEERS: usize = 8;
+
+/// The maximal number of chunks per downloading peer which can be downloaded ahead of the chunk
+/// currently awaiting to be committed to consensus. Bounds the memory held by out-of-order chunks
+const IBD_BODIES_CHUNKS_AHEAD_PER_PEER: usize = 2;
+
+/// The time a helper peer is given to deliver a full chunk before the chunk is re-assigned
+const IBD_BODIES_HELPER_CHUNK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Peers of older protocol versions might be legacy nodes which do not echo request ids, hence
+/// their responses cannot be routed to a dedicated route
+const IBD_BODIES_HELPER_MIN_PROTOCOL_VERSION: u32 = 7;
+
+struct QueueChunkOutput {
+    jobs: Vec<BlockValidationFuture>,
+    daa_score: u64,
+    timestamp: u64,
+}
+
+/// A dedicated response route over a helper peer. The route is unsubscribed on drop so that
+/// its registration does not outlive the body download
+struct HelperRoute {
+    router: Arc<Router>,
+    route: IncomingRoute,
+}
+
+impl HelperRoute {
+    fn new(router: Arc<Router>) -> Self {
+        let route = router.subscribe(vec![]);
+        Self { router, route }
+    }
+}
+
+impl Drop for HelperRoute {
+    fn drop(&mut self) {
+        self.router.unsubscribe(self.route.id());
+    }
+}
+
+/// A peer participating in the block body download. The syncer responds over the IBD flow route,
+/// while helper peers respond over dedicated routes by request id
+struct BodiesPeer<'a> {
+    router: Arc<Router>,
+    route: &'a mut IncomingRoute,
+    request_id: u32,
+    is_syncer: bool,
+}
+
+impl IbdFlow {
+    pub(super) async fn sync_missing_block_bodies(&mut self, consensus: &ConsensusProxy, high: Hash) -> Result<(), ProtocolError> {
+        // TODO (relaxed): query consensus in batches
+        let sleep_task = sleep(Duration::from_secs(2));
+        let hashes_task = consensus.async_get_missing_block_body_hashes(high);
+        tokio::pin!(sleep_task);
+        tokio::pin!(hashes_task);
+        let hashes = match select(sleep_task, hashes_task).await {
+            Either::Left((_, hashes_task)) => {
+                // We select between the tasks in order to inform the user if this operation is taking too long. On full IBD
+                // this operation requires traversing the full DAG which indeed might take several seconds or even minutes.
+                info!(
+                    "IBD: searching for missing block bodies to request from peer {}. This operation might take several seconds.",
+                    self.router
+                );
+                // Now re-await the original task
+                hashes_task.await
+            }
+            Either::Right((hashes_result, _)) => hashes_result,
+        }?;
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        let low_header = consensus.async_get_header(*hashes.first().expect("hashes was non empty")).await?;
+        let high_header = consensus.async_get_header(*hashes.last().expect("hashes was non empty")).await?;
+        let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks");
+
+        /*
+            Algorithm:
+                Split the missing bodies (which are in topological order) into chunks
+                Assign the lowest unassigned chunks to idle peers, within a bounded window above the next chunk to commit
+                Commit downloaded chunks to consensus strictly in chunk order
+                A helper peer which fails or times out is dropped and its chunk is re-assigned, while syncer errors end the IBD
+                A helper peer which sends bodies not matching their headers is disconnected and its chunk is re-assigned to the syncer
+        */
+
+        let mut helper_routes = self.select_bodies_helpers().into_iter().map(HelperRoute::new).collect_vec();
+        if !helper_routes.is_empty() {
+            info!("IBD: downloading block bodies from peer {} and {} additional peers", self.router, helper_routes.len());
+        }
+        let mut idle_peers = helper_routes
+            .iter_mut()
+            .map(|helper| BodiesPeer {
+                router: helper.router.clone(),
+                request_id: helper.route.id(),
+                route: &mut helper.route,
+                is_syncer: false,
+            })
+            .collect_vec();
+        idle_peers.push(BodiesPeer {
+            router: self.router.clone(),
+            route: &mut self.incoming_route,
+            request_id: BLANK_ROUTE_ID,
+            is_syncer: true,
+        });
+        let mut active_peers = idle_peers.len();
+        progress_reporter.set_peers(active_peers);
+        let crescendo_activation = self.ctx.config.crescendo_activation;
+
+        let chunks = hashes.chunks(IBD_BATCH_SIZE).collect_vec();
+        let mut unassigned: BTreeSet<usize> = (0..chunks.len()).collect();
+        // Chunks a helper peer misbehaved on, which are only re-assigned to the syncer
+        let mut syncer_chunks: HashSet<usize> = HashSet::new();
+        let mut downloaded: HashMap<usize, Vec<Block>> = HashMap::new();
+        let mut in_flight = FuturesUnordered::new();
+        let mut next_to_commit = 0;
+        let mut prev_output: Option<QueueChunkOutput> = None;
+
+        while next_to_commit < chunks.len() {
+            let window_end = next_to_commit + IBD_BODIES_CHUNKS_AHEAD_PER_PEER * active_peers;
+            let assignable = unassigned.range(..window_end).copied().collect_vec();
+            for index in assignable {
+                if idle_peers.is_empty() {
+                    break;
+                }
+                let position = if syncer_chunks.contains(&index) {
+                    idle_peers.iter().position(|peer| peer.is_syncer)
+                } else {
+                    Some(idle_peers.len() - 1)
+                };
+                let Some(position) = position else {
+                    continue;
+                };
+                let peer = idle_peers.swap_remove(position);
+                unassigned.remove(&index);
+                in_flight.push(Self::download_bodies_chunk(peer, index, chunks[index], crescendo_activation));
+            }
+
+            // The next chunk to commit is either unassigned, in which case it was just assigned to an idle
+            // peer, or is already in flight, hence there is always at least one download in flight here
+            let (peer, index, result) = in_flight.next().await.expect("at least one chunk download is in flight");
+            match result {
+                Ok(blocks) => {
+                    downloaded.insert(index, blocks);
+                    idle_peers.push(peer);
+                }
+                Err(err) if peer.is_syncer => return Err(err),
+                Err(err @ ProtocolError::MisbehavingPeer(_)) => {
+                    warn!("IBD: disconnecting from peer {} and re-assigning its chunk to the syncer: {}", peer.router, err);
+                    peer.router.close().await;
+                    unassigned.insert(index);
+                    syncer_chunks.insert(index);
+                    active_peers -= 1;
+                    progress_reporter.set_peers(active_peers);
+                }
+                Err(err) => {
+                    warn!("IBD: dropping peer {} from block body download and re-assigning its chunk: {}", peer.router, err);
+                    unassigned.insert(index);
+                    active_peers -= 1;
+                    progress_reporter.set_peers(active_peers);
+                }
+            }
+
+            while let Some(blocks) = downloaded.remove(&next_to_commit) {
+                let current_output = Self::queue_block_processing_chunk(consensus, blocks);
+                if let Some(QueueChunkOutput { jobs, daa_score, timestamp }) = prev_output.replace(current_output) {
+                    let prev_chunk_len = jobs.len();
+                    // Join the previous chunk so that we always concurrently process a chunk and receive others
+                    try_join_all(jobs).await?;
+                    // Log the progress
+                    progress_reporter.report(prev_chunk_len, daa_score, timestamp);
+                }
+                next_to_commit += 1;
+            }
+        }
+
+        let QueueChunkOutput { jobs, .. } = prev_output.expect("hashes was non empty");
+        let prev_chunk_len = jobs.len();
+        try_join_all(jobs).await?;
+        progress_reporter.report_completion(prev_chunk_len);
+
+        Ok(())
+    }
+
+    /// Selects the peers which assist the syncer with block body download, preferring peers with lower latency.
+    /// Light peers hold no block bodies, hence they are never selected
+    fn select_bodies_helpers(&self) -> Vec<Arc<Router>> {
+        let syncer_key = self.router.key();
+        self.ctx
+            .hub()
+            .active_routers()
+            .into_iter()
+            .filter(|router| {
+                let properties = router.properties();
+                router.key() != syncer_key
+                    && properties.protocol_version >= IBD_BODIES_HELPER_MIN_PROTOCOL_VERSION
+                    && properties.services & LIGHT_SERVICE_FLAG == 0
+            })
+            .sorted_by_key(|router| router.last_ping_duration())
+            .take(IBD_BODIES_MAX_PEERS - 1)
+            .collect()
+    }
+
+    /// Downloads a chunk of block bodies from `peer`, returning the peer along with the chunk index for re-scheduling
+    async fn download_bodies_chunk<'a>(
+        mut peer: BodiesPeer<'a>,
+        index: usize,
+        chunk: &[Hash],
+        crescendo_activation: ForkActivation,
+    ) -> (BodiesPeer<'a>, usize, Result<Vec<Block>, ProtocolError>) {
+        let result = if peer.is_syncer {
+            Self::request_bodies_chunk(&mut peer, chunk).await
+        } else {
+            match timeout(IBD_BODIES_HELPER_CHUNK_TIMEOUT, Self::request_bodies_chunk(&mut peer, chunk)).await {
+                // Bodies of the syncer are validated by consensus, while a helper sending bodies which do not match their
+                // headers must be caught here, before consensus marks the blocks as invalid
+                Ok(Ok(blocks)) => {
+                    blocks.iter().try_for_each(|block| check_body_merkle_root(block, crescendo_activation)).map(|_| blocks)
+                }
+                Ok(Err(err)) => Err(err),
+                Err(_) => Err(ProtocolError::Timeout(IBD_BODIES_HELPER_CHUNK_TIMEOUT)),
+            }
+        };
+        (peer, index, result)
+    }
+
+    async fn request_bodies_chunk(peer: &mut BodiesPeer<'_>, chunk: &[Hash]) -> Result<Vec<Block>, ProtocolError> {
+        debug!("IBD: requesting {} block bodies from peer {}", chunk.len(), peer.router);
+        peer.router
+            .enqueue(make_message!(
+                Payload::RequestIbdBlocks,
+                RequestIbdBlocksMessage { hashes: chunk.iter().map(|h| h.into()).collect() },
+                BLANK_ROUTE_ID,
+                peer.request_id
+            ))
+            .await?;
+        let mut blocks = Vec::with_capacity(chunk.len());
+        for &expected_hash in chunk {
+            let msg = dequeue_with_timeout!(peer.route, Payload::IbdBlock)?;
+            let block: Block = msg.try_into()?;
+            if block.hash() != expected_hash {
+                return Err(ProtocolError::OtherOwned(format!("expected block {} but got {}", expected_hash, block.hash())));
+            }
+            if block.is_header_only() {
+                return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
+            }
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    fn queue_block_processing_chunk(consensus: &ConsensusProxy, chunk: Vec<Block>) -> QueueChunkOutput {
+        let mut jobs = Vec::with_capacity(chunk.len());
+        let mut current_daa_score = 0;
+        let mut current_timestamp = 0;
+        for block in chunk {
+            current_daa_score = block.header.daa_score;
+            current_timestamp = block.header.timestamp;
+            jobs.push(consensus.validate_and_insert_block(block).virtual_state_task);
+        }
+        QueueChunkOutput { jobs, daa_score: current_daa_score, timestamp: current_timestamp }
+    }
+}
+
+/// Verifies that the transactions of a downloaded block body are the ones committed to by its header
+fn check_body_merkle_root(block: &Block, crescendo_activation: ForkActivation) -> Result<(), ProtocolError> {
+    let include_mass_field = crescendo_activation.is_active(block.header.daa_score);
+    let calculated = calc_hash_merkle_root(block.transactions.iter(), include_mass_field);
+    if calculated != block.header.hash_merkle_root {
+        return Err(ProtocolError::MisbehavingPeer(format!(
+            "sent body of block {} with merkle root {} where header commits to {}",
+            block.hash(),
+            calculated,
+            block.header.hash_merkle_root
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        header::Header,
+        subnets::SUBNETWORK_ID_COINBASE,
+        tx::{Transaction, TransactionOutput},
+    };
+
+    fn transaction(value: u64) -> Transaction {
+        Transaction::new(0, vec![], vec![TransactionOutput::new(value, Default::default())], 0, SUBNETWORK_ID_COINBASE, 0, vec![])
+    }
+
+    #[test]
+    fn test_misbehaving_helper_body() {
+        let crescendo_activation = ForkActivation::never();
+        let txs = vec![transaction(1), transaction(2)];
+        let mut header = Header::from_precomputed_hash(1.into(), vec![]);
+        header.hash_merkle_root = calc_hash_merkle_root(txs.iter(), false);
+
+        // A body matching its header passes
+        let block = Block::new(header.clone(), txs.clone());
+        assert!(check_body_merkle_root(&block, crescendo_activation).is_ok());
+
+        // A helper replacing, omitting or reordering transactions of the requested block is marked as misbehaving,
+        // even though the header it sent (and hence the block hash) matches the requested one
+        for tampered in [vec![transaction(1), transaction(3)], vec![transaction(1)], vec![transaction(2), transaction(1)]] {
+            let block = Block::new(header.clone(), tampered);
+            assert!(matches!(check_body_merkle_root(&block, crescendo_activation), Err(ProtocolError::MisbehavingPeer(_))));
+        }
+    }
+}
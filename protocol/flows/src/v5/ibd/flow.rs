@@ -5,13 +5,14 @@ use crate::{
         Flow,
     },
 };
-use futures::future::{join_all, select, try_join_all, Either};
+use futures::future::{join_all, try_join_all};
 use itertools::Itertools;
 use kaspa_consensus_core::{
     api::BlockValidationFuture,
     block::Block,
     header::Header,
-    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+    pruning::{IbdCheckpoint, PruningPointProof, PruningPointsList, PruningProofMetadata},
+    tx::TransactionOutpoint,
     BlockHashSet,
 };
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy, StagingConsensus};
@@ -23,8 +24,8 @@ use kaspa_p2p_lib::{
     convert::model::trusted::TrustedDataPackage,
     dequeue_with_timeout, make_message,
     pb::{
-        kaspad_message::Payload, RequestAntipastMessage, RequestHeadersMessage, RequestIbdBlocksMessage,
-        RequestPruningPointAndItsAnticoneMessage, RequestPruningPointProofMessage, RequestPruningPointUtxoSetMessage,
+        kaspad_message::Payload, RequestAntipastMessage, RequestHeadersMessage, RequestPruningPointAndItsAnticoneMessage,
+        RequestPruningPointProofMessage, RequestPruningPointUtxoSetMessage,
     },
     IncomingRoute, Router,
 };
@@ -33,9 +34,20 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::time::sleep;
 
-use super::{progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, IBD_BATCH_SIZE};
+use super::{progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream};
+
+/// Returns whether the trusted pruning point is ahead of the current pruning point, i.e., the current pruning point is
+/// genesis or in the past of the trusted point. Otherwise syncing from the trusted point would move the pruning point
+/// backwards or sideways, so IBD falls back to the regular headers proof logic
+async fn is_trusted_pruning_point_ahead(consensus: &ConsensusProxy, trusted: Hash, genesis: Hash) -> bool {
+    let pruning_point = consensus.async_pruning_point().await;
+    if pruning_point == trusted {
+        return false;
+    }
+    // A trusted point which is not known locally cannot be related to the current pruning point
+    pruning_point == genesis || consensus.async_is_chain_ancestor_of(pruning_point, trusted).await.unwrap_or(false)
+}
 
 /// Flow for managing IBD - Initial Block Download
 pub struct IbdFlow {
@@ -62,13 +74,20 @@ pub enum IbdType {
     None,
     Sync(Hash),
     DownloadHeadersProof,
+    /// Same as [`IbdType::DownloadHeadersProof`], only that the proof pruning point is the trusted pruning point
+    /// configured by the operator, hence the proof itself is not validated
+    TrustedPruningPoint,
 }
 
-struct QueueChunkOutput {
-    jobs: Vec<BlockValidationFuture>,
-    daa_score: u64,
-    timestamp: u64,
+/// The point from which an interrupted IBD with headers proof resumes
+struct StagingResumePoint {
+    checkpoint: IbdCheckpoint,
+    highest_known_syncer_chain_hash: Hash,
 }
+
+/// Syncers of older protocol versions ignore the outpoint from which the pruning point UTXO set is requested
+const RESUMABLE_UTXOSET_MIN_PROTOCOL_VERSION: u32 = 8;
+
 // TODO: define a peer banning strategy
 
 impl IbdFlow {
@@ -113,20 +132,42 @@ impl IbdFlow {
                 )
                 .await?;
             }
-            IbdType::DownloadHeadersProof => {
+            IbdType::DownloadHeadersProof | IbdType::TrustedPruningPoint => {
+                let trusted = matches!(ibd_type, IbdType::TrustedPruningPoint);
                 drop(session); // Avoid holding the previous consensus throughout the staging IBD
-                let staging = self.ctx.consensus_manager.new_staging_consensus();
-                match self.ibd_with_headers_proof(&staging, negotiation_output.syncer_virtual_selected_parent, &relay_block).await {
+                let (staging, resume_point) = match self.resume_staging_consensus().await? {
+                    Some((staging, resume_point)) => (staging, Some(resume_point)),
+                    None => (self.ctx.consensus_manager.new_staging_consensus(), None),
+                };
+                match self
+                    .ibd_with_headers_proof(
+                        &staging,
+                        negotiation_output.syncer_virtual_selected_parent,
+                        &relay_block,
+                        resume_point,
+                        trusted,
+                    )
+                    .await
+                {
                     Ok(()) => {
                         spawn_blocking(|| staging.commit()).await.unwrap();
                         info!(
                             "Header download stage of IBD with headers proof completed successfully from {}. Committed staging consensus.",
                             self.router
                         );
-                        self.ctx.on_pruning_point_utxoset_override();
+                        if !self.ctx.config.is_light {
+                            self.ctx.on_pruning_point_utxoset_override();
+                        }
                         // This will reobtain the freshly committed staging consensus
                         session = self.ctx.consensus().session().await;
                     }
+                    Err(e) if matches!(e, ProtocolError::ConnectionClosed | ProtocolError::Timeout(_)) => {
+                        // The syncer disconnected or stalled (which is also the case when the node is shutting down), so the
+                        // progress made so far is valid and is retained for resuming the IBD
+                        info!("IBD with headers proof from {} was interrupted ({}). Retaining the staging consensus", self.router, e);
+                        staging.suspend();
+                        return Err(e);
+                    }
                     Err(e) => {
                         info!("IBD with headers proof from {} was unsuccessful ({})", self.router, e);
                         staging.cancel();
@@ -136,12 +177,22 @@ impl IbdFlow {
             }
         }
 
-        // Sync missing bodies in the past of syncer sink (virtual selected parent)
-        self.sync_missing_block_bodies(&session, negotiation_output.syncer_virtual_selected_parent).await?;
+        // Light nodes only follow headers, so bodies are never synced
+        if !self.ctx.config.is_light {
+            // Sync missing bodies in the past of syncer sink (virtual selected parent)
+            self.sync_missing_block_bodies(&session, negotiation_output.syncer_virtual_selected_parent).await?;
 
-        // Relay block might be in the antipast of syncer sink, thus
-        // check its past for missing bodies as well.
-        self.sync_missing_block_bodies(&session, relay_block.hash()).await?;
+            // Relay block might be in the antipast of syncer sink, thus
+            // check its past for missing bodies as well.
+            self.sync_missing_block_bodies(&session, relay_block.hash()).await?;
+        }
+
+        if matches!(ibd_type, IbdType::Sync(_)) {
+            // The current consensus caught up with the syncer without a headers proof, so the staging consensus left by
+            // a previously interrupted IBD with headers proof, if such exists, is obsolete and would otherwise never be deleted
+            let consensus_manager = self.ctx.consensus_manager.clone();
+            spawn_blocking(move || consensus_manager.delete_staging_entry()).await.unwrap();
+        }
 
         // Following IBD we revalidate orphans since many of them might have been processed during the IBD
         // or are now processable
@@ -193,6 +244,18 @@ impl IbdFlow {
         // Having a shorter depth for this condition for the fork transition period (if hst is shortly before activation)
         // is negligible since there are other conditions required for activating an headers proof IBD. The important
         // thing is that we eventually adjust to the longer period.
+        if let Some(trusted) = self.ctx.config.trusted_pruning_point {
+            // A syncer whose pruning point is the trusted one is synced from regardless of the headers proof conditions
+            // below, since the proof is not relied upon. The pruning point header and its UTXO commitment are matched
+            // against the configured values once received
+            if relay_header.pruning_point == trusted.hash
+                && relay_header.blue_work > hst_header.blue_work
+                && is_trusted_pruning_point_ahead(consensus, trusted.hash, self.ctx.config.genesis.hash).await
+            {
+                return Ok(IbdType::TrustedPruningPoint);
+            }
+        }
+
         let pruning_depth = self.ctx.config.pruning_depth().get(hst_header.daa_score);
         if relay_header.blue_score >= hst_header.blue_score + pruning_depth && relay_header.blue_work > hst_header.blue_work {
             // [Crescendo]: switch to the new *shorter* finality duration only after sufficient time has passed
@@ -222,25 +285,103 @@ impl IbdFlow {
         }
     }
 
+    /// Loads the staging consensus of a previously interrupted IBD with headers proof, if such exists, and re-checks
+    /// its state against the syncer. A staging consensus which cannot be resumed is discarded
+    async fn resume_staging_consensus(&mut self) -> Result<Option<(StagingConsensus, StagingResumePoint)>, ProtocolError> {
+        let Some(staging) = self.ctx.consensus_manager.existing_staging_consensus() else {
+            return Ok(None);
+        };
+        match self.check_staging_resume_point(&staging).await {
+            Ok(Some(resume_point)) => Ok(Some((staging, resume_point))),
+            Ok(None) => {
+                info!("The staging consensus of a previous IBD cannot be resumed with peer {}. Discarding it", self.router);
+                staging.cancel();
+                Ok(None)
+            }
+            Err(e) => {
+                staging.suspend();
+                Err(e)
+            }
+        }
+    }
+
+    async fn check_staging_resume_point(&mut self, staging: &StagingConsensus) -> Result<Option<StagingResumePoint>, ProtocolError> {
+        let staging_session = staging.session().await;
+        let Some(checkpoint) = staging_session.async_get_ibd_checkpoint().await else {
+            // The previous IBD was interrupted before the proof was applied
+            return Ok(None);
+        };
+        if matches!(checkpoint, IbdCheckpoint::UtxosetChunk { .. })
+            && self.router.properties().protocol_version < RESUMABLE_UTXOSET_MIN_PROTOCOL_VERSION
+        {
+            // The syncer cannot send the UTXO set starting from the last imported outpoint
+            return Ok(None);
+        }
+
+        // The staging state is re-checked by locating the syncer chain within it. The IBD can resume only if the
+        // syncer chain passes through the staging pruning point, in which case headers are synced from the highest
+        // shared chain block
+        let negotiation_output = self.negotiate_missing_syncer_chain_segment(&staging_session).await?;
+        let Some(highest_known_syncer_chain_hash) = negotiation_output.highest_known_syncer_chain_hash else {
+            return Ok(None);
+        };
+        if !staging_session.async_is_chain_ancestor_of(checkpoint.pruning_point(), highest_known_syncer_chain_hash).await? {
+            return Ok(None);
+        }
+        Ok(Some(StagingResumePoint { checkpoint, highest_known_syncer_chain_hash }))
+    }
+
     async fn ibd_with_headers_proof(
         &mut self,
         staging: &StagingConsensus,
         syncer_virtual_selected_parent: Hash,
         relay_block: &Block,
+        resume_point: Option<StagingResumePoint>,
+        trusted: bool,
     ) -> Result<(), ProtocolError> {
-        info!("Starting IBD with headers proof with peer {}", self.router);
-
         let staging_session = staging.session().await;
 
-        let pruning_point = self.sync_and_validate_pruning_proof(&staging_session, relay_block).await?;
-        self.sync_headers(&staging_session, syncer_virtual_selected_parent, pruning_point, relay_block).await?;
+        let (pruning_point, highest_known_syncer_chain_hash, utxoset_resume_point) = match resume_point {
+            Some(StagingResumePoint { checkpoint, highest_known_syncer_chain_hash }) => {
+                let pruning_point = checkpoint.pruning_point();
+                let utxoset_resume_point = match checkpoint {
+                    IbdCheckpoint::ProofApplied { .. } => {
+                        info!("Resuming IBD with headers proof with peer {} from pruning point {}", self.router, pruning_point);
+                        None
+                    }
+                    IbdCheckpoint::UtxosetChunk { last_outpoint, multiset, .. } => {
+                        info!(
+                            "Resuming IBD with headers proof with peer {} from pruning point {} and UTXO {}",
+                            self.router, pruning_point, last_outpoint
+                        );
+                        Some((last_outpoint, multiset))
+                    }
+                };
+                (pruning_point, highest_known_syncer_chain_hash, utxoset_resume_point)
+            }
+            None => {
+                info!("Starting IBD with headers proof with peer {}", self.router);
+                let pruning_point = self.sync_and_validate_pruning_proof(&staging_session, relay_block, trusted).await?;
+                staging_session.async_set_ibd_checkpoint(IbdCheckpoint::ProofApplied { pruning_point }).await;
+                (pruning_point, pruning_point, None)
+            }
+        };
+
+        self.sync_headers(&staging_session, syncer_virtual_selected_parent, highest_known_syncer_chain_hash, relay_block).await?;
         staging_session.async_validate_pruning_points(syncer_virtual_selected_parent).await?;
         self.validate_staging_timestamps(&self.ctx.consensus().session().await, &staging_session).await?;
-        self.sync_pruning_point_utxoset(&staging_session, pruning_point).await?;
+        if !self.ctx.config.is_light {
+            self.sync_pruning_point_utxoset(&staging_session, pruning_point, utxoset_resume_point).await?;
+        }
         Ok(())
     }
 
-    async fn sync_and_validate_pruning_proof(&mut self, staging: &ConsensusProxy, relay_block: &Block) -> Result<Hash, ProtocolError> {
+    async fn sync_and_validate_pruning_proof(
+        &mut self,
+        staging: &ConsensusProxy,
+        relay_block: &Block,
+        trusted: bool,
+    ) -> Result<Hash, ProtocolError> {
         self.router.enqueue(make_message!(Payload::RequestPruningPointProof, RequestPruningPointProofMessage {})).await?;
 
         // Pruning proof generation and communication might take several minutes, so we allow a long 10 minute timeout
@@ -257,9 +398,13 @@ impl IbdFlow {
         // Get a new session for current consensus (non staging)
         let consensus = self.ctx.consensus().session().await;
 
-        // The proof is validated in the context of current consensus
-        let proof =
-            consensus.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?;
+        let proof = if trusted {
+            self.check_trusted_pruning_proof(&proof)?;
+            proof
+        } else {
+            // The proof is validated in the context of current consensus
+            consensus.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?
+        };
 
         let proof_pruning_point = proof[0].last().expect("was just ensured by validation").hash;
 
@@ -378,6 +523,34 @@ impl IbdFlow {
         Ok(proof_pruning_point)
     }
 
+    /// Checks that the proof is built over the trusted pruning point configured by the operator, which replaces the
+    /// proof validation. Only the structure of the proof is verified, so that applying it is well-defined
+    fn check_trusted_pruning_proof(&self, proof: &PruningPointProof) -> Result<(), ProtocolError> {
+        let trusted = self.ctx.config.trusted_pruning_point.expect("trusted IBD requires a trusted pruning point");
+        if proof.len() != self.ctx.config.max_block_level as usize + 1 || proof.iter().any(|level| level.is_empty()) {
+            return Err(ProtocolError::Other("the headers proof of the trusted pruning point is malformed"));
+        }
+        let proof_pp_header = proof[0].last().expect("checked if empty");
+        if proof_pp_header.hash != trusted.hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "the proof pruning point {} is not the trusted pruning point {}",
+                proof_pp_header.hash, trusted.hash
+            )));
+        }
+        if proof_pp_header.utxo_commitment != trusted.utxo_commitment {
+            return Err(ProtocolError::OtherOwned(format!(
+                "the UTXO commitment {} of the trusted pruning point does not match the configured commitment {}",
+                proof_pp_header.utxo_commitment, trusted.utxo_commitment
+            )));
+        }
+        warn!(
+            "Skipping the validation of the headers proof received from {} for trusted pruning point {}. The node trusts \
+its syncer to provide the correct chain up to this pruning point",
+            self.router, trusted.hash
+        );
+        Ok(())
+    }
+
     async fn sync_headers(
         &mut self,
         consensus: &ConsensusProxy,
@@ -506,20 +679,42 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         }
     }
 
-    async fn sync_pruning_point_utxoset(&mut self, consensus: &ConsensusProxy, pruning_point: Hash) -> Result<(), ProtocolError> {
+    /// Downloads and imports the pruning point UTXO set. If `resume_point` is provided, the download continues
+    /// following the last imported outpoint, with the multiset accumulated up to it
+    async fn sync_pruning_point_utxoset(
+        &mut self,
+        consensus: &ConsensusProxy,
+        pruning_point: Hash,
+        resume_point: Option<(TransactionOutpoint, MuHash)>,
+    ) -> Result<(), ProtocolError> {
+        let (from_outpoint, mut multiset) = match resume_point {
+            Some((last_outpoint, multiset)) => (Some(last_outpoint), multiset),
+            None => (None, MuHash::new()),
+        };
         self.router
             .enqueue(make_message!(
                 Payload::RequestPruningPointUtxoSet,
-                RequestPruningPointUtxoSetMessage { pruning_point_hash: Some(pruning_point.into()) }
+                RequestPruningPointUtxoSetMessage {
+                    pruning_point_hash: Some(pruning_point.into()),
+                    from_outpoint: from_outpoint.as_ref().map(|outpoint| outpoint.into())
+                }
             ))
             .await?;
         let mut chunk_stream = PruningPointUtxosetChunkStream::new(&self.router, &mut self.incoming_route);
-        let mut multiset = MuHash::new();
         while let Some(chunk) = chunk_stream.next().await? {
             multiset = consensus
                 .clone()
                 .spawn_blocking(move |c| {
                     c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                    // The checkpoint is set only after the chunk was written, so a crash in between only leads to
+                    // re-writing the same UTXOs, which are then accumulated into the persisted multiset exactly once
+                    if let Some((last_outpoint, _)) = chunk.last() {
+                        c.set_ibd_checkpoint(IbdCheckpoint::UtxosetChunk {
+                            pruning_point,
+                            last_outpoint: *last_outpoint,
+                            multiset: multiset.clone(),
+                        });
+                    }
                     multiset
                 })
                 .await;
@@ -527,86 +722,50 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         consensus.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset)).await?;
         Ok(())
     }
+}
 
-    async fn sync_missing_block_bodies(&mut self, consensus: &ConsensusProxy, high: Hash) -> Result<(), ProtocolError> {
-        // TODO (relaxed): query consensus in batches
-        let sleep_task = sleep(Duration::from_secs(2));
-        let hashes_task = consensus.async_get_missing_block_body_hashes(high);
-        tokio::pin!(sleep_task);
-        tokio::pin!(hashes_task);
-        let hashes = match select(sleep_task, hashes_task).await {
-            Either::Left((_, hashes_task)) => {
-                // We select between the tasks in order to inform the user if this operation is taking too long. On full IBD
-                // this operation requires traversing the full DAG which indeed might take several seconds or even minutes.
-                info!(
-                    "IBD: searching for missing block bodies to request from peer {}. This operation might take several seconds.",
-                    self.router
-                );
-                // Now re-await the original task
-                hashes_task.await
-            }
-            Either::Right((hashes_result, _)) => hashes_result,
-        }?;
-        if hashes.is_empty() {
-            return Ok(());
-        }
-
-        let low_header = consensus.async_get_header(*hashes.first().expect("hashes was non empty")).await?;
-        let high_header = consensus.async_get_header(*hashes.last().expect("hashes was non empty")).await?;
-        let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks");
-
-        let mut iter = hashes.chunks(IBD_BATCH_SIZE);
-        let QueueChunkOutput { jobs: mut prev_jobs, daa_score: mut prev_daa_score, timestamp: mut prev_timestamp } =
-            self.queue_block_processing_chunk(consensus, iter.next().expect("hashes was non empty")).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        errors::consensus::{ConsensusError, ConsensusResult},
+    };
+    use kaspa_consensusmanager::{ConsensusInstance, SessionLock};
+
+    /// A consensus with a single selected chain `1 <- 2 <- ... <- tip` and a fixed pruning point on it
+    struct MockConsensus {
+        pruning_point: Hash,
+        tip: u64,
+    }
 
-        for chunk in iter {
-            let QueueChunkOutput { jobs: current_jobs, daa_score: current_daa_score, timestamp: current_timestamp } =
-                self.queue_block_processing_chunk(consensus, chunk).await?;
-            let prev_chunk_len = prev_jobs.len();
-            // Join the previous chunk so that we always concurrently process a chunk and receive another
-            try_join_all(prev_jobs).await?;
-            // Log the progress
-            progress_reporter.report(prev_chunk_len, prev_daa_score, prev_timestamp);
-            prev_daa_score = current_daa_score;
-            prev_timestamp = current_timestamp;
-            prev_jobs = current_jobs;
+    impl ConsensusApi for MockConsensus {
+        fn pruning_point(&self) -> Hash {
+            self.pruning_point
         }
 
-        let prev_chunk_len = prev_jobs.len();
-        try_join_all(prev_jobs).await?;
-        progress_reporter.report_completion(prev_chunk_len);
-
-        Ok(())
+        fn is_chain_ancestor_of(&self, low: Hash, high: Hash) -> ConsensusResult<bool> {
+            let index = |hash: Hash| (1..=self.tip).find(|&i| Hash::from(i) == hash).ok_or(ConsensusError::HeaderNotFound(hash));
+            Ok(index(low)? <= index(high)?)
+        }
     }
 
-    async fn queue_block_processing_chunk(
-        &mut self,
-        consensus: &ConsensusProxy,
-        chunk: &[Hash],
-    ) -> Result<QueueChunkOutput, ProtocolError> {
-        let mut jobs = Vec::with_capacity(chunk.len());
-        let mut current_daa_score = 0;
-        let mut current_timestamp = 0;
-        self.router
-            .enqueue(make_message!(
-                Payload::RequestIbdBlocks,
-                RequestIbdBlocksMessage { hashes: chunk.iter().map(|h| h.into()).collect() }
-            ))
-            .await?;
-        for &expected_hash in chunk {
-            let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdBlock)?;
-            let block: Block = msg.try_into()?;
-            if block.hash() != expected_hash {
-                return Err(ProtocolError::OtherOwned(format!("expected block {} but got {}", expected_hash, block.hash())));
-            }
-            if block.is_header_only() {
-                return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
-            }
-            current_daa_score = block.header.daa_score;
-            current_timestamp = block.header.timestamp;
-            jobs.push(consensus.validate_and_insert_block(block).virtual_state_task);
-        }
+    async fn is_ahead(pruning_point: u64, trusted: u64) -> bool {
+        let consensus = Arc::new(MockConsensus { pruning_point: pruning_point.into(), tip: 10 });
+        let session = ConsensusInstance::new(SessionLock::new(), consensus).session().await;
+        is_trusted_pruning_point_ahead(&session, trusted.into(), 1.into()).await
+    }
 
-        Ok(QueueChunkOutput { jobs, daa_score: current_daa_score, timestamp: current_timestamp })
+    #[tokio::test]
+    async fn test_trusted_pruning_point_ibd_type() {
+        // The current pruning point is genesis or in the past of the trusted point, hence the trusted IBD applies
+        assert!(is_ahead(1, 5).await);
+        assert!(is_ahead(1, 20).await);
+        assert!(is_ahead(3, 5).await);
+
+        // The trusted point equals, precedes or is unknown to a non-genesis pruning point, hence the regular proof logic applies
+        assert!(!is_ahead(5, 5).await);
+        assert!(!is_ahead(5, 3).await);
+        assert!(!is_ahead(5, 20).await);
     }
 }
@@ -16,6 +16,7 @@ pub struct ProgressReporter {
     last_log_time: Instant,
     current_batch: usize,
     processed: usize,
+    peers: usize,
 }
 
 impl ProgressReporter {
@@ -32,9 +33,15 @@ impl ProgressReporter {
             last_log_time: Instant::now(),
             current_batch: 0,
             processed: 0,
+            peers: 1,
         }
     }
 
+    /// Sets the number of peers objects are currently downloaded from
+    pub fn set_peers(&mut self, peers: usize) {
+        self.peers = peers;
+    }
+
     pub fn report(&mut self, processed_delta: usize, current_daa_score: u64, current_timestamp: u64) {
         self.current_batch += processed_delta;
         let now = Instant::now();
@@ -53,7 +60,14 @@ impl ProgressReporter {
                 LocalResult::None | LocalResult::Ambiguous(_, _) => "cannot parse date".into(),
                 LocalResult::Single(date) => date.format("%Y-%m-%d %H:%M:%S.%3f:%z").to_string(),
             };
-            info!("IBD: Processed {} {} ({}%) last block timestamp: {}", self.processed, self.object_name, percent, date);
+            if self.peers > 1 {
+                info!(
+                    "IBD: Processed {} {} ({}%) last block timestamp: {} (downloading from {} peers)",
+                    self.processed, self.object_name, percent, date, self.peers
+                );
+            } else {
+                info!("IBD: Processed {} {} ({}%) last block timestamp: {}", self.processed, self.object_name, percent, date);
+            }
             self.last_reported_percent = percent;
         }
         self.last_log_time = now;
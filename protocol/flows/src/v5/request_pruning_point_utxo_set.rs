@@ -1,6 +1,6 @@
 use crate::{flow_context::FlowContext, flow_trait::Flow, v5::ibd::IBD_BATCH_SIZE};
 use itertools::Itertools;
-use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_consensus_core::{errors::consensus::ConsensusError, tx::TransactionOutpoint};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
@@ -37,14 +37,17 @@ impl RequestPruningPointUtxoSetFlow {
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
-            let expected_pp = dequeue!(self.incoming_route, Payload::RequestPruningPointUtxoSet)?.try_into()?;
-            self.handle_request(expected_pp).await?
+            let (expected_pp, from_outpoint) = dequeue!(self.incoming_route, Payload::RequestPruningPointUtxoSet)?.try_into()?;
+            self.handle_request(expected_pp, from_outpoint).await?
         }
     }
 
-    async fn handle_request(&mut self, expected_pp: Hash) -> Result<(), ProtocolError> {
+    async fn handle_request(
+        &mut self,
+        expected_pp: Hash,
+        mut from_outpoint: Option<TransactionOutpoint>,
+    ) -> Result<(), ProtocolError> {
         const CHUNK_SIZE: usize = 1000;
-        let mut from_outpoint = None;
         let mut chunks_sent = 0;
 
         let consensus = self.ctx.consensus();
@@ -53,7 +56,8 @@ impl RequestPruningPointUtxoSetFlow {
         loop {
             // We avoid keeping the consensus session across the limitless dequeue call below
             let pruning_point_utxos =
-                match session.async_get_pruning_point_utxos(expected_pp, from_outpoint, CHUNK_SIZE, chunks_sent != 0).await {
+                // The outpoint marking the beginning of a chunk was already sent (or was already obtained by the requester)
+                match session.async_get_pruning_point_utxos(expected_pp, from_outpoint, CHUNK_SIZE, from_outpoint.is_some()).await {
                     Err(ConsensusError::UnexpectedPruningPoint) => return self.send_unexpected_pruning_point_message().await,
                     res => res,
                 }?;
@@ -215,14 +215,20 @@ impl RelayTransactionsFlow {
                 transactions.push(transaction);
             }
         }
-        let insert_results = self
+        let insertion = self
             .ctx
             .mining_manager()
             .clone()
             .validate_and_insert_transaction_batch(&consensus, transactions, Priority::Low, Orphan::Allowed, RbfPolicy::Allowed)
             .await;
+        self.ctx.on_transactions_replaced(insertion.replacements.iter());
 
-        for res in insert_results.iter() {
+        if insertion.results.iter().any(|res| res.is_ok()) {
+            // The peer relayed new transactions, which protects it from inbound eviction
+            self.router.mark_tx_relay();
+        }
+
+        for res in insertion.results.iter() {
             match res {
                 Ok(_) => {}
                 Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(transaction_id))) => {
@@ -241,7 +247,7 @@ impl RelayTransactionsFlow {
 
         self.ctx
             .broadcast_transactions(
-                insert_results.into_iter().filter_map(|res| match res {
+                insertion.results.into_iter().filter_map(|res| match res {
                     Ok(x) => Some(x.id()),
                     Err(_) => None,
                 }),
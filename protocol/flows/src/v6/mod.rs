@@ -23,6 +23,12 @@ use crate::v6::request_pruning_point_and_anticone::PruningPointAndItsAnticoneReq
 pub(crate) mod request_pruning_point_and_anticone;
 
 pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+    register_flows(ctx, router, false)
+}
+
+/// Registers the flows of protocol version 6, with block relay going through compact blocks if `compact_blocks`
+/// is set. Later protocol versions build upon this set of flows.
+pub(crate) fn register_flows(ctx: FlowContext, router: Arc<Router>, compact_blocks: bool) -> Vec<Box<dyn Flow>> {
     // IBD flow <-> invs flow communication uses a job channel in order to always
     // maintain at most a single pending job which can be updated
     let (ibd_sender, relay_receiver) = channel::job();
@@ -53,6 +59,7 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             ctx.clone(),
             router.clone(),
             router.subscribe(vec![KaspadMessagePayloadType::RequestRelayBlocks]),
+            compact_blocks,
         )),
         Box::new(ReceivePingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![KaspadMessagePayloadType::Ping]))),
         Box::new(SendPingsFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![KaspadMessagePayloadType::Pong]))),
@@ -136,6 +143,7 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             shared_invs_route.clone(),
             router.subscribe(vec![]),
             ibd_sender.clone(),
+            compact_blocks,
         )) as Box<dyn Flow>
     }));
 
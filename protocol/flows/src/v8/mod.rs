@@ -0,0 +1,46 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+
+use kaspa_p2p_lib::{KaspadMessagePayloadType, Router};
+use std::sync::Arc;
+
+use crate::v8::{
+    request_block_transactions::HandleBlockTransactionsRequests, request_receipts::HandleReceiptRequests,
+    txrecon::flow::TxReconciliationFlow,
+};
+
+pub(crate) mod request_block_transactions;
+pub(crate) mod request_receipts;
+pub(crate) mod txrecon;
+
+pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+    // Version 8 relays blocks as compact blocks and adds the below flows on top of the flows of version 6
+    let mut flows = crate::v6::register_flows(ctx.clone(), router.clone(), true);
+
+    flows.extend([
+        Box::new(HandleBlockTransactionsRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![KaspadMessagePayloadType::RequestBlockTransactions]),
+        )) as Box<dyn Flow>,
+        Box::new(HandleReceiptRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                KaspadMessagePayloadType::RequestTxReceipt,
+                KaspadMessagePayloadType::RequestPochm,
+                KaspadMessagePayloadType::RequestPosterityHeaders,
+            ]),
+        )),
+        Box::new(TxReconciliationFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![
+                KaspadMessagePayloadType::RequestTxSketch,
+                KaspadMessagePayloadType::TxSketch,
+                KaspadMessagePayloadType::TxReconciliationDifference,
+            ]),
+        )),
+    ]);
+
+    flows
+}
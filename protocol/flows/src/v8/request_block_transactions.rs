@@ -0,0 +1,61 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    dequeue_with_request_id, make_response,
+    pb::{kaspad_message::Payload, BlockTransactionsMessage},
+    IncomingRoute, Router,
+};
+use std::sync::Arc;
+
+/// Serves the transactions of a relayed compact block which the peer could not resolve from its mempool
+pub struct HandleBlockTransactionsRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleBlockTransactionsRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleBlockTransactionsRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let (msg, request_id) = dequeue_with_request_id!(self.incoming_route, Payload::RequestBlockTransactions)?;
+            let (hash, indexes): (Hash, Vec<u32>) = msg.try_into()?;
+
+            let session = self.ctx.consensus().unguarded_session();
+            let block = session.async_get_block(hash).await?;
+            let transactions = indexes
+                .iter()
+                .map(|&index| {
+                    block.transactions.get(index as usize).map(|tx| tx.into()).ok_or_else(|| {
+                        ProtocolError::OtherOwned(format!("requested transaction index {} of block {} is out of range", index, hash))
+                    })
+                })
+                .collect::<Result<Vec<_>, ProtocolError>>()?;
+
+            self.router
+                .enqueue(make_response!(
+                    Payload::BlockTransactions,
+                    BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                    request_id
+                ))
+                .await?;
+            debug!("sent {} transactions of block {} to peer {}", indexes.len(), hash, self.router);
+        }
+    }
+}
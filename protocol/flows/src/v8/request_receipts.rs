@@ -0,0 +1,202 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_consensusmanager::ConsensusProxy;
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    make_response,
+    pb::{kaspad_message::Payload, KaspadMessage, PochmMessage, PosterityHeadersMessage, TxReceiptMessage},
+    IncomingRoute, Router,
+};
+use std::{fmt::Display, sync::Arc};
+
+/// The maximum number of posterity headers sent in response to a single request
+pub const MAX_POSTERITY_HEADERS: u32 = 500;
+
+/// Serves transaction receipts, proofs of chain membership and posterity headers to peers verifying receipts
+/// without a trusted node. Proofs which cannot be produced are answered with an empty response rather than an
+/// error, since the requester cannot tell in advance whether the data is available.
+pub struct HandleReceiptRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleReceiptRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleReceiptRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let msg = self.incoming_route.recv().await.ok_or(ProtocolError::ConnectionClosed)?;
+            let session = self.ctx.consensus().unguarded_session();
+            let response = Self::respond(&session, msg, &self.router).await?;
+            self.router.enqueue(response).await?;
+        }
+    }
+
+    /// Builds the response to a single request of `peer`
+    async fn respond(session: &ConsensusProxy, msg: KaspadMessage, peer: impl Display) -> Result<KaspadMessage, ProtocolError> {
+        let request_id = msg.request_id;
+        let response = match msg.payload {
+            Some(Payload::RequestTxReceipt(payload)) => {
+                let (tx_id, accepting_block): (TransactionId, Hash) = payload.try_into()?;
+                let receipt = if Self::has_block_body(session, accepting_block).await {
+                    session.async_generate_tx_receipt(tx_id, accepting_block).await.ok()
+                } else {
+                    None
+                };
+                debug!("serving receipt of tx {} (found: {}) to peer {}", tx_id, receipt.is_some(), peer);
+                make_response!(
+                    Payload::TxReceipt,
+                    TxReceiptMessage { transaction_id: Some(tx_id.into()), receipt: receipt.as_ref().map(|r| r.into()) },
+                    request_id
+                )
+            }
+            Some(Payload::RequestPochm(payload)) => {
+                let hash: Hash = payload.try_into()?;
+                let pochm =
+                    if Self::has_block_body(session, hash).await { session.async_generate_pochm(hash).await.ok() } else { None };
+                debug!("serving pochm of block {} (found: {}) to peer {}", hash, pochm.is_some(), peer);
+                make_response!(
+                    Payload::Pochm,
+                    PochmMessage { block_hash: Some(hash.into()), pochm: pochm.as_ref().map(|p| p.into()) },
+                    request_id
+                )
+            }
+            Some(Payload::RequestPosterityHeaders(payload)) => {
+                let (low, limit): (Hash, u32) = payload.try_into()?;
+                let limit = limit.min(MAX_POSTERITY_HEADERS) as usize;
+                let headers = if Self::has_block_body(session, low).await {
+                    session.async_get_posterity_headers(low, limit).await.unwrap_or_default()
+                } else {
+                    vec![]
+                };
+                debug!("serving {} posterity headers above {} to peer {}", headers.len(), low, peer);
+                make_response!(
+                    Payload::PosterityHeaders,
+                    PosterityHeadersMessage { headers: headers.iter().map(|header| header.as_ref().into()).collect() },
+                    request_id
+                )
+            }
+            _ => {
+                return Err(ProtocolError::UnexpectedMessage(
+                    stringify!(Payload::RequestTxReceipt | Payload::RequestPochm | Payload::RequestPosterityHeaders),
+                    msg.payload.as_ref().map(|v| v.into()),
+                ))
+            }
+        };
+        Ok(response)
+    }
+
+    /// Proofs are only generated for blocks with a body, since the receipts manager assumes the
+    /// data above them was not pruned
+    async fn has_block_body(session: &ConsensusProxy, hash: Hash) -> bool {
+        session.async_get_block_status(hash).await.is_some_and(|status| status.has_block_body())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        blockstatus::BlockStatus,
+        errors::consensus::{ConsensusError, ConsensusResult},
+        header::Header,
+        receipts::{Pochm, TxReceipt},
+    };
+    use kaspa_consensusmanager::{ConsensusInstance, SessionLock};
+    use kaspa_p2p_lib::pb::{PingMessage, RequestPochmMessage, RequestPosterityHeadersMessage, RequestTxReceiptMessage};
+
+    /// Block 1 has a body, block 2 is header-only and any other block is unknown. Receipts and proofs cannot be
+    /// generated, while posterity headers are always available up to the requested limit.
+    struct MockConsensus;
+
+    impl ConsensusApi for MockConsensus {
+        fn get_block_status(&self, hash: Hash) -> Option<BlockStatus> {
+            match hash {
+                hash if hash == 1.into() => Some(BlockStatus::StatusUTXOValid),
+                hash if hash == 2.into() => Some(BlockStatus::StatusHeaderOnly),
+                _ => None,
+            }
+        }
+
+        fn generate_tx_receipt(&self, tx_id: Hash, _: Option<Hash>, _: Option<u64>) -> ConsensusResult<TxReceipt> {
+            Err(ConsensusError::MissingTx(tx_id))
+        }
+
+        fn generate_pochm(&self, _: Hash) -> ConsensusResult<Pochm> {
+            Err(ConsensusError::General("required data to create a proof of chain membership appears missing"))
+        }
+
+        fn get_posterity_headers(&self, _: Hash, limit: usize) -> ConsensusResult<Vec<Arc<Header>>> {
+            Ok((1..=limit as u64).map(|i| Arc::new(Header::from_precomputed_hash(i.into(), vec![]))).collect())
+        }
+    }
+
+    async fn respond(payload: Payload) -> Result<Payload, ProtocolError> {
+        let session = ConsensusInstance::new(SessionLock::new(), Arc::new(MockConsensus)).session().await;
+        let response =
+            HandleReceiptRequests::respond(&session, KaspadMessage { payload: Some(payload), request_id: 7, response_id: 0 }, "test")
+                .await?;
+        assert_eq!(7, response.response_id);
+        Ok(response.payload.unwrap())
+    }
+
+    async fn posterity_headers(low: u64, limit: u32) -> usize {
+        let request = RequestPosterityHeadersMessage { low_hash: Some(Hash::from(low).into()), limit };
+        match respond(Payload::RequestPosterityHeaders(request)).await.unwrap() {
+            Payload::PosterityHeaders(response) => response.headers.len(),
+            payload => panic!("unexpected response {payload:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_posterity_headers_requests() {
+        assert_eq!(3, posterity_headers(1, 3).await);
+        // The limit requested by the peer is capped
+        assert_eq!(MAX_POSTERITY_HEADERS as usize, posterity_headers(1, u32::MAX).await);
+        // Blocks without a body, known or not, are answered with an empty response
+        assert_eq!(0, posterity_headers(2, 3).await);
+        assert_eq!(0, posterity_headers(3, 3).await);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_requests() {
+        // Consensus errors are answered with a missing proof rather than failing the flow
+        let tx_id = Hash::from(5);
+        let request = RequestTxReceiptMessage { transaction_id: Some(tx_id.into()), accepting_block_hash: Some(Hash::from(1).into()) };
+        match respond(Payload::RequestTxReceipt(request)).await.unwrap() {
+            Payload::TxReceipt(response) => {
+                let (id, receipt): (TransactionId, Option<TxReceipt>) = response.try_into().unwrap();
+                assert_eq!(tx_id, id);
+                assert!(receipt.is_none());
+            }
+            payload => panic!("unexpected response {payload:?}"),
+        }
+        let request = RequestPochmMessage { block_hash: Some(Hash::from(1).into()) };
+        match respond(Payload::RequestPochm(request)).await.unwrap() {
+            Payload::Pochm(response) => assert!(response.pochm.is_none()),
+            payload => panic!("unexpected response {payload:?}"),
+        }
+
+        // Malformed and unexpected requests fail the flow
+        let request = RequestPochmMessage { block_hash: None };
+        assert!(matches!(respond(Payload::RequestPochm(request)).await, Err(ProtocolError::ConversionError(_))));
+        assert!(matches!(respond(Payload::Ping(PingMessage { nonce: 1 })).await, Err(ProtocolError::UnexpectedMessage(..))));
+    }
+}
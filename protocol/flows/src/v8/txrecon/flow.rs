@@ -0,0 +1,227 @@
+use super::sketch::{SketchCell, TxSketch};
+use crate::{
+    flow_context::FlowContext,
+    flow_trait::Flow,
+    flowcontext::transactions::{MAX_RECONCILIATION_SET_SIZE, TRANSACTION_ID_BYTES},
+};
+use itertools::Itertools;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::debug;
+use kaspa_mining::model::short_id::{ShortIdSalt, ShortTransactionId};
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    dequeue, dequeue_with_timeout, make_message,
+    pb::{
+        kaspad_message::Payload, InvTransactionsMessage, RequestTxSketchMessage, TxReconciliationDifferenceMessage, TxSketchMessage,
+    },
+    IncomingRoute, Router,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// The interval between reconciliation rounds initiated with each outbound peer
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(4);
+
+/// The expected fraction (as a divisor) of the smaller set which is missing from the other side, on top of the
+/// difference in set sizes. Underestimating only costs a fallback to flooding the sets of the round
+const RECONCILIATION_DIFFERENCE_DIVISOR: usize = 4;
+
+/// Approximate wire sizes used for estimating the bandwidth spent on reconciliation
+const SKETCH_CELL_BYTES: u64 = 20;
+const SHORT_ID_BYTES: u64 = 8;
+
+/// Relays transactions with a peer by periodic set reconciliation, as negotiated through the version message.
+///
+/// Transactions which are not flooded to the peer accumulate in its reconciliation set. Every round, the peer
+/// which opened the connection requests a sketch of the short ids withheld by the other side, subtracts it from
+/// a sketch of its own set, and both sides then announce only the transactions the other side is missing through
+/// the regular inv flow. If the difference cannot be decoded, both sides fall back to announcing their full sets.
+///
+/// Short ids are salted per connection, so that transactions cannot be crafted to collide on all connections.
+/// Transactions whose short ids still collide within a set are announced in full instead of being reconciled.
+pub struct TxReconciliationFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for TxReconciliationFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        if !self.ctx.is_tx_reconciliation_peer(&self.router) {
+            return Ok(());
+        }
+        self.ctx.register_tx_reconciliation_peer(self.router.key()).await;
+        let res = if self.router.is_outbound() { self.initiate_rounds().await } else { self.respond_to_rounds().await };
+        self.ctx.unregister_tx_reconciliation_peer(self.router.key()).await;
+        res
+    }
+}
+
+impl TxReconciliationFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn initiate_rounds(&mut self) -> Result<(), ProtocolError> {
+        let (salt_k0, salt_k1) = rand::random();
+        let salt = ShortIdSalt::new(salt_k0, salt_k1);
+        loop {
+            sleep(RECONCILIATION_INTERVAL).await;
+
+            let (local_set, colliding) = self.take_reconciliation_set(salt).await;
+            self.router
+                .enqueue(make_message!(
+                    Payload::RequestTxSketch,
+                    RequestTxSketchMessage { set_size: local_set.len() as u64, salt_k0, salt_k1 }
+                ))
+                .await?;
+
+            let msg = dequeue_with_timeout!(self.incoming_route, Payload::TxSketch)?;
+            let remote_sketch = TxSketch::from_cells(msg.cells.iter().map(SketchCell::from).collect())
+                .ok_or(ProtocolError::Other("received a malformed transaction sketch"))?;
+            let mut local_sketch = TxSketch::empty_like(&remote_sketch);
+            local_set.keys().for_each(|&short_id| local_sketch.insert(short_id));
+
+            let (mut announced, msg) = match local_sketch.decode_difference(&remote_sketch) {
+                Some((local_only, remote_only)) => {
+                    debug!(
+                        "Transaction reconciliation with peer {}: {} transactions missing locally, {} missing remotely",
+                        self.router,
+                        remote_only.len(),
+                        local_only.len()
+                    );
+                    let announced = local_only.iter().filter_map(|short_id| local_set.get(short_id).copied()).collect_vec();
+                    (announced, TxReconciliationDifferenceMessage { success: true, missing_short_ids: remote_only })
+                }
+                None => {
+                    debug!("Transaction reconciliation with peer {} failed to decode, falling back to flooding", self.router);
+                    (
+                        local_set.into_values().collect_vec(),
+                        TxReconciliationDifferenceMessage { success: false, missing_short_ids: vec![] },
+                    )
+                }
+            };
+            announced.extend(colliding);
+            let difference_bytes = msg.missing_short_ids.len() as u64 * SHORT_ID_BYTES;
+            self.router.enqueue(make_message!(Payload::TxReconciliationDifference, msg)).await?;
+            self.announce(announced, difference_bytes).await?;
+        }
+    }
+
+    async fn respond_to_rounds(&mut self) -> Result<(), ProtocolError> {
+        let mut connection_salt = None;
+        loop {
+            let msg = dequeue!(self.incoming_route, Payload::RequestTxSketch)?;
+            let remote_set_size = (msg.set_size as usize).min(MAX_RECONCILIATION_SET_SIZE);
+            let salt = *connection_salt.get_or_insert(ShortIdSalt::new(msg.salt_k0, msg.salt_k1));
+            if salt != ShortIdSalt::new(msg.salt_k0, msg.salt_k1) {
+                return Err(ProtocolError::Other("peer changed the short id salt of the connection"));
+            }
+
+            let (local_set, colliding) = self.take_reconciliation_set(salt).await;
+            let mut sketch = TxSketch::with_capacity(Self::estimate_difference(local_set.len(), remote_set_size));
+            local_set.keys().for_each(|&short_id| sketch.insert(short_id));
+            let sketch_bytes = sketch.cells().len() as u64 * SKETCH_CELL_BYTES;
+            self.router
+                .enqueue(make_message!(
+                    Payload::TxSketch,
+                    TxSketchMessage { cells: sketch.cells().iter().map(|cell| cell.into()).collect() }
+                ))
+                .await?;
+
+            let msg = dequeue_with_timeout!(self.incoming_route, Payload::TxReconciliationDifference)?;
+            if msg.missing_short_ids.len() > local_set.len() {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "peer reported {} missing transactions out of a reconciliation set of {}",
+                    msg.missing_short_ids.len(),
+                    local_set.len()
+                )));
+            }
+            let mut announced = if msg.success {
+                msg.missing_short_ids.iter().filter_map(|short_id| local_set.get(short_id).copied()).collect_vec()
+            } else {
+                debug!("Transaction reconciliation with peer {} failed to decode, falling back to flooding", self.router);
+                local_set.into_values().collect_vec()
+            };
+            announced.extend(colliding);
+            self.announce(announced, sketch_bytes).await?;
+        }
+    }
+
+    /// Takes the transactions withheld from the peer, keyed by their short ids salted with `salt`, along with
+    /// the transactions whose short ids collide
+    async fn take_reconciliation_set(&self, salt: ShortIdSalt) -> (HashMap<ShortTransactionId, TransactionId>, Vec<TransactionId>) {
+        let set: HashSet<TransactionId> = self.ctx.take_tx_reconciliation_set(self.router.key()).await;
+        key_by_short_ids(set, |id| salt.short_id(id))
+    }
+
+    /// Estimates the size of the symmetric difference between the reconciliation sets of both sides
+    fn estimate_difference(local_set_size: usize, remote_set_size: usize) -> usize {
+        local_set_size.abs_diff(remote_set_size) + local_set_size.min(remote_set_size) / RECONCILIATION_DIFFERENCE_DIVISOR + 1
+    }
+
+    /// Announces the transactions resulting from a reconciliation round through the regular inv flow of the peer
+    async fn announce(&self, transaction_ids: Vec<TransactionId>, round_bytes: u64) -> Result<(), ProtocolError> {
+        self.ctx
+            .tx_reconciliation_counters()
+            .reconciliation_bytes
+            .fetch_add(round_bytes + transaction_ids.len() as u64 * TRANSACTION_ID_BYTES, Ordering::Relaxed);
+        if transaction_ids.is_empty() {
+            return Ok(());
+        }
+        // Reconciliation sets are bounded well below the inv message capacity
+        self.router
+            .enqueue(make_message!(
+                Payload::InvTransactions,
+                InvTransactionsMessage { ids: transaction_ids.into_iter().map(|id| id.into()).collect() }
+            ))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Keys `transaction_ids` by their short ids. Transactions sharing a short id cannot be told apart by the peer,
+/// so they are returned separately
+fn key_by_short_ids(
+    transaction_ids: impl IntoIterator<Item = TransactionId>,
+    short_id: impl Fn(&TransactionId) -> ShortTransactionId,
+) -> (HashMap<ShortTransactionId, TransactionId>, Vec<TransactionId>) {
+    let mut keyed: HashMap<ShortTransactionId, TransactionId> = HashMap::new();
+    let mut colliding_short_ids = HashSet::new();
+    let mut colliding = vec![];
+    for id in transaction_ids {
+        let key = short_id(&id);
+        if colliding_short_ids.contains(&key) {
+            colliding.push(id);
+        } else if let Some(other) = keyed.remove(&key) {
+            colliding_short_ids.insert(key);
+            colliding.extend([other, id]);
+        } else {
+            keyed.insert(key, id);
+        }
+    }
+    (keyed, colliding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colliding_short_ids() {
+        let ids = (0..5).map(TransactionId::from_u64_word).collect_vec();
+        // The short ids of the first three transactions collide
+        let (keyed, colliding) = key_by_short_ids(ids.clone(), |id| id.to_le_u64()[3].max(2));
+        assert_eq!(HashMap::from([(3, ids[3]), (4, ids[4])]), keyed);
+        assert_eq!(ids[..3].iter().copied().collect::<HashSet<_>>(), colliding.into_iter().collect());
+    }
+}
@@ -0,0 +1,188 @@
+use kaspa_mining::model::short_id::ShortTransactionId;
+use kaspa_p2p_lib::pb::TxSketchCell;
+
+/// The number of cells each short id is mapped to. Cells are split into this many equal sub-tables
+/// so that the cells of a single short id are always distinct
+const HASH_COUNT: usize = 3;
+
+/// Per sub-table seeds used for mapping short ids to cells
+const CELL_SEEDS: [u64; HASH_COUNT] = [0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b];
+
+/// Seed of the checksum used for recognizing cells which hold a single short id
+const CHECKSUM_SEED: u64 = 0xa54ff53a5f1d36f1;
+
+/// The maximal number of cells accepted in a sketch received from a peer
+pub(crate) const MAX_SKETCH_CELLS: usize = HASH_COUNT * 4096;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SketchCell {
+    pub count: i32,
+    pub key_sum: u64,
+    pub check_sum: u64,
+}
+
+impl From<&TxSketchCell> for SketchCell {
+    fn from(cell: &TxSketchCell) -> Self {
+        Self { count: cell.count, key_sum: cell.key_sum, check_sum: cell.check_sum }
+    }
+}
+
+impl From<&SketchCell> for TxSketchCell {
+    fn from(cell: &SketchCell) -> Self {
+        Self { count: cell.count, key_sum: cell.key_sum, check_sum: cell.check_sum }
+    }
+}
+
+/// An invertible bloom lookup table over transaction short ids.
+///
+/// Subtracting the sketches of two sets yields a sketch of their symmetric difference, which can be
+/// decoded as long as the difference is small relative to the number of cells, regardless of the size
+/// of the sets themselves. This allows two peers to find the transactions known to only one of them
+/// by exchanging a single sketch sized by the expected difference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxSketch {
+    cells: Vec<SketchCell>,
+}
+
+impl TxSketch {
+    /// Creates an empty sketch expected to decode set differences of up to `capacity` short ids
+    pub fn with_capacity(capacity: usize) -> Self {
+        let cells = (HASH_COUNT * (capacity.div_ceil(2) + 2)).min(MAX_SKETCH_CELLS);
+        Self { cells: vec![SketchCell::default(); cells] }
+    }
+
+    /// Builds a sketch from cells received from a peer. Returns `None` if the cell count is not valid
+    pub fn from_cells(cells: Vec<SketchCell>) -> Option<Self> {
+        let is_valid = !cells.is_empty() && cells.len() % HASH_COUNT == 0 && cells.len() <= MAX_SKETCH_CELLS;
+        is_valid.then_some(Self { cells })
+    }
+
+    /// Creates an empty sketch with the same dimensions as `other`
+    pub fn empty_like(other: &Self) -> Self {
+        Self { cells: vec![SketchCell::default(); other.cells.len()] }
+    }
+
+    pub fn cells(&self) -> &[SketchCell] {
+        &self.cells
+    }
+
+    pub fn insert(&mut self, short_id: ShortTransactionId) {
+        self.toggle(short_id, 1);
+    }
+
+    /// Decodes the symmetric difference between the set summarized by this sketch and the set summarized by `other`,
+    /// returning the short ids found only in this set and the short ids found only in the other set respectively.
+    ///
+    /// Returns `None` if the sketches have different dimensions or if the difference exceeds the sketch capacity.
+    pub fn decode_difference(&self, other: &Self) -> Option<(Vec<ShortTransactionId>, Vec<ShortTransactionId>)> {
+        if self.cells.len() != other.cells.len() {
+            return None;
+        }
+        let mut diff = Self {
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| SketchCell {
+                    count: a.count.wrapping_sub(b.count),
+                    key_sum: a.key_sum ^ b.key_sum,
+                    check_sum: a.check_sum ^ b.check_sum,
+                })
+                .collect(),
+        };
+
+        let mut local_only = Vec::new();
+        let mut remote_only = Vec::new();
+        let mut pure_cells = (0..diff.cells.len()).filter(|&i| diff.is_pure(i)).collect::<Vec<_>>();
+        while let Some(index) = pure_cells.pop() {
+            // The cell might have been peeled since it was queued
+            if !diff.is_pure(index) {
+                continue;
+            }
+            let SketchCell { count, key_sum: short_id, .. } = diff.cells[index];
+            if count == 1 {
+                local_only.push(short_id);
+            } else {
+                remote_only.push(short_id);
+            }
+            diff.toggle(short_id, -count);
+            pure_cells.extend(diff.cell_indexes(short_id).into_iter().filter(|&i| diff.is_pure(i)));
+        }
+
+        // Decoding succeeded only if every short id was peeled off
+        diff.cells.iter().all(|cell| *cell == SketchCell::default()).then_some((local_only, remote_only))
+    }
+
+    fn toggle(&mut self, short_id: ShortTransactionId, count: i32) {
+        let check_sum = mix(short_id ^ CHECKSUM_SEED);
+        for index in self.cell_indexes(short_id) {
+            let cell = &mut self.cells[index];
+            cell.count = cell.count.wrapping_add(count);
+            cell.key_sum ^= short_id;
+            cell.check_sum ^= check_sum;
+        }
+    }
+
+    fn cell_indexes(&self, short_id: ShortTransactionId) -> [usize; HASH_COUNT] {
+        let sub_table_len = self.cells.len() / HASH_COUNT;
+        std::array::from_fn(|i| i * sub_table_len + (mix(short_id ^ CELL_SEEDS[i]) % sub_table_len as u64) as usize)
+    }
+
+    fn is_pure(&self, index: usize) -> bool {
+        let cell = &self.cells[index];
+        (cell.count == 1 || cell.count == -1) && mix(cell.key_sum ^ CHECKSUM_SEED) == cell.check_sum
+    }
+}
+
+/// A deterministic 64-bit mixing function (the splitmix64 finalizer). Sketches are exchanged between
+/// nodes, so cell mapping must not depend on per-process hasher randomization
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58476d1ce4e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn sketch_of(capacity: usize, short_ids: impl IntoIterator<Item = u64>) -> TxSketch {
+        let mut sketch = TxSketch::with_capacity(capacity);
+        short_ids.into_iter().for_each(|id| sketch.insert(mix(id)));
+        sketch
+    }
+
+    #[test]
+    fn test_sketch_decodes_difference() {
+        // Large sets sharing most elements, with a small difference in each direction
+        let local = sketch_of(40, 0..1020);
+        let remote = sketch_of(40, 20..1040);
+        let (local_only, remote_only) = local.decode_difference(&remote).expect("difference is within capacity");
+        assert_eq!(local_only.into_iter().sorted().collect_vec(), (0..20).map(mix).sorted().collect_vec());
+        assert_eq!(remote_only.into_iter().sorted().collect_vec(), (1020..1040).map(mix).sorted().collect_vec());
+
+        // Identical sets decode to an empty difference
+        let (local_only, remote_only) = local.decode_difference(&local.clone()).unwrap();
+        assert!(local_only.is_empty() && remote_only.is_empty());
+    }
+
+    #[test]
+    fn test_sketch_decoding_failures() {
+        // A difference far beyond capacity fails to decode rather than yielding a partial result
+        let local = sketch_of(10, 0..500);
+        let remote = sketch_of(10, 500..1000);
+        assert!(local.decode_difference(&remote).is_none());
+
+        // Sketches of different dimensions cannot be compared
+        assert!(sketch_of(10, 0..5).decode_difference(&sketch_of(20, 0..5)).is_none());
+
+        // Malformed cell counts are rejected
+        assert!(TxSketch::from_cells(vec![]).is_none());
+        assert!(TxSketch::from_cells(vec![SketchCell::default(); HASH_COUNT + 1]).is_none());
+        assert!(TxSketch::from_cells(vec![SketchCell::default(); MAX_SKETCH_CELLS + HASH_COUNT]).is_none());
+        assert!(TxSketch::from_cells(vec![SketchCell::default(); HASH_COUNT * 7]).is_some());
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    sync::{atomic::AtomicBool, Arc},
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -21,7 +21,12 @@ use kaspa_core::{
 };
 use kaspa_p2p_lib::Hub;
 
-use crate::rules::{mining_rule::MiningRule, sync_rate_rule::SyncRateRule, ExtraData};
+use crate::rules::{
+    mining_rule::MiningRuleEffect,
+    registry::{MiningRuleRegistry, MiningRuleState, MiningRuleTransition},
+    sync_rate_rule::{SyncRateRule, SyncRateRuleConfig},
+    ExtraData,
+};
 
 const RULE_ENGINE: &str = "mining-rule-engine";
 pub const SNAPSHOT_INTERVAL: u64 = 10;
@@ -31,12 +36,10 @@ pub struct MiningRuleEngine {
     config: Arc<Config>,
     processing_counters: Arc<ProcessingCounters>,
     tick_service: Arc<TickService>,
-    // Sync Rate Rule: Allow mining if sync rate is below threshold AND finality point is "recent" (defined below)
-    use_sync_rate_rule: Arc<AtomicBool>,
     consensus_manager: Arc<ConsensusManager>,
     hub: Hub,
     mining_rules: Arc<MiningRules>,
-    rules: Vec<Arc<dyn MiningRule>>,
+    rules: Arc<MiningRuleRegistry>,
 }
 
 impl MiningRuleEngine {
@@ -80,9 +83,7 @@ impl MiningRuleEngine {
                 trace!("Current Mining Rule: {:?}", self.mining_rules);
 
                 // Check for all the rules
-                for rule in &self.rules {
-                    rule.check_rule(&delta, &extra_data);
-                }
+                self.rules.check_rules(&delta, &extra_data, unix_now());
             }
 
             last_snapshot = snapshot;
@@ -97,21 +98,40 @@ impl MiningRuleEngine {
         tick_service: Arc<TickService>,
         hub: Hub,
         mining_rules: Arc<MiningRules>,
+        sync_rate_rule_config: SyncRateRuleConfig,
     ) -> Self {
-        let use_sync_rate_rule = Arc::new(AtomicBool::new(false));
-        let rules: Vec<Arc<(dyn MiningRule + 'static)>> = vec![Arc::new(SyncRateRule::new(use_sync_rate_rule.clone()))];
+        let rules = Arc::new(MiningRuleRegistry::default());
+        // Sync Rate Rule: Allow mining if sync rate is below threshold AND finality point is "recent"
+        rules.register(Arc::new(SyncRateRule::new(sync_rate_rule_config)));
+
+        Self { consensus_manager, config, processing_counters, tick_service, hub, mining_rules, rules }
+    }
+
+    pub fn rule_states(&self) -> Vec<MiningRuleState> {
+        self.rules.states()
+    }
+
+    /// Returns the recent rule transitions, oldest first
+    pub fn rule_transitions(&self) -> Vec<MiningRuleTransition> {
+        self.rules.transitions()
+    }
+
+    pub fn triggered_rules_count(&self) -> usize {
+        self.rules.triggered_count()
+    }
 
-        Self { consensus_manager, config, processing_counters, tick_service, hub, use_sync_rate_rule, mining_rules, rules }
+    pub fn rule_transitions_count(&self) -> u64 {
+        self.rules.total_transitions_count()
     }
 
     pub fn should_mine(&self, sink_daa_score_timestamp: DaaScoreTimestamp) -> bool {
-        if !self.has_sufficient_peer_connectivity() {
+        if !self.has_sufficient_peer_connectivity() || self.rules.is_effect_active(MiningRuleEffect::PreventMining) {
             return false;
         }
 
         let is_nearly_synced = self.is_nearly_synced(sink_daa_score_timestamp);
 
-        is_nearly_synced || self.use_sync_rate_rule.load(std::sync::atomic::Ordering::Relaxed)
+        is_nearly_synced || self.rules.is_effect_active(MiningRuleEffect::AllowMining)
     }
 
     /// In non-mining contexts, we consider the node synced if the sink is recent and it is connected
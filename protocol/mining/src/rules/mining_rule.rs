@@ -1,7 +1,47 @@
+use std::fmt::Display;
+
 use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
 
 use super::ExtraData;
 
+/// The effect a triggered mining rule has on the decision whether block templates may be used for mining
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningRuleEffect {
+    /// Allow mining even though the node is not considered nearly synced
+    AllowMining,
+    /// Prevent mining even though the node is considered nearly synced
+    PreventMining,
+}
+
+impl Display for MiningRuleEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiningRuleEffect::AllowMining => write!(f, "allow-mining"),
+            MiningRuleEffect::PreventMining => write!(f, "prevent-mining"),
+        }
+    }
+}
+
 pub trait MiningRule: Send + Sync + 'static {
+    /// A unique name identifying the rule
+    fn name(&self) -> &'static str;
+
+    /// The effect the rule has on mining while it is triggered
+    fn effect(&self) -> MiningRuleEffect;
+
+    /// Evaluates the rule against the processing counters delta of the last snapshot interval
     fn check_rule(&self, delta: &ProcessingCountersSnapshot, extra_data: &ExtraData);
+
+    /// Returns whether the rule is currently triggered
+    fn is_triggered(&self) -> bool;
+
+    /// The value measured by the rule on its latest evaluation, if any
+    fn observed_value(&self) -> Option<f64> {
+        None
+    }
+
+    /// The threshold the observed value is compared against, if any
+    fn threshold(&self) -> Option<f64> {
+        None
+    }
 }
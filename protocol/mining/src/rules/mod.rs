@@ -4,6 +4,8 @@ pub mod sync_rate_rule;
 
 pub mod mining_rule;
 
+pub mod registry;
+
 pub struct ExtraData {
     pub finality_point_timestamp: u64,
     pub target_time_per_block: u64,
@@ -0,0 +1,277 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, RwLock},
+};
+
+use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
+use kaspa_core::info;
+
+use super::{
+    mining_rule::{MiningRule, MiningRuleEffect},
+    ExtraData,
+};
+
+/// Maximum number of rule transitions kept in the history
+pub const MINING_RULE_TRANSITIONS_HISTORY_SIZE: usize = 64;
+
+/// The current state of a registered mining rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiningRuleState {
+    pub name: &'static str,
+    pub effect: MiningRuleEffect,
+    pub is_triggered: bool,
+    pub observed_value: Option<f64>,
+    pub threshold: Option<f64>,
+    /// Number of times the rule was triggered since the node started
+    pub trigger_count: u64,
+    /// Unix timestamp in milliseconds of the latest transition of the rule, if any
+    pub last_transition_timestamp: Option<u64>,
+}
+
+/// A change in the triggered state of a mining rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiningRuleTransition {
+    pub name: &'static str,
+    pub is_triggered: bool,
+    pub observed_value: Option<f64>,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+struct RuleEntry {
+    rule: Arc<dyn MiningRule>,
+    is_triggered: bool,
+    trigger_count: u64,
+    transitions_count: u64,
+    last_transition_timestamp: Option<u64>,
+}
+
+/// Holds the registered mining rules and tracks their state transitions
+#[derive(Default)]
+pub struct MiningRuleRegistry {
+    entries: RwLock<Vec<RuleEntry>>,
+    transitions: RwLock<VecDeque<MiningRuleTransition>>,
+}
+
+impl MiningRuleRegistry {
+    /// Registers a new rule. Returns false if a rule with the same name is already registered.
+    pub fn register(&self, rule: Arc<dyn MiningRule>) -> bool {
+        let mut entries = self.entries.write().unwrap();
+        if entries.iter().any(|entry| entry.rule.name() == rule.name()) {
+            return false;
+        }
+        let is_triggered = rule.is_triggered();
+        entries.push(RuleEntry { rule, is_triggered, trigger_count: 0, transitions_count: 0, last_transition_timestamp: None });
+        true
+    }
+
+    /// Evaluates all registered rules and records any resulting state transitions at `timestamp`
+    pub fn check_rules(&self, delta: &ProcessingCountersSnapshot, extra_data: &ExtraData, timestamp: u64) {
+        let mut entries = self.entries.write().unwrap();
+        for entry in entries.iter_mut() {
+            entry.rule.check_rule(delta, extra_data);
+
+            let is_triggered = entry.rule.is_triggered();
+            if is_triggered == entry.is_triggered {
+                continue;
+            }
+
+            entry.is_triggered = is_triggered;
+            entry.transitions_count += 1;
+            entry.last_transition_timestamp = Some(timestamp);
+            if is_triggered {
+                entry.trigger_count += 1;
+            }
+
+            let observed_value = entry.rule.observed_value();
+            info!(
+                "Mining rule {} {} ({})",
+                entry.rule.name(),
+                if is_triggered { "triggered" } else { "recovered" },
+                entry.rule.effect()
+            );
+
+            let mut transitions = self.transitions.write().unwrap();
+            transitions.push_back(MiningRuleTransition { name: entry.rule.name(), is_triggered, observed_value, timestamp });
+            while transitions.len() > MINING_RULE_TRANSITIONS_HISTORY_SIZE {
+                transitions.pop_front();
+            }
+        }
+    }
+
+    /// Returns whether any triggered rule has the given effect
+    pub fn is_effect_active(&self, effect: MiningRuleEffect) -> bool {
+        self.entries.read().unwrap().iter().any(|entry| entry.rule.effect() == effect && entry.rule.is_triggered())
+    }
+
+    pub fn states(&self) -> Vec<MiningRuleState> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|entry| MiningRuleState {
+                name: entry.rule.name(),
+                effect: entry.rule.effect(),
+                is_triggered: entry.is_triggered,
+                observed_value: entry.rule.observed_value(),
+                threshold: entry.rule.threshold(),
+                trigger_count: entry.trigger_count,
+                last_transition_timestamp: entry.last_transition_timestamp,
+            })
+            .collect()
+    }
+
+    /// Returns the recent rule transitions, oldest first
+    pub fn transitions(&self) -> Vec<MiningRuleTransition> {
+        self.transitions.read().unwrap().iter().cloned().collect()
+    }
+
+    pub fn triggered_count(&self) -> usize {
+        self.entries.read().unwrap().iter().filter(|entry| entry.is_triggered).count()
+    }
+
+    /// Total number of transitions of all rules since the node started
+    pub fn total_transitions_count(&self) -> u64 {
+        self.entries.read().unwrap().iter().map(|entry| entry.transitions_count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{atomic::AtomicBool, atomic::Ordering, Arc},
+        time::Duration,
+    };
+
+    use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
+    use kaspa_core::time::unix_now;
+
+    use super::{MiningRuleRegistry, MINING_RULE_TRANSITIONS_HISTORY_SIZE};
+    use crate::rules::{
+        mining_rule::{MiningRule, MiningRuleEffect},
+        sync_rate_rule::{SyncRateRule, SyncRateRuleConfig, SYNC_RATE_RULE_NAME},
+        ExtraData,
+    };
+
+    /// Prevents mining while no block bodies are being processed
+    #[derive(Default)]
+    struct StalledBodiesRule {
+        triggered: AtomicBool,
+    }
+
+    impl MiningRule for StalledBodiesRule {
+        fn name(&self) -> &'static str {
+            "stalled-bodies"
+        }
+
+        fn effect(&self) -> MiningRuleEffect {
+            MiningRuleEffect::PreventMining
+        }
+
+        fn check_rule(&self, delta: &ProcessingCountersSnapshot, _extra_data: &ExtraData) {
+            self.triggered.store(delta.body_counts == 0, Ordering::Relaxed);
+        }
+
+        fn is_triggered(&self) -> bool {
+            self.triggered.load(Ordering::Relaxed)
+        }
+    }
+
+    fn extra_data() -> ExtraData {
+        ExtraData {
+            elapsed_time: Duration::from_secs(10),
+            target_time_per_block: 100, // 10bps value
+            finality_point_timestamp: unix_now(),
+            finality_duration: 1000,
+            has_sufficient_peer_connectivity: true,
+        }
+    }
+
+    #[test]
+    fn test_register_rules() {
+        let registry = MiningRuleRegistry::default();
+        assert!(registry.register(Arc::new(SyncRateRule::new(SyncRateRuleConfig::default()))));
+        assert!(registry.register(Arc::new(StalledBodiesRule::default())));
+        assert!(!registry.register(Arc::new(StalledBodiesRule::default())), "Expected a duplicate rule name to be rejected");
+
+        let states = registry.states();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].name, SYNC_RATE_RULE_NAME);
+        assert_eq!(states[0].threshold, Some(SyncRateRuleConfig::default().threshold));
+        assert_eq!(states[1].name, "stalled-bodies");
+        assert!(states.iter().all(|state| !state.is_triggered && state.last_transition_timestamp.is_none()));
+    }
+
+    #[test]
+    fn test_rule_transitions() {
+        let registry = MiningRuleRegistry::default();
+        registry.register(Arc::new(SyncRateRule::new(SyncRateRuleConfig::new(0.5, 60, 10))));
+        registry.register(Arc::new(StalledBodiesRule::default()));
+
+        let extra_data = extra_data();
+        let good_snapshot = ProcessingCountersSnapshot { header_counts: 100, body_counts: 100, ..Default::default() };
+        let slow_snapshot = ProcessingCountersSnapshot { header_counts: 100, body_counts: 0, ..Default::default() };
+        let bad_snapshot = ProcessingCountersSnapshot::default();
+
+        registry.check_rules(&good_snapshot, &extra_data, 1);
+        assert!(registry.transitions().is_empty());
+        assert!(!registry.is_effect_active(MiningRuleEffect::AllowMining));
+        assert!(!registry.is_effect_active(MiningRuleEffect::PreventMining));
+
+        // Headers still arrive at the expected rate, but no bodies are processed
+        registry.check_rules(&slow_snapshot, &extra_data, 2);
+        assert!(!registry.is_effect_active(MiningRuleEffect::AllowMining));
+        assert!(registry.is_effect_active(MiningRuleEffect::PreventMining));
+
+        // Nothing is received, the sync rate drops below 0.5 on the fourth sample
+        for timestamp in 3..6 {
+            registry.check_rules(&bad_snapshot, &extra_data, timestamp);
+        }
+        assert!(registry.is_effect_active(MiningRuleEffect::AllowMining));
+        assert_eq!(registry.triggered_count(), 2);
+
+        // Bodies are processed again and the sync rate recovers to 0.5
+        registry.check_rules(&good_snapshot, &extra_data, 6);
+        assert!(!registry.is_effect_active(MiningRuleEffect::AllowMining));
+        assert!(!registry.is_effect_active(MiningRuleEffect::PreventMining));
+
+        let transitions = registry.transitions();
+        let summary = transitions.iter().map(|t| (t.name, t.is_triggered, t.timestamp)).collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("stalled-bodies", true, 2),
+                (SYNC_RATE_RULE_NAME, true, 5),
+                (SYNC_RATE_RULE_NAME, false, 6),
+                ("stalled-bodies", false, 6)
+            ]
+        );
+        assert_eq!(transitions[1].observed_value, Some(200.0 / 500.0));
+        assert_eq!(transitions[3].observed_value, None);
+
+        let states = registry.states();
+        assert!(states.iter().all(|state| state.trigger_count == 1 && state.last_transition_timestamp == Some(6)));
+        assert_eq!(registry.total_transitions_count(), 4);
+    }
+
+    #[test]
+    fn test_transitions_history_is_bounded() {
+        let registry = MiningRuleRegistry::default();
+        registry.register(Arc::new(StalledBodiesRule::default()));
+
+        let extra_data = extra_data();
+        let good_snapshot = ProcessingCountersSnapshot { header_counts: 100, body_counts: 100, ..Default::default() };
+        let bad_snapshot = ProcessingCountersSnapshot::default();
+
+        for i in 0..MINING_RULE_TRANSITIONS_HISTORY_SIZE as u64 {
+            registry.check_rules(&bad_snapshot, &extra_data, 2 * i);
+            registry.check_rules(&good_snapshot, &extra_data, 2 * i + 1);
+        }
+
+        let transitions = registry.transitions();
+        assert_eq!(transitions.len(), MINING_RULE_TRANSITIONS_HISTORY_SIZE);
+        assert_eq!(transitions.first().unwrap().timestamp, MINING_RULE_TRANSITIONS_HISTORY_SIZE as u64);
+        assert_eq!(registry.total_transitions_count(), 2 * MINING_RULE_TRANSITIONS_HISTORY_SIZE as u64);
+        assert_eq!(registry.states()[0].trigger_count, MINING_RULE_TRANSITIONS_HISTORY_SIZE as u64);
+    }
+}
@@ -2,7 +2,7 @@ use std::{
     collections::VecDeque,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
-        Arc, RwLock,
+        RwLock,
     },
 };
 
@@ -11,31 +11,76 @@ use kaspa_core::{time::unix_now, trace, warn};
 
 use crate::rule_engine::SNAPSHOT_INTERVAL;
 
-use super::{mining_rule::MiningRule, ExtraData};
+use super::{
+    mining_rule::{MiningRule, MiningRuleEffect},
+    ExtraData,
+};
+
+pub const SYNC_RATE_RULE_NAME: &str = "sync-rate";
 
 // within a 5 minute period, we expect sync rate less sensitive to sudden changes
 // but we use a lower threshold anyway because we want the warns to be less frequent
-const SYNC_RATE_THRESHOLD: f64 = 0.50;
+pub const SYNC_RATE_THRESHOLD: f64 = 0.50;
+// duration in seconds of the sync rate sample window
+pub const SYNC_RATE_WINDOW_DURATION: u64 = 5 * 60;
+// duration in seconds of samples required before considering this rule
+pub const SYNC_RATE_WINDOW_MIN_DURATION: u64 = 60;
 // number of samples you expect in a 5 minute interval, sampled every 10s
-const SYNC_RATE_WINDOW_MAX_SIZE: usize = 5 * 60 / (SNAPSHOT_INTERVAL as usize);
+const SYNC_RATE_WINDOW_MAX_SIZE: usize = (SYNC_RATE_WINDOW_DURATION / SNAPSHOT_INTERVAL) as usize;
 // number of samples required before considering this rule. This allows using the sync rate rule
 // even before the full window size is reached. Represents the number of samples in 1 minute
-const SYNC_RATE_WINDOW_MIN_THRESHOLD: usize = 60 / (SNAPSHOT_INTERVAL as usize);
+const SYNC_RATE_WINDOW_MIN_THRESHOLD: usize = (SYNC_RATE_WINDOW_MIN_DURATION / SNAPSHOT_INTERVAL) as usize;
+
+/// Operator-tunable parameters of the [`SyncRateRule`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncRateRuleConfig {
+    /// Sync rate below which the rule triggers
+    pub threshold: f64,
+    /// Maximum number of samples kept in the window
+    pub window_max_size: usize,
+    /// Number of samples required before the rule is evaluated
+    pub window_min_threshold: usize,
+}
+
+impl SyncRateRuleConfig {
+    /// Creates a config from a threshold and window durations given in seconds
+    pub fn new(threshold: f64, window_duration: u64, window_min_duration: u64) -> Self {
+        Self {
+            threshold,
+            window_max_size: ((window_duration / SNAPSHOT_INTERVAL) as usize).max(1),
+            window_min_threshold: ((window_min_duration / SNAPSHOT_INTERVAL) as usize).max(1),
+        }
+    }
+}
+
+impl Default for SyncRateRuleConfig {
+    fn default() -> Self {
+        Self {
+            threshold: SYNC_RATE_THRESHOLD,
+            window_max_size: SYNC_RATE_WINDOW_MAX_SIZE,
+            window_min_threshold: SYNC_RATE_WINDOW_MIN_THRESHOLD,
+        }
+    }
+}
 
 pub struct SyncRateRule {
-    pub use_sync_rate_rule: Arc<AtomicBool>,
+    config: SyncRateRuleConfig,
+    use_sync_rate_rule: AtomicBool,
     sync_rate_samples: RwLock<VecDeque<(u64, u64)>>,
     total_expected_blocks: AtomicU64,
     total_received_blocks: AtomicU64,
+    last_rate: RwLock<Option<f64>>,
 }
 
 impl SyncRateRule {
-    pub fn new(use_sync_rate_rule: Arc<AtomicBool>) -> Self {
+    pub fn new(config: SyncRateRuleConfig) -> Self {
         Self {
-            use_sync_rate_rule,
+            config,
+            use_sync_rate_rule: AtomicBool::new(false),
             sync_rate_samples: RwLock::new(VecDeque::new()),
             total_expected_blocks: AtomicU64::new(0),
             total_received_blocks: AtomicU64::new(0),
+            last_rate: RwLock::new(None),
         }
     }
 
@@ -51,13 +96,13 @@ impl SyncRateRule {
         samples.push_back((received_blocks, expected_blocks));
 
         // Remove old samples. Usually is a single op after the window is full per 10s:
-        while samples.len() > SYNC_RATE_WINDOW_MAX_SIZE {
+        while samples.len() > self.config.window_max_size {
             let (old_received_blocks, old_expected_blocks) = samples.pop_front().unwrap();
             self.total_received_blocks.fetch_sub(old_received_blocks, Ordering::SeqCst);
             self.total_expected_blocks.fetch_sub(old_expected_blocks, Ordering::SeqCst);
         }
 
-        samples.len() >= SYNC_RATE_WINDOW_MIN_THRESHOLD
+        samples.len() >= self.config.window_min_threshold
     }
 }
 
@@ -69,6 +114,14 @@ impl SyncRateRule {
 /// Trigger: Sync rate is below threshold and finality point is recent
 /// Recovery: Sync rate is back above threshold
 impl MiningRule for SyncRateRule {
+    fn name(&self) -> &'static str {
+        SYNC_RATE_RULE_NAME
+    }
+
+    fn effect(&self) -> MiningRuleEffect {
+        MiningRuleEffect::AllowMining
+    }
+
     fn check_rule(&self, delta: &ProcessingCountersSnapshot, extra_data: &ExtraData) {
         let expected_blocks = (extra_data.elapsed_time.as_millis() as u64) / extra_data.target_time_per_block;
         let received_blocks = delta.body_counts.max(delta.header_counts);
@@ -80,6 +133,7 @@ impl MiningRule for SyncRateRule {
 
         let rate: f64 =
             (self.total_received_blocks.load(Ordering::SeqCst) as f64) / (self.total_expected_blocks.load(Ordering::SeqCst) as f64);
+        *self.last_rate.write().unwrap() = Some(rate);
 
         // Finality point is considered "recent" if it is within 3 finality durations from the current time
         let is_finality_recent = extra_data.finality_point_timestamp >= unix_now().saturating_sub(extra_data.finality_duration * 3);
@@ -94,46 +148,54 @@ impl MiningRule for SyncRateRule {
             expected_blocks,
         );
 
-        if is_finality_recent && rate < SYNC_RATE_THRESHOLD {
+        if is_finality_recent && rate < self.config.threshold {
             // if sync rate rule conditions are met:
             if let Ok(false) = self.use_sync_rate_rule.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed) {
-                warn!("Sync rate {:.2} is below threshold: {}", rate, SYNC_RATE_THRESHOLD);
+                warn!("Sync rate {:.2} is below threshold: {}", rate, self.config.threshold);
             }
         } else {
             // else when sync rate conditions are not met:
             if let Ok(true) = self.use_sync_rate_rule.compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed) {
                 if !is_finality_recent {
-                    warn!("Sync rate {:.2} recovered: {} by entering IBD", rate, SYNC_RATE_THRESHOLD);
+                    warn!("Sync rate {:.2} recovered: {} by entering IBD", rate, self.config.threshold);
                 } else {
-                    warn!("Sync rate {:.2} recovered: {}", rate, SYNC_RATE_THRESHOLD);
+                    warn!("Sync rate {:.2} recovered: {}", rate, self.config.threshold);
                 }
             } else if !is_finality_recent {
                 trace!("Finality period is old. Timestamp: {}. Sync rate: {:.2}", extra_data.finality_point_timestamp, rate);
             }
         }
     }
+
+    fn is_triggered(&self) -> bool {
+        self.use_sync_rate_rule.load(Ordering::Relaxed)
+    }
+
+    fn observed_value(&self) -> Option<f64> {
+        *self.last_rate.read().unwrap()
+    }
+
+    fn threshold(&self) -> Option<f64> {
+        Some(self.config.threshold)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::{atomic::AtomicBool, Arc};
-
     use crate::rules::{mining_rule::MiningRule, sync_rate_rule::SYNC_RATE_WINDOW_MAX_SIZE, ExtraData};
     use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
     use kaspa_core::time::unix_now;
     use std::sync::atomic::*;
 
-    use super::{SyncRateRule, SYNC_RATE_WINDOW_MIN_THRESHOLD};
+    use super::{SyncRateRule, SyncRateRuleConfig, SYNC_RATE_WINDOW_MIN_THRESHOLD};
 
-    fn create_rule() -> (Arc<AtomicBool>, SyncRateRule) {
-        let use_sync_rate_rule = Arc::new(AtomicBool::new(false));
-        let rule = SyncRateRule::new(use_sync_rate_rule.clone());
-        (use_sync_rate_rule, rule)
+    fn create_rule() -> SyncRateRule {
+        SyncRateRule::new(SyncRateRuleConfig::default())
     }
 
     #[test]
     fn test_rule_end_to_end_flow() {
-        let (use_sync_rate_rule, rule) = create_rule();
+        let rule = create_rule();
 
         let good_snapshot =
             ProcessingCountersSnapshot { blocks_submitted: 100, header_counts: 100, body_counts: 100, ..Default::default() };
@@ -154,7 +216,7 @@ mod tests {
         }
 
         assert!(
-            !use_sync_rate_rule.load(Ordering::SeqCst),
+            !rule.is_triggered(),
             "Expected rule to not be triggered during normal operation. {} | {}",
             rule.total_received_blocks.load(Ordering::SeqCst),
             rule.total_expected_blocks.load(Ordering::SeqCst)
@@ -166,7 +228,7 @@ mod tests {
         }
 
         assert!(
-            use_sync_rate_rule.load(Ordering::SeqCst),
+            rule.is_triggered(),
             "Expected rule to trigger. {} | {}",
             rule.total_received_blocks.load(Ordering::SeqCst),
             rule.total_expected_blocks.load(Ordering::SeqCst)
@@ -177,7 +239,7 @@ mod tests {
         }
 
         assert!(
-            !use_sync_rate_rule.load(Ordering::SeqCst),
+            !rule.is_triggered(),
             "Expected rule to not be triggered during normal operation. {} | {}",
             rule.total_received_blocks.load(Ordering::SeqCst),
             rule.total_expected_blocks.load(Ordering::SeqCst)
@@ -186,7 +248,7 @@ mod tests {
 
     #[test]
     fn test_rule_with_old_finality() {
-        let (use_sync_rate_rule, rule) = create_rule();
+        let rule = create_rule();
 
         let bad_snapshot = ProcessingCountersSnapshot::default();
 
@@ -203,7 +265,7 @@ mod tests {
         }
 
         assert!(
-            !use_sync_rate_rule.load(Ordering::SeqCst),
+            !rule.is_triggered(),
             "Expected rule to trigger even with low sync rate if finality is old. {} | {}",
             rule.total_received_blocks.load(Ordering::SeqCst),
             rule.total_expected_blocks.load(Ordering::SeqCst)
@@ -212,7 +274,7 @@ mod tests {
 
     #[test]
     fn test_sync_rate_window_updates() {
-        let (_, rule) = create_rule();
+        let rule = create_rule();
 
         let received_blocks = 123;
         let expected_blocks = 456;
@@ -228,7 +290,7 @@ mod tests {
 
     #[test]
     fn test_sync_rate_window_update_result_sample_sizes() {
-        let (_, rule) = create_rule();
+        let rule = create_rule();
 
         for _ in 0..(SYNC_RATE_WINDOW_MIN_THRESHOLD - 1) {
             assert!(!rule.update_sync_rate_window(1, 1), "Expected false when window min size threshold is not filled but got true");
@@ -251,7 +313,7 @@ mod tests {
 
     #[test]
     fn test_sync_rate_window_update_result_when_window_is_filled() {
-        let (_, rule) = create_rule();
+        let rule = create_rule();
 
         let received_blocks = 10;
         let expected_blocks = 10;
@@ -281,4 +343,31 @@ mod tests {
             "Expected total expected blocks to be updated correctly"
         );
     }
+
+    #[test]
+    fn test_rule_with_configured_threshold() {
+        // Trigger as soon as the sync rate drops below 0.9, after a single sample
+        let rule = SyncRateRule::new(SyncRateRuleConfig::new(0.9, 60, 10));
+        assert_eq!(rule.threshold(), Some(0.9));
+        assert_eq!(rule.observed_value(), None);
+
+        let extra_data = &ExtraData {
+            elapsed_time: std::time::Duration::from_secs(10),
+            target_time_per_block: 100, // 10bps value
+            finality_point_timestamp: unix_now(),
+            finality_duration: 1000,
+            has_sufficient_peer_connectivity: true,
+        };
+
+        // 80 out of 100 expected blocks
+        let snapshot = ProcessingCountersSnapshot { header_counts: 80, body_counts: 80, ..Default::default() };
+        rule.check_rule(&snapshot, extra_data);
+
+        assert!(rule.is_triggered(), "Expected rule to trigger with a sync rate of 0.8 and a threshold of 0.9");
+        assert_eq!(rule.observed_value(), Some(0.8));
+
+        let default_rule = create_rule();
+        default_rule.check_rule(&snapshot, extra_data);
+        assert!(!default_rule.is_triggered(), "Expected default rule to wait for the minimum window size");
+    }
 }
@@ -8,12 +8,12 @@ async fn main() {
     kaspa_core::log::init_logger(None, "debug");
     // [0] - init p2p-adaptor
     let initializer = Arc::new(EchoFlowInitializer::new());
-    let adaptor = kaspa_p2p_lib::Adaptor::client_only(kaspa_p2p_lib::Hub::new(), initializer, Default::default());
+    let adaptor = kaspa_p2p_lib::Adaptor::client_only(kaspa_p2p_lib::Hub::new(), initializer, Default::default(), Default::default());
     // [1] - connect 128 peers + flows
     let ip_port = String::from("[::1]:50051");
     for i in 0..1 {
         debug!("P2P, p2p_client::main - starting peer:{}", i);
-        let _peer_key = adaptor.connect_peer_with_retries(ip_port.clone(), 16, Duration::from_secs(1)).await;
+        let _peer_key = adaptor.connect_peer_with_retries(ip_port.clone(), 0, 16, Duration::from_secs(1)).await;
     }
     // [2] - wait a few seconds and terminate
     tokio::time::sleep(Duration::from_secs(5)).await;
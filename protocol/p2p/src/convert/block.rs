@@ -1,6 +1,8 @@
-use super::{error::ConversionError, option::TryIntoOptionEx};
+use super::{error::ConversionError, model::compact::CompactBlock, option::TryIntoOptionEx};
 use crate::pb as protowire;
 use kaspa_consensus_core::{block::Block, tx::Transaction};
+use kaspa_hashes::Hash;
+use std::sync::Arc;
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -12,6 +14,21 @@ impl From<&Block> for protowire::BlockMessage {
     }
 }
 
+impl From<&CompactBlock> for protowire::CompactBlockMessage {
+    fn from(block: &CompactBlock) -> Self {
+        Self {
+            header: Some(block.header.as_ref().into()),
+            short_transaction_ids: block.short_ids.clone(),
+            prefilled_transactions: block
+                .prefilled_transactions
+                .iter()
+                .map(|(index, tx)| protowire::PrefilledTransaction { index: *index, transaction: Some(tx.into()) })
+                .collect(),
+            nonce: block.nonce,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -26,3 +43,39 @@ impl TryFrom<protowire::BlockMessage> for Block {
         ))
     }
 }
+
+impl TryFrom<protowire::CompactBlockMessage> for CompactBlock {
+    type Error = ConversionError;
+
+    fn try_from(block: protowire::CompactBlockMessage) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            Arc::new(block.header.try_into_ex()?),
+            block.nonce,
+            block.short_transaction_ids,
+            block
+                .prefilled_transactions
+                .into_iter()
+                .map(|p| Ok((p.index, p.transaction.try_into_ex()?)))
+                .collect::<Result<Vec<(u32, Transaction)>, Self::Error>>()?,
+        ))
+    }
+}
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((
+            msg.block_hash.try_into_ex()?,
+            msg.transactions.into_iter().map(|i| i.try_into()).collect::<Result<Vec<Transaction>, Self::Error>>()?,
+        ))
+    }
+}
@@ -13,7 +13,7 @@ use kaspa_consensus_core::{
     tx::{TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_hashes::Hash;
-use kaspa_utils::networking::{IpAddress, PeerId};
+use kaspa_utils::networking::{AddressRecord, PeerId};
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -151,11 +151,11 @@ impl TryFrom<protowire::PruningPointUtxoSetChunkMessage> for Vec<(TransactionOut
     }
 }
 
-impl TryFrom<protowire::RequestPruningPointUtxoSetMessage> for Hash {
+impl TryFrom<protowire::RequestPruningPointUtxoSetMessage> for (Hash, Option<TransactionOutpoint>) {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::RequestPruningPointUtxoSetMessage) -> Result<Self, Self::Error> {
-        msg.pruning_point_hash.try_into_ex()
+        Ok((msg.pruning_point_hash.try_into_ex()?, msg.from_outpoint.map(|outpoint| outpoint.try_into()).transpose()?))
     }
 }
 
@@ -191,7 +191,7 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     }
 }
 
-impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
+impl TryFrom<protowire::AddressesMessage> for Vec<AddressRecord> {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
@@ -0,0 +1,157 @@
+//!
+//! Model structures of compact block relay. A compact block carries the block header along with short ids of
+//! its transactions, which the receiving peer resolves from its mempool before requesting the missing ones.
+//!
+
+use kaspa_consensus_core::{
+    block::Block,
+    header::Header,
+    tx::{Transaction, TransactionId},
+};
+use kaspa_hashes::Hash;
+use std::sync::Arc;
+
+use crate::common::ProtocolError;
+
+/// A block where all non-prefilled transactions are referenced by their short ids
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+    /// A nonce chosen by the sending peer, salting the short ids along with the block hash
+    pub nonce: u64,
+    /// The short ids of the non-prefilled transactions, ordered as in the block
+    pub short_ids: Vec<u64>,
+    /// Transactions sent in full along with their index within the block, ordered by index
+    pub prefilled_transactions: Vec<(u32, Transaction)>,
+}
+
+impl CompactBlock {
+    pub fn new(header: Arc<Header>, nonce: u64, short_ids: Vec<u64>, prefilled_transactions: Vec<(u32, Transaction)>) -> Self {
+        Self { header, nonce, short_ids, prefilled_transactions }
+    }
+
+    /// Builds the compact representation of `block`, where `short_id` is salted with `nonce`. The coinbase
+    /// transaction is never found in mempools so it is always prefilled.
+    pub fn from_block(block: &Block, nonce: u64, short_id: impl Fn(&TransactionId) -> u64) -> Self {
+        let prefilled_transactions = block.transactions.first().map(|coinbase| (0, coinbase.clone())).into_iter().collect();
+        let short_ids = block.transactions.iter().skip(1).map(|tx| short_id(&tx.id())).collect();
+        Self::new(block.header.clone(), nonce, short_ids, prefilled_transactions)
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled_transactions.len()
+    }
+}
+
+/// A block under reconstruction from a compact block
+pub struct PartialBlock {
+    header: Arc<Header>,
+    transactions: Vec<Option<Transaction>>,
+}
+
+impl PartialBlock {
+    /// Places the prefilled transactions of `compact` and the resolved transactions at their block indices.
+    /// `resolved` holds an entry per short id of the compact block, in the same order.
+    pub fn new(compact: CompactBlock, resolved: Vec<Option<Arc<Transaction>>>) -> Result<Self, ProtocolError> {
+        if resolved.len() != compact.short_ids.len() {
+            return Err(ProtocolError::Other("resolved transactions do not match the short ids of the compact block"));
+        }
+        let transaction_count = compact.transaction_count();
+        let mut transactions: Vec<Option<Transaction>> = vec![None; transaction_count];
+        let mut is_prefilled = vec![false; transaction_count];
+        let mut last_index = None;
+        for (index, transaction) in compact.prefilled_transactions {
+            let index = index as usize;
+            if index >= transaction_count || last_index.is_some_and(|last| index <= last) {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "compact block {} has an out of order or out of range prefilled transaction index {}",
+                    compact.header.hash, index
+                )));
+            }
+            transactions[index] = Some(transaction);
+            is_prefilled[index] = true;
+            last_index = Some(index);
+        }
+        let free_slots = (0..transaction_count).filter(|&i| !is_prefilled[i]);
+        for (slot, transaction) in free_slots.zip(resolved) {
+            transactions[slot] = transaction.map(|tx| tx.as_ref().clone());
+        }
+        Ok(Self { header: compact.header, transactions })
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    /// Returns the block indices of the transactions which are still missing
+    pub fn missing_indexes(&self) -> Vec<u32> {
+        self.transactions.iter().enumerate().filter(|(_, tx)| tx.is_none()).map(|(i, _)| i as u32).collect()
+    }
+
+    /// Fills the transactions at the provided block indices
+    pub fn fill(&mut self, indexes: &[u32], transactions: Vec<Transaction>) -> Result<(), ProtocolError> {
+        if indexes.len() != transactions.len() {
+            return Err(ProtocolError::OtherOwned(format!(
+                "expected {} transactions of block {} but got {}",
+                indexes.len(),
+                self.header.hash,
+                transactions.len()
+            )));
+        }
+        for (&index, transaction) in indexes.iter().zip(transactions) {
+            let Some(slot) = self.transactions.get_mut(index as usize) else {
+                return Err(ProtocolError::OtherOwned(format!("block {} has no transaction index {}", self.header.hash, index)));
+            };
+            *slot = Some(transaction);
+        }
+        Ok(())
+    }
+
+    /// Returns the reconstructed block if no transaction is missing
+    pub fn try_into_block(self) -> Option<Block> {
+        let transactions = self.transactions.into_iter().collect::<Option<Vec<_>>>()?;
+        Some(Block::from_arcs(self.header, Arc::new(transactions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+
+    fn transaction(lock_time: u64) -> Transaction {
+        Transaction::new(0, vec![], vec![], lock_time, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    fn short_id(id: &TransactionId) -> u64 {
+        id.to_le_u64()[0]
+    }
+
+    #[test]
+    fn test_compact_block_reconstruction() {
+        let transactions = (0..4).map(transaction).collect::<Vec<_>>();
+        let block = Block::new(Header::from_precomputed_hash(1.into(), vec![]), transactions.clone());
+        let compact = CompactBlock::from_block(&block, 0, short_id);
+        assert_eq!(1, compact.prefilled_transactions.len());
+        assert_eq!(
+            vec![short_id(&transactions[1].id()), short_id(&transactions[2].id()), short_id(&transactions[3].id())],
+            compact.short_ids
+        );
+
+        // Only the transaction at index 2 is known
+        let resolved = vec![None, Some(Arc::new(transactions[2].clone())), None];
+        let mut partial = PartialBlock::new(compact, resolved).unwrap();
+        assert_eq!(vec![1, 3], partial.missing_indexes());
+        assert!(partial.fill(&[1, 3], vec![transactions[1].clone()]).is_err());
+        partial.fill(&[1, 3], vec![transactions[1].clone(), transactions[3].clone()]).unwrap();
+        assert!(partial.missing_indexes().is_empty());
+        let reconstructed = partial.try_into_block().unwrap();
+        assert_eq!(
+            transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>(),
+            reconstructed.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>()
+        );
+
+        // Prefilled indices must be strictly increasing and within range
+        let compact = CompactBlock::new(block.header.clone(), 0, vec![], vec![(1, transactions[0].clone())]);
+        assert!(PartialBlock::new(compact, vec![]).is_err());
+    }
+}
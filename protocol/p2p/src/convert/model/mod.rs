@@ -1,2 +1,3 @@
+pub mod compact;
 pub mod trusted;
 pub mod version;
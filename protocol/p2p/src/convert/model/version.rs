@@ -8,10 +8,31 @@ use kaspa_utils::networking::{NetAddress, PeerId};
 /// Maximum allowed length for the user agent field in a version message `VersionMessage`.
 pub const MAX_USER_AGENT_LEN: usize = 256;
 
+/// Service flag advertised by peers supporting the encrypted transport
+pub const ENCRYPTED_TRANSPORT_SERVICE_FLAG: u64 = 1 << 0;
+
+/// Service flag advertised by peers serving transaction receipts, proofs of chain membership and posterity headers
+pub const RECEIPTS_SERVICE_FLAG: u64 = 1 << 1;
+
+/// Service flag advertised by peers relaying transactions by set reconciliation
+pub const TX_RECONCILIATION_SERVICE_FLAG: u64 = 1 << 2;
+
+/// Service flag advertised by peers accepting onion addresses in address gossip
+pub const ONION_ADDRESSES_SERVICE_FLAG: u64 = 1 << 3;
+
+/// Service flag advertised by archival peers, which keep and serve block bodies below the pruning point
+pub const ARCHIVAL_SERVICE_FLAG: u64 = 1 << 4;
+
+/// Service flag advertised by peers maintaining a UTXO index
+pub const UTXOINDEX_SERVICE_FLAG: u64 = 1 << 5;
+
+/// Service flag advertised by header-only light nodes, which cannot serve block bodies
+pub const LIGHT_SERVICE_FLAG: u64 = 1 << 6;
+
 pub struct Version {
     pub protocol_version: u32,
     pub network: String,
-    pub services: u64, // TODO
+    pub services: u64,
     pub timestamp: u64,
     pub address: Option<NetAddress>,
     pub id: PeerId,
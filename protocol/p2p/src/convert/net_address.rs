@@ -4,7 +4,7 @@ use super::error::ConversionError;
 use crate::pb as protowire;
 
 use itertools::Itertools;
-use kaspa_utils::networking::{IpAddress, NetAddress};
+use kaspa_utils::networking::{AddressRecord, IpAddress, NetAddress, OnionAddress};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -13,20 +13,32 @@ use kaspa_utils::networking::{IpAddress, NetAddress};
 impl From<(IpAddress, u16)> for protowire::NetAddress {
     fn from((ip, port): (IpAddress, u16)) -> Self {
         Self {
-            timestamp: 0, // This field is not used anymore
+            timestamp: 0,
             ip: match ip.0 {
                 // We follow the IP encoding of golang's net.IP type
                 IpAddr::V4(ip) => ip.octets().to_vec(),
                 IpAddr::V6(ip) => ip.octets().to_vec(),
             },
             port: port as u32,
+            onion: vec![],
+            services: 0,
         }
     }
 }
 
 impl From<NetAddress> for protowire::NetAddress {
     fn from(item: NetAddress) -> Self {
-        (item.ip, item.port).into()
+        let mut addr: Self = (item.ip, item.port).into();
+        if let Some(onion) = item.onion {
+            addr.onion = onion.pubkey().to_vec();
+        }
+        addr
+    }
+}
+
+impl From<AddressRecord> for protowire::NetAddress {
+    fn from(item: AddressRecord) -> Self {
+        Self { timestamp: item.last_seen as i64, services: item.services, ..item.address.into() }
     }
 }
 
@@ -60,14 +72,29 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
     type Error = ConversionError;
 
     fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        if !item.onion.is_empty() {
+            // The ip of an onion address is derived from its public key, so the sent one is ignored
+            let pubkey =
+                <[u8; 32]>::try_from(item.onion.as_slice()).map_err(|_| ConversionError::IllegalOnionLength(item.onion.len()))?;
+            return Ok(NetAddress::new_onion(OnionAddress::from_pubkey(pubkey), item.port.try_into()?));
+        }
         let (ip, port) = item.try_into()?;
         Ok(NetAddress::new(ip, port))
     }
 }
 
+impl TryFrom<protowire::NetAddress> for AddressRecord {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        let (services, last_seen) = (item.services, item.timestamp.max(0) as u64);
+        Ok(AddressRecord::new(item.try_into()?, services, last_seen))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use kaspa_utils::networking::IpAddress;
+    use kaspa_utils::networking::{AddressRecord, IpAddress, NetAddress};
 
     use crate::pb;
     use std::{
@@ -77,14 +104,53 @@ mod tests {
 
     #[test]
     fn test_netaddress() {
-        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123 };
+        let net_addr_ipv4 =
+            pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123, onion: vec![], services: 0 };
         let ipv4 = Ipv4Addr::from_str("106.10.138.240").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv4.clone()).unwrap(), (ipv4, 123u16));
         assert_eq!(pb::NetAddress::from((ipv4, 123u16)), net_addr_ipv4);
 
-        let net_addr_ipv6 = pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456 };
+        let net_addr_ipv6 = pb::NetAddress {
+            timestamp: 0,
+            ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(),
+            port: 456,
+            onion: vec![],
+            services: 0,
+        };
         let ipv6 = Ipv6Addr::from_str("2001:0db8:85a3:0000:0000:8a2e:0370:7334").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv6.clone()).unwrap(), (ipv6, 456u16));
         assert_eq!(pb::NetAddress::from((ipv6, 456u16)), net_addr_ipv6);
     }
+
+    #[test]
+    fn test_onion_netaddress() {
+        let addr = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111").unwrap();
+        let pb_addr = pb::NetAddress::from(addr);
+        assert_eq!(pb_addr.onion, addr.onion.unwrap().pubkey().to_vec());
+        assert_eq!(NetAddress::try_from(pb_addr.clone()).unwrap(), addr);
+
+        // The sent ip is ignored in favor of the OnionCat mapping of the key
+        let spoofed = pb::NetAddress { ip: hex::decode("6a0a8af0").unwrap(), ..pb_addr };
+        assert_eq!(NetAddress::try_from(spoofed).unwrap(), addr);
+
+        let truncated = pb::NetAddress { onion: vec![1; 31], ..pb::NetAddress::from(addr) };
+        assert!(NetAddress::try_from(truncated).is_err());
+    }
+
+    #[test]
+    fn test_address_record() {
+        let record = AddressRecord::new(NetAddress::from_str("1.2.3.4:16111").unwrap(), 0b101, 1_700_000_000_000);
+        let pb_addr = pb::NetAddress::from(record);
+        assert_eq!((pb_addr.services, pb_addr.timestamp), (0b101, 1_700_000_000_000));
+        assert_eq!(AddressRecord::try_from(pb_addr.clone()).unwrap(), record);
+        assert_eq!(NetAddress::try_from(pb_addr).unwrap(), record.address);
+
+        // Addresses sent by older peers carry neither services nor a timestamp
+        let legacy = pb::NetAddress::from(record.address);
+        assert_eq!(AddressRecord::try_from(legacy).unwrap(), AddressRecord::from(record.address));
+
+        // A negative timestamp is treated as unknown
+        let negative = pb::NetAddress { timestamp: -1, ..pb::NetAddress::from(record) };
+        assert_eq!(AddressRecord::try_from(negative).unwrap().last_seen, 0);
+    }
 }
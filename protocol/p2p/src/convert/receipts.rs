@@ -0,0 +1,231 @@
+use super::{error::ConversionError, option::TryIntoOptionEx};
+use crate::pb as protowire;
+use kaspa_consensus_core::{
+    header::Header,
+    merkle::{LeafRoute, MerkleWitness, WitnessSegment},
+    receipts::{LegacyPochm, LogPathPochm, Pochm, PochmSegment, TxReceipt},
+    tx::TransactionId,
+};
+use kaspa_hashes::Hash;
+use prost::Message;
+use std::sync::Arc;
+
+// ----------------------------------------------------------------------------
+// consensus_core to protowire
+// ----------------------------------------------------------------------------
+
+impl From<&WitnessSegment> for protowire::MerkleWitnessSegment {
+    fn from(item: &WitnessSegment) -> Self {
+        Self { companion_hash: Some(item.companion_hash().into()), is_left_route: item.leaf_route() == LeafRoute::Left }
+    }
+}
+
+fn witness_to_protowire(witness: &MerkleWitness) -> Vec<protowire::MerkleWitnessSegment> {
+    witness.iter().map(|segment| segment.into()).collect()
+}
+
+impl From<&Pochm> for protowire::Pochm {
+    fn from(item: &Pochm) -> Self {
+        let pochm = match item {
+            Pochm::LogPath(log_path) => protowire::pochm::Pochm::LogPath(protowire::LogPathPochm {
+                segments: log_path
+                    .vec
+                    .iter()
+                    .map(|segment| protowire::PochmSegment {
+                        header: Some(segment.header.as_ref().into()),
+                        pchmr_witness: witness_to_protowire(&segment.leaf_in_pchmr_witness),
+                    })
+                    .collect(),
+            }),
+            Pochm::Legacy(legacy) => protowire::pochm::Pochm::Legacy(protowire::LegacyPochm {
+                top: Some(legacy.top.into()),
+                bottom: Some(legacy.bottom.into()),
+                headers: legacy.bfs_map.values().map(|header| header.as_ref().into()).collect(),
+            }),
+        };
+        Self { pochm: Some(pochm) }
+    }
+}
+
+impl From<&TxReceipt> for protowire::TxReceipt {
+    fn from(item: &TxReceipt) -> Self {
+        Self {
+            accepting_block_header: Some(item.accepting_block_header.as_ref().into()),
+            pochm: Some((&item.pochm).into()),
+            transaction_acceptance_proof: witness_to_protowire(&item.tx_acc_proof),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// protowire to consensus_core
+// ----------------------------------------------------------------------------
+
+impl TryFrom<protowire::MerkleWitnessSegment> for WitnessSegment {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::MerkleWitnessSegment) -> Result<Self, Self::Error> {
+        let leaf_route = if item.is_left_route { LeafRoute::Left } else { LeafRoute::Right };
+        Ok(Self::new(item.companion_hash.try_into_ex()?, leaf_route))
+    }
+}
+
+fn witness_from_protowire(witness: Vec<protowire::MerkleWitnessSegment>) -> Result<MerkleWitness, ConversionError> {
+    witness.into_iter().map(|segment| segment.try_into()).collect()
+}
+
+impl TryFrom<protowire::Pochm> for Pochm {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::Pochm) -> Result<Self, Self::Error> {
+        match item.pochm.ok_or(ConversionError::NoneValue)? {
+            protowire::pochm::Pochm::LogPath(log_path) => Ok(Pochm::LogPath(LogPathPochm {
+                vec: log_path
+                    .segments
+                    .into_iter()
+                    .map(|segment| {
+                        Ok(PochmSegment {
+                            header: Arc::new(segment.header.try_into_ex()?),
+                            leaf_in_pchmr_witness: witness_from_protowire(segment.pchmr_witness)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Self::Error>>()?,
+            })),
+            protowire::pochm::Pochm::Legacy(legacy) => Ok(Pochm::Legacy(LegacyPochm {
+                top: legacy.top.try_into_ex()?,
+                bottom: legacy.bottom.try_into_ex()?,
+                bfs_map: legacy
+                    .headers
+                    .into_iter()
+                    .map(|header| {
+                        let header: Header = header.try_into()?;
+                        Ok((header.hash, Arc::new(header)))
+                    })
+                    .collect::<Result<_, Self::Error>>()?,
+            })),
+        }
+    }
+}
+
+impl TryFrom<protowire::RequestTxReceiptMessage> for (TransactionId, Hash) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestTxReceiptMessage) -> Result<Self, Self::Error> {
+        Ok((msg.transaction_id.try_into_ex()?, msg.accepting_block_hash.try_into_ex()?))
+    }
+}
+
+impl TryFrom<protowire::TxReceiptMessage> for (TransactionId, Option<TxReceipt>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::TxReceiptMessage) -> Result<Self, Self::Error> {
+        let tracked_tx_id: TransactionId = msg.transaction_id.try_into_ex()?;
+        let receipt = msg
+            .receipt
+            .map(|receipt| {
+                Ok::<_, Self::Error>(TxReceipt {
+                    tracked_tx_id,
+                    accepting_block_header: Arc::new(receipt.accepting_block_header.try_into_ex()?),
+                    pochm: receipt.pochm.try_into_ex()?,
+                    tx_acc_proof: witness_from_protowire(receipt.transaction_acceptance_proof)?,
+                })
+            })
+            .transpose()?;
+        Ok((tracked_tx_id, receipt))
+    }
+}
+
+/// Decodes the wire encoding of a `TxReceiptMessage`, as received from peers serving receipts
+pub fn tx_receipt_from_bytes(bytes: &[u8]) -> Result<(TransactionId, Option<TxReceipt>), ConversionError> {
+    protowire::TxReceiptMessage::decode(bytes)?.try_into()
+}
+
+impl TryFrom<protowire::RequestPochmMessage> for Hash {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestPochmMessage) -> Result<Self, Self::Error> {
+        msg.block_hash.try_into_ex()
+    }
+}
+
+impl TryFrom<protowire::PochmMessage> for (Hash, Option<Pochm>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::PochmMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.pochm.map(|pochm| pochm.try_into()).transpose()?))
+    }
+}
+
+impl TryFrom<protowire::RequestPosterityHeadersMessage> for (Hash, u32) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestPosterityHeadersMessage) -> Result<Self, Self::Error> {
+        Ok((msg.low_hash.try_into_ex()?, msg.limit))
+    }
+}
+
+impl TryFrom<protowire::PosterityHeadersMessage> for Vec<Arc<Header>> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::PosterityHeadersMessage) -> Result<Self, Self::Error> {
+        msg.headers.into_iter().map(|header| header.try_into().map(Arc::new)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn header(hash: u64) -> Arc<Header> {
+        Arc::new(Header::from_precomputed_hash(hash.into(), vec![]))
+    }
+
+    fn witness() -> MerkleWitness {
+        vec![WitnessSegment::new(3.into(), LeafRoute::Left), WitnessSegment::new(4.into(), LeafRoute::Right)]
+    }
+
+    #[test]
+    fn test_tx_receipt_conversions() {
+        let tx_id = TransactionId::from(5);
+        let log_path = Pochm::LogPath(LogPathPochm {
+            vec: vec![
+                PochmSegment { header: header(1), leaf_in_pchmr_witness: witness() },
+                PochmSegment { header: header(2), leaf_in_pchmr_witness: vec![] },
+            ],
+        });
+        let legacy = Pochm::Legacy(LegacyPochm { bfs_map: HashMap::from([(2.into(), header(2))]), top: 1.into(), bottom: 2.into() });
+        for pochm in [log_path, legacy] {
+            let receipt = TxReceipt { tracked_tx_id: tx_id, accepting_block_header: header(1), pochm, tx_acc_proof: witness() };
+            let msg = protowire::TxReceiptMessage { transaction_id: Some(tx_id.into()), receipt: Some((&receipt).into()) };
+            let (id, converted) = tx_receipt_from_bytes(&msg.encode_to_vec()).unwrap();
+            let converted = converted.unwrap();
+            assert_eq!(tx_id, id);
+            assert_eq!(tx_id, converted.tracked_tx_id);
+            assert_eq!(msg.receipt, Some((&converted).into()));
+        }
+
+        // A missing receipt is not an error, while missing fields and malformed bytes are
+        let msg = protowire::TxReceiptMessage { transaction_id: Some(tx_id.into()), receipt: None };
+        assert!(matches!(tx_receipt_from_bytes(&msg.encode_to_vec()), Ok((id, None)) if id == tx_id));
+        let msg = protowire::TxReceiptMessage { transaction_id: None, receipt: None };
+        assert!(matches!(tx_receipt_from_bytes(&msg.encode_to_vec()), Err(ConversionError::NoneValue)));
+        assert!(matches!(tx_receipt_from_bytes(&[0xff; 8]), Err(ConversionError::DecodeError(_))));
+        assert!(matches!(Pochm::try_from(protowire::Pochm { pochm: None }), Err(ConversionError::NoneValue)));
+    }
+
+    #[test]
+    fn test_receipt_request_conversions() {
+        let msg = protowire::RequestPosterityHeadersMessage { low_hash: Some(Hash::from(1).into()), limit: 10 };
+        assert_eq!((Hash::from(1), 10), <(Hash, u32)>::try_from(msg).unwrap());
+        let msg = protowire::RequestPosterityHeadersMessage { low_hash: None, limit: 10 };
+        assert!(<(Hash, u32)>::try_from(msg).is_err());
+
+        let msg = protowire::PosterityHeadersMessage { headers: vec![header(1).as_ref().into(), header(2).as_ref().into()] };
+        let headers: Vec<Arc<Header>> = msg.clone().try_into().unwrap();
+        assert_eq!(msg.headers, headers.iter().map(|header| header.as_ref().into()).collect::<Vec<protowire::BlockHeader>>());
+
+        let msg = protowire::PochmMessage { block_hash: Some(Hash::from(1).into()), pochm: None };
+        assert!(matches!(<(Hash, Option<Pochm>)>::try_from(msg), Ok((hash, None)) if hash == Hash::from(1)));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::common::ProtocolError;
 use crate::core::hub::Hub;
+use crate::core::socks::OutboundProxies;
 use crate::ConnectionError;
 use crate::{core::connection_handler::ConnectionHandler, Router};
 use kaspa_utils::networking::NetAddress;
@@ -46,9 +47,14 @@ impl Adaptor {
     }
 
     /// Creates a P2P adaptor with only client-side support. Typical Kaspa nodes should use `Adaptor::bidirectional`
-    pub fn client_only(hub: Hub, initializer: Arc<dyn ConnectionInitializer>, counters: Arc<TowerConnectionCounters>) -> Arc<Self> {
+    pub fn client_only(
+        hub: Hub,
+        initializer: Arc<dyn ConnectionInitializer>,
+        counters: Arc<TowerConnectionCounters>,
+        proxies: OutboundProxies,
+    ) -> Arc<Self> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, proxies);
         let adaptor = Arc::new(Adaptor::new(None, connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         adaptor
@@ -60,28 +66,36 @@ impl Adaptor {
         hub: Hub,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        proxies: OutboundProxies,
     ) -> Result<Arc<Self>, ConnectionError> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, proxies);
         let server_termination = connection_handler.serve(serve_address)?;
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         Ok(adaptor)
     }
 
-    /// Connect to a new peer (no retries)
-    pub async fn connect_peer(&self, peer_address: String) -> Result<PeerKey, ConnectionError> {
-        self.connection_handler.connect_with_retry(peer_address, 1, Default::default()).await.map(|r| r.key())
+    /// Connect to a new peer (no retries). The `peer_services` known for the peer determine whether the connection
+    /// is encrypted, so that peers not known to support encryption are connected to in plaintext
+    pub async fn connect_peer(&self, peer_address: String, peer_services: u64) -> Result<PeerKey, ConnectionError> {
+        self.connection_handler.connect_with_retry(peer_address, peer_services, 1, Default::default()).await.map(|r| r.key())
     }
 
     /// Connect to a new peer (with params controlling retry behavior)
     pub async fn connect_peer_with_retries(
         &self,
         peer_address: String,
+        peer_services: u64,
         retry_attempts: u8,
         retry_interval: Duration,
     ) -> Result<PeerKey, ConnectionError> {
-        self.connection_handler.connect_with_retry(peer_address, retry_attempts, retry_interval).await.map(|r| r.key())
+        self.connection_handler.connect_with_retry(peer_address, peer_services, retry_attempts, retry_interval).await.map(|r| r.key())
+    }
+
+    /// Whether onion addresses can be connected to, which requires a proxy
+    pub fn can_reach_onion(&self) -> bool {
+        self.connection_handler.proxies().can_reach_onion()
     }
 
     /// Terminates all peers and cleans up any additional async resources
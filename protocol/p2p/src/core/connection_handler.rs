@@ -1,33 +1,45 @@
 use crate::common::ProtocolError;
+use crate::convert::model::version::ENCRYPTED_TRANSPORT_SERVICE_FLAG;
 use crate::core::hub::HubEvent;
+use crate::core::socks::{OutboundProxies, Socks5Proxy};
+use crate::core::transport::{accept_transport, initiate_encrypted, PlainStream, TransportConnectInfo, TransportStream};
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, KaspadMessage,
 };
 use crate::{ConnectionInitializer, Router};
 use futures::FutureExt;
-use kaspa_core::{debug, info};
+use hyper_util::rt::TokioIo;
+use kaspa_core::{debug, info, warn};
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{BodyExt, CountBytesBody, MapRequestBodyLayer, MapResponseBodyLayer, ServiceBuilder},
 };
-use std::net::ToSocketAddrs;
+use socket2::{SockRef, TcpKeepalive};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc::{channel as mpsc_channel, Sender as MpscSender};
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
-use tonic::transport::{Error as TonicError, Server as TonicServer};
+use tonic::transport::{Endpoint, Error as TonicError, Server as TonicServer, Uri};
 use tonic::{Request, Response, Status as TonicStatus, Streaming};
+use tower::service_fn;
 
 #[derive(Error, Debug)]
 pub enum ConnectionError {
     #[error("missing socket address")]
     NoAddress,
 
+    #[error("onion address {0} is unreachable with no proxy configured")]
+    NoOnionProxy(NetAddress),
+
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -51,6 +63,7 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    proxies: OutboundProxies,
 }
 
 impl ConnectionHandler {
@@ -58,8 +71,13 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        proxies: OutboundProxies,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, proxies }
+    }
+
+    pub(crate) fn proxies(&self) -> &OutboundProxies {
+        &self.proxies
     }
 
     /// Launches a P2P server listener loop
@@ -71,6 +89,39 @@ impl ConnectionHandler {
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
 
+        // Bind synchronously so that binding errors are reported to the caller
+        let listener = std::net::TcpListener::bind(SocketAddr::from(serve_address))?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+        let (incoming_sender, incoming_receiver) = mpsc_channel(Self::incoming_channel_size());
+
+        // Every accepted connection first negotiates its transport, after which it is handed to the gRPC server
+        tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    _ = incoming_sender.closed() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(err) => {
+                            warn!("P2P, failed accepting an incoming connection: {}", err);
+                            continue;
+                        }
+                    },
+                };
+                let incoming_sender = incoming_sender.clone();
+                tokio::spawn(async move {
+                    let handshake_timeout = Duration::from_millis(Self::transport_handshake_timeout());
+                    match tokio::time::timeout(handshake_timeout, accept_transport(stream)).await {
+                        Ok(Ok(stream)) => {
+                            let _ = incoming_sender.send(Ok::<_, std::io::Error>(stream)).await;
+                        }
+                        Ok(Err(err)) => debug!("P2P, transport handshake with an incoming connection failed: {}", err),
+                        Err(_) => debug!("P2P, transport handshake with an incoming connection timed out"),
+                    }
+                });
+            }
+        });
+
         tokio::spawn(async move {
             let proto_server = ProtoP2pServer::new(connection_handler)
                 .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
@@ -82,7 +133,7 @@ impl ConnectionHandler {
                 .layer(MapRequestBodyLayer::new(move |body| CountBytesBody::new(body, bytes_rx.clone()).boxed_unsync()))
                 .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, bytes_tx.clone())))
                 .add_service(proto_server)
-                .serve_with_shutdown(serve_address.into(), termination_receiver.map(drop))
+                .serve_with_incoming_shutdown(ReceiverStream::new(incoming_receiver), termination_receiver.map(drop))
                 .await;
 
             match serve_result {
@@ -93,18 +144,55 @@ impl ConnectionHandler {
         Ok(termination_sender)
     }
 
-    /// Connect to a new peer
-    pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
-            return Err(ConnectionError::NoAddress);
+    /// Connect to a new peer. The connection is encrypted if `peer_services` include the [`ENCRYPTED_TRANSPORT_SERVICE_FLAG`]
+    pub(crate) async fn connect(&self, peer_address: String, peer_services: u64) -> Result<Arc<Router>, ConnectionError> {
+        let (net_address, hostname) = match NetAddress::from_str(&peer_address) {
+            Ok(net_address) => (net_address, None),
+            Err(_) if self.proxies.default.is_some() => {
+                // The hostname is resolved by the proxy so that no DNS lookup leaks outside of it. The address
+                // of the peer remains unknown, so it is identified by the unspecified address
+                let Some((hostname, port)) = peer_address.rsplit_once(':').and_then(|(h, p)| Some((h, u16::from_str(p).ok()?))) else {
+                    return Err(ConnectionError::NoAddress);
+                };
+                (NetAddress::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED).into(), port), Some(hostname.to_owned()))
+            }
+            Err(_) => {
+                let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
+                    return Err(ConnectionError::NoAddress);
+                };
+                (socket_address.into(), None)
+            }
         };
+        if net_address.is_onion() && !self.proxies.can_reach_onion() {
+            return Err(ConnectionError::NoOnionProxy(net_address));
+        }
+        // Onion peers are identified by the OnionCat mapping of their address
+        let socket_address = SocketAddr::from(net_address);
         let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let proxy = self.proxies.proxy_for(&net_address).cloned();
+        let connect_timeout = match proxy {
+            // Proxied connections are given more time since circuits through the Tor network take a while to build
+            Some(_) => Self::proxy_connect_timeout(),
+            None => Self::connect_timeout(),
+        };
+        let encrypt = peer_services & ENCRYPTED_TRANSPORT_SERVICE_FLAG != 0;
+        let is_encrypted = Arc::new(AtomicBool::new(false));
+        let connector = {
+            let is_encrypted = is_encrypted.clone();
+            service_fn(move |_: Uri| {
+                let (proxy, hostname, is_encrypted) = (proxy.clone(), hostname.clone(), is_encrypted.clone());
+                async move {
+                    let stream = Self::connect_transport(net_address, hostname.as_deref(), proxy.as_ref(), encrypt).await?;
+                    is_encrypted.store(stream.is_encrypted(), Ordering::Relaxed);
+                    Ok::<_, std::io::Error>(TokioIo::new(stream))
+                }
+            })
+        };
+        let channel = Endpoint::new(peer_address)?
             .timeout(Duration::from_millis(Self::communication_timeout()))
-            .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
+            .connect_timeout(Duration::from_millis(connect_timeout))
+            .connect_with_connector(connector)
             .await?;
 
         let channel = ServiceBuilder::new()
@@ -120,7 +208,15 @@ impl ConnectionHandler {
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_network_channel_size());
         let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
 
-        let router = Router::new(socket_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(
+            socket_address,
+            true,
+            is_encrypted.load(Ordering::Relaxed),
+            self.hub_sender.clone(),
+            incoming_stream,
+            outgoing_route,
+        )
+        .await;
 
         // For outbound peers, we perform the initialization as part of the connect logic
         match self.initializer.initialize_connection(router.clone()).await {
@@ -141,17 +237,62 @@ impl ConnectionHandler {
         Ok(router)
     }
 
+    /// Opens a TCP stream to `address`, directly or through `proxy`. A `hostname` takes precedence over the
+    /// address when connecting through a proxy
+    async fn open_tcp_stream(address: NetAddress, hostname: Option<&str>, proxy: Option<&Socks5Proxy>) -> std::io::Result<TcpStream> {
+        let stream = match (proxy, hostname) {
+            (Some(proxy), Some(hostname)) => proxy.connect_hostname(hostname, address.port).await?,
+            (Some(proxy), None) => proxy.connect(address).await?,
+            (None, _) => TcpStream::connect(SocketAddr::from(address)).await?,
+        };
+        stream.set_nodelay(true)?;
+        SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(Duration::from_millis(Self::keep_alive())))?;
+        Ok(stream)
+    }
+
+    /// Opens a connection to `address`, over the encrypted transport if `encrypt` is set and in plaintext otherwise.
+    /// A direct encrypted connection falls back to plaintext if the peer turns out not to support encryption, which
+    /// only happens when the services known for it are stale. Proxied connections never fall back to plaintext
+    async fn connect_transport(
+        address: NetAddress,
+        hostname: Option<&str>,
+        proxy: Option<&Socks5Proxy>,
+        encrypt: bool,
+    ) -> std::io::Result<TransportStream<TcpStream>> {
+        let stream = Self::open_tcp_stream(address, hostname, proxy).await?;
+        if !encrypt {
+            return Ok(TransportStream::Plain(PlainStream::new(stream)));
+        }
+        let handshake_timeout = match proxy {
+            // Round trips through the Tor network are slow, so the handshake is given as much time as the connection
+            Some(_) => Self::proxy_connect_timeout(),
+            None => Self::transport_handshake_timeout(),
+        };
+        let err = match tokio::time::timeout(Duration::from_millis(handshake_timeout), initiate_encrypted(stream)).await {
+            Ok(Ok(stream)) => return Ok(TransportStream::Encrypted(stream)),
+            Ok(Err(err)) => err,
+            Err(_) => std::io::Error::new(std::io::ErrorKind::TimedOut, "encrypted transport handshake timed out"),
+        };
+        if proxy.is_some() {
+            return Err(err);
+        }
+        // Legacy peers drop connections opened with the transport magic
+        debug!("P2P, encrypted transport with {} failed, reconnecting in plaintext: {}", address, err);
+        Ok(TransportStream::Plain(PlainStream::new(Self::open_tcp_stream(address, hostname, proxy).await?)))
+    }
+
     /// Connect to a new peer with `retry_attempts` retries and `retry_interval` duration between each attempt
     pub(crate) async fn connect_with_retry(
         &self,
         address: String,
+        peer_services: u64,
         retry_attempts: u8,
         retry_interval: Duration,
     ) -> Result<Arc<Router>, ConnectionError> {
         let mut counter = 0;
         loop {
             counter += 1;
-            match self.connect(address.clone()).await {
+            match self.connect(address.clone(), peer_services).await {
                 Ok(router) => {
                     debug!("P2P, Client connected, peer: {:?}", address);
                     return Ok(router);
@@ -192,6 +333,18 @@ impl ConnectionHandler {
     fn connect_timeout() -> u64 {
         1_000
     }
+
+    fn proxy_connect_timeout() -> u64 {
+        20_000
+    }
+
+    fn transport_handshake_timeout() -> u64 {
+        4_000
+    }
+
+    fn incoming_channel_size() -> usize {
+        128
+    }
 }
 
 #[tonic::async_trait]
@@ -203,7 +356,7 @@ impl ProtoP2p for ConnectionHandler {
         &self,
         request: Request<Streaming<KaspadMessage>>,
     ) -> Result<Response<Self::MessageStreamStream>, TonicStatus> {
-        let Some(remote_address) = request.remote_addr() else {
+        let Some(&TransportConnectInfo { remote_addr: Some(remote_address), is_encrypted }) = request.extensions().get() else {
             return Err(TonicStatus::new(tonic::Code::InvalidArgument, "Incoming connection opening request has no remote address"));
         };
 
@@ -212,7 +365,7 @@ impl ProtoP2p for ConnectionHandler {
         let incoming_stream = request.into_inner();
 
         // Build the router object
-        let router = Router::new(remote_address, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(remote_address, false, is_encrypted, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
 
         // Notify the central Hub about the new peer
         self.hub_sender.send(HubEvent::NewPeer(router)).await.expect("hub receiver should never drop before senders");
@@ -177,11 +177,21 @@ impl Hub {
         self.peers.read().values().map(|r| r.as_ref().into()).collect()
     }
 
+    /// Returns the routers of all currently active peers
+    pub fn active_routers(&self) -> Vec<Arc<Router>> {
+        self.peers.read().values().cloned().collect()
+    }
+
     /// Returns the number of currently active peers
     pub fn active_peers_len(&self) -> usize {
         self.peers.read().len()
     }
 
+    /// Returns the number of active peers connected over the encrypted transport
+    pub fn encrypted_peers_len(&self) -> usize {
+        self.peers.read().values().filter(|r| r.is_encrypted()).count()
+    }
+
     /// Returns the number of outbound/inbound active peers (depending on the `outbound` argument)
     pub fn peers_query(&self, outbound: bool) -> usize {
         self.peers.read().values().filter(|r| r.is_outbound() == outbound).count()
@@ -46,6 +46,18 @@ pub enum KaspadMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    CompactBlock,
+    RequestBlockTransactions,
+    BlockTransactions,
+    RequestTxReceipt,
+    TxReceipt,
+    RequestPochm,
+    Pochm,
+    RequestPosterityHeaders,
+    PosterityHeaders,
+    RequestTxSketch,
+    TxSketch,
+    TxReconciliationDifference,
 }
 
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
@@ -100,6 +112,18 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
             KaspadMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            KaspadMessagePayload::CompactBlock(_) => KaspadMessagePayloadType::CompactBlock,
+            KaspadMessagePayload::RequestBlockTransactions(_) => KaspadMessagePayloadType::RequestBlockTransactions,
+            KaspadMessagePayload::BlockTransactions(_) => KaspadMessagePayloadType::BlockTransactions,
+            KaspadMessagePayload::RequestTxReceipt(_) => KaspadMessagePayloadType::RequestTxReceipt,
+            KaspadMessagePayload::TxReceipt(_) => KaspadMessagePayloadType::TxReceipt,
+            KaspadMessagePayload::RequestPochm(_) => KaspadMessagePayloadType::RequestPochm,
+            KaspadMessagePayload::Pochm(_) => KaspadMessagePayloadType::Pochm,
+            KaspadMessagePayload::RequestPosterityHeaders(_) => KaspadMessagePayloadType::RequestPosterityHeaders,
+            KaspadMessagePayload::PosterityHeaders(_) => KaspadMessagePayloadType::PosterityHeaders,
+            KaspadMessagePayload::RequestTxSketch(_) => KaspadMessagePayloadType::RequestTxSketch,
+            KaspadMessagePayload::TxSketch(_) => KaspadMessagePayloadType::TxSketch,
+            KaspadMessagePayload::TxReconciliationDifference(_) => KaspadMessagePayloadType::TxReconciliationDifference,
         }
     }
 }
@@ -5,7 +5,7 @@ use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
 #[derive(Debug, Clone, Default)]
 pub struct PeerProperties {
     pub user_agent: String,
-    // TODO: add services
+    pub services: u64,
     pub advertised_protocol_version: u32,
     pub protocol_version: u32,
     pub disable_relay_tx: bool,
@@ -21,6 +21,9 @@ pub struct Peer {
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    is_encrypted: bool,
+    last_block_relay: Option<Instant>,
+    last_tx_relay: Option<Instant>,
 }
 
 impl Peer {
@@ -31,8 +34,21 @@ impl Peer {
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        is_encrypted: bool,
+        last_block_relay: Option<Instant>,
+        last_tx_relay: Option<Instant>,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self {
+            identity,
+            net_address,
+            is_outbound,
+            connection_started,
+            properties,
+            last_ping_duration,
+            is_encrypted,
+            last_block_relay,
+            last_tx_relay,
+        }
     }
 
     /// Internal identity of this peer
@@ -65,6 +81,21 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Indicates whether the connection to this peer runs over the encrypted transport
+    pub fn is_encrypted(&self) -> bool {
+        self.is_encrypted
+    }
+
+    /// Milliseconds since this peer last relayed a new valid block to us, if ever
+    pub fn time_since_block_relay(&self) -> Option<u64> {
+        self.last_block_relay.map(|instant| instant.elapsed().as_millis() as u64)
+    }
+
+    /// Milliseconds since this peer last relayed a new transaction which was accepted to the mempool, if ever
+    pub fn time_since_tx_relay(&self) -> Option<u64> {
+        self.last_tx_relay.map(|instant| instant.elapsed().as_millis() as u64)
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -102,6 +102,12 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// Time this peer last relayed a new valid block to us
+    last_block_relay: Option<Instant>,
+
+    /// Time this peer last relayed a new transaction which was accepted to the mempool
+    last_tx_relay: Option<Instant>,
 }
 
 impl RouterMutableState {
@@ -123,6 +129,9 @@ pub struct Router {
     /// Indicates whether this connection is an outbound connection
     is_outbound: bool,
 
+    /// Indicates whether the underlying connection runs over the encrypted transport
+    is_encrypted: bool,
+
     /// Time of creation of this object and the connection it holds
     connection_started: Instant,
 
@@ -162,6 +171,9 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.is_encrypted(),
+            router.last_block_relay(),
+            router.last_tx_relay(),
         )
     }
 }
@@ -176,6 +188,7 @@ impl Router {
     pub(crate) async fn new(
         net_address: SocketAddr,
         is_outbound: bool,
+        is_encrypted: bool,
         hub_sender: MpscSender<HubEvent>,
         mut incoming_stream: Streaming<KaspadMessage>,
         outgoing_route: MpscSender<KaspadMessage>,
@@ -187,6 +200,7 @@ impl Router {
             identity: Default::default(),
             net_address,
             is_outbound,
+            is_encrypted,
             connection_started: Instant::now(),
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
@@ -294,6 +308,24 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Records that the peer just relayed a new valid block to us
+    pub fn mark_block_relay(&self) {
+        self.mutable_state.lock().last_block_relay = Some(Instant::now());
+    }
+
+    pub fn last_block_relay(&self) -> Option<Instant> {
+        self.mutable_state.lock().last_block_relay
+    }
+
+    /// Records that the peer just relayed a new transaction which was accepted to the mempool
+    pub fn mark_tx_relay(&self) {
+        self.mutable_state.lock().last_tx_relay = Some(Instant::now());
+    }
+
+    pub fn last_tx_relay(&self) -> Option<Instant> {
+        self.mutable_state.lock().last_tx_relay
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -361,6 +393,20 @@ impl Router {
         incoming_route
     }
 
+    /// Unsubscribe an id-only route (i.e., a route subscribed with no message types) so that its
+    /// registration does not outlive the flow which created it. Responses which arrive for the
+    /// route afterwards are considered unroutable.
+    pub fn unsubscribe(&self, route_id: u32) {
+        if self.routing_map_by_id.write().remove(&route_id).is_none() {
+            trace!("P2P, Router::unsubscribe - route id: {:?} is not registered, router-id:{:?}", route_id, self.identity());
+        }
+    }
+
+    /// Indicates whether the connection to this peer runs over the encrypted transport
+    pub fn is_encrypted(&self) -> bool {
+        self.is_encrypted
+    }
+
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         if msg.payload.is_none() {
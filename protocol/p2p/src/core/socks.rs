@@ -0,0 +1,259 @@
+//!
+//! A minimal SOCKS5 client (RFC 1928) for routing outbound p2p connections through a proxy such as Tor.
+//!
+//! Only the CONNECT command is supported, with either no authentication or username/password authentication
+//! (RFC 1929). Onion addresses and hostnames are passed to the proxy as domain names so that they are resolved
+//! remotely (within the Tor network for onions), while IP addresses are passed as is, so that no DNS lookups leak
+//! outside of the proxy.
+//!
+
+use kaspa_utils::networking::NetAddress;
+use std::{
+    fmt::Display,
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, SocketAddr},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// The port a local Tor daemon accepts SOCKS5 connections on by default
+pub const DEFAULT_SOCKS_PORT: u16 = 9050;
+
+const SOCKS_VERSION: u8 = 5;
+const AUTH_METHOD_NONE: u8 = 0;
+const AUTH_METHOD_PASSWORD: u8 = 2;
+const PASSWORD_AUTH_VERSION: u8 = 1;
+const PASSWORD_AUTH_SUCCEEDED: u8 = 0;
+const COMMAND_CONNECT: u8 = 1;
+const ADDRESS_TYPE_IPV4: u8 = 1;
+const ADDRESS_TYPE_DOMAIN: u8 = 3;
+const ADDRESS_TYPE_IPV6: u8 = 4;
+const REPLY_SUCCEEDED: u8 = 0;
+
+/// A SOCKS5 proxy along with optional username/password credentials
+#[derive(Clone, Debug)]
+pub struct Socks5Proxy {
+    pub address: SocketAddr,
+    pub credentials: Option<(String, String)>,
+}
+
+impl Socks5Proxy {
+    pub fn new(address: SocketAddr, credentials: Option<(String, String)>) -> Self {
+        Self { address, credentials }
+    }
+
+    /// Opens a TCP stream to `target` which is tunneled through the proxy
+    pub(crate) async fn connect(&self, target: NetAddress) -> Result<TcpStream> {
+        let destination = match (target.onion, target.ip.0) {
+            (Some(onion), _) => return self.connect_hostname(&onion.to_string(), target.port).await,
+            (None, IpAddr::V4(ip)) => [&[ADDRESS_TYPE_IPV4], ip.octets().as_slice()].concat(),
+            (None, IpAddr::V6(ip)) => [&[ADDRESS_TYPE_IPV6], ip.octets().as_slice()].concat(),
+        };
+        self.connect_destination(destination, target.port, &target).await
+    }
+
+    /// Opens a TCP stream to `host`, which is resolved by the proxy, tunneled through the proxy
+    pub(crate) async fn connect_hostname(&self, host: &str, port: u16) -> Result<TcpStream> {
+        if host.len() > u8::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "proxied hostnames must not exceed 255 bytes"));
+        }
+        let destination = [&[ADDRESS_TYPE_DOMAIN, host.len() as u8], host.as_bytes()].concat();
+        self.connect_destination(destination, port, &format!("{host}:{port}")).await
+    }
+
+    /// Sends a connect request for `destination`, which is the address type followed by the address
+    async fn connect_destination(&self, destination: Vec<u8>, port: u16, target: &dyn Display) -> Result<TcpStream> {
+        let mut stream = TcpStream::connect(self.address).await?;
+        self.authenticate(&mut stream).await?;
+
+        let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0];
+        request.extend(destination);
+        request.extend(port.to_be_bytes());
+        stream.write_all(&request).await?;
+
+        // The reply consists of the version, the reply code, a reserved byte and the address bound by the proxy
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != SOCKS_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("unexpected SOCKS version {} in proxy reply", reply[0])));
+        }
+        if reply[1] != REPLY_SUCCEEDED {
+            return Err(Error::new(
+                ErrorKind::ConnectionRefused,
+                format!("proxy failed connecting to {}: {}", target, reply_message(reply[1])),
+            ));
+        }
+        let bound_address_len = match reply[3] {
+            ADDRESS_TYPE_IPV4 => 4,
+            ADDRESS_TYPE_IPV6 => 16,
+            ADDRESS_TYPE_DOMAIN => stream.read_u8().await? as usize,
+            address_type => {
+                return Err(Error::new(ErrorKind::InvalidData, format!("unexpected address type {} in proxy reply", address_type)))
+            }
+        };
+        let mut bound_address = vec![0u8; bound_address_len + size_of::<u16>()];
+        stream.read_exact(&mut bound_address).await?;
+
+        Ok(stream)
+    }
+
+    async fn authenticate(&self, stream: &mut TcpStream) -> Result<()> {
+        let method = if self.credentials.is_some() { AUTH_METHOD_PASSWORD } else { AUTH_METHOD_NONE };
+        stream.write_all(&[SOCKS_VERSION, 1, method]).await?;
+        let mut reply = [0u8; 2];
+        stream.read_exact(&mut reply).await?;
+        if reply[0] != SOCKS_VERSION || reply[1] != method {
+            return Err(Error::new(ErrorKind::PermissionDenied, "proxy rejected the offered authentication method"));
+        }
+
+        if let Some((username, password)) = &self.credentials {
+            if username.len() > u8::MAX as usize || password.len() > u8::MAX as usize {
+                return Err(Error::new(ErrorKind::InvalidInput, "proxy username and password must not exceed 255 bytes"));
+            }
+            let mut request = vec![PASSWORD_AUTH_VERSION, username.len() as u8];
+            request.extend(username.as_bytes());
+            request.push(password.len() as u8);
+            request.extend(password.as_bytes());
+            stream.write_all(&request).await?;
+            stream.read_exact(&mut reply).await?;
+            if reply[1] != PASSWORD_AUTH_SUCCEEDED {
+                return Err(Error::new(ErrorKind::PermissionDenied, "proxy authentication failed"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn reply_message(code: u8) -> &'static str {
+    match code {
+        1 => "general failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+/// The proxies used for outbound connections
+#[derive(Clone, Debug, Default)]
+pub struct OutboundProxies {
+    /// Proxy for all outbound connections, also used for onion addresses if no onion proxy is set
+    pub default: Option<Socks5Proxy>,
+    /// Proxy for connections to onion addresses
+    pub onion: Option<Socks5Proxy>,
+}
+
+impl OutboundProxies {
+    pub fn new(default: Option<Socks5Proxy>, onion: Option<Socks5Proxy>) -> Self {
+        Self { default, onion }
+    }
+
+    /// Returns the proxy to connect to `address` through, if any
+    pub fn proxy_for(&self, address: &NetAddress) -> Option<&Socks5Proxy> {
+        if address.is_onion() {
+            self.onion.as_ref().or(self.default.as_ref())
+        } else {
+            self.default.as_ref()
+        }
+    }
+
+    /// Whether onion addresses are reachable, which is only possible through a proxy
+    pub fn can_reach_onion(&self) -> bool {
+        self.onion.is_some() || self.default.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_socks5_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Socks5Proxy::new(listener.local_addr().unwrap(), Some(("user".into(), "pass".into())));
+        let target = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111").unwrap();
+
+        // A mock proxy which expects password authentication and a domain connect request
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [SOCKS_VERSION, 1, AUTH_METHOD_PASSWORD]);
+            stream.write_all(&[SOCKS_VERSION, AUTH_METHOD_PASSWORD]).await.unwrap();
+
+            let mut auth = [0u8; 11];
+            stream.read_exact(&mut auth).await.unwrap();
+            assert_eq!(&auth, b"\x01\x04user\x04pass");
+            stream.write_all(&[PASSWORD_AUTH_VERSION, PASSWORD_AUTH_SUCCEEDED]).await.unwrap();
+
+            let host = target.onion.unwrap().to_string();
+            let mut request = vec![0u8; 5 + host.len() + 2];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[..5], [SOCKS_VERSION, COMMAND_CONNECT, 0, ADDRESS_TYPE_DOMAIN, host.len() as u8]);
+            assert_eq!(&request[5..5 + host.len()], host.as_bytes());
+            assert_eq!(request[5 + host.len()..], 16111u16.to_be_bytes());
+            stream.write_all(&[SOCKS_VERSION, REPLY_SUCCEEDED, 0, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+
+            // Data following the handshake is tunneled as is
+            stream.write_all(b"tunneled").await.unwrap();
+        });
+
+        let mut stream = proxy.connect(target).await.unwrap();
+        let mut data = [0u8; 8];
+        stream.read_exact(&mut data).await.unwrap();
+        assert_eq!(&data, b"tunneled");
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_socks5_connect_hostname() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = Socks5Proxy::new(listener.local_addr().unwrap(), None);
+
+        // The hostname is passed to the proxy unresolved
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [SOCKS_VERSION, 1, AUTH_METHOD_NONE]);
+            stream.write_all(&[SOCKS_VERSION, AUTH_METHOD_NONE]).await.unwrap();
+
+            let mut request = [0u8; 5 + 11 + 2];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[..5], [SOCKS_VERSION, COMMAND_CONNECT, 0, ADDRESS_TYPE_DOMAIN, 11]);
+            assert_eq!(&request[5..16], b"example.com");
+            assert_eq!(request[16..], 16111u16.to_be_bytes());
+            stream.write_all(&[SOCKS_VERSION, REPLY_SUCCEEDED, 0, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        });
+
+        proxy.connect_hostname("example.com", 16111).await.unwrap();
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_proxy_selection() {
+        let default = Socks5Proxy::new("127.0.0.1:1080".parse().unwrap(), None);
+        let onion = Socks5Proxy::new("127.0.0.1:9050".parse().unwrap(), None);
+        let ip_address = NetAddress::from_str("1.2.3.4:16111").unwrap();
+        let onion_address = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111").unwrap();
+
+        let proxies = OutboundProxies::default();
+        assert!(proxies.proxy_for(&ip_address).is_none() && !proxies.can_reach_onion());
+
+        // Onion addresses fall back to the default proxy
+        let proxies = OutboundProxies::new(Some(default.clone()), None);
+        assert_eq!(proxies.proxy_for(&onion_address).unwrap().address, default.address);
+
+        let proxies = OutboundProxies::new(None, Some(onion.clone()));
+        assert!(proxies.proxy_for(&ip_address).is_none());
+        assert_eq!(proxies.proxy_for(&onion_address).unwrap().address, onion.address);
+    }
+}
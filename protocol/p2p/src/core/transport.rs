@@ -0,0 +1,422 @@
+//!
+//! Opportunistic encryption of the p2p transport.
+//!
+//! Encryption is applied to the raw byte stream beneath the gRPC (HTTP/2) layer. A connecting peer supporting
+//! encryption opens the stream with [`TRANSPORT_MAGIC`] followed by an ephemeral secp256k1 public key, to which
+//! the listening peer replies with its own ephemeral public key. Both sides derive a shared secret via ECDH, from
+//! which a ChaCha20-Poly1305 key is derived for each direction. All following bytes are then sent as length-prefixed
+//! frames, each sealed with a nonce equal to the number of frames previously sent in that direction.
+//!
+//! Since the magic never prefixes a plaintext HTTP/2 connection, the listening side detects legacy peers from the
+//! first bytes of the stream and serves them in plaintext.
+//!
+//! Peers able to accept encrypted connections advertise the [`ENCRYPTED_TRANSPORT_SERVICE_FLAG`], and connecting
+//! peers encrypt only connections to peers known to advertise it. Legacy listeners drop the connection upon receiving
+//! the magic, so if the known services turn out stale a direct connection is reopened in plaintext, while a proxied
+//! one fails rather than silently downgrading.
+//!
+//! [`ENCRYPTED_TRANSPORT_SERVICE_FLAG`]: crate::convert::model::version::ENCRYPTED_TRANSPORT_SERVICE_FLAG
+//!
+
+use blake2b_simd::Params;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use secp256k1::{PublicKey, Scalar, SecretKey, SECP256K1};
+use std::{
+    io::{Error, ErrorKind, Result},
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::TcpStream;
+use tonic::transport::server::Connected;
+
+/// Opens an encrypted connection. Never matches the HTTP/2 connection preface ("PRI * HTTP/2.0...")
+pub const TRANSPORT_MAGIC: [u8; 8] = *b"KASENC\x00\x01";
+
+const SHARED_SECRET_DOMAIN: &[u8] = b"KaspaP2PSecret";
+const INITIATOR_KEY_DOMAIN: &[u8] = b"KaspaP2PInitiatr";
+const RESPONDER_KEY_DOMAIN: &[u8] = b"KaspaP2PRespondr";
+
+const PUBLIC_KEY_LEN: usize = 33;
+const FRAME_HEADER_LEN: usize = 4;
+const TAG_LEN: usize = 16;
+
+/// Maximum plaintext length of a single frame
+const MAX_FRAME_PAYLOAD: usize = 1 << 16;
+
+/// An ephemeral key pair generated for a single connection
+struct TransportKeyPair {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl TransportKeyPair {
+    fn generate() -> Self {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let public_key = PublicKey::from_secret_key_global(&secret_key);
+        Self { secret_key, public_key }
+    }
+
+    /// Derives the sealing and opening ciphers of a connection with the peer owning `peer_public_key`.
+    /// `is_initiator` indicates whether this side initiated the connection.
+    fn derive_ciphers(&self, peer_public_key: &[u8], is_initiator: bool) -> Result<(ChaCha20Poly1305, ChaCha20Poly1305)> {
+        let invalid_key = || Error::new(ErrorKind::InvalidData, "invalid transport public key");
+        let peer_public_key = PublicKey::from_slice(peer_public_key).map_err(|_| invalid_key())?;
+        let shared_point = peer_public_key.mul_tweak(SECP256K1, &Scalar::from(self.secret_key)).map_err(|_| invalid_key())?;
+
+        // Bind the secret to both ephemeral keys, ordered from initiator to responder
+        let (initiator_key, responder_key) =
+            if is_initiator { (self.public_key, peer_public_key) } else { (peer_public_key, self.public_key) };
+        let shared_secret = Params::new()
+            .hash_length(32)
+            .personal(SHARED_SECRET_DOMAIN)
+            .to_state()
+            .update(&shared_point.serialize())
+            .update(&initiator_key.serialize())
+            .update(&responder_key.serialize())
+            .finalize();
+        let derive_key = |domain: &[u8]| {
+            let key = Params::new().hash_length(32).key(shared_secret.as_bytes()).personal(domain).hash(&[]);
+            ChaCha20Poly1305::new(Key::from_slice(key.as_bytes()))
+        };
+
+        let (sealing_domain, opening_domain) =
+            if is_initiator { (INITIATOR_KEY_DOMAIN, RESPONDER_KEY_DOMAIN) } else { (RESPONDER_KEY_DOMAIN, INITIATOR_KEY_DOMAIN) };
+        Ok((derive_key(sealing_domain), derive_key(opening_domain)))
+    }
+}
+
+fn nonce(counter: u64) -> Nonce {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    *Nonce::from_slice(&nonce)
+}
+
+/// Opens an encrypted transport over `stream` as the connecting side
+pub async fn initiate_encrypted<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S) -> Result<EncryptedStream<S>> {
+    let key_pair = TransportKeyPair::generate();
+    let mut hello = TRANSPORT_MAGIC.to_vec();
+    hello.extend(key_pair.public_key.serialize());
+    stream.write_all(&hello).await?;
+    stream.flush().await?;
+
+    let mut peer_public_key = [0u8; PUBLIC_KEY_LEN];
+    stream.read_exact(&mut peer_public_key).await?;
+    let (sealer, opener) = key_pair.derive_ciphers(&peer_public_key, true)?;
+    Ok(EncryptedStream::new(stream, sealer, opener))
+}
+
+/// Accepts a connection as the listening side, establishing the encrypted transport if the connecting
+/// peer opened the stream with [`TRANSPORT_MAGIC`] and serving it in plaintext otherwise
+pub async fn accept_transport<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S) -> Result<TransportStream<S>> {
+    let mut prefix = [0u8; TRANSPORT_MAGIC.len()];
+    stream.read_exact(&mut prefix).await?;
+    if prefix != TRANSPORT_MAGIC {
+        // A legacy peer, so the bytes read are replayed to the gRPC server
+        return Ok(TransportStream::Plain(PlainStream { inner: stream, prefix: prefix.to_vec(), prefix_pos: 0 }));
+    }
+
+    let key_pair = TransportKeyPair::generate();
+    let mut peer_public_key = [0u8; PUBLIC_KEY_LEN];
+    stream.read_exact(&mut peer_public_key).await?;
+    let (sealer, opener) = key_pair.derive_ciphers(&peer_public_key, false)?;
+    stream.write_all(&key_pair.public_key.serialize()).await?;
+    stream.flush().await?;
+    Ok(TransportStream::Encrypted(EncryptedStream::new(stream, sealer, opener)))
+}
+
+/// A stream sealing all written bytes into authenticated frames and opening the frames read
+pub struct EncryptedStream<S> {
+    inner: S,
+    sealer: ChaCha20Poly1305,
+    seal_counter: u64,
+    opener: ChaCha20Poly1305,
+    open_counter: u64,
+    /// Raw bytes read from the inner stream which do not complete a frame yet
+    read_buf: Vec<u8>,
+    /// Opened bytes of the last frame which were not yet consumed by the reader
+    plaintext: Vec<u8>,
+    plaintext_pos: usize,
+    /// A sealed frame which was not fully written to the inner stream yet
+    write_buf: Vec<u8>,
+    write_pos: usize,
+}
+
+impl<S> EncryptedStream<S> {
+    fn new(inner: S, sealer: ChaCha20Poly1305, opener: ChaCha20Poly1305) -> Self {
+        Self {
+            inner,
+            sealer,
+            seal_counter: 0,
+            opener,
+            open_counter: 0,
+            read_buf: Vec::new(),
+            plaintext: Vec::new(),
+            plaintext_pos: 0,
+            write_buf: Vec::new(),
+            write_pos: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Opens the frame at the front of the read buffer, if complete
+    fn open_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.read_buf.len() < FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+        let header: [u8; FRAME_HEADER_LEN] = self.read_buf[..FRAME_HEADER_LEN].try_into().unwrap();
+        let len = u32::from_le_bytes(header) as usize;
+        if len > MAX_FRAME_PAYLOAD + TAG_LEN {
+            return Err(Error::new(ErrorKind::InvalidData, "encrypted frame exceeds the maximum length"));
+        }
+        if self.read_buf.len() < FRAME_HEADER_LEN + len {
+            return Ok(None);
+        }
+        let msg = &self.read_buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + len];
+        let plaintext = self
+            .opener
+            .decrypt(&nonce(self.open_counter), Payload { msg, aad: &header })
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "failed to authenticate an encrypted frame"))?;
+        self.open_counter += 1;
+        self.read_buf.drain(..FRAME_HEADER_LEN + len);
+        Ok(Some(plaintext))
+    }
+
+    fn seal_frame(&mut self, plaintext: &[u8]) {
+        let header = ((plaintext.len() + TAG_LEN) as u32).to_le_bytes();
+        let ciphertext = self
+            .sealer
+            .encrypt(&nonce(self.seal_counter), Payload { msg: plaintext, aad: &header })
+            .expect("encryption is infallible");
+        self.seal_counter += 1;
+        self.write_buf.clear();
+        self.write_buf.extend(header);
+        self.write_buf.extend(ciphertext);
+        self.write_pos = 0;
+    }
+}
+
+impl<S: AsyncWrite + Unpin> EncryptedStream<S> {
+    /// Writes the pending sealed frame to the inner stream
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.write_pos < self.write_buf.len() {
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_buf[self.write_pos..]))?;
+            if written == 0 {
+                return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+            }
+            self.write_pos += written;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for EncryptedStream<S> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.plaintext_pos < this.plaintext.len() {
+                let len = buf.remaining().min(this.plaintext.len() - this.plaintext_pos);
+                buf.put_slice(&this.plaintext[this.plaintext_pos..this.plaintext_pos + len]);
+                this.plaintext_pos += len;
+                return Poll::Ready(Ok(()));
+            }
+            if let Some(plaintext) = this.open_frame()? {
+                this.plaintext = plaintext;
+                this.plaintext_pos = 0;
+                continue;
+            }
+
+            let mut chunk = [0u8; 8192];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut chunk_buf))?;
+            if chunk_buf.filled().is_empty() {
+                if this.read_buf.is_empty() {
+                    return Poll::Ready(Ok(()));
+                }
+                return Poll::Ready(Err(Error::new(ErrorKind::UnexpectedEof, "encrypted stream ended within a frame")));
+            }
+            this.read_buf.extend_from_slice(chunk_buf.filled());
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for EncryptedStream<S> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let len = buf.len().min(MAX_FRAME_PAYLOAD);
+        this.seal_frame(&buf[..len]);
+        // The frame is buffered, so it is fine if the inner stream is not ready yet
+        if let Poll::Ready(Err(err)) = this.poll_write_pending(cx) {
+            return Poll::Ready(Err(err));
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// A plaintext stream replaying the bytes consumed while detecting the transport
+pub struct PlainStream<S> {
+    inner: S,
+    prefix: Vec<u8>,
+    prefix_pos: usize,
+}
+
+impl<S> PlainStream<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner, prefix: Vec::new(), prefix_pos: 0 }
+    }
+}
+
+/// A p2p connection stream, either encrypted or plaintext
+pub enum TransportStream<S> {
+    Plain(PlainStream<S>),
+    Encrypted(EncryptedStream<S>),
+}
+
+impl<S> TransportStream<S> {
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, Self::Encrypted(_))
+    }
+
+    pub fn get_ref(&self) -> &S {
+        match self {
+            Self::Plain(stream) => &stream.inner,
+            Self::Encrypted(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for TransportStream<S> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) if stream.prefix_pos < stream.prefix.len() => {
+                let len = buf.remaining().min(stream.prefix.len() - stream.prefix_pos);
+                buf.put_slice(&stream.prefix[stream.prefix_pos..stream.prefix_pos + len]);
+                stream.prefix_pos += len;
+                Poll::Ready(Ok(()))
+            }
+            Self::Plain(stream) => Pin::new(&mut stream.inner).poll_read(cx, buf),
+            Self::Encrypted(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for TransportStream<S> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(&mut stream.inner).poll_write(cx, buf),
+            Self::Encrypted(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(&mut stream.inner).poll_flush(cx),
+            Self::Encrypted(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => Pin::new(&mut stream.inner).poll_shutdown(cx),
+            Self::Encrypted(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Connection info attached by the gRPC server to the requests of an incoming connection
+#[derive(Clone, Copy, Debug)]
+pub struct TransportConnectInfo {
+    pub remote_addr: Option<SocketAddr>,
+    pub is_encrypted: bool,
+}
+
+impl Connected for TransportStream<TcpStream> {
+    type ConnectInfo = TransportConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        TransportConnectInfo { remote_addr: self.get_ref().peer_addr().ok(), is_encrypted: self.is_encrypted() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    #[tokio::test]
+    async fn test_encrypted_transport() {
+        let (client, server) = duplex(1024);
+        let (client, server) = tokio::join!(initiate_encrypted(client), accept_transport(server));
+        let (mut client, mut server) = (client.unwrap(), server.unwrap());
+        assert!(server.is_encrypted());
+
+        // Writes larger than a frame are split, and bytes flow in both directions with independent counters
+        let payload = (0..3 * MAX_FRAME_PAYLOAD + 17).map(|i| i as u8).collect::<Vec<_>>();
+        let (write_res, read_res) = tokio::join!(client.write_all(&payload), async {
+            let mut received = vec![0u8; payload.len()];
+            server.read_exact(&mut received).await.map(|_| received)
+        });
+        write_res.unwrap();
+        client.flush().await.unwrap();
+        assert_eq!(payload, read_res.unwrap());
+
+        server.write_all(b"pong").await.unwrap();
+        server.flush().await.unwrap();
+        let mut pong = [0u8; 4];
+        client.read_exact(&mut pong).await.unwrap();
+        assert_eq!(b"pong", &pong);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_transport() {
+        let (client, server) = duplex(1024);
+        let (client, server) = tokio::join!(initiate_encrypted(client), accept_transport(server));
+        let (mut client, server) = (client.unwrap(), server.unwrap());
+        let TransportStream::Encrypted(mut server) = server else { panic!("expected an encrypted stream") };
+
+        // Flip a ciphertext bit on the wire
+        client.seal_frame(b"ping");
+        client.write_buf[FRAME_HEADER_LEN] ^= 1;
+        client.inner.write_all(&client.write_buf).await.unwrap();
+        let mut ping = [0u8; 4];
+        assert_eq!(ErrorKind::InvalidData, server.read_exact(&mut ping).await.unwrap_err().kind());
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_transport() {
+        let (mut client, server) = duplex(1024);
+        let preface = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+        let (write_res, server) = tokio::join!(client.write_all(preface), accept_transport(server));
+        write_res.unwrap();
+        let mut server = server.unwrap();
+        assert!(!server.is_encrypted());
+
+        // The bytes consumed while detecting the transport are replayed
+        let mut received = [0u8; 24];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(preface, &received);
+    }
+}
@@ -64,6 +64,18 @@ impl EchoFlow {
             KaspadMessagePayloadType::IbdChainBlockLocator,
             KaspadMessagePayloadType::RequestAntipast,
             KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            KaspadMessagePayloadType::CompactBlock,
+            KaspadMessagePayloadType::RequestBlockTransactions,
+            KaspadMessagePayloadType::BlockTransactions,
+            KaspadMessagePayloadType::RequestTxReceipt,
+            KaspadMessagePayloadType::TxReceipt,
+            KaspadMessagePayloadType::RequestPochm,
+            KaspadMessagePayloadType::Pochm,
+            KaspadMessagePayloadType::RequestPosterityHeaders,
+            KaspadMessagePayloadType::PosterityHeaders,
+            KaspadMessagePayloadType::RequestTxSketch,
+            KaspadMessagePayloadType::TxSketch,
+            KaspadMessagePayloadType::TxReconciliationDifference,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
@@ -148,7 +160,7 @@ mod tests {
     use std::{str::FromStr, time::Duration};
 
     use super::*;
-    use crate::{Adaptor, Hub};
+    use crate::{convert::model::version::ENCRYPTED_TRANSPORT_SERVICE_FLAG, Adaptor, Hub};
     use kaspa_core::debug;
     use kaspa_utils::networking::NetAddress;
 
@@ -157,14 +169,18 @@ mod tests {
         kaspa_core::log::try_init_logger("debug");
 
         let address1 = NetAddress::from_str("[::1]:50053").unwrap();
-        let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor1 =
+            Adaptor::bidirectional(address1, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), Default::default())
+                .unwrap();
 
         let address2 = NetAddress::from_str("[::1]:50054").unwrap();
-        let adaptor2 = Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap();
+        let adaptor2 =
+            Adaptor::bidirectional(address2, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), Default::default())
+                .unwrap();
 
         // Initiate the connection from `adaptor1` (outbound) to `adaptor2` (inbound)
         let peer2_id = adaptor1
-            .connect_peer_with_retries(String::from("[::1]:50054"), 16, Duration::from_secs(1))
+            .connect_peer_with_retries(String::from("[::1]:50054"), ENCRYPTED_TRANSPORT_SERVICE_FLAG, 16, Duration::from_secs(1))
             .await
             .expect("peer connection failed");
 
@@ -180,6 +196,7 @@ mod tests {
 
         assert!(adaptor1_initial_peers[0].is_outbound());
         assert!(!adaptor2_initial_peers[0].is_outbound());
+        assert!(adaptor1_initial_peers[0].is_encrypted() && adaptor2_initial_peers[0].is_encrypted());
 
         adaptor1.terminate(peer2_id).await;
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use crate::convert::model::version::ENCRYPTED_TRANSPORT_SERVICE_FLAG;
 use crate::pb::{kaspad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
@@ -68,7 +69,12 @@ impl<'a> KaspadHandshake<'a> {
     }
 
     /// Performs the handshake with the peer, essentially exchanging version messages
-    pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+    pub async fn handshake(&mut self, mut self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+        // The transport is encrypted (or not) beneath the gRPC stream before the handshake begins, so the service flag
+        // advertises that this node accepts encrypted connections. Once recorded in address books, it lets peers
+        // decide whether to encrypt their future connections to this node
+        self_version_message.services |= ENCRYPTED_TRANSPORT_SERVICE_FLAG;
+
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
         let (send_res, recv_res) = tokio::join!(
             Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
@@ -16,4 +16,5 @@ pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::KaspadMessagePayloadType;
 pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
+pub use crate::core::socks::{OutboundProxies, Socks5Proxy, DEFAULT_SOCKS_PORT};
 pub use handshake::KaspadHandshake;
@@ -9,12 +9,12 @@ use kaspa_notify::{
     notification::{full_featured, Notification as NotificationTrait},
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{OverallSubscription, TransactionReplacedSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{iter::once, sync::Arc};
 use wasm_bindgen::JsValue;
 use workflow_serializer::prelude::*;
 use workflow_wasm::serde::to_value;
@@ -48,6 +48,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "TransactionReplaced notification: transaction id {}, {} evicted transactions", "_0.transaction_id", "_0.evicted_transaction_ids.len()")]
+    TransactionReplaced(TransactionReplacedNotification),
+
+    #[display(fmt = "ChainReorg notification: {} removed chain blocks, {} added chain blocks", "_0.reorg.removed_depth", "_0.reorg.added_depth")]
+    ChainReorg(ChainReorgNotification),
 }
 }
 
@@ -64,6 +70,8 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::TransactionReplaced(v) => to_value(&v),
+            Notification::ChainReorg(v) => to_value(&v),
         }
     }
 }
@@ -112,6 +120,22 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_transaction_replaced_subscription(
+        &self,
+        subscription: &TransactionReplacedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::TransactionReplaced(notification) = self else { return None };
+                subscription
+                    .matches(once(&notification.transaction_id).chain(notification.evicted_transaction_ids.iter()))
+                    .then(|| self.clone())
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -157,6 +181,14 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::TransactionReplaced(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(TransactionReplacedNotification, notification, writer)?;
+            }
+            Notification::ChainReorg(notification) => {
+                store!(u16, &10, writer)?;
+                serialize!(ChainReorgNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +234,14 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(TransactionReplacedNotification, reader)?;
+                Ok(Notification::TransactionReplaced(notification))
+            }
+            10 => {
+                let notification = deserialize!(ChainReorgNotification, reader)?;
+                Ok(Notification::ChainReorg(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
@@ -40,6 +40,8 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyTransactionReplaced = 19,
+    NotifyChainReorg = 20,
 
     // Notification ops required by wRPC
 
@@ -54,6 +56,8 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    TransactionReplacedNotification = 69,
+    ChainReorgNotification = 70,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -138,6 +142,16 @@ pub enum RpcApiOps {
     GetCurrentBlockColor = 149,
     /// Get UTXO Return Addresses
     GetUtxoReturnAddress = 150,
+    /// Get the state and recent transitions of the mining rules
+    GetMiningRules = 151,
+    /// Change the retention period of the node at runtime
+    SetRetentionPeriod = 152,
+    /// Get the most recent reorgs of the virtual selected chain
+    GetRecentReorgs = 153,
+    /// Get the confirmation depth and finality status of an accepted transaction
+    GetTransactionConfirmations = 154,
+    /// Verify a transaction receipt obtained from a peer
+    VerifyTransactionReceipt = 155,
 }
 
 impl RpcApiOps {
@@ -153,6 +167,8 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyTransactionReplaced
+                | RpcApiOps::NotifyChainReorg
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -179,6 +195,8 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::TransactionReplaced => RpcApiOps::TransactionReplacedNotification,
+            EventType::ChainReorg => RpcApiOps::ChainReorgNotification,
         }
     }
 }
@@ -481,6 +481,66 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    /// Requests the state of the mining rules and their recent transitions.
+    async fn get_mining_rules(&self) -> RpcResult<GetMiningRulesResponse> {
+        self.get_mining_rules_call(None, GetMiningRulesRequest {}).await
+    }
+    async fn get_mining_rules_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMiningRulesRequest,
+    ) -> RpcResult<GetMiningRulesResponse>;
+
+    /// Changes the retention period of the node. The new period is reached gradually.
+    /// It is not persisted, so it reverts to the node setting on restart and on a consensus reset.
+    async fn set_retention_period(&self, retention_period_days: Option<f64>) -> RpcResult<()> {
+        self.set_retention_period_call(None, SetRetentionPeriodRequest::new(retention_period_days)).await?;
+        Ok(())
+    }
+    async fn set_retention_period_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SetRetentionPeriodRequest,
+    ) -> RpcResult<SetRetentionPeriodResponse>;
+
+    /// Requests the most recent reorgs of the virtual selected chain, ordered from the most recent one.
+    /// Returns up to `limit` reorgs, or all the reorgs kept by the node if `limit` is not specified.
+    async fn get_recent_reorgs(&self, limit: Option<u32>) -> RpcResult<Vec<RpcChainReorg>> {
+        Ok(self.get_recent_reorgs_call(None, GetRecentReorgsRequest::new(limit)).await?.reorgs)
+    }
+    async fn get_recent_reorgs_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetRecentReorgsRequest,
+    ) -> RpcResult<GetRecentReorgsResponse>;
+
+    /// Requests the confirmation depth and finality status of a transaction, as accepted by the given selected chain block.
+    async fn get_transaction_confirmations(
+        &self,
+        transaction_id: RpcTransactionId,
+        accepting_block_hash: RpcHash,
+    ) -> RpcResult<GetTransactionConfirmationsResponse> {
+        Ok(self
+            .get_transaction_confirmations_call(None, GetTransactionConfirmationsRequest::new(transaction_id, accepting_block_hash))
+            .await?)
+    }
+    async fn get_transaction_confirmations_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionConfirmationsRequest,
+    ) -> RpcResult<GetTransactionConfirmationsResponse>;
+
+    /// Verifies a transaction receipt, given as the wire encoding of the `TxReceiptMessage` served by p2p peers,
+    /// against the headers known to the node.
+    async fn verify_transaction_receipt(&self, receipt: Vec<u8>) -> RpcResult<VerifyTransactionReceiptResponse> {
+        Ok(self.verify_transaction_receipt_call(None, VerifyTransactionReceiptRequest::new(receipt)).await?)
+    }
+    async fn verify_transaction_receipt_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: VerifyTransactionReceiptRequest,
+    ) -> RpcResult<VerifyTransactionReceiptResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1,9 +1,10 @@
 //! Conversion of Notification related types
 
 use crate::{
-    convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
-    SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+    convert::utxo::utxo_set_into_rpc, BlockAddedNotification, ChainReorgNotification, FinalityConflictNotification,
+    FinalityConflictResolvedNotification, NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification,
+    RpcAcceptedTransactionIds, SinkBlueScoreChangedNotification, TransactionReplacedNotification, UtxosChangedNotification,
+    VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
 use kaspa_index_core::notification as index_notify;
@@ -31,6 +32,8 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::TransactionReplaced(msg) => Notification::TransactionReplaced(msg.into()),
+            consensus_notify::Notification::ChainReorg(msg) => Notification::ChainReorg(msg.into()),
         }
     }
 }
@@ -112,6 +115,22 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<&consensus_notify::TransactionReplacedNotification> for TransactionReplacedNotification {
+    fn from(item: &consensus_notify::TransactionReplacedNotification) -> Self {
+        Self {
+            transaction_id: item.transaction_id,
+            replaced_transaction_ids: item.replaced_transaction_ids.as_ref().clone(),
+            evicted_transaction_ids: item.evicted_transaction_ids.as_ref().clone(),
+        }
+    }
+}
+
+impl From<&consensus_notify::ChainReorgNotification> for ChainReorgNotification {
+    fn from(item: &consensus_notify::ChainReorgNotification) -> Self {
+        Self { reorg: (&item.reorg).into() }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -75,6 +75,9 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable. The node runs in light mode (--light) and stores neither block bodies nor the UTXO set.")]
+    UnavailableInLightMode,
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
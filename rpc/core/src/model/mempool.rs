@@ -33,6 +33,46 @@ impl Deserializer for RpcMempoolEntry {
     }
 }
 
+/// Statistics about the chains of dependent transactions a mempool entry belongs to.
+///
+/// Both the ancestor and the descendant sets include the entry itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolEntryStats {
+    pub ancestor_count: u64,
+    pub ancestor_mass: u64,
+    pub descendant_count: u64,
+    pub descendant_mass: u64,
+}
+
+impl RpcMempoolEntryStats {
+    pub fn new(ancestor_count: u64, ancestor_mass: u64, descendant_count: u64, descendant_mass: u64) -> Self {
+        Self { ancestor_count, ancestor_mass, descendant_count, descendant_mass }
+    }
+}
+
+impl Serializer for RpcMempoolEntryStats {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.ancestor_count, writer)?;
+        store!(u64, &self.ancestor_mass, writer)?;
+        store!(u64, &self.descendant_count, writer)?;
+        store!(u64, &self.descendant_mass, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMempoolEntryStats {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let ancestor_count = load!(u64, reader)?;
+        let ancestor_mass = load!(u64, reader)?;
+        let descendant_count = load!(u64, reader)?;
+        let descendant_mass = load!(u64, reader)?;
+        Ok(Self { ancestor_count, ancestor_mass, descendant_count, descendant_mass })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RpcMempoolEntryByAddress {
     pub address: RpcAddress,
@@ -79,6 +119,19 @@ cfg_if::cfg_if! {
                 transaction : ITransaction;
                 isOrphan : boolean;
             }
+
+            /**
+             * Ancestor and descendant statistics of a mempool entry,
+             * the entry itself being included in both sets.
+             * 
+             * @category Node RPC
+             */
+            export interface IMempoolEntryStats {
+                ancestorCount : bigint;
+                ancestorMass : bigint;
+                descendantCount : bigint;
+                descendantMass : bigint;
+            }
         "#;
     }
 }
@@ -382,18 +382,24 @@ impl GetPeerAddressesResponse {
 
 impl Serializer for GetPeerAddressesResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerAddress>, &self.known_addresses, writer)?;
         store!(Vec<RpcIpAddress>, &self.banned_addresses, writer)?;
+        // Version 2
+        store!(Vec<Option<RpcOnionAddress>>, &self.known_addresses.iter().map(|address| address.onion).collect::<Vec<_>>(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetPeerAddressesResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let known_addresses = load!(Vec<RpcPeerAddress>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut known_addresses = load!(Vec<RpcPeerAddress>, reader)?;
         let banned_addresses = load!(Vec<RpcIpAddress>, reader)?;
+        if version >= 2 {
+            let onions = load!(Vec<Option<RpcOnionAddress>>, reader)?;
+            known_addresses.iter_mut().zip(onions).for_each(|(address, onion)| address.onion = onion);
+        }
         Ok(Self { known_addresses, banned_addresses })
     }
 }
@@ -485,27 +491,31 @@ impl Deserializer for GetMempoolEntryRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetMempoolEntryResponse {
     pub mempool_entry: RpcMempoolEntry,
+    /// Ancestor and descendant statistics of the entry, absent for orphans
+    pub stats: Option<RpcMempoolEntryStats>,
 }
 
 impl GetMempoolEntryResponse {
-    pub fn new(mempool_entry: RpcMempoolEntry) -> Self {
-        Self { mempool_entry }
+    pub fn new(mempool_entry: RpcMempoolEntry, stats: Option<RpcMempoolEntryStats>) -> Self {
+        Self { mempool_entry, stats }
     }
 }
 
 impl Serializer for GetMempoolEntryResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(RpcMempoolEntry, &self.mempool_entry, writer)?;
+        serialize!(Option<RpcMempoolEntryStats>, &self.stats, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetMempoolEntryResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let mempool_entry = deserialize!(RpcMempoolEntry, reader)?;
-        Ok(Self { mempool_entry })
+        let stats = if payload_version > 1 { deserialize!(Option<RpcMempoolEntryStats>, reader)? } else { None };
+        Ok(Self { mempool_entry, stats })
     }
 }
 
@@ -603,16 +613,22 @@ impl GetConnectedPeerInfoResponse {
 
 impl Serializer for GetConnectedPeerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerInfo>, &self.peer_info, writer)?;
+        // Version 2
+        store!(Vec<bool>, &self.peer_info.iter().map(|peer| peer.is_encrypted).collect::<Vec<_>>(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetConnectedPeerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        if version >= 2 {
+            let is_encrypted = load!(Vec<bool>, reader)?;
+            peer_info.iter_mut().zip(is_encrypted).for_each(|(peer, is_encrypted)| peer.is_encrypted = is_encrypted);
+        }
         Ok(Self { peer_info })
     }
 }
@@ -632,9 +648,11 @@ impl AddPeerRequest {
 
 impl Serializer for AddPeerRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcContextualPeerAddress, &self.peer_address, writer)?;
         store!(bool, &self.is_permanent, writer)?;
+        // Version 2
+        store!(Option<RpcOnionAddress>, &self.peer_address.onion(), writer)?;
 
         Ok(())
     }
@@ -642,9 +660,14 @@ impl Serializer for AddPeerRequest {
 
 impl Deserializer for AddPeerRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_address = load!(RpcContextualPeerAddress, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_address = load!(RpcContextualPeerAddress, reader)?;
         let is_permanent = load!(bool, reader)?;
+        if version >= 2 {
+            if let Some(onion) = load!(Option<RpcOnionAddress>, reader)? {
+                peer_address = peer_address.with_onion(onion);
+            }
+        }
 
         Ok(Self { peer_address, is_permanent })
     }
@@ -766,19 +789,26 @@ impl Deserializer for SubmitTransactionReplacementRequest {
 pub struct SubmitTransactionReplacementResponse {
     pub transaction_id: RpcTransactionId,
     pub replaced_transaction: RpcTransaction,
+    /// Ids of all the transactions evicted from the mempool, ie. the replaced transaction followed by its descendants
+    pub evicted_transaction_ids: Vec<RpcTransactionId>,
 }
 
 impl SubmitTransactionReplacementResponse {
-    pub fn new(transaction_id: RpcTransactionId, replaced_transaction: RpcTransaction) -> Self {
-        Self { transaction_id, replaced_transaction }
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        replaced_transaction: RpcTransaction,
+        evicted_transaction_ids: Vec<RpcTransactionId>,
+    ) -> Self {
+        Self { transaction_id, replaced_transaction, evicted_transaction_ids }
     }
 }
 
 impl Serializer for SubmitTransactionReplacementResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcTransactionId, &self.transaction_id, writer)?;
         serialize!(RpcTransaction, &self.replaced_transaction, writer)?;
+        store!(Vec<RpcTransactionId>, &self.evicted_transaction_ids, writer)?;
 
         Ok(())
     }
@@ -786,11 +816,12 @@ impl Serializer for SubmitTransactionReplacementResponse {
 
 impl Deserializer for SubmitTransactionReplacementResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let transaction_id = load!(RpcTransactionId, reader)?;
         let replaced_transaction = deserialize!(RpcTransaction, reader)?;
+        let evicted_transaction_ids = if payload_version > 1 { load!(Vec<RpcTransactionId>, reader)? } else { vec![] };
 
-        Ok(Self { transaction_id, replaced_transaction })
+        Ok(Self { transaction_id, replaced_transaction, evicted_transaction_ids })
     }
 }
 
@@ -2055,11 +2086,13 @@ pub struct ConnectionMetrics {
     pub json_handshake_failures: u64,
 
     pub active_peers: u32,
+    pub encrypted_peers: u32,
+    pub inbound_evictions: u64,
 }
 
 impl Serializer for ConnectionMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(u32, &self.borsh_live_connections, writer)?;
         store!(u64, &self.borsh_connection_attempts, writer)?;
         store!(u64, &self.borsh_handshake_failures, writer)?;
@@ -2067,6 +2100,8 @@ impl Serializer for ConnectionMetrics {
         store!(u64, &self.json_connection_attempts, writer)?;
         store!(u64, &self.json_handshake_failures, writer)?;
         store!(u32, &self.active_peers, writer)?;
+        store!(u32, &self.encrypted_peers, writer)?;
+        store!(u64, &self.inbound_evictions, writer)?;
 
         Ok(())
     }
@@ -2074,7 +2109,7 @@ impl Serializer for ConnectionMetrics {
 
 impl Deserializer for ConnectionMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let borsh_live_connections = load!(u32, reader)?;
         let borsh_connection_attempts = load!(u64, reader)?;
         let borsh_handshake_failures = load!(u64, reader)?;
@@ -2082,6 +2117,8 @@ impl Deserializer for ConnectionMetrics {
         let json_connection_attempts = load!(u64, reader)?;
         let json_handshake_failures = load!(u64, reader)?;
         let active_peers = load!(u32, reader)?;
+        let encrypted_peers = if payload_version > 1 { load!(u32, reader)? } else { 0 };
+        let inbound_evictions = if payload_version > 2 { load!(u64, reader)? } else { 0 };
 
         Ok(Self {
             borsh_live_connections,
@@ -2091,6 +2128,8 @@ impl Deserializer for ConnectionMetrics {
             json_connection_attempts,
             json_handshake_failures,
             active_peers,
+            encrypted_peers,
+            inbound_evictions,
         })
     }
 }
@@ -2106,11 +2145,12 @@ pub struct BandwidthMetrics {
     pub p2p_bytes_rx: u64,
     pub grpc_bytes_tx: u64,
     pub grpc_bytes_rx: u64,
+    pub p2p_tx_relay_bytes_saved: u64,
 }
 
 impl Serializer for BandwidthMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.borsh_bytes_tx, writer)?;
         store!(u64, &self.borsh_bytes_rx, writer)?;
         store!(u64, &self.json_bytes_tx, writer)?;
@@ -2119,6 +2159,7 @@ impl Serializer for BandwidthMetrics {
         store!(u64, &self.p2p_bytes_rx, writer)?;
         store!(u64, &self.grpc_bytes_tx, writer)?;
         store!(u64, &self.grpc_bytes_rx, writer)?;
+        store!(u64, &self.p2p_tx_relay_bytes_saved, writer)?;
 
         Ok(())
     }
@@ -2126,7 +2167,7 @@ impl Serializer for BandwidthMetrics {
 
 impl Deserializer for BandwidthMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let borsh_bytes_tx = load!(u64, reader)?;
         let borsh_bytes_rx = load!(u64, reader)?;
         let json_bytes_tx = load!(u64, reader)?;
@@ -2135,6 +2176,7 @@ impl Deserializer for BandwidthMetrics {
         let p2p_bytes_rx = load!(u64, reader)?;
         let grpc_bytes_tx = load!(u64, reader)?;
         let grpc_bytes_rx = load!(u64, reader)?;
+        let p2p_tx_relay_bytes_saved = if payload_version > 1 { load!(u64, reader)? } else { 0 };
 
         Ok(Self {
             borsh_bytes_tx,
@@ -2145,6 +2187,7 @@ impl Deserializer for BandwidthMetrics {
             p2p_bytes_rx,
             grpc_bytes_tx,
             grpc_bytes_rx,
+            p2p_tx_relay_bytes_saved,
         })
     }
 }
@@ -2169,11 +2212,28 @@ pub struct ConsensusMetrics {
     pub network_past_median_time: u64,
     pub network_virtual_parent_hashes_count: u32,
     pub network_virtual_daa_score: u64,
+
+    pub node_triggered_mining_rules_count: u32,
+    pub node_mining_rule_transitions_count: u64,
+
+    pub node_retention_pending_blocks_count: u64,
+    pub node_retention_pruned_blocks_count: u64,
+
+    pub node_pruning_proof_build_duration_ms: u64,
+    pub node_pruning_proof_validation_duration_ms: u64,
+
+    /// Selected chain reorg counts by the number of removed chain blocks
+    pub node_reorgs_depth_1_count: u64,
+    pub node_reorgs_depth_2_to_3_count: u64,
+    pub node_reorgs_depth_4_to_7_count: u64,
+    pub node_reorgs_depth_8_or_more_count: u64,
+    /// The max number of chain blocks removed by a single reorg since the node started
+    pub node_max_reorg_depth: u64,
 }
 
 impl Serializer for ConsensusMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &5, writer)?;
         store!(u64, &self.node_blocks_submitted_count, writer)?;
         store!(u64, &self.node_headers_processed_count, writer)?;
         store!(u64, &self.node_dependencies_processed_count, writer)?;
@@ -2189,6 +2249,17 @@ impl Serializer for ConsensusMetrics {
         store!(u64, &self.network_past_median_time, writer)?;
         store!(u32, &self.network_virtual_parent_hashes_count, writer)?;
         store!(u64, &self.network_virtual_daa_score, writer)?;
+        store!(u32, &self.node_triggered_mining_rules_count, writer)?;
+        store!(u64, &self.node_mining_rule_transitions_count, writer)?;
+        store!(u64, &self.node_retention_pending_blocks_count, writer)?;
+        store!(u64, &self.node_retention_pruned_blocks_count, writer)?;
+        store!(u64, &self.node_pruning_proof_build_duration_ms, writer)?;
+        store!(u64, &self.node_pruning_proof_validation_duration_ms, writer)?;
+        store!(u64, &self.node_reorgs_depth_1_count, writer)?;
+        store!(u64, &self.node_reorgs_depth_2_to_3_count, writer)?;
+        store!(u64, &self.node_reorgs_depth_4_to_7_count, writer)?;
+        store!(u64, &self.node_reorgs_depth_8_or_more_count, writer)?;
+        store!(u64, &self.node_max_reorg_depth, writer)?;
 
         Ok(())
     }
@@ -2196,7 +2267,7 @@ impl Serializer for ConsensusMetrics {
 
 impl Deserializer for ConsensusMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let node_blocks_submitted_count = load!(u64, reader)?;
         let node_headers_processed_count = load!(u64, reader)?;
         let node_dependencies_processed_count = load!(u64, reader)?;
@@ -2212,6 +2283,23 @@ impl Deserializer for ConsensusMetrics {
         let network_past_median_time = load!(u64, reader)?;
         let network_virtual_parent_hashes_count = load!(u32, reader)?;
         let network_virtual_daa_score = load!(u64, reader)?;
+        let (node_triggered_mining_rules_count, node_mining_rule_transitions_count) =
+            if payload_version > 1 { (load!(u32, reader)?, load!(u64, reader)?) } else { (0, 0) };
+        let (node_retention_pending_blocks_count, node_retention_pruned_blocks_count) =
+            if payload_version > 2 { (load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0) };
+        let (node_pruning_proof_build_duration_ms, node_pruning_proof_validation_duration_ms) =
+            if payload_version > 3 { (load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0) };
+        let (
+            node_reorgs_depth_1_count,
+            node_reorgs_depth_2_to_3_count,
+            node_reorgs_depth_4_to_7_count,
+            node_reorgs_depth_8_or_more_count,
+            node_max_reorg_depth,
+        ) = if payload_version > 4 {
+            (load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?, load!(u64, reader)?)
+        } else {
+            (0, 0, 0, 0, 0)
+        };
 
         Ok(Self {
             node_blocks_submitted_count,
@@ -2229,20 +2317,67 @@ impl Deserializer for ConsensusMetrics {
             network_past_median_time,
             network_virtual_parent_hashes_count,
             network_virtual_daa_score,
+            node_triggered_mining_rules_count,
+            node_mining_rule_transitions_count,
+            node_retention_pending_blocks_count,
+            node_retention_pruned_blocks_count,
+            node_pruning_proof_build_duration_ms,
+            node_pruning_proof_validation_duration_ms,
+            node_reorgs_depth_1_count,
+            node_reorgs_depth_2_to_3_count,
+            node_reorgs_depth_4_to_7_count,
+            node_reorgs_depth_8_or_more_count,
+            node_max_reorg_depth,
         })
     }
 }
 
+/// Estimated on-disk size of the data held by a single database store
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStoreSize {
+    /// The database prefix of the store, identifying it regardless of its name
+    pub prefix: u8,
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+impl Serializer for RpcStoreSize {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u8, &self.prefix, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(u64, &self.size_bytes, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcStoreSize {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let prefix = load!(u8, reader)?;
+        let name = load!(String, reader)?;
+        let size_bytes = load!(u64, reader)?;
+
+        Ok(Self { prefix, name, size_bytes })
+    }
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageMetrics {
+    /// Estimated total size of the consensus databases
     pub storage_size_bytes: u64,
+    /// Estimated size of each consensus store, omitting stores holding no data
+    pub store_sizes: Vec<RpcStoreSize>,
 }
 
 impl Serializer for StorageMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.storage_size_bytes, writer)?;
+        serialize!(Vec<RpcStoreSize>, &self.store_sizes, writer)?;
 
         Ok(())
     }
@@ -2250,10 +2385,11 @@ impl Serializer for StorageMetrics {
 
 impl Deserializer for StorageMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let storage_size_bytes = load!(u64, reader)?;
+        let store_sizes = if payload_version > 1 { deserialize!(Vec<RpcStoreSize>, reader)? } else { vec![] };
 
-        Ok(Self { storage_size_bytes })
+        Ok(Self { storage_size_bytes, store_sizes })
     }
 }
 
@@ -2393,11 +2529,13 @@ pub struct GetServerInfoResponse {
     pub has_utxo_index: bool,
     pub is_synced: bool,
     pub virtual_daa_score: u64,
+    /// Names of the mining rules currently triggered by the node
+    pub triggered_mining_rules: Vec<String>,
 }
 
 impl Serializer for GetServerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
 
         store!(u16, &self.rpc_api_version, writer)?;
         store!(u16, &self.rpc_api_revision, writer)?;
@@ -2407,6 +2545,7 @@ impl Serializer for GetServerInfoResponse {
         store!(bool, &self.has_utxo_index, writer)?;
         store!(bool, &self.is_synced, writer)?;
         store!(u64, &self.virtual_daa_score, writer)?;
+        store!(Vec<String>, &self.triggered_mining_rules, writer)?;
 
         Ok(())
     }
@@ -2414,7 +2553,7 @@ impl Serializer for GetServerInfoResponse {
 
 impl Deserializer for GetServerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
 
         let rpc_api_version = load!(u16, reader)?;
         let rpc_api_revision = load!(u16, reader)?;
@@ -2424,8 +2563,18 @@ impl Deserializer for GetServerInfoResponse {
         let has_utxo_index = load!(bool, reader)?;
         let is_synced = load!(bool, reader)?;
         let virtual_daa_score = load!(u64, reader)?;
+        let triggered_mining_rules = if payload_version > 1 { load!(Vec<String>, reader)? } else { vec![] };
 
-        Ok(Self { rpc_api_version, rpc_api_revision, server_version, network_id, has_utxo_index, is_synced, virtual_daa_score })
+        Ok(Self {
+            rpc_api_version,
+            rpc_api_revision,
+            server_version,
+            network_id,
+            has_utxo_index,
+            is_synced,
+            virtual_daa_score,
+            triggered_mining_rules,
+        })
     }
 }
 
@@ -2729,6 +2878,338 @@ impl Deserializer for GetUtxoReturnAddressResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMiningRulesRequest {}
+
+impl Serializer for GetMiningRulesRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMiningRulesRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMiningRulesResponse {
+    /// Whether the node currently provides block templates for mining
+    pub should_mine: bool,
+    /// Whether the sink is recent enough for the node to be considered nearly synced
+    pub is_nearly_synced: bool,
+    pub rules: Vec<RpcMiningRule>,
+    /// Recent rule transitions, oldest first
+    pub transitions: Vec<RpcMiningRuleTransition>,
+}
+
+impl Serializer for GetMiningRulesResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.should_mine, writer)?;
+        store!(bool, &self.is_nearly_synced, writer)?;
+        serialize!(Vec<RpcMiningRule>, &self.rules, writer)?;
+        serialize!(Vec<RpcMiningRuleTransition>, &self.transitions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMiningRulesResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let should_mine = load!(bool, reader)?;
+        let is_nearly_synced = load!(bool, reader)?;
+        let rules = deserialize!(Vec<RpcMiningRule>, reader)?;
+        let transitions = deserialize!(Vec<RpcMiningRuleTransition>, reader)?;
+
+        Ok(Self { should_mine, is_nearly_synced, rules, transitions })
+    }
+}
+
+/// Changes the retention period of a running node. The change is applied gradually by the pruning processor,
+/// and its progress is reported through the consensus metrics.
+///
+/// The new period is not persisted: it reverts to the `--retention-period-days` setting of the node when the node
+/// restarts, and when the consensus is replaced following an IBD with headers proof. Use the command line setting
+/// for a lasting change.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRetentionPeriodRequest {
+    /// The new retention period in days, or `None` to retain data down to the pruning point only
+    pub retention_period_days: Option<f64>,
+}
+
+impl SetRetentionPeriodRequest {
+    pub fn new(retention_period_days: Option<f64>) -> Self {
+        Self { retention_period_days }
+    }
+}
+
+impl Serializer for SetRetentionPeriodRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<f64>, &self.retention_period_days, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SetRetentionPeriodRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let retention_period_days = load!(Option<f64>, reader)?;
+
+        Ok(Self { retention_period_days })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRetentionPeriodResponse {}
+
+impl Serializer for SetRetentionPeriodResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SetRetentionPeriodResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// GetRecentReorgsRequest requests the most recent reorgs of the virtual selected chain,
+/// as recorded by the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRecentReorgsRequest {
+    /// The max number of reorgs to return, or `None` for all the reorgs kept by the node
+    pub limit: Option<u32>,
+}
+
+impl GetRecentReorgsRequest {
+    pub fn new(limit: Option<u32>) -> Self {
+        Self { limit }
+    }
+}
+
+impl Serializer for GetRecentReorgsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<u32>, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetRecentReorgsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let limit = load!(Option<u32>, reader)?;
+
+        Ok(Self { limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRecentReorgsResponse {
+    /// The reorgs, ordered from the most recent one
+    pub reorgs: Vec<RpcChainReorg>,
+}
+
+impl GetRecentReorgsResponse {
+    pub fn new(reorgs: Vec<RpcChainReorg>) -> Self {
+        Self { reorgs }
+    }
+}
+
+impl Serializer for GetRecentReorgsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcChainReorg>, &self.reorgs, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetRecentReorgsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let reorgs = deserialize!(Vec<RpcChainReorg>, reader)?;
+
+        Ok(Self { reorgs })
+    }
+}
+
+/// GetTransactionConfirmationsRequest requests the confirmation depth and finality status of a transaction,
+/// as accepted by a block of the virtual selected chain (see `VirtualChainChangedNotification`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionConfirmationsRequest {
+    pub transaction_id: RpcTransactionId,
+    /// The chain block reported to accept the transaction
+    pub accepting_block_hash: RpcHash,
+}
+
+impl GetTransactionConfirmationsRequest {
+    pub fn new(transaction_id: RpcTransactionId, accepting_block_hash: RpcHash) -> Self {
+        Self { transaction_id, accepting_block_hash }
+    }
+}
+
+impl Serializer for GetTransactionConfirmationsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionConfirmationsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+
+        Ok(Self { transaction_id, accepting_block_hash })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionConfirmationsResponse {
+    /// Whether the block is on the virtual selected chain and accepts the transaction.
+    /// All the remaining status fields are `false` (or zero) if not. The request fails for chain
+    /// blocks below the pruning point, since their acceptance data is pruned
+    pub is_accepted: bool,
+    pub accepting_block_daa_score: u64,
+    pub accepting_block_blue_score: u64,
+    /// The DAA score difference between virtual and the accepting block
+    pub confirmations: u64,
+    /// Whether the accepting block is in the past of the finality point, hence cannot be reorged
+    pub is_final: bool,
+    /// Whether the posterity block following the accepting block was reached with a safety margin
+    pub is_posterity_reached: bool,
+    /// Whether a receipt proving the acceptance of the transaction can already be generated
+    pub is_receipt_available: bool,
+}
+
+impl Serializer for GetTransactionConfirmationsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.is_accepted, writer)?;
+        store!(u64, &self.accepting_block_daa_score, writer)?;
+        store!(u64, &self.accepting_block_blue_score, writer)?;
+        store!(u64, &self.confirmations, writer)?;
+        store!(bool, &self.is_final, writer)?;
+        store!(bool, &self.is_posterity_reached, writer)?;
+        store!(bool, &self.is_receipt_available, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionConfirmationsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let is_accepted = load!(bool, reader)?;
+        let accepting_block_daa_score = load!(u64, reader)?;
+        let accepting_block_blue_score = load!(u64, reader)?;
+        let confirmations = load!(u64, reader)?;
+        let is_final = load!(bool, reader)?;
+        let is_posterity_reached = load!(bool, reader)?;
+        let is_receipt_available = load!(bool, reader)?;
+
+        Ok(Self {
+            is_accepted,
+            accepting_block_daa_score,
+            accepting_block_blue_score,
+            confirmations,
+            is_final,
+            is_posterity_reached,
+            is_receipt_available,
+        })
+    }
+}
+
+/// VerifyTransactionReceiptRequest verifies a transaction receipt against the headers known to the node.
+/// The receipt is the wire encoding of the `TxReceiptMessage` served by p2p peers in response to `RequestTxReceipt`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyTransactionReceiptRequest {
+    pub receipt: Vec<u8>,
+}
+
+impl VerifyTransactionReceiptRequest {
+    pub fn new(receipt: Vec<u8>) -> Self {
+        Self { receipt }
+    }
+}
+
+impl Serializer for VerifyTransactionReceiptRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<u8>, &self.receipt, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for VerifyTransactionReceiptRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let receipt = load!(Vec<u8>, reader)?;
+
+        Ok(Self { receipt })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyTransactionReceiptResponse {
+    pub transaction_id: RpcTransactionId,
+    pub accepting_block_hash: RpcHash,
+    /// Whether the receipt proves the acceptance of the transaction by the accepting block,
+    /// and the membership of the accepting block in the selected chain
+    pub is_valid: bool,
+}
+
+impl Serializer for VerifyTransactionReceiptResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(bool, &self.is_valid, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for VerifyTransactionReceiptResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let is_valid = load!(bool, reader)?;
+
+        Ok(Self { transaction_id, accepting_block_hash, is_valid })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -3461,6 +3942,177 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TransactionReplacedNotification
+
+/// NotifyTransactionReplacedRequest registers this connection for transactionReplaced notifications.
+///
+/// A notification is sent when the inserted transaction or any of the evicted transactions is one of
+/// `transaction_ids`. Leave `transaction_ids` empty to start/stop all notifications.
+///
+/// See: TransactionReplacedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionReplacedRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub command: Command,
+}
+impl NotifyTransactionReplacedRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, command: Command) -> Self {
+        Self { transaction_ids, command }
+    }
+}
+
+impl Serializer for NotifyTransactionReplacedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionReplacedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { transaction_ids, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyTransactionReplacedResponse {}
+
+impl Serializer for NotifyTransactionReplacedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyTransactionReplacedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// TransactionReplacedNotification is sent whenever a transaction entering the mempool
+/// replaces by fee some mempool transactions.
+///
+/// Wallets can match the replaced and evicted transaction ids against their own pending transactions.
+///
+/// See: NotifyTransactionReplacedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReplacedNotification {
+    /// Id of the transaction inserted into the mempool
+    pub transaction_id: RpcTransactionId,
+    /// Ids of the mempool transactions double spent by the inserted transaction
+    pub replaced_transaction_ids: Vec<RpcTransactionId>,
+    /// Ids of all the transactions evicted from the mempool, ie. the replaced transactions followed by their descendants
+    pub evicted_transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl Serializer for TransactionReplacedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(Vec<RpcTransactionId>, &self.replaced_transaction_ids, writer)?;
+        store!(Vec<RpcTransactionId>, &self.evicted_transaction_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for TransactionReplacedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let replaced_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let evicted_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        Ok(Self { transaction_id, replaced_transaction_ids, evicted_transaction_ids })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~
+// ChainReorgNotification
+
+/// NotifyChainReorgRequest registers this connection for chainReorg notifications.
+///
+/// See: ChainReorgNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyChainReorgRequest {
+    pub command: Command,
+}
+impl NotifyChainReorgRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifyChainReorgRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyChainReorgRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyChainReorgResponse {}
+
+impl Serializer for NotifyChainReorgResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyChainReorgResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// ChainReorgNotification is sent whenever a reorg of the virtual selected chain removes at least
+/// the number of chain blocks configured by the node (see `--reorg-notification-depth`).
+///
+/// See: NotifyChainReorgRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainReorgNotification {
+    pub reorg: RpcChainReorg,
+}
+
+impl Serializer for ChainReorgNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcChainReorg, &self.reorg, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ChainReorgNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let reorg = deserialize!(RpcChainReorg, reader)?;
+        Ok(Self { reorg })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
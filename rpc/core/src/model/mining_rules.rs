@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// State of a mining rule evaluated by the node when deciding whether block templates may be used for mining
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMiningRule {
+    pub name: String,
+    /// Effect of the rule while triggered: `allow-mining` or `prevent-mining`
+    pub effect: String,
+    pub is_triggered: bool,
+    /// The value measured by the rule on its latest evaluation, if any
+    pub observed_value: Option<f64>,
+    /// The threshold the observed value is compared against, if any
+    pub threshold: Option<f64>,
+    /// Number of times the rule was triggered since the node started
+    pub trigger_count: u64,
+    /// Unix timestamp in milliseconds of the latest transition of the rule, if any
+    pub last_transition_timestamp: Option<u64>,
+}
+
+impl Serializer for RpcMiningRule {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(String, &self.effect, writer)?;
+        store!(bool, &self.is_triggered, writer)?;
+        store!(Option<f64>, &self.observed_value, writer)?;
+        store!(Option<f64>, &self.threshold, writer)?;
+        store!(u64, &self.trigger_count, writer)?;
+        store!(Option<u64>, &self.last_transition_timestamp, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMiningRule {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let name = load!(String, reader)?;
+        let effect = load!(String, reader)?;
+        let is_triggered = load!(bool, reader)?;
+        let observed_value = load!(Option<f64>, reader)?;
+        let threshold = load!(Option<f64>, reader)?;
+        let trigger_count = load!(u64, reader)?;
+        let last_transition_timestamp = load!(Option<u64>, reader)?;
+        Ok(Self { name, effect, is_triggered, observed_value, threshold, trigger_count, last_transition_timestamp })
+    }
+}
+
+/// A change in the triggered state of a mining rule
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMiningRuleTransition {
+    pub name: String,
+    pub is_triggered: bool,
+    pub observed_value: Option<f64>,
+    /// Unix timestamp in milliseconds
+    pub timestamp: u64,
+}
+
+impl Serializer for RpcMiningRuleTransition {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(bool, &self.is_triggered, writer)?;
+        store!(Option<f64>, &self.observed_value, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMiningRuleTransition {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let name = load!(String, reader)?;
+        let is_triggered = load!(bool, reader)?;
+        let observed_value = load!(Option<f64>, reader)?;
+        let timestamp = load!(u64, reader)?;
+        Ok(Self { name, is_triggered, observed_value, timestamp })
+    }
+}
@@ -10,8 +10,10 @@ pub mod header;
 pub mod hex_cnv;
 pub mod mempool;
 pub mod message;
+pub mod mining_rules;
 pub mod network;
 pub mod peer;
+pub mod reorg;
 pub mod script_class;
 pub mod subnets;
 mod tests;
@@ -26,7 +28,9 @@ pub use header::*;
 pub use hex_cnv::*;
 pub use mempool::*;
 pub use message::*;
+pub use mining_rules::*;
 pub use network::*;
 pub use peer::*;
+pub use reorg::*;
 pub use subnets::*;
 pub use tx::*;
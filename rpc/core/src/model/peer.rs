@@ -1,11 +1,12 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
+use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, OnionAddress, PeerId};
 use serde::{Deserialize, Serialize};
 
 pub type RpcNodeId = PeerId;
 pub type RpcIpAddress = IpAddress;
 pub type RpcPeerAddress = NetAddress;
 pub type RpcContextualPeerAddress = ContextualNetAddress;
+pub type RpcOnionAddress = OnionAddress;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct RpcPeerInfo {
@@ -20,4 +21,8 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+    /// Whether the connection runs over the encrypted p2p transport.
+    /// Serialized separately by [`GetConnectedPeerInfoResponse`](crate::GetConnectedPeerInfoResponse) for compatibility
+    #[borsh(skip)]
+    pub is_encrypted: bool,
 }
@@ -0,0 +1,61 @@
+use crate::RpcHash;
+use kaspa_consensus_core::reorg::ChainReorg;
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// A reorganization of the virtual selected chain, i.e., a sink change which removed blocks from the selected chain
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcChainReorg {
+    /// The number of blocks removed from the selected chain
+    pub removed_depth: u64,
+    /// The number of blocks added to the selected chain
+    pub added_depth: u64,
+    /// The sink prior to the reorg, i.e., the tip of the removed chain
+    pub removed_sink: RpcHash,
+    /// The sink following the reorg
+    pub added_sink: RpcHash,
+    /// The DAA score of the sink following the reorg
+    pub daa_score: u64,
+    /// Unix timestamp in milliseconds at which the reorg was applied by the node
+    pub timestamp: u64,
+}
+
+impl From<&ChainReorg> for RpcChainReorg {
+    fn from(item: &ChainReorg) -> Self {
+        Self {
+            removed_depth: item.removed_depth,
+            added_depth: item.added_depth,
+            removed_sink: item.removed_sink,
+            added_sink: item.added_sink,
+            daa_score: item.daa_score,
+            timestamp: item.timestamp,
+        }
+    }
+}
+
+impl Serializer for RpcChainReorg {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.removed_depth, writer)?;
+        store!(u64, &self.added_depth, writer)?;
+        store!(RpcHash, &self.removed_sink, writer)?;
+        store!(RpcHash, &self.added_sink, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcChainReorg {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let removed_depth = load!(u64, reader)?;
+        let added_depth = load!(u64, reader)?;
+        let removed_sink = load!(RpcHash, reader)?;
+        let added_sink = load!(RpcHash, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let timestamp = load!(u64, reader)?;
+        Ok(Self { removed_depth, added_depth, removed_sink, added_sink, daa_score, timestamp })
+    }
+}
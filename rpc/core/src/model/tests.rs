@@ -303,6 +303,7 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                is_encrypted: mock(),
             }
         }
     }
@@ -313,6 +314,12 @@ mod mockery {
         }
     }
 
+    impl Mock for RpcMempoolEntryStats {
+        fn mock() -> Self {
+            RpcMempoolEntryStats { ancestor_count: mock(), ancestor_mass: mock(), descendant_count: mock(), descendant_mass: mock() }
+        }
+    }
+
     impl Mock for RpcMempoolEntryByAddress {
         fn mock() -> Self {
             RpcMempoolEntryByAddress { address: mock(), sending: mock(), receiving: mock() }
@@ -371,6 +378,8 @@ mod mockery {
                 json_connection_attempts: mock(),
                 json_handshake_failures: mock(),
                 active_peers: mock(),
+                encrypted_peers: mock(),
+                inbound_evictions: mock(),
             }
         }
     }
@@ -386,6 +395,7 @@ mod mockery {
                 p2p_bytes_rx: mock(),
                 grpc_bytes_tx: mock(),
                 grpc_bytes_rx: mock(),
+                p2p_tx_relay_bytes_saved: mock(),
             }
         }
     }
@@ -408,13 +418,30 @@ mod mockery {
                 network_past_median_time: mock(),
                 network_virtual_parent_hashes_count: mock(),
                 network_virtual_daa_score: mock(),
+                node_triggered_mining_rules_count: mock(),
+                node_mining_rule_transitions_count: mock(),
+                node_retention_pending_blocks_count: mock(),
+                node_retention_pruned_blocks_count: mock(),
+                node_pruning_proof_build_duration_ms: mock(),
+                node_pruning_proof_validation_duration_ms: mock(),
+                node_reorgs_depth_1_count: mock(),
+                node_reorgs_depth_2_to_3_count: mock(),
+                node_reorgs_depth_4_to_7_count: mock(),
+                node_reorgs_depth_8_or_more_count: mock(),
+                node_max_reorg_depth: mock(),
             }
         }
     }
 
+    impl Mock for RpcStoreSize {
+        fn mock() -> Self {
+            RpcStoreSize { prefix: mock(), name: "Headers".to_string(), size_bytes: mock() }
+        }
+    }
+
     impl Mock for StorageMetrics {
         fn mock() -> Self {
-            StorageMetrics { storage_size_bytes: mock() }
+            StorageMetrics { storage_size_bytes: mock(), store_sizes: mock() }
         }
     }
 
@@ -552,7 +579,10 @@ mod mockery {
 
     impl Mock for GetMempoolEntryResponse {
         fn mock() -> Self {
-            GetMempoolEntryResponse { mempool_entry: RpcMempoolEntry { fee: mock(), transaction: mock(), is_orphan: false } }
+            GetMempoolEntryResponse {
+                mempool_entry: RpcMempoolEntry { fee: mock(), transaction: mock(), is_orphan: false },
+                stats: mock(),
+            }
         }
     }
 
@@ -1026,12 +1056,134 @@ mod mockery {
                 has_utxo_index: true,
                 is_synced: false,
                 virtual_daa_score: mock(),
+                triggered_mining_rules: vec!["sync-rate".to_string()],
             }
         }
     }
 
     test!(GetServerInfoResponse);
 
+    impl Mock for RpcMiningRule {
+        fn mock() -> Self {
+            RpcMiningRule {
+                name: "sync-rate".to_string(),
+                effect: "allow-mining".to_string(),
+                is_triggered: mock(),
+                observed_value: mock(),
+                threshold: mock(),
+                trigger_count: mock(),
+                last_transition_timestamp: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcMiningRuleTransition {
+        fn mock() -> Self {
+            RpcMiningRuleTransition { name: "sync-rate".to_string(), is_triggered: mock(), observed_value: mock(), timestamp: mock() }
+        }
+    }
+
+    impl Mock for GetMiningRulesRequest {
+        fn mock() -> Self {
+            GetMiningRulesRequest {}
+        }
+    }
+
+    test!(GetMiningRulesRequest);
+
+    impl Mock for GetMiningRulesResponse {
+        fn mock() -> Self {
+            GetMiningRulesResponse { should_mine: mock(), is_nearly_synced: mock(), rules: mock(), transitions: mock() }
+        }
+    }
+
+    test!(GetMiningRulesResponse);
+
+    impl Mock for SetRetentionPeriodRequest {
+        fn mock() -> Self {
+            SetRetentionPeriodRequest { retention_period_days: mock() }
+        }
+    }
+
+    test!(SetRetentionPeriodRequest);
+
+    impl Mock for SetRetentionPeriodResponse {
+        fn mock() -> Self {
+            SetRetentionPeriodResponse {}
+        }
+    }
+
+    test!(SetRetentionPeriodResponse);
+
+    impl Mock for RpcChainReorg {
+        fn mock() -> Self {
+            RpcChainReorg {
+                removed_depth: mock(),
+                added_depth: mock(),
+                removed_sink: mock(),
+                added_sink: mock(),
+                daa_score: mock(),
+                timestamp: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetRecentReorgsRequest {
+        fn mock() -> Self {
+            GetRecentReorgsRequest { limit: mock() }
+        }
+    }
+
+    test!(GetRecentReorgsRequest);
+
+    impl Mock for GetRecentReorgsResponse {
+        fn mock() -> Self {
+            GetRecentReorgsResponse { reorgs: mock() }
+        }
+    }
+
+    test!(GetRecentReorgsResponse);
+
+    impl Mock for GetTransactionConfirmationsRequest {
+        fn mock() -> Self {
+            GetTransactionConfirmationsRequest { transaction_id: mock(), accepting_block_hash: mock() }
+        }
+    }
+
+    test!(GetTransactionConfirmationsRequest);
+
+    impl Mock for GetTransactionConfirmationsResponse {
+        fn mock() -> Self {
+            GetTransactionConfirmationsResponse {
+                is_accepted: true,
+                accepting_block_daa_score: mock(),
+                accepting_block_blue_score: mock(),
+                confirmations: mock(),
+                is_final: false,
+                is_posterity_reached: false,
+                is_receipt_available: true,
+            }
+        }
+    }
+
+    test!(GetTransactionConfirmationsResponse);
+
+    impl Mock for VerifyTransactionReceiptRequest {
+        fn mock() -> Self {
+            VerifyTransactionReceiptRequest { receipt: mock() }
+        }
+    }
+
+    test!(VerifyTransactionReceiptRequest);
+
+    impl Mock for VerifyTransactionReceiptResponse {
+        fn mock() -> Self {
+            VerifyTransactionReceiptResponse { transaction_id: mock(), accepting_block_hash: mock(), is_valid: true }
+        }
+    }
+
+    test!(VerifyTransactionReceiptResponse);
+
     impl Mock for GetSyncStatusRequest {
         fn mock() -> Self {
             GetSyncStatusRequest {}
@@ -1284,6 +1436,58 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyTransactionReplacedRequest {
+        fn mock() -> Self {
+            NotifyTransactionReplacedRequest { transaction_ids: mock(), command: Command::Start }
+        }
+    }
+
+    test!(NotifyTransactionReplacedRequest);
+
+    impl Mock for NotifyTransactionReplacedResponse {
+        fn mock() -> Self {
+            NotifyTransactionReplacedResponse {}
+        }
+    }
+
+    test!(NotifyTransactionReplacedResponse);
+
+    impl Mock for TransactionReplacedNotification {
+        fn mock() -> Self {
+            TransactionReplacedNotification {
+                transaction_id: mock(),
+                replaced_transaction_ids: mock(),
+                evicted_transaction_ids: mock(),
+            }
+        }
+    }
+
+    test!(TransactionReplacedNotification);
+
+    impl Mock for NotifyChainReorgRequest {
+        fn mock() -> Self {
+            NotifyChainReorgRequest { command: Command::Start }
+        }
+    }
+
+    test!(NotifyChainReorgRequest);
+
+    impl Mock for NotifyChainReorgResponse {
+        fn mock() -> Self {
+            NotifyChainReorgResponse {}
+        }
+    }
+
+    test!(NotifyChainReorgResponse);
+
+    impl Mock for ChainReorgNotification {
+        fn mock() -> Self {
+            ChainReorgNotification { reorg: mock() }
+        }
+    }
+
+    test!(ChainReorgNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
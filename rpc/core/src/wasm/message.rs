@@ -474,6 +474,7 @@ declare! {
         hasUtxoIndex : boolean;
         isSynced : boolean;
         virtualDaaScore : bigint;
+        triggeredMiningRules : string[];
     }
     "#,
 }
@@ -898,6 +899,271 @@ try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
 
 // ---
 
+declare! {
+    IGetMiningRulesRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetMiningRulesRequest { }
+    "#,
+}
+
+try_from! ( args: IGetMiningRulesRequest, GetMiningRulesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMiningRulesResponse,
+    r#"
+    /**
+     * State of the mining rules evaluated by the node and their recent transitions.
+     *
+     * @category Node RPC
+     */
+    export interface IGetMiningRulesResponse {
+        shouldMine : boolean;
+        isNearlySynced : boolean;
+        rules : {
+            name : string;
+            effect : string;
+            isTriggered : boolean;
+            observedValue? : number;
+            threshold? : number;
+            triggerCount : bigint;
+            lastTransitionTimestamp? : bigint;
+        }[];
+        transitions : {
+            name : string;
+            isTriggered : boolean;
+            observedValue? : number;
+            timestamp : bigint;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: GetMiningRulesResponse, IGetMiningRulesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISetRetentionPeriodRequest,
+    r#"
+    /**
+     * Changes the retention period of the node. The new period is reached gradually.
+     *
+     * The new period is not persisted: it reverts to the `--retention-period-days` setting
+     * of the node when the node restarts, and when the consensus is replaced following
+     * an IBD with headers proof.
+     *
+     * @category Node RPC
+     */
+    export interface ISetRetentionPeriodRequest {
+        /**
+         * The new retention period in days. If omitted, data is retained down to the pruning point only.
+         */
+        retentionPeriodDays? : number;
+    }
+    "#,
+}
+
+try_from! ( args: ISetRetentionPeriodRequest, SetRetentionPeriodRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISetRetentionPeriodResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISetRetentionPeriodResponse { }
+    "#,
+}
+
+try_from! ( args: SetRetentionPeriodResponse, ISetRetentionPeriodResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetRecentReorgsRequest,
+    r#"
+    /**
+     * Requests the most recent reorgs of the virtual selected chain, as recorded by the node.
+     *
+     * @category Node RPC
+     */
+    export interface IGetRecentReorgsRequest {
+        /**
+         * The max number of reorgs to return. If omitted, all the reorgs kept by the node are returned.
+         */
+        limit? : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetRecentReorgsRequest, GetRecentReorgsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetRecentReorgsResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetRecentReorgsResponse {
+        /**
+         * The reorgs, ordered from the most recent one.
+         */
+        reorgs : IChainReorgEntry[];
+    }
+
+    /**
+     * A reorganization of the virtual selected chain.
+     *
+     * @category Node RPC
+     */
+    export interface IChainReorgEntry {
+        removedDepth : bigint;
+        addedDepth : bigint;
+        removedSink : HexString;
+        addedSink : HexString;
+        daaScore : bigint;
+        timestamp : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetRecentReorgsResponse, IGetRecentReorgsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionConfirmationsRequest,
+    r#"
+    /**
+     * Requests the confirmation depth and finality status of a transaction,
+     * as accepted by a block of the virtual selected chain.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionConfirmationsRequest {
+        transactionId : HexString;
+        /**
+         * The chain block reported to accept the transaction.
+         */
+        acceptingBlockHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionConfirmationsRequest, GetTransactionConfirmationsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionConfirmationsResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionConfirmationsResponse {
+        /**
+         * Whether the block is on the virtual selected chain and accepts the transaction.
+         * All the remaining status fields are false (or zero) if not. The request fails for chain
+         * blocks below the pruning point, since their acceptance data is pruned.
+         */
+        isAccepted : boolean;
+        acceptingBlockDaaScore : bigint;
+        acceptingBlockBlueScore : bigint;
+        /**
+         * The DAA score difference between virtual and the accepting block.
+         */
+        confirmations : bigint;
+        /**
+         * Whether the accepting block is in the past of the finality point, hence cannot be reorged.
+         */
+        isFinal : boolean;
+        /**
+         * Whether the posterity block following the accepting block was reached with a safety margin.
+         */
+        isPosterityReached : boolean;
+        /**
+         * Whether a receipt proving the acceptance of the transaction can already be generated.
+         */
+        isReceiptAvailable : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionConfirmationsResponse, IGetTransactionConfirmationsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IVerifyTransactionReceiptRequest,
+    r#"
+    /**
+     * Verifies a transaction receipt against the headers known to the node.
+     *
+     * @category Node RPC
+     */
+    export interface IVerifyTransactionReceiptRequest {
+        /**
+         * The wire encoding of the `TxReceiptMessage` served by p2p peers,
+         * either as a hex string or as a byte array represented by `Uint8Array`.
+         */
+        receipt : HexString | Uint8Array;
+    }
+    "#,
+}
+
+try_from! ( args: IVerifyTransactionReceiptRequest, VerifyTransactionReceiptRequest, {
+    let receipt = args
+        .try_get_value("receipt")?
+        .ok_or_else(|| Error::MissingRpcFieldError("VerifyTransactionReceiptRequest".to_string(), "receipt".to_string()))?;
+    let receipt = if let Some(hex) = receipt.as_string() {
+        Vec::<u8>::from_rpc_hex(&hex)?
+    } else {
+        receipt.try_as_vec_u8()?
+    };
+    Ok(VerifyTransactionReceiptRequest { receipt })
+});
+
+declare! {
+    IVerifyTransactionReceiptResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IVerifyTransactionReceiptResponse {
+        transactionId : HexString;
+        acceptingBlockHash : HexString;
+        /**
+         * Whether the receipt proves the acceptance of the transaction by the accepting block,
+         * and the membership of the accepting block in the selected chain.
+         */
+        isValid : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: VerifyTransactionReceiptResponse, IVerifyTransactionReceiptResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetDaaScoreTimestampEstimateRequest,
     r#"
@@ -1121,6 +1387,7 @@ declare! {
      */
     export interface IGetMempoolEntryResponse {
         mempoolEntry : IMempoolEntry;
+        stats? : IMempoolEntryStats;
     }
     "#,
 }
@@ -1409,6 +1676,7 @@ declare! {
     export interface ISubmitTransactionReplacementResponse {
         transactionId : HexString;
         replacedTransaction: Transaction;
+        evictedTransactionIds: HexString[];
     }
     "#,
 }
@@ -1421,6 +1689,7 @@ try_from! ( args: SubmitTransactionReplacementResponse, ISubmitTransactionReplac
     let response = ISubmitTransactionReplacementResponse::default();
     response.set("transactionId", &transaction_id.into())?;
     response.set("replacedTransaction", &replaced_transaction.into())?;
+    response.set("evictedTransactionIds", &to_value(&args.evicted_transaction_ids)?)?;
     Ok(response)
 });
 
@@ -277,6 +277,11 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
     route!(get_utxo_return_address_call, GetUtxoReturnAddress);
+    route!(get_mining_rules_call, GetMiningRules);
+    route!(set_retention_period_call, SetRetentionPeriod);
+    route!(get_recent_reorgs_call, GetRecentReorgs);
+    route!(get_transaction_confirmations_call, GetTransactionConfirmations);
+    route!(verify_transaction_receipt_call, VerifyTransactionReceipt);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -64,6 +64,11 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetFeeEstimateExperimental);
     impl_into_kaspad_request!(GetCurrentBlockColor);
     impl_into_kaspad_request!(GetUtxoReturnAddress);
+    impl_into_kaspad_request!(GetMiningRules);
+    impl_into_kaspad_request!(SetRetentionPeriod);
+    impl_into_kaspad_request!(GetRecentReorgs);
+    impl_into_kaspad_request!(GetTransactionConfirmations);
+    impl_into_kaspad_request!(VerifyTransactionReceipt);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -73,6 +78,8 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyTransactionReplaced);
+    impl_into_kaspad_request!(NotifyChainReorg);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -202,6 +209,11 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetFeeEstimateExperimental);
     impl_into_kaspad_response!(GetCurrentBlockColor);
     impl_into_kaspad_response!(GetUtxoReturnAddress);
+    impl_into_kaspad_response!(GetMiningRules);
+    impl_into_kaspad_response!(SetRetentionPeriod);
+    impl_into_kaspad_response!(GetRecentReorgs);
+    impl_into_kaspad_response!(GetTransactionConfirmations);
+    impl_into_kaspad_response!(VerifyTransactionReceipt);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -211,6 +223,8 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyTransactionReplaced);
+    impl_into_kaspad_notify_response!(NotifyChainReorg);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
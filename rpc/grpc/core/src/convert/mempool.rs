@@ -10,6 +10,15 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntry, protowire::RpcMempoolEntry, {
     Self { fee: item.fee, transaction: Some((&item.transaction).into()), is_orphan: item.is_orphan }
 });
 
+from!(item: &kaspa_rpc_core::RpcMempoolEntryStats, protowire::RpcMempoolEntryStats, {
+    Self {
+        ancestor_count: item.ancestor_count,
+        ancestor_mass: item.ancestor_mass,
+        descendant_count: item.descendant_count,
+        descendant_mass: item.descendant_mass,
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntryByAddress, {
     Self {
         address: (&item.address).into(),
@@ -33,6 +42,10 @@ try_from!(item: &protowire::RpcMempoolEntry, kaspa_rpc_core::RpcMempoolEntry, {
     )
 });
 
+try_from!(item: &protowire::RpcMempoolEntryStats, kaspa_rpc_core::RpcMempoolEntryStats, {
+    Self::new(item.ancestor_count, item.ancestor_mass, item.descendant_count, item.descendant_mass)
+});
+
 try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempoolEntryByAddress, {
     Self::new(
         item.address.as_str().try_into()?,
@@ -194,6 +194,16 @@ from!(item: &kaspa_rpc_core::NotifyNewBlockTemplateRequest, protowire::NotifyNew
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyNewBlockTemplateResponse>, protowire::NotifyNewBlockTemplateResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyTransactionReplacedRequest, protowire::NotifyTransactionReplacedRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyTransactionReplacedResponse>, protowire::NotifyTransactionReplacedResponseMessage);
+
+from!(item: &kaspa_rpc_core::NotifyChainReorgRequest, protowire::NotifyChainReorgRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyChainReorgResponse>, protowire::NotifyChainReorgResponseMessage);
+
 // ~~~
 
 from!(&kaspa_rpc_core::GetCurrentNetworkRequest, protowire::GetCurrentNetworkRequestMessage);
@@ -223,7 +233,7 @@ from!(item: &kaspa_rpc_core::GetMempoolEntryRequest, protowire::GetMempoolEntryR
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntryResponse>, protowire::GetMempoolEntryResponseMessage, {
-    Self { entry: Some((&item.mempool_entry).into()), error: None }
+    Self { entry: Some((&item.mempool_entry).into()), stats: item.stats.as_ref().map(|x| x.into()), error: None }
 });
 
 from!(item: &kaspa_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
@@ -254,7 +264,12 @@ from!(item: &kaspa_rpc_core::SubmitTransactionReplacementRequest, protowire::Sub
     Self { transaction: Some((&item.transaction).into()) }
 });
 from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionReplacementResponse>, protowire::SubmitTransactionReplacementResponseMessage, {
-    Self { transaction_id: item.transaction_id.to_string(), replaced_transaction: Some((&item.replaced_transaction).into()), error: None }
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        replaced_transaction: Some((&item.replaced_transaction).into()),
+        evicted_transaction_ids: item.evicted_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
@@ -441,6 +456,59 @@ from!(item: RpcResult<&kaspa_rpc_core::GetUtxoReturnAddressResponse>, protowire:
     Self { return_address: item.return_address.address_to_string(), error: None }
 });
 
+from!(&kaspa_rpc_core::GetMiningRulesRequest, protowire::GetMiningRulesRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetMiningRulesResponse>, protowire::GetMiningRulesResponseMessage, {
+    Self {
+        should_mine: item.should_mine,
+        is_nearly_synced: item.is_nearly_synced,
+        rules: item.rules.iter().map(|x| x.into()).collect(),
+        transitions: item.transitions.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::SetRetentionPeriodRequest, protowire::SetRetentionPeriodRequestMessage, {
+    Self { retention_period_days: item.retention_period_days.unwrap_or_default() }
+});
+from!(_item: RpcResult<&kaspa_rpc_core::SetRetentionPeriodResponse>, protowire::SetRetentionPeriodResponseMessage, {
+    Self { error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetRecentReorgsRequest, protowire::GetRecentReorgsRequestMessage, {
+    Self { limit: item.limit.unwrap_or_default() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetRecentReorgsResponse>, protowire::GetRecentReorgsResponseMessage, {
+    Self { reorgs: item.reorgs.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionConfirmationsRequest, protowire::GetTransactionConfirmationsRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), accepting_block_hash: item.accepting_block_hash.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionConfirmationsResponse>, protowire::GetTransactionConfirmationsResponseMessage, {
+    Self {
+        is_accepted: item.is_accepted,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        accepting_block_blue_score: item.accepting_block_blue_score,
+        confirmations: item.confirmations,
+        is_final: item.is_final,
+        is_posterity_reached: item.is_posterity_reached,
+        is_receipt_available: item.is_receipt_available,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::VerifyTransactionReceiptRequest, protowire::VerifyTransactionReceiptRequestMessage, {
+    Self { receipt: item.receipt.to_hex() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::VerifyTransactionReceiptResponse>, protowire::VerifyTransactionReceiptResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        is_valid: item.is_valid,
+        error: None,
+    }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -506,6 +574,7 @@ from!(item: RpcResult<&kaspa_rpc_core::GetServerInfoResponse>, protowire::GetSer
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        triggered_mining_rules: item.triggered_mining_rules.clone(),
         error: None,
     }
 });
@@ -658,6 +727,19 @@ try_from!(item: &protowire::NotifyNewBlockTemplateRequestMessage, kaspa_rpc_core
 });
 try_from!(&protowire::NotifyNewBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::NotifyNewBlockTemplateResponse>);
 
+try_from!(item: &protowire::NotifyTransactionReplacedRequestMessage, kaspa_rpc_core::NotifyTransactionReplacedRequest, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(&protowire::NotifyTransactionReplacedResponseMessage, RpcResult<kaspa_rpc_core::NotifyTransactionReplacedResponse>);
+
+try_from!(item: &protowire::NotifyChainReorgRequestMessage, kaspa_rpc_core::NotifyChainReorgRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyChainReorgResponseMessage, RpcResult<kaspa_rpc_core::NotifyChainReorgResponse>);
+
 // ~~~
 
 try_from!(&protowire::GetCurrentNetworkRequestMessage, kaspa_rpc_core::GetCurrentNetworkRequest);
@@ -695,6 +777,7 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<kaspa_rpc_
             .as_ref()
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetMempoolEntryResponseMessage".to_string(), "entry".to_string()))?
             .try_into()?,
+        stats: item.stats.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
@@ -746,6 +829,7 @@ try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResu
             .as_ref()
             .ok_or_else(|| RpcError::MissingRpcFieldError("SubmitTransactionReplacementRequestMessage".to_string(), "replaced_transaction".to_string()))?
             .try_into()?,
+        evicted_transaction_ids: item.evicted_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
     }
 });
 
@@ -937,6 +1021,57 @@ try_from!(item: &protowire::GetUtxoReturnAddressResponseMessage, RpcResult<kaspa
     Self { return_address: Address::try_from(item.return_address.clone())? }
 });
 
+try_from!(&protowire::GetMiningRulesRequestMessage, kaspa_rpc_core::GetMiningRulesRequest);
+try_from!(item: &protowire::GetMiningRulesResponseMessage, RpcResult<kaspa_rpc_core::GetMiningRulesResponse>, {
+    Self {
+        should_mine: item.should_mine,
+        is_nearly_synced: item.is_nearly_synced,
+        rules: item.rules.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        transitions: item.transitions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::SetRetentionPeriodRequestMessage, kaspa_rpc_core::SetRetentionPeriodRequest, {
+    Self { retention_period_days: (item.retention_period_days > 0.0).then_some(item.retention_period_days) }
+});
+try_from!(&protowire::SetRetentionPeriodResponseMessage, RpcResult<kaspa_rpc_core::SetRetentionPeriodResponse>);
+
+try_from!(item: &protowire::GetRecentReorgsRequestMessage, kaspa_rpc_core::GetRecentReorgsRequest, {
+    Self { limit: (item.limit > 0).then_some(item.limit) }
+});
+try_from!(item: &protowire::GetRecentReorgsResponseMessage, RpcResult<kaspa_rpc_core::GetRecentReorgsResponse>, {
+    Self { reorgs: item.reorgs.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetTransactionConfirmationsRequestMessage, kaspa_rpc_core::GetTransactionConfirmationsRequest, {
+    Self {
+        transaction_id: kaspa_rpc_core::RpcTransactionId::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+    }
+});
+try_from!(item: &protowire::GetTransactionConfirmationsResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionConfirmationsResponse>, {
+    Self {
+        is_accepted: item.is_accepted,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+        accepting_block_blue_score: item.accepting_block_blue_score,
+        confirmations: item.confirmations,
+        is_final: item.is_final,
+        is_posterity_reached: item.is_posterity_reached,
+        is_receipt_available: item.is_receipt_available,
+    }
+});
+
+try_from!(item: &protowire::VerifyTransactionReceiptRequestMessage, kaspa_rpc_core::VerifyTransactionReceiptRequest, {
+    Self { receipt: FromHex::from_hex(&item.receipt)? }
+});
+try_from!(item: &protowire::VerifyTransactionReceiptResponseMessage, RpcResult<kaspa_rpc_core::VerifyTransactionReceiptResponse>, {
+    Self {
+        transaction_id: kaspa_rpc_core::RpcTransactionId::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        is_valid: item.is_valid,
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -997,6 +1132,7 @@ try_from!(item: &protowire::GetServerInfoResponseMessage, RpcResult<kaspa_rpc_co
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        triggered_mining_rules: item.triggered_mining_rules.clone(),
     }
 });
 
@@ -37,6 +37,8 @@ from!(item: &kaspa_rpc_core::ConnectionMetrics, protowire::ConnectionMetrics, {
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        encrypted_peers: item.encrypted_peers,
+        inbound_evictions: item.inbound_evictions,
     }
 });
 
@@ -50,6 +52,7 @@ from!(item: &kaspa_rpc_core::BandwidthMetrics, protowire::BandwidthMetrics, {
         grpc_p2p_bytes_rx: item.p2p_bytes_rx,
         grpc_user_bytes_tx: item.grpc_bytes_tx,
         grpc_user_bytes_rx: item.grpc_bytes_rx,
+        p2p_tx_relay_bytes_saved: item.p2p_tx_relay_bytes_saved,
     }
 });
 
@@ -71,12 +74,36 @@ from!(item: &kaspa_rpc_core::ConsensusMetrics, protowire::ConsensusMetrics, {
         past_median_time: item.network_past_median_time,
         virtual_parent_hashes_count: item.network_virtual_parent_hashes_count,
         virtual_daa_score: item.network_virtual_daa_score,
+
+        triggered_mining_rules_count: item.node_triggered_mining_rules_count,
+        mining_rule_transitions_count: item.node_mining_rule_transitions_count,
+
+        retention_pending_blocks_count: item.node_retention_pending_blocks_count,
+        retention_pruned_blocks_count: item.node_retention_pruned_blocks_count,
+
+        pruning_proof_build_duration_ms: item.node_pruning_proof_build_duration_ms,
+        pruning_proof_validation_duration_ms: item.node_pruning_proof_validation_duration_ms,
+
+        reorgs_depth1_count: item.node_reorgs_depth_1_count,
+        reorgs_depth2_to3_count: item.node_reorgs_depth_2_to_3_count,
+        reorgs_depth4_to7_count: item.node_reorgs_depth_4_to_7_count,
+        reorgs_depth8_or_more_count: item.node_reorgs_depth_8_or_more_count,
+        max_reorg_depth: item.node_max_reorg_depth,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcStoreSize, protowire::RpcStoreSize, {
+    Self {
+        prefix: item.prefix as u32,
+        name: item.name.clone(),
+        size_bytes: item.size_bytes,
     }
 });
 
 from!(item: &kaspa_rpc_core::StorageMetrics, protowire::StorageMetrics, {
     Self {
         storage_size_bytes: item.storage_size_bytes,
+        store_sizes: item.store_sizes.iter().map(|x| x.into()).collect(),
     }
 });
 
@@ -111,6 +138,8 @@ try_from!(item: &protowire::ConnectionMetrics, kaspa_rpc_core::ConnectionMetrics
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        encrypted_peers: item.encrypted_peers,
+        inbound_evictions: item.inbound_evictions,
     }
 });
 
@@ -124,6 +153,7 @@ try_from!(item: &protowire::BandwidthMetrics, kaspa_rpc_core::BandwidthMetrics,
         p2p_bytes_rx: item.grpc_p2p_bytes_rx,
         grpc_bytes_tx: item.grpc_user_bytes_tx,
         grpc_bytes_rx: item.grpc_user_bytes_rx,
+        p2p_tx_relay_bytes_saved: item.p2p_tx_relay_bytes_saved,
     }
 });
 
@@ -145,11 +175,35 @@ try_from!(item: &protowire::ConsensusMetrics, kaspa_rpc_core::ConsensusMetrics,
         network_past_median_time: item.past_median_time,
         network_virtual_parent_hashes_count: item.virtual_parent_hashes_count,
         network_virtual_daa_score: item.virtual_daa_score,
+
+        node_triggered_mining_rules_count: item.triggered_mining_rules_count,
+        node_mining_rule_transitions_count: item.mining_rule_transitions_count,
+
+        node_retention_pending_blocks_count: item.retention_pending_blocks_count,
+        node_retention_pruned_blocks_count: item.retention_pruned_blocks_count,
+
+        node_pruning_proof_build_duration_ms: item.pruning_proof_build_duration_ms,
+        node_pruning_proof_validation_duration_ms: item.pruning_proof_validation_duration_ms,
+
+        node_reorgs_depth_1_count: item.reorgs_depth1_count,
+        node_reorgs_depth_2_to_3_count: item.reorgs_depth2_to3_count,
+        node_reorgs_depth_4_to_7_count: item.reorgs_depth4_to7_count,
+        node_reorgs_depth_8_or_more_count: item.reorgs_depth8_or_more_count,
+        node_max_reorg_depth: item.max_reorg_depth,
+    }
+});
+
+try_from!(item: &protowire::RpcStoreSize, kaspa_rpc_core::RpcStoreSize, {
+    Self {
+        prefix: u8::try_from(item.prefix)?,
+        name: item.name.clone(),
+        size_bytes: item.size_bytes,
     }
 });
 
 try_from!(item: &protowire::StorageMetrics, kaspa_rpc_core::StorageMetrics, {
     Self {
         storage_size_bytes: item.storage_size_bytes,
+        store_sizes: item.store_sizes.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     }
 });
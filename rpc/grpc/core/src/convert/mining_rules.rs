@@ -0,0 +1,55 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcMiningRule, protowire::RpcMiningRule, {
+    Self {
+        name: item.name.clone(),
+        effect: item.effect.clone(),
+        is_triggered: item.is_triggered,
+        has_observed_value: item.observed_value.is_some(),
+        observed_value: item.observed_value.unwrap_or_default(),
+        threshold: item.threshold.unwrap_or_default(),
+        trigger_count: item.trigger_count,
+        last_transition_timestamp: item.last_transition_timestamp.unwrap_or_default(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcMiningRuleTransition, protowire::RpcMiningRuleTransition, {
+    Self {
+        name: item.name.clone(),
+        is_triggered: item.is_triggered,
+        has_observed_value: item.observed_value.is_some(),
+        observed_value: item.observed_value.unwrap_or_default(),
+        timestamp: item.timestamp,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcMiningRule, kaspa_rpc_core::RpcMiningRule, {
+    Self {
+        name: item.name.clone(),
+        effect: item.effect.clone(),
+        is_triggered: item.is_triggered,
+        observed_value: item.has_observed_value.then_some(item.observed_value),
+        threshold: (item.threshold > 0.0).then_some(item.threshold),
+        trigger_count: item.trigger_count,
+        last_transition_timestamp: (item.last_transition_timestamp > 0).then_some(item.last_transition_timestamp),
+    }
+});
+
+try_from!(item: &protowire::RpcMiningRuleTransition, kaspa_rpc_core::RpcMiningRuleTransition, {
+    Self {
+        name: item.name.clone(),
+        is_triggered: item.is_triggered,
+        observed_value: item.has_observed_value.then_some(item.observed_value),
+        timestamp: item.timestamp,
+    }
+});
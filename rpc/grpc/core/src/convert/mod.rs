@@ -7,6 +7,8 @@ pub mod kaspad;
 pub mod mempool;
 pub mod message;
 pub mod metrics;
+pub mod mining_rules;
 pub mod notification;
 pub mod peer;
+pub mod reorg;
 pub mod tx;
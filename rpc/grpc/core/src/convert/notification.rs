@@ -2,11 +2,12 @@ use crate::protowire::{
     kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
-    StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
+    ChainReorgNotificationMessage, FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
+    SinkBlueScoreChangedNotificationMessage, StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage, StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage, TransactionReplacedNotificationMessage, UtxosChangedNotificationMessage,
     VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
@@ -34,6 +35,8 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::TransactionReplaced(ref notification) => Payload::TransactionReplacedNotification(notification.into()),
+        Notification::ChainReorg(ref notification) => Payload::ChainReorgNotification(notification.into()),
     }
 });
 
@@ -74,6 +77,16 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &kaspa_rpc_core::TransactionReplacedNotification, TransactionReplacedNotificationMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        replaced_transaction_ids: item.replaced_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        evicted_transaction_ids: item.evicted_transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::ChainReorgNotification, ChainReorgNotificationMessage, { Self { reorg: Some((&item.reorg).into()) } });
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +130,8 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::TransactionReplacedNotification(ref notification) => Notification::TransactionReplaced(notification.try_into()?),
+        Payload::ChainReorgNotification(ref notification) => Notification::ChainReorg(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +186,24 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &TransactionReplacedNotificationMessage, kaspa_rpc_core::TransactionReplacedNotification, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        replaced_transaction_ids: item.replaced_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        evicted_transaction_ids: item.evicted_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &ChainReorgNotificationMessage, kaspa_rpc_core::ChainReorgNotification, {
+    Self {
+        reorg: item
+            .reorg
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("ChainReorgNotificationMessage".to_string(), "reorg".to_string()))?
+            .try_into()?,
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
@@ -0,0 +1,34 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcChainReorg, protowire::RpcChainReorg, {
+    Self {
+        removed_depth: item.removed_depth,
+        added_depth: item.added_depth,
+        removed_sink: item.removed_sink.to_string(),
+        added_sink: item.added_sink.to_string(),
+        daa_score: item.daa_score,
+        timestamp: item.timestamp,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcChainReorg, kaspa_rpc_core::RpcChainReorg, {
+    Self {
+        removed_depth: item.removed_depth,
+        added_depth: item.added_depth,
+        removed_sink: RpcHash::from_str(&item.removed_sink)?,
+        added_sink: RpcHash::from_str(&item.added_sink)?,
+        daa_score: item.daa_score,
+        timestamp: item.timestamp,
+    }
+});
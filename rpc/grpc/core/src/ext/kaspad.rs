@@ -1,10 +1,10 @@
 use kaspa_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
-    kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
+    kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage, NotifyChainReorgRequestMessage,
     NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifySinkBlueScoreChangedRequestMessage, NotifyTransactionReplacedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
@@ -64,6 +64,15 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::TransactionReplaced(ref scope) => {
+                kaspad_request::Payload::NotifyTransactionReplacedRequest(NotifyTransactionReplacedRequestMessage {
+                    transaction_ids: scope.transaction_ids.iter().map(|x| x.to_string()).collect(),
+                    command: command.into(),
+                })
+            }
+            Scope::ChainReorg(_) => {
+                kaspad_request::Payload::NotifyChainReorgRequest(NotifyChainReorgRequestMessage { command: command.into() })
+            }
         }
     }
 
@@ -79,6 +88,8 @@ impl kaspad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyTransactionReplacedRequest(_)
+                | Payload::NotifyChainReorgRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +119,8 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::TransactionReplacedNotification(_) => true,
+            Payload::ChainReorgNotification(_) => true,
             _ => false,
         }
     }
@@ -88,6 +88,11 @@ pub enum KaspadPayloadOps {
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
     GetUtxoReturnAddress,
+    GetMiningRules,
+    SetRetentionPeriod,
+    GetRecentReorgs,
+    GetTransactionConfirmations,
+    VerifyTransactionReceipt,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -98,6 +103,8 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyTransactionReplaced,
+    NotifyChainReorg,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
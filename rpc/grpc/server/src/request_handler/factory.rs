@@ -82,6 +82,11 @@ impl Factory {
                 GetFeeEstimateExperimental,
                 GetCurrentBlockColor,
                 GetUtxoReturnAddress,
+                GetMiningRules,
+                SetRetentionPeriod,
+                GetRecentReorgs,
+                GetTransactionConfirmations,
+                VerifyTransactionReceipt,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -90,6 +95,8 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyTransactionReplaced,
+                NotifyChainReorg,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -0,0 +1,35 @@
+use kaspa_p2p_mining::rules::registry::{MiningRuleState, MiningRuleTransition};
+use kaspa_rpc_core::{RpcMiningRule, RpcMiningRuleTransition};
+
+pub trait MiningRuleStateConverter {
+    fn into_rpc(self) -> RpcMiningRule;
+}
+
+impl MiningRuleStateConverter for MiningRuleState {
+    fn into_rpc(self) -> RpcMiningRule {
+        RpcMiningRule {
+            name: self.name.to_string(),
+            effect: self.effect.to_string(),
+            is_triggered: self.is_triggered,
+            observed_value: self.observed_value,
+            threshold: self.threshold,
+            trigger_count: self.trigger_count,
+            last_transition_timestamp: self.last_transition_timestamp,
+        }
+    }
+}
+
+pub trait MiningRuleTransitionConverter {
+    fn into_rpc(self) -> RpcMiningRuleTransition;
+}
+
+impl MiningRuleTransitionConverter for MiningRuleTransition {
+    fn into_rpc(self) -> RpcMiningRuleTransition {
+        RpcMiningRuleTransition {
+            name: self.name.to_string(),
+            is_triggered: self.is_triggered,
+            observed_value: self.observed_value,
+            timestamp: self.timestamp,
+        }
+    }
+}
@@ -25,6 +25,7 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            is_encrypted: peer.is_encrypted(),
         }
     }
 
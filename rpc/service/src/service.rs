@@ -2,6 +2,7 @@
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::mining_rules::{MiningRuleStateConverter, MiningRuleTransitionConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use async_trait::async_trait;
 use kaspa_consensus_core::api::counters::ProcessingCounters;
@@ -21,7 +22,7 @@ use kaspa_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
 use kaspa_core::time::unix_now;
 use kaspa_core::{
     core::Core,
@@ -53,7 +54,7 @@ use kaspa_notify::{
     subscriber::{Subscriber, SubscriptionManager},
 };
 use kaspa_p2p_flows::flow_context::FlowContext;
-use kaspa_p2p_lib::common::ProtocolError;
+use kaspa_p2p_lib::{common::ProtocolError, convert::receipts::tx_receipt_from_bytes};
 use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
 use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
@@ -275,6 +276,34 @@ impl RpcCoreService {
             .unwrap_or_default()
     }
 
+    /// Fails methods which depend on block bodies, the UTXO set or the mempool when the node runs in light mode
+    fn require_full_node(&self) -> RpcResult<()> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        Ok(())
+    }
+
+    /// Returns the tip of the selected chain served by the node. Light nodes never move the sink since they do not
+    /// process block bodies, so the headers selected tip is used instead.
+    async fn get_chain_tip(&self, session: &ConsensusProxy) -> RpcHash {
+        if self.config.is_light {
+            session.async_get_headers_selected_tip().await
+        } else {
+            session.async_get_sink().await
+        }
+    }
+
+    /// Returns the DAA score and timestamp by which the node is considered synced, see [`Self::get_chain_tip`]
+    async fn get_sync_daa_score_timestamp(&self, session: &ConsensusProxy) -> DaaScoreTimestamp {
+        if self.config.is_light {
+            let header = session.async_get_header(self.get_chain_tip(session).await).await.unwrap();
+            DaaScoreTimestamp { daa_score: header.daa_score, timestamp: header.timestamp }
+        } else {
+            session.async_get_sink_daa_score_timestamp().await
+        }
+    }
+
     fn extract_tx_query(&self, filter_transaction_pool: bool, include_orphan_pool: bool) -> RpcResult<TransactionQuery> {
         match (filter_transaction_pool, include_orphan_pool) {
             (true, true) => Ok(TransactionQuery::OrphansOnly),
@@ -350,6 +379,7 @@ impl RpcApi for RpcCoreService {
         _connection: Option<&DynRpcConnection>,
         request: SubmitBlockRequest,
     ) -> RpcResult<SubmitBlockResponse> {
+        self.require_full_node()?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let sink_daa_score_timestamp = session.async_get_sink_daa_score_timestamp().await;
 
@@ -420,6 +450,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetBlockTemplateRequest,
     ) -> RpcResult<GetBlockTemplateResponse> {
+        self.require_full_node()?;
         trace!("incoming GetBlockTemplate request");
 
         if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
@@ -457,6 +488,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse> {
+        self.require_full_node()?;
         let session = self.consensus_manager.consensus().unguarded_session();
 
         match session.async_get_current_block_color(request.hash).await {
@@ -467,6 +499,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
     async fn get_block_call(&self, _connection: Option<&DynRpcConnection>, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         // TODO: test
+        if request.include_transactions {
+            self.require_full_node()?;
+        }
         let session = self.consensus_manager.consensus().session().await;
         let block = session.async_get_block_even_if_header_only(request.hash).await?;
         Ok(GetBlockResponse {
@@ -486,6 +521,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if !request.include_blocks && request.include_transactions {
             return Err(RpcError::InvalidGetBlocksRequest);
         }
+        if request.include_transactions {
+            self.require_full_node()?;
+        }
 
         let session = self.consensus_manager.consensus().session().await;
 
@@ -499,8 +537,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             None => self.config.genesis.hash,
         };
 
-        // Get hashes between low_hash and sink
-        let sink_hash = session.async_get_sink().await;
+        // Get hashes between low_hash and sink (the headers selected tip on light nodes)
+        let sink_hash = self.get_chain_tip(&session).await;
 
         // We use +1 because low_hash is also returned
         // max_blocks MUST be >= mergeset_size_limit + 1
@@ -532,7 +570,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
     async fn get_info_call(&self, _connection: Option<&DynRpcConnection>, _request: GetInfoRequest) -> RpcResult<GetInfoResponse> {
         let sink_daa_score_timestamp =
-            self.consensus_manager.consensus().unguarded_session().async_get_sink_daa_score_timestamp().await;
+            self.get_sync_daa_score_timestamp(&self.consensus_manager.consensus().unguarded_session()).await;
         Ok(GetInfoResponse {
             p2p_id: self.flow_context.node_id.to_string(),
             mempool_size: self.mining_manager.transaction_count_sample(TransactionQuery::TransactionsOnly),
@@ -549,12 +587,25 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetMempoolEntryRequest,
     ) -> RpcResult<GetMempoolEntryResponse> {
+        self.require_full_node()?;
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let Some(transaction) = self.mining_manager.clone().get_transaction(request.transaction_id, query).await else {
             return Err(RpcError::TransactionNotFound(request.transaction_id));
         };
         let session = self.consensus_manager.consensus().unguarded_session();
-        Ok(GetMempoolEntryResponse::new(self.consensus_converter.get_mempool_entry(&session, &transaction)))
+        let mempool_entry = self.consensus_converter.get_mempool_entry(&session, &transaction);
+        let stats = match mempool_entry.is_orphan {
+            true => None,
+            false => self.mining_manager.clone().get_transaction_chain_stats(request.transaction_id).await.map(|stats| {
+                RpcMempoolEntryStats::new(
+                    stats.ancestor_count as u64,
+                    stats.ancestor_mass,
+                    stats.descendant_count as u64,
+                    stats.descendant_mass,
+                )
+            }),
+        };
+        Ok(GetMempoolEntryResponse::new(mempool_entry, stats))
     }
 
     async fn get_mempool_entries_call(
@@ -562,6 +613,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetMempoolEntriesRequest,
     ) -> RpcResult<GetMempoolEntriesResponse> {
+        self.require_full_node()?;
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query).await;
@@ -578,6 +630,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse> {
+        self.require_full_node()?;
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
         let script_public_keys = request.addresses.iter().map(pay_to_address_script).collect();
@@ -604,6 +657,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: SubmitTransactionRequest,
     ) -> RpcResult<SubmitTransactionResponse> {
+        self.require_full_node()?;
         let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
         if !self.config.unsafe_rpc && request.allow_orphan {
             debug!("SubmitTransaction RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
@@ -629,16 +683,20 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: SubmitTransactionReplacementRequest,
     ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        self.require_full_node()?;
         let transaction: Transaction = request.transaction.try_into()?;
         let transaction_id = transaction.id();
         let session = self.consensus_manager.consensus().unguarded_session();
-        let replaced_transaction =
-            self.flow_context.submit_rpc_transaction_replacement(&session, transaction).await.map_err(|err| {
-                let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
-                debug!("{err}");
-                err
-            })?;
-        Ok(SubmitTransactionReplacementResponse::new(transaction_id, (&*replaced_transaction).into()))
+        let replacement = self.flow_context.submit_rpc_transaction_replacement(&session, transaction).await.map_err(|err| {
+            let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+            debug!("{err}");
+            err
+        })?;
+        // A mandatory replacement double spends exactly one mempool transaction
+        let replaced_transaction = replacement.replaced.first().ok_or(RpcError::General(format!(
+            "transaction {transaction_id} was accepted as a replacement but no replaced transaction was returned"
+        )))?;
+        Ok(SubmitTransactionReplacementResponse::new(transaction_id, replaced_transaction.as_ref().into(), replacement.evicted))
     }
 
     async fn get_current_network_call(
@@ -658,7 +716,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 
     async fn get_sink_call(&self, _connection: Option<&DynRpcConnection>, _: GetSinkRequest) -> RpcResult<GetSinkResponse> {
-        Ok(GetSinkResponse::new(self.consensus_manager.consensus().unguarded_session().async_get_sink().await))
+        Ok(GetSinkResponse::new(self.get_chain_tip(&self.consensus_manager.consensus().unguarded_session()).await))
     }
 
     async fn get_sink_blue_score_call(
@@ -667,7 +725,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _: GetSinkBlueScoreRequest,
     ) -> RpcResult<GetSinkBlueScoreResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
-        Ok(GetSinkBlueScoreResponse::new(session.async_get_ghostdag_data(session.async_get_sink().await).await?.blue_score))
+        Ok(GetSinkBlueScoreResponse::new(session.async_get_ghostdag_data(self.get_chain_tip(&session).await).await?.blue_score))
     }
 
     async fn get_virtual_chain_from_block_call(
@@ -675,6 +733,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetVirtualChainFromBlockRequest,
     ) -> RpcResult<GetVirtualChainFromBlockResponse> {
+        // Accepted transactions are known only from the acceptance data of processed block bodies
+        if request.include_accepted_transaction_ids {
+            self.require_full_node()?;
+        }
         let session = self.consensus_manager.consensus().session().await;
 
         // batch_size is set to 10 times the mergeset_size_limit.
@@ -683,7 +745,12 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         // else it returns the batch_size amount on pure chain blocks.
         // Note: batch_size does not bound removed chain blocks, only added chain blocks.
         let batch_size = (self.config.mergeset_size_limit().upper_bound() * 10) as usize;
-        let mut virtual_chain_batch = session.async_get_virtual_chain_from_block(request.start_hash, Some(batch_size)).await?;
+        let mut virtual_chain_batch = if self.config.is_light {
+            // Light nodes follow the headers selected chain in place of the virtual chain
+            session.async_get_headers_selected_chain_from_block(request.start_hash, Some(batch_size)).await?
+        } else {
+            session.async_get_virtual_chain_from_block(request.start_hash, Some(batch_size)).await?
+        };
         let accepted_transaction_ids = if request.include_accepted_transaction_ids {
             let accepted_transaction_ids = self
                 .consensus_converter
@@ -839,6 +906,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         _request: GetFeeEstimateRequest,
     ) -> RpcResult<GetFeeEstimateResponse> {
+        self.require_full_node()?;
         let mining_manager = self.mining_manager.clone();
         let consensus_manager = self.consensus_manager.clone();
         let estimate = self
@@ -858,6 +926,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         connection: Option<&DynRpcConnection>,
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse> {
+        self.require_full_node()?;
         if request.verbose {
             let mining_manager = self.mining_manager.clone();
             let consensus_manager = self.consensus_manager.clone();
@@ -882,6 +951,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _connection: Option<&DynRpcConnection>,
         request: GetUtxoReturnAddressRequest,
     ) -> RpcResult<GetUtxoReturnAddressResponse> {
+        self.require_full_node()?;
         let session = self.consensus_manager.consensus().session().await;
 
         match session.async_get_populated_transaction(request.txid, request.accepting_block_daa_score).await {
@@ -977,7 +1047,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         let peer_address = request.peer_address.normalize(self.config.net.default_p2p_port());
         if let Some(connection_manager) = self.flow_context.connection_manager() {
-            connection_manager.add_connection_request(peer_address.into(), request.is_permanent).await;
+            connection_manager.add_connection_request(peer_address, request.is_permanent).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -1119,6 +1189,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             json_handshake_failures: self.wrpc_json_counters.handshake_failures.load(Ordering::Relaxed) as u64,
 
             active_peers: self.flow_context.hub().active_peers_len() as u32,
+            encrypted_peers: self.flow_context.hub().encrypted_peers_len() as u32,
+            inbound_evictions: self.flow_context.connection_manager().map_or(0, |cm| cm.inbound_evictions()),
         });
 
         let bandwidth_metrics = req.bandwidth_metrics.then(|| BandwidthMetrics {
@@ -1130,6 +1202,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
             grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
             grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+            p2p_tx_relay_bytes_saved: self.flow_context.tx_reconciliation_counters().bytes_saved(),
         });
 
         let consensus_metrics = if req.consensus_metrics {
@@ -1154,12 +1227,39 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 network_past_median_time: consensus_stats.virtual_stats.past_median_time,
                 network_virtual_parent_hashes_count: consensus_stats.virtual_stats.num_parents,
                 network_virtual_daa_score: consensus_stats.virtual_stats.daa_score,
+                // ---
+                node_triggered_mining_rules_count: self.mining_rule_engine.triggered_rules_count() as u32,
+                node_mining_rule_transitions_count: self.mining_rule_engine.rule_transitions_count(),
+                // ---
+                node_retention_pending_blocks_count: consensus_stats.retention_stats.pending_blocks,
+                node_retention_pruned_blocks_count: consensus_stats.retention_stats.pruned_blocks,
+                // ---
+                node_pruning_proof_build_duration_ms: consensus_stats.pruning_proof_stats.build_duration_ms,
+                node_pruning_proof_validation_duration_ms: consensus_stats.pruning_proof_stats.validation_duration_ms,
+                // ---
+                node_reorgs_depth_1_count: processing_counters.reorg_depth_counts[0],
+                node_reorgs_depth_2_to_3_count: processing_counters.reorg_depth_counts[1],
+                node_reorgs_depth_4_to_7_count: processing_counters.reorg_depth_counts[2],
+                node_reorgs_depth_8_or_more_count: processing_counters.reorg_depth_counts[3],
+                node_max_reorg_depth: processing_counters.max_reorg_depth,
             })
         } else {
             None
         };
 
-        let storage_metrics = req.storage_metrics.then_some(StorageMetrics { storage_size_bytes: 0 });
+        let storage_metrics = if req.storage_metrics {
+            let storage_stats = self.consensus_manager.consensus().unguarded_session().async_get_storage_stats().await;
+            Some(StorageMetrics {
+                storage_size_bytes: storage_stats.total_size_bytes,
+                store_sizes: storage_stats
+                    .store_sizes
+                    .into_iter()
+                    .map(|(prefix, name, size_bytes)| RpcStoreSize { prefix, name, size_bytes })
+                    .collect(),
+            })
+        } else {
+            None
+        };
 
         let custom_metrics: Option<HashMap<String, CustomMetricValue>> = None;
 
@@ -1202,9 +1302,16 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _request: GetServerInfoRequest,
     ) -> RpcResult<GetServerInfoResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
-        let sink_daa_score_timestamp = session.async_get_sink_daa_score_timestamp().await;
+        let sink_daa_score_timestamp = self.get_sync_daa_score_timestamp(&session).await;
         let is_synced: bool = self.mining_rule_engine.is_sink_recent_and_connected(sink_daa_score_timestamp);
         let virtual_daa_score = session.get_virtual_daa_score();
+        let triggered_mining_rules = self
+            .mining_rule_engine
+            .rule_states()
+            .into_iter()
+            .filter(|rule| rule.is_triggered)
+            .map(|rule| rule.name.to_string())
+            .collect();
 
         Ok(GetServerInfoResponse {
             rpc_api_version: RPC_API_VERSION,
@@ -1214,6 +1321,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             has_utxo_index: self.config.utxoindex,
             is_synced,
             virtual_daa_score,
+            triggered_mining_rules,
         })
     }
 
@@ -1223,11 +1331,84 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         _request: GetSyncStatusRequest,
     ) -> RpcResult<GetSyncStatusResponse> {
         let sink_daa_score_timestamp =
-            self.consensus_manager.consensus().unguarded_session().async_get_sink_daa_score_timestamp().await;
+            self.get_sync_daa_score_timestamp(&self.consensus_manager.consensus().unguarded_session()).await;
         let is_synced: bool = self.mining_rule_engine.is_sink_recent_and_connected(sink_daa_score_timestamp);
         Ok(GetSyncStatusResponse { is_synced })
     }
 
+    async fn get_mining_rules_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMiningRulesRequest,
+    ) -> RpcResult<GetMiningRulesResponse> {
+        let sink_daa_score_timestamp =
+            self.get_sync_daa_score_timestamp(&self.consensus_manager.consensus().unguarded_session()).await;
+        Ok(GetMiningRulesResponse {
+            should_mine: self.mining_rule_engine.should_mine(sink_daa_score_timestamp),
+            is_nearly_synced: self.mining_rule_engine.is_nearly_synced(sink_daa_score_timestamp),
+            rules: self.mining_rule_engine.rule_states().into_iter().map(MiningRuleStateConverter::into_rpc).collect(),
+            transitions: self.mining_rule_engine.rule_transitions().into_iter().map(MiningRuleTransitionConverter::into_rpc).collect(),
+        })
+    }
+
+    async fn set_retention_period_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SetRetentionPeriodRequest,
+    ) -> RpcResult<SetRetentionPeriodResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetRetentionPeriod RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        self.consensus_manager.consensus().unguarded_session().async_set_retention_period_days(request.retention_period_days).await?;
+        Ok(SetRetentionPeriodResponse {})
+    }
+
+    async fn get_recent_reorgs_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetRecentReorgsRequest,
+    ) -> RpcResult<GetRecentReorgsResponse> {
+        let limit = request.limit.map(|limit| limit as usize).unwrap_or(usize::MAX);
+        let reorgs = self.consensus_manager.consensus().unguarded_session().async_get_recent_reorgs(limit).await;
+        Ok(GetRecentReorgsResponse::new(reorgs.iter().map(|reorg| reorg.into()).collect()))
+    }
+
+    async fn get_transaction_confirmations_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionConfirmationsRequest,
+    ) -> RpcResult<GetTransactionConfirmationsResponse> {
+        self.require_full_node()?;
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let confirmations = session.async_get_tx_confirmations(request.transaction_id, request.accepting_block_hash).await?;
+        Ok(GetTransactionConfirmationsResponse {
+            is_accepted: confirmations.is_accepted,
+            accepting_block_daa_score: confirmations.accepting_block_daa_score,
+            accepting_block_blue_score: confirmations.accepting_block_blue_score,
+            confirmations: confirmations.confirmations,
+            is_final: confirmations.is_final,
+            is_posterity_reached: confirmations.is_posterity_reached,
+            is_receipt_available: confirmations.is_receipt_available,
+        })
+    }
+
+    async fn verify_transaction_receipt_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: VerifyTransactionReceiptRequest,
+    ) -> RpcResult<VerifyTransactionReceiptResponse> {
+        // Receipts are verified against headers only, so light nodes serve this method as well
+        let (transaction_id, receipt) =
+            tx_receipt_from_bytes(&request.receipt).map_err(|err| RpcError::General(format!("invalid receipt: {err}")))?;
+        let receipt =
+            receipt.ok_or_else(|| RpcError::General(format!("the receipt message of transaction {transaction_id} is empty")))?;
+        let accepting_block_hash = receipt.accepting_block_header.hash;
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let is_valid = session.async_verify_tx_receipt(receipt).await;
+        Ok(VerifyTransactionReceiptResponse { transaction_id, accepting_block_hash, is_valid })
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -76,6 +76,8 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::TransactionReplacedNotification,
+            RpcApiOps::ChainReorgNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -637,6 +639,11 @@ impl RpcApi for KaspaRpcClient {
             GetMempoolEntriesByAddresses,
             GetMempoolEntry,
             GetMetrics,
+            GetMiningRules,
+            SetRetentionPeriod,
+            GetRecentReorgs,
+            GetTransactionConfirmations,
+            VerifyTransactionReceipt,
             GetPeerAddresses,
             GetServerInfo,
             GetSink,
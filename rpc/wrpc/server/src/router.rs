@@ -59,6 +59,11 @@ impl Router {
                 GetMempoolEntriesByAddresses,
                 GetMempoolEntry,
                 GetMetrics,
+                GetMiningRules,
+                SetRetentionPeriod,
+                GetRecentReorgs,
+                GetTransactionConfirmations,
+                VerifyTransactionReceipt,
                 GetConnections,
                 GetPeerAddresses,
                 GetServerInfo,
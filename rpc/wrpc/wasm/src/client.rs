@@ -841,6 +841,42 @@ impl RpcClient {
         Ok(())
     }
 
+    /// Subscribe for a transaction replaced notification event.
+    /// Transaction replaced notification event is produced when a transaction
+    /// entering the mempool replaces by fee some mempool transactions. The event
+    /// notification will be scoped to the provided list of transaction ids, matching
+    /// either the inserted or an evicted transaction. Leave the list empty to get
+    /// notified about all replacements.
+    #[wasm_bindgen(js_name = subscribeTransactionReplaced)]
+    pub async fn subscribe_transaction_replaced(&self, transaction_ids: Vec<String>) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let transaction_ids = parse_transaction_ids(transaction_ids)?;
+            self.inner
+                .client
+                .start_notify(listener_id, Scope::TransactionReplaced(TransactionReplacedScope { transaction_ids }))
+                .await?;
+        } else {
+            log_error!("RPC subscribe on a closed connection");
+        }
+        Ok(())
+    }
+
+    /// Unsubscribe from transaction replaced notification event
+    /// for a specific set of transaction ids.
+    #[wasm_bindgen(js_name = unsubscribeTransactionReplaced)]
+    pub async fn unsubscribe_transaction_replaced(&self, transaction_ids: Vec<String>) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let transaction_ids = parse_transaction_ids(transaction_ids)?;
+            self.inner
+                .client
+                .stop_notify(listener_id, Scope::TransactionReplaced(TransactionReplacedScope { transaction_ids }))
+                .await?;
+        } else {
+            log_error!("RPC unsubscribe on a closed connection");
+        }
+        Ok(())
+    }
+
     // TODO: scope variant with field functions
 
     /// Manage subscription for a virtual chain changed notification event.
@@ -876,11 +912,16 @@ impl RpcClient {
     }
 }
 
+fn parse_transaction_ids(transaction_ids: Vec<String>) -> Result<Vec<RpcTransactionId>> {
+    transaction_ids.iter().map(|id| RpcTransactionId::from_str(id).map_err(|err| Error::from(err.to_string()))).collect()
+}
+
 // Build subscribe functions
 build_wrpc_wasm_bindgen_subscriptions!([
     // Manually implemented subscriptions (above)
     // - VirtualChainChanged, // can't used this here due to non-C-style enum variant
     // - UtxosChanged, // can't used this here due to non-C-style enum variant
+    // - TransactionReplaced, // can't used this here due to non-C-style enum variant
     // - VirtualDaaScoreChanged,
     /// Manage subscription for a block added notification event.
     /// Block added notification event is produced when a new
@@ -907,6 +948,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a chain reorg notification event.
+    /// Chain reorg notification event is produced when a reorg of the virtual
+    /// selected chain removes at least the number of chain blocks configured by the node.
+    ChainReorg,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -981,6 +1026,9 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves the current network configuration.
         /// Returned information: Current network configuration.
         GetCurrentNetwork,
+        /// Retrieves the state of the mining rules and their recent transitions.
+        /// Returned information: Mining rules state, recent rule transitions.
+        GetMiningRules,
     ],
     [
         // functions with `request` argument
@@ -1029,6 +1077,16 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves a specific mempool entry by transaction ID.
         /// Returned information: Mempool entry information.
         GetMempoolEntry,
+        /// Retrieves the most recent reorgs of the virtual selected chain recorded by the Kaspa node.
+        /// Returned information: List of reorgs with their depths, DAA score and time.
+        GetRecentReorgs,
+        /// Retrieves the confirmation depth and finality status of a transaction
+        /// accepted by a given block of the virtual selected chain.
+        /// Returned information: Acceptance, confirmation count, finality, posterity and receipt availability.
+        GetTransactionConfirmations,
+        /// Verifies a transaction receipt served by p2p peers against the headers known to the node.
+        /// Returned information: The transaction and accepting block of the receipt, and whether it is valid.
+        VerifyTransactionReceipt,
         /// Retrieves information about a subnetwork in the Kaspa BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
@@ -1042,6 +1100,9 @@ build_wrpc_wasm_bindgen_interface!(
         /// Resolves a finality conflict in the Kaspa BlockDAG.
         /// Returned information: None.
         ResolveFinalityConflict,
+        /// Changes the retention period of the Kaspa node at runtime.
+        /// Returned information: None.
+        SetRetentionPeriod,
         /// Submits a block to the Kaspa network.
         /// Returned information: None.
         SubmitBlock,
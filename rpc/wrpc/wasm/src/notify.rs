@@ -25,6 +25,8 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    TransactionReplaced = "transaction-replaced",
+    ChainReorg = "chain-reorg",
 }
 
 /**
@@ -40,7 +42,9 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | ITransactionReplaced 
+    | IChainReorg;
 
 /**
  * RPC notification event data map.
@@ -59,6 +63,8 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "transaction-replaced" : ITransactionReplaced,
+    "chain-reorg" : IChainReorg,
 }
 
 /**
@@ -84,6 +90,8 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeTransactionReplaced},
+ * {@link RpcClient.subscribeChainReorg},
  * 
  * @category Node RPC
  */
@@ -241,3 +249,33 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    ITransactionReplaced,
+    r#"
+    /**
+     * Transaction replaced notification event is produced when a transaction
+     * entering the mempool replaces by fee some mempool transactions.
+     * 
+     * @category Node RPC
+     */
+    export interface ITransactionReplaced {
+        [key: string]: any;
+    }
+    "#,
+}
+
+declare! {
+    IChainReorg,
+    r#"
+    /**
+     * Chain reorg notification event is produced when a reorg of the virtual
+     * selected chain removes at least the number of chain blocks configured by the node.
+     * 
+     * @category Node RPC
+     */
+    export interface IChainReorg {
+        [key: string]: any;
+    }
+    "#,
+}
@@ -234,6 +234,7 @@ fn main_impl(mut args: Args) {
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             config.clone(),
             Default::default(),
             notification_root,
@@ -301,11 +302,15 @@ fn main_impl(mut args: Args) {
     }
 
     // Benchmark the DAG validation time
-    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
+    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default()
+        .with_parallelism(num_cpus::get())
+        .with_column_families(true)
+        .with_files_limit(default_fd));
     let (dummy_notification_sender, _) = unbounded();
     let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
     let consensus2 = Arc::new(Consensus::new(
         db2,
+        None,
         config.clone(),
         Default::default(),
         notification_root,
@@ -56,7 +56,8 @@ impl KaspaNetworkSimulator {
         let secp = secp256k1::Secp256k1::new();
         let mut rng = rand::thread_rng();
         for i in 0..num_miners {
-            let mut builder = ConnBuilder::default().with_files_limit(fd_budget::limit() / 2 / num_miners as i32);
+            let mut builder =
+                ConnBuilder::default().with_column_families(true).with_files_limit(fd_budget::limit() / 2 / num_miners as i32);
             if let Some(rocksdb_files_limit) = rocksdb_files_limit {
                 builder = builder.with_files_limit(rocksdb_files_limit);
             }
@@ -81,6 +82,7 @@ impl KaspaNetworkSimulator {
             let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
             let consensus = Arc::new(Consensus::new(
                 db,
+                None,
                 self.config.clone(),
                 Default::default(),
                 notification_root,
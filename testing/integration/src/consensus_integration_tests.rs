@@ -1737,6 +1737,8 @@ fn selected_chain_store_iterator(consensus: &TestConsensus, pruning_point: Hash)
 
 #[tokio::test]
 async fn staging_consensus_test() {
+    use kaspa_consensus_core::pruning::IbdCheckpoint;
+
     init_allocator_with_default_settings();
     let config = ConfigBuilder::new(MAINNET_PARAMS).build();
 
@@ -1756,12 +1758,14 @@ async fn staging_consensus_test() {
         meta_db,
         &config,
         consensus_db_dir,
+        None,
         4,
         notification_root,
         counters,
         tx_script_cache_counters,
         200,
         Arc::new(MiningRules::default()),
+        None,
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
 
@@ -1769,13 +1773,80 @@ async fn staging_consensus_test() {
     core.bind(consensus_manager.clone());
     let joins = core.start();
 
+    // A suspended staging consensus is retained along with its IBD checkpoint
     let staging = consensus_manager.new_staging_consensus();
+    let pruning_point = config.genesis.hash;
+    staging.session().await.async_set_ibd_checkpoint(IbdCheckpoint::ProofApplied { pruning_point }).await;
+    staging.suspend();
+
+    let staging = consensus_manager.existing_staging_consensus().expect("the staging consensus was suspended");
+    let checkpoint = staging.session().await.async_get_ibd_checkpoint().await;
+    assert!(matches!(checkpoint, Some(IbdCheckpoint::ProofApplied { pruning_point: pp }) if pp == pruning_point));
     staging.commit();
+    assert!(consensus_manager.existing_staging_consensus().is_none());
 
     core.shutdown();
     core.join(joins);
 }
 
+#[tokio::test]
+async fn light_node_header_pruning_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("info");
+
+    const FINALITY_DEPTH: u64 = 20;
+    const PRUNING_DEPTH: u64 = FINALITY_DEPTH * 3 - 5;
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.prior_max_block_parents = 4;
+            p.prior_mergeset_size_limit = 10;
+            p.prior_ghostdag_k = 4;
+            p.prior_finality_depth = FINALITY_DEPTH;
+            p.prior_pruning_depth = PRUNING_DEPTH;
+        })
+        .apply_args(|config| config.is_light = true)
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Light nodes only ever receive headers, so virtual never moves and the pruning point must follow the headers selected tip
+    let chain_length = PRUNING_DEPTH + 3 * FINALITY_DEPTH;
+    let mut tip = config.genesis.hash;
+    for i in 1..=chain_length {
+        let hash: Hash = i.into();
+        let header = consensus.build_header_with_parents(hash, vec![tip]);
+        consensus.validate_and_insert_block(Block::from_header(header)).block_task.await.unwrap();
+        tip = hash;
+    }
+    assert_eq!(consensus.get_headers_selected_tip(), tip);
+    assert_eq!(consensus.get_sink(), config.genesis.hash);
+
+    // Pruning runs in the background, so wait for the headers below the new pruning point to be deleted
+    let headers_store = consensus.headers_store();
+    let is_pruned = |i: u64| headers_store.get_daa_score(i.into()).is_err();
+    for _ in 0..100 {
+        if (1..chain_length).any(is_pruned) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    let pruning_point = consensus.pruning_point();
+    assert_ne!(pruning_point, config.genesis.hash, "the pruning point should advance with the headers selected tip");
+    let pruning_point_blue_score = headers_store.get_blue_score(pruning_point).unwrap();
+    assert!(consensus.get_header(tip).unwrap().blue_score >= pruning_point_blue_score + PRUNING_DEPTH);
+    assert!((1..pruning_point_blue_score).any(is_pruned), "headers below the pruning point should be pruned");
+    assert!((pruning_point_blue_score..=chain_length).all(|i| !is_pruned(i)), "headers above the pruning point should be kept");
+
+    // The selected chain is served from the headers up to the headers selected tip
+    let chain_path = consensus.get_headers_selected_chain_from_block(pruning_point, None).unwrap();
+    assert!(chain_path.removed.is_empty());
+    assert_eq!(chain_path.added, (pruning_point_blue_score + 1..=chain_length).map(Hash::from).collect::<Vec<_>>());
+
+    consensus.shutdown(wait_handles);
+}
+
 /// Tests the KIP-10 transaction introspection opcode activation by verifying that:
 /// 1. Transactions using these opcodes are rejected before the activation DAA score
 /// 2. The same transactions are accepted at and after the activation score
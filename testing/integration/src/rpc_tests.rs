@@ -11,8 +11,8 @@ use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, ChainReorgScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
+        SinkBlueScoreChangedScope, TransactionReplacedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -673,6 +673,58 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetMiningRules => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mining_rules_call(None, GetMiningRulesRequest {}).await.unwrap();
+                    assert!(response.rules.iter().any(|rule| rule.name == "sync-rate"));
+                    assert!(response.rules.iter().all(|rule| !rule.is_triggered));
+                    assert!(response.transitions.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::SetRetentionPeriod => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Values below the minimum retention period are rejected
+                    assert!(rpc_client.set_retention_period(Some(0.5)).await.is_err());
+                    rpc_client.set_retention_period(Some(3.0)).await.unwrap();
+                    rpc_client.set_retention_period(None).await.unwrap();
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionConfirmations => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Genesis is a chain block which accepts no transaction
+                    let response =
+                        rpc_client.get_transaction_confirmations(RpcHash::from_bytes([0; 32]), SIMNET_GENESIS.hash).await.unwrap();
+                    assert!(!response.is_accepted);
+                    assert_eq!(response.confirmations, 0);
+
+                    // Non-existing blocks should return an error
+                    assert!(rpc_client.get_transaction_confirmations(RpcHash::from_bytes([0; 32]), 999.into()).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::VerifyTransactionReceipt => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Malformed receipts, and receipt messages missing the transaction id, should return an error
+                    assert!(rpc_client.verify_transaction_receipt(vec![0xff; 4]).await.is_err());
+                    assert!(rpc_client.verify_transaction_receipt(vec![]).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetRecentReorgs => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The node is not mining so its selected chain never reorgs
+                    assert!(rpc_client.get_recent_reorgs(None).await.unwrap().is_empty());
+                    assert!(rpc_client.get_recent_reorgs(Some(10)).await.unwrap().is_empty());
+                })
+            }
+
             KaspadPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -734,6 +786,20 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifyTransactionReplaced => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, TransactionReplacedScope::default().into()).await.unwrap();
+                })
+            }
+            KaspadPayloadOps::NotifyChainReorg => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, ChainReorgScope {}.into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
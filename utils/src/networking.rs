@@ -1,13 +1,15 @@
 // #![allow(dead_code)]
 use borsh::{BorshDeserialize, BorshSerialize};
 use ipnet::IpNet;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Sha3_256};
 use std::{
     fmt::Display,
     net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     ops::Deref,
     str::FromStr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
@@ -128,6 +130,11 @@ impl IpAddress {
     pub fn prefix_bucket(&self) -> PrefixBucket {
         PrefixBucket::from(self)
     }
+
+    /// Whether this is an IPv6 address within the OnionCat range which onion addresses are mapped into
+    pub fn is_onioncat(&self) -> bool {
+        matches!(self.0, IpAddr::V6(ip) if ip.octets().starts_with(&ONIONCAT_PREFIX))
+    }
 }
 
 impl From<IpAddr> for IpAddress {
@@ -218,21 +225,224 @@ impl BorshDeserialize for IpAddress {
     }
 }
 
+const ONION_PUBKEY_LEN: usize = 32;
+const ONION_CHECKSUM_LEN: usize = 2;
+const ONION_VERSION: u8 = 3;
+const ONION_SUFFIX: &str = ".onion";
+const ONION_BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// The OnionCat IPv6 prefix (fd87:d87e:eb43::/48) which onion addresses are mapped into
+const ONIONCAT_PREFIX: [u8; 6] = [0xfd, 0x87, 0xd8, 0x7e, 0xeb, 0x43];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OnionAddressError {
+    #[error("onion address must end with {ONION_SUFFIX}")]
+    MissingSuffix,
+
+    #[error("onion address has illegal length {0}")]
+    InvalidLength(usize),
+
+    #[error("onion address is not valid base32")]
+    InvalidEncoding,
+
+    #[error("unsupported onion address version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("onion address checksum mismatch")]
+    InvalidChecksum,
+}
+
+/// A Tor v3 onion service address, identified by the ed25519 public key of the service.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct OnionAddress([u8; ONION_PUBKEY_LEN]);
+
+impl OnionAddress {
+    pub fn from_pubkey(pubkey: [u8; ONION_PUBKEY_LEN]) -> Self {
+        Self(pubkey)
+    }
+
+    pub fn pubkey(&self) -> &[u8; ONION_PUBKEY_LEN] {
+        &self.0
+    }
+
+    /// Maps the address into the OnionCat IPv6 range, allowing it to be keyed, bucketed and banned as an IP.
+    /// The mapping is not reversible, so the onion address itself must be kept alongside it
+    pub fn onioncat_ip(&self) -> IpAddress {
+        let mut octets = [0u8; 16];
+        octets[..ONIONCAT_PREFIX.len()].copy_from_slice(&ONIONCAT_PREFIX);
+        octets[ONIONCAT_PREFIX.len()..].copy_from_slice(&self.0[..16 - ONIONCAT_PREFIX.len()]);
+        Ipv6Addr::from(octets).into()
+    }
+
+    fn checksum(pubkey: &[u8; ONION_PUBKEY_LEN]) -> [u8; ONION_CHECKSUM_LEN] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b".onion checksum");
+        hasher.update(pubkey);
+        hasher.update([ONION_VERSION]);
+        hasher.finalize()[..ONION_CHECKSUM_LEN].try_into().unwrap()
+    }
+}
+
+impl FromStr for OnionAddress {
+    type Err = OnionAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded = s.strip_suffix(ONION_SUFFIX).ok_or(OnionAddressError::MissingSuffix)?;
+        let decoded = base32_decode(&encoded.to_ascii_lowercase()).ok_or(OnionAddressError::InvalidEncoding)?;
+        // The address encodes pubkey || checksum || version
+        if decoded.len() != ONION_PUBKEY_LEN + ONION_CHECKSUM_LEN + 1 {
+            return Err(OnionAddressError::InvalidLength(decoded.len()));
+        }
+        let pubkey: [u8; ONION_PUBKEY_LEN] = decoded[..ONION_PUBKEY_LEN].try_into().unwrap();
+        let version = decoded[ONION_PUBKEY_LEN + ONION_CHECKSUM_LEN];
+        if version != ONION_VERSION {
+            return Err(OnionAddressError::UnsupportedVersion(version));
+        }
+        if decoded[ONION_PUBKEY_LEN..ONION_PUBKEY_LEN + ONION_CHECKSUM_LEN] != Self::checksum(&pubkey) {
+            return Err(OnionAddressError::InvalidChecksum);
+        }
+        Ok(Self(pubkey))
+    }
+}
+
+impl Display for OnionAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = self.0.to_vec();
+        bytes.extend_from_slice(&Self::checksum(&self.0));
+        bytes.push(ONION_VERSION);
+        write!(f, "{}{}", base32_encode(&bytes), ONION_SUFFIX)
+    }
+}
+
+impl Serialize for OnionAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for OnionAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        OnionAddress::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Unpadded RFC 4648 base32 encoding, as used by onion addresses
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u16, 0u32);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ONION_BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ONION_BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(s.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u16, 0u32);
+    for c in s.bytes() {
+        let value = ONION_BASE32_ALPHABET.iter().position(|&a| a == c)? as u16;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
 /// A network address, equivalent of a [SocketAddr].
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
 pub struct NetAddress {
     pub ip: IpAddress,
     pub port: u16,
+    /// The onion service this address stands for, in which case `ip` holds its OnionCat mapping.
+    /// Skipped by borsh for compatibility, so RPC messages carrying addresses serialize it in a later payload version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[borsh(skip)]
+    pub onion: Option<OnionAddress>,
 }
 
 impl NetAddress {
     pub fn new(ip: IpAddress, port: u16) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
+    }
+
+    pub fn new_onion(onion: OnionAddress, port: u16) -> Self {
+        Self { ip: onion.onioncat_ip(), port, onion: Some(onion) }
+    }
+
+    pub fn is_onion(&self) -> bool {
+        self.onion.is_some()
     }
 
     pub fn prefix_bucket(&self) -> PrefixBucket {
         PrefixBucket::from(self)
     }
+
+    /// The network this address is reachable through
+    pub fn network(&self) -> AddressNetwork {
+        match (self.onion, self.ip.0) {
+            (Some(_), _) => AddressNetwork::Onion,
+            (None, IpAddr::V4(_)) => AddressNetwork::Ipv4,
+            (None, IpAddr::V6(ip)) if ip.to_ipv4_mapped().is_some() => AddressNetwork::Ipv4,
+            (None, IpAddr::V6(_)) => AddressNetwork::Ipv6,
+        }
+    }
+}
+
+/// The network type of a [`NetAddress`]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum AddressNetwork {
+    Ipv4,
+    Ipv6,
+    Onion,
+}
+
+impl Display for AddressNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressNetwork::Ipv4 => write!(f, "ipv4"),
+            AddressNetwork::Ipv6 => write!(f, "ipv6"),
+            AddressNetwork::Onion => write!(f, "onion"),
+        }
+    }
+}
+
+/// A peer address as exchanged in address gossip, along with the service flags the peer advertised
+/// and the time it was last seen. Zero values mean the services or the last seen time are unknown
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct AddressRecord {
+    pub address: NetAddress,
+    pub services: u64,
+    /// Unix time in milliseconds
+    pub last_seen: u64,
+}
+
+impl AddressRecord {
+    pub fn new(address: NetAddress, services: u64, last_seen: u64) -> Self {
+        Self { address, services, last_seen }
+    }
+
+    /// Whether the peer is known to offer all of the `required` services
+    pub fn has_services(&self, required: u64) -> bool {
+        self.services & required == required
+    }
+}
+
+impl From<NetAddress> for AddressRecord {
+    fn from(address: NetAddress) -> Self {
+        Self::new(address, 0, 0)
+    }
 }
 
 impl From<SocketAddr> for NetAddress {
@@ -251,13 +461,21 @@ impl FromStr for NetAddress {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((host, port)) = s.rsplit_once(':') {
+            if let (Ok(onion), Ok(port)) = (OnionAddress::from_str(host), u16::from_str(port)) {
+                return Ok(Self::new_onion(onion, port));
+            }
+        }
         SocketAddr::from_str(s).map(NetAddress::from)
     }
 }
 
 impl Display for NetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        SocketAddr::from(self.to_owned()).fmt(f)
+        match self.onion {
+            Some(onion) => write!(f, "{}:{}", onion, self.port),
+            None => SocketAddr::from(self.to_owned()).fmt(f),
+        }
     }
 }
 
@@ -268,11 +486,23 @@ impl Display for NetAddress {
 pub struct ContextualNetAddress {
     ip: IpAddress,
     port: Option<u16>,
+    /// Skipped by borsh for compatibility, see [`NetAddress::onion`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[borsh(skip)]
+    onion: Option<OnionAddress>,
 }
 
 impl ContextualNetAddress {
     pub fn new(ip: IpAddress, port: Option<u16>) -> Self {
-        Self { ip, port }
+        Self { ip, port, onion: None }
+    }
+
+    pub fn new_onion(onion: OnionAddress, port: Option<u16>) -> Self {
+        Self { ip: onion.onioncat_ip(), port, onion: Some(onion) }
+    }
+
+    pub fn is_onion(&self) -> bool {
+        self.onion.is_some()
     }
 
     pub fn has_port(&self) -> bool {
@@ -280,15 +510,15 @@ impl ContextualNetAddress {
     }
 
     pub fn normalize(&self, default_port: u16) -> NetAddress {
-        NetAddress::new(self.ip, self.port.unwrap_or(default_port))
+        NetAddress { ip: self.ip, port: self.port.unwrap_or(default_port), onion: self.onion }
     }
 
     pub fn unspecified() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)).into(), None)
     }
 
     pub fn loopback() -> Self {
-        Self { ip: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), port: None }
+        Self::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)).into(), None)
     }
 
     pub fn port_not_specified(&self) -> bool {
@@ -296,13 +526,21 @@ impl ContextualNetAddress {
     }
 
     pub fn with_port(&self, port: u16) -> Self {
-        Self { ip: self.ip, port: Some(port) }
+        Self { port: Some(port), ..*self }
+    }
+
+    pub fn onion(&self) -> Option<OnionAddress> {
+        self.onion
+    }
+
+    pub fn with_onion(&self, onion: OnionAddress) -> Self {
+        Self { ip: onion.onioncat_ip(), onion: Some(onion), ..*self }
     }
 }
 
 impl From<NetAddress> for ContextualNetAddress {
     fn from(value: NetAddress) -> Self {
-        Self::new(value.ip, Some(value.port))
+        Self { ip: value.ip, port: Some(value.port), onion: value.onion }
     }
 }
 
@@ -310,6 +548,15 @@ impl FromStr for ContextualNetAddress {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, port) = match s.rsplit_once(':') {
+            Some((host, port)) => (host, u16::from_str(port).ok()),
+            None => (s, None),
+        };
+        if let Ok(onion) = OnionAddress::from_str(host) {
+            if port.is_some() || host == s {
+                return Ok(Self::new_onion(onion, port));
+            }
+        }
         match SocketAddr::from_str(s) {
             Ok(socket) => Ok(Self::new(socket.ip().into(), Some(socket.port()))),
             Err(_) => Ok(Self::new(IpAddress::from_str(s)?, None)),
@@ -335,9 +582,11 @@ impl TryFrom<String> for ContextualNetAddress {
 
 impl Display for ContextualNetAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.port {
-            Some(port) => SocketAddr::new(self.ip.into(), port).fmt(f),
-            None => self.ip.fmt(f),
+        match (self.onion, self.port) {
+            (Some(onion), Some(port)) => write!(f, "{}:{}", onion, port),
+            (Some(onion), None) => onion.fmt(f),
+            (None, Some(port)) => SocketAddr::new(self.ip.into(), port).fmt(f),
+            (None, None) => self.ip.fmt(f),
         }
     }
 }
@@ -447,6 +696,58 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_onion_address() {
+        let s = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+        let onion = OnionAddress::from_str(s).unwrap();
+        assert_eq!(onion.to_string(), s);
+        assert_eq!(OnionAddress::from_str(&s.to_uppercase().replace(".ONION", ".onion")).unwrap(), onion);
+
+        let onion = OnionAddress::from_pubkey(core::array::from_fn(|i| i as u8 + 1));
+        assert_eq!(onion.to_string(), "aebagbafaydqqcikbmga2dqpcaireeyuculbogazdinryhi6d4qcmeqd.onion");
+        assert_eq!(onion.onioncat_ip(), IpAddress::from_str("fd87:d87e:eb43:102:304:506:708:90a").unwrap());
+        assert!(onion.onioncat_ip().is_onioncat() && !onion.onioncat_ip().is_publicly_routable());
+
+        // Tampering with the checksum, the version or the length is rejected
+        assert_eq!(
+            OnionAddress::from_str("aebagbafaydqqcikbmga2dqpcaireeyuculbogazdinryhi6d4qcaeqd.onion"),
+            Err(OnionAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            OnionAddress::from_str("aebagbafaydqqcikbmga2dqpcaireeyuculbogazdinryhi6d4qcmeqc.onion"),
+            Err(OnionAddressError::UnsupportedVersion(2))
+        );
+        assert!(matches!(OnionAddress::from_str("expyuzz4wqqyqhjn.onion"), Err(OnionAddressError::InvalidLength(_))));
+        assert_eq!(
+            OnionAddress::from_str("aebagbafaydqqcikbmga2dqpcaireeyuculbogazdinryhi6d4qcmeqd"),
+            Err(OnionAddressError::MissingSuffix)
+        );
+        assert_eq!(
+            OnionAddress::from_str("1ebagbafaydqqcikbmga2dqpcaireeyuculbogazdinryhi6d4qcmeqd.onion"),
+            Err(OnionAddressError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_onion_net_address() {
+        let s = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111";
+        let addr = NetAddress::from_str(s).unwrap();
+        assert!(addr.is_onion());
+        assert_eq!(addr.ip, addr.onion.unwrap().onioncat_ip());
+        assert_eq!(addr.to_string(), s);
+        assert!(NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion").is_err());
+
+        // The onion field is only serialized when present, keeping the ip address representation unchanged
+        let json = serde_json::to_string(&addr).unwrap();
+        assert_eq!(serde_json::from_str::<NetAddress>(&json).unwrap(), addr);
+        assert_eq!(serde_json::to_string(&NetAddress::from_str("1.2.3.4:5678").unwrap()).unwrap(), r#"{"ip":"1.2.3.4","port":5678}"#);
+
+        let contextual = ContextualNetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion").unwrap();
+        assert!(contextual.port_not_specified());
+        assert_eq!(contextual.normalize(16111), addr);
+        assert_eq!(ContextualNetAddress::from_str(s).unwrap().to_string(), s);
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
@@ -454,6 +755,15 @@ mod tests {
         assert!(addr.prefix_bucket() == PrefixBucket(u16::from_be_bytes(prefix_bytes) as u64));
     }
 
+    #[test]
+    fn test_address_network() {
+        assert_eq!(NetAddress::from_str("1.2.3.4:16111").unwrap().network(), AddressNetwork::Ipv4);
+        assert_eq!(NetAddress::from_str("[::ffff:1.2.3.4]:16111").unwrap().network(), AddressNetwork::Ipv4);
+        assert_eq!(NetAddress::from_str("[2001:db8::1]:16111").unwrap().network(), AddressNetwork::Ipv6);
+        let onion = NetAddress::from_str("2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:16111").unwrap();
+        assert_eq!(onion.network(), AddressNetwork::Onion);
+    }
+
     #[test]
     fn test_contextual_address_ser() {
         let addr = IpAddress::from_str("127.0.0.1").unwrap();
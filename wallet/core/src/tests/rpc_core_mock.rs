@@ -387,6 +387,46 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_mining_rules_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMiningRulesRequest,
+    ) -> RpcResult<GetMiningRulesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_retention_period_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SetRetentionPeriodRequest,
+    ) -> RpcResult<SetRetentionPeriodResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_recent_reorgs_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetRecentReorgsRequest,
+    ) -> RpcResult<GetRecentReorgsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_confirmations_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionConfirmationsRequest,
+    ) -> RpcResult<GetTransactionConfirmationsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn verify_transaction_receipt_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: VerifyTransactionReceiptRequest,
+    ) -> RpcResult<VerifyTransactionReceiptResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 